@@ -0,0 +1,187 @@
+//! Criterion benchmarks of the hot paths of the planner: the extremal profiles of step 1, the
+//! time-synchronized profiles of step 2, the synchronization of multiple DoFs and the sampling of
+//! the trajectory. Run with `cargo bench -p rsruckig-benchmarks`.
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand_core::SeedableRng;
+use rand_distr::{Distribution, Normal, Uniform};
+use rand_pcg::Pcg64Mcg;
+use rsruckig::block::Block;
+use rsruckig::position_third_step1::PositionThirdOrderStep1;
+use rsruckig::position_third_step2::PositionThirdOrderStep2;
+use rsruckig::prelude::*;
+
+/// Single-DoF states as [p0, v0, a0, pf, vf, af] with limits as [v_max, a_max, j_max], named after
+/// the limits reached by their time-optimal profile, and a state with non-zero velocity and
+/// acceleration at both ends
+const PROFILES: [(&str, [f64; 6], [f64; 3]); 4] = [
+    (
+        "acc0_acc1_vel",
+        [0.0, 0.0, 0.0, 10.0, 0.0, 0.0],
+        [1.0, 1.0, 1.0],
+    ),
+    (
+        "acc0_acc1",
+        [0.0, 0.0, 0.0, 4.0, 0.0, 0.0],
+        [10.0, 1.0, 1.0],
+    ),
+    ("none", [0.0, 0.0, 0.0, 0.1, 0.0, 0.0], [1.0, 1.0, 1.0]),
+    (
+        "target_state",
+        [0.0, 0.3, -0.2, 1.0, 0.5, 0.1],
+        [1.0, 1.0, 1.0],
+    ),
+];
+
+fn step1(state: &[f64; 6], limits: &[f64; 3]) -> PositionThirdOrderStep1 {
+    PositionThirdOrderStep1::new(
+        state[0], state[1], state[2], state[3], state[4], state[5], limits[0], -limits[0],
+        limits[1], -limits[1], limits[2],
+    )
+}
+
+fn boundary(state: &[f64; 6]) -> Profile {
+    let mut profile = Profile::default();
+    profile.set_boundary(
+        &state[0], &state[1], &state[2], &state[3], &state[4], &state[5],
+    );
+    profile
+}
+
+fn bench_step1(c: &mut Criterion) {
+    let mut group = c.benchmark_group("step1");
+    for (name, state, limits) in PROFILES {
+        let profile = boundary(&state);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let mut block = Block::default();
+                let found =
+                    step1(black_box(&state), black_box(&limits)).get_profile(&profile, &mut block);
+                black_box((found, block.t_min))
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_step2(c: &mut Criterion) {
+    let mut group = c.benchmark_group("step2");
+    for (name, state, limits) in PROFILES {
+        // Synchronize to a duration half again as long as the time-optimal one
+        let mut block = Block::default();
+        assert!(step1(&state, &limits).get_profile(&boundary(&state), &mut block));
+        let tf = 1.5 * block.t_min;
+
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let mut profile = boundary(&state);
+                let found = PositionThirdOrderStep2::new(
+                    black_box(tf),
+                    state[0],
+                    state[1],
+                    state[2],
+                    state[3],
+                    state[4],
+                    state[5],
+                    limits[0],
+                    -limits[0],
+                    limits[1],
+                    -limits[1],
+                    limits[2],
+                )
+                .get_profile(&mut profile);
+                black_box((found, profile.t))
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Random input in the same distributions as the benchmark target
+fn random_input<const DOF: usize>(seed: u64) -> InputParameter<DOF> {
+    let mut rng = Pcg64Mcg::seed_from_u64(seed);
+    let position = Normal::new(0.0, 4.0).unwrap();
+    let dynamic = Normal::new(0.0, 0.8).unwrap();
+    let limit = Uniform::new(0.1, 12.0);
+
+    let mut input = InputParameter::new(None);
+    for dof in 0..DOF {
+        input.current_position[dof] = position.sample(&mut rng);
+        input.current_velocity[dof] = dynamic.sample(&mut rng);
+        input.target_position[dof] = position.sample(&mut rng);
+        input.target_velocity[dof] = dynamic.sample(&mut rng);
+        input.max_velocity[dof] = limit.sample(&mut rng) + input.target_velocity[dof].abs();
+        input.max_acceleration[dof] = limit.sample(&mut rng);
+        input.max_jerk[dof] = limit.sample(&mut rng);
+    }
+    input
+}
+
+/// `synchronize` is internal to the calculation, so it is measured by the calculation with time
+/// synchronization against the one without
+fn bench_synchronize_dofs<const DOF: usize>(c: &mut Criterion) {
+    let mut group = c.benchmark_group("synchronize");
+    let mut otg = Ruckig::<DOF, ThrowErrorHandler>::new(None, 0.01);
+    let mut traj = Trajectory::new(None);
+    for (name, synchronization) in [
+        ("time", Synchronization::Time),
+        ("none", Synchronization::None),
+    ] {
+        let mut input = random_input::<DOF>(42);
+        input.synchronization = synchronization;
+        otg.calculate(&input, &mut traj).unwrap();
+
+        group.bench_with_input(BenchmarkId::new(name, DOF), &input, |b, input| {
+            b.iter(|| black_box(otg.calculate(black_box(input), &mut traj)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_at_time_dofs<const DOF: usize>(c: &mut Criterion) {
+    let mut otg = Ruckig::<DOF, ThrowErrorHandler>::new(None, 0.01);
+    let mut traj = Trajectory::new(None);
+    otg.calculate(&random_input::<DOF>(43), &mut traj).unwrap();
+
+    let mut position = DataArrayOrVec::new(None, 0.0);
+    let mut velocity = DataArrayOrVec::new(None, 0.0);
+    let mut acceleration = DataArrayOrVec::new(None, 0.0);
+    let mut jerk = DataArrayOrVec::new(None, 0.0);
+    let mut section = None;
+    let time = 0.6 * traj.get_duration();
+
+    c.bench_with_input(BenchmarkId::new("at_time", DOF), &time, |b, &time| {
+        b.iter(|| {
+            traj.at_time(
+                black_box(time),
+                &mut Some(&mut position),
+                &mut Some(&mut velocity),
+                &mut Some(&mut acceleration),
+                &mut Some(&mut jerk),
+                &mut section,
+            );
+            black_box(&position);
+        })
+    });
+}
+
+fn bench_synchronize(c: &mut Criterion) {
+    bench_synchronize_dofs::<1>(c);
+    bench_synchronize_dofs::<3>(c);
+    bench_synchronize_dofs::<7>(c);
+}
+
+fn bench_at_time(c: &mut Criterion) {
+    bench_at_time_dofs::<1>(c);
+    bench_at_time_dofs::<3>(c);
+    bench_at_time_dofs::<7>(c);
+    bench_at_time_dofs::<12>(c);
+}
+
+criterion_group!(
+    benches,
+    bench_step1,
+    bench_step2,
+    bench_synchronize,
+    bench_at_time
+);
+criterion_main!(benches);
@@ -0,0 +1,59 @@
+//! Calculates trajectories of arbitrary inputs. The calculation may fail, but never panics and
+//! never outputs non-finite values. A trajectory of an input whose current and target states are
+//! within the limits reaches its target within the limits, if the input is in the tested range
+//! and the duration within the maximal duration. A single DoF isn't rejected beyond the maximal
+//! duration, but exceeds the numerical precision.
+#![no_main]
+
+use arbitrary::Unstructured;
+use libfuzzer_sys::fuzz_target;
+use rsruckig::prelude::*;
+
+fuzz_target!(|data: &[u8]| {
+    let mut u = Unstructured::new(data);
+    let Ok(input) = rsruckig_fuzz::input(&mut u) else {
+        return;
+    };
+
+    let dofs = input.degrees_of_freedom;
+    let mut otg = Ruckig::<0, ThrowErrorHandler>::new(Some(dofs), 0.01);
+    let mut traj = Trajectory::new(Some(dofs));
+    if otg.calculate(&input, &mut traj) != Ok(RuckigResult::Working) {
+        return;
+    }
+
+    let duration = traj.get_duration();
+    assert!(
+        duration.is_finite() && duration >= 0.0,
+        "duration {}",
+        duration
+    );
+    for step in 0..=20 {
+        let state = traj.state_at(duration * step as f64 / 20.0);
+        for values in [
+            &state.position,
+            &state.velocity,
+            &state.acceleration,
+            &state.jerk,
+        ] {
+            assert!(values.iter().all(|value| value.is_finite()), "{:?}", state);
+        }
+    }
+
+    // Without synchronization, a DoF whose profile ends early continues with its target velocity
+    // and acceleration until the end of the trajectory
+    let drifts = input.synchronization == Synchronization::None
+        && input
+            .target_velocity
+            .iter()
+            .chain(input.target_acceleration.iter())
+            .any(|&value| value != 0.0);
+    if duration <= Settings::default().maximal_duration
+        && !drifts
+        && rsruckig_fuzz::is_in_tested_range(&input)
+        && input.validate::<ThrowErrorHandler>(true, true).is_ok()
+    {
+        let violations = traj.check_limits(&input, (duration / 100.0).max(1e-3));
+        assert!(violations.is_empty(), "{:?} of {:?}", violations, input);
+    }
+});
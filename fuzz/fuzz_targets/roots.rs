@@ -0,0 +1,56 @@
+//! Solves polynomials with arbitrary coefficients. The solvers never panic, and the cubic and
+//! quartic solvers only return non-negative roots. In the supported range of the coefficients,
+//! these are finite, and the general solver returns roots within the interval in ascending order.
+#![no_main]
+
+use arbitrary::{Result, Unstructured};
+use libfuzzer_sys::fuzz_target;
+use rsruckig::roots::{self, ArrayVec};
+use rsruckig_fuzz::value;
+
+/// Largest magnitude of the coefficients, as of the kinematic limits
+const SUPPORTED_MAGNITUDE: f64 = 1e12;
+
+fn check(u: &mut Unstructured) -> Result<()> {
+    let coeffs = [value(u)?, value(u)?, value(u)?, value(u)?, value(u)?];
+    let (l, h) = (value(u)?.abs(), value(u)?.abs());
+    let (l, h) = (l.min(h), l.max(h));
+
+    let cubic = roots::solve_cub(coeffs[0], coeffs[1], coeffs[2], coeffs[3]);
+    let quartic = roots::solve_quart_monic_coeffs(coeffs[0], coeffs[1], coeffs[2], coeffs[3]);
+    let found = roots::solve_poly(&coeffs.into_iter().collect::<ArrayVec<f64, 5>>(), l, h);
+
+    let extremal = cubic.get_data().iter().chain(quartic.get_data());
+    assert!(extremal.clone().all(|&root| root >= 0.0), "{:?}", coeffs);
+    if !coeffs
+        .iter()
+        .chain([&h])
+        .all(|value| value.abs() <= SUPPORTED_MAGNITUDE)
+    {
+        return Ok(());
+    }
+
+    assert!(
+        extremal.clone().all(|root| root.is_finite()),
+        "{:?}",
+        coeffs
+    );
+    assert!(
+        found.iter().all(|&root| l <= root && root <= h),
+        "roots {:?} of {:?} in [{}, {}]",
+        found,
+        coeffs,
+        l,
+        h
+    );
+    assert!(
+        found.windows(2).all(|roots| roots[0] <= roots[1]),
+        "{:?}",
+        found
+    );
+    Ok(())
+}
+
+fuzz_target!(|data: &[u8]| {
+    let _ = check(&mut Unstructured::new(data));
+});
@@ -0,0 +1,105 @@
+//! Decoding of the arbitrary bytes of the fuzzer into inputs of the planner. The values are spread
+//! over the magnitudes that Ruckig supports, with occasional zero, non-finite and extreme values.
+use arbitrary::{Result, Unstructured};
+use rsruckig::prelude::*;
+
+/// Largest number of DoFs of a decoded input
+pub const MAX_DOFS: usize = 4;
+
+/// A value with a magnitude between 1e-6 and 1e6, or rarely zero or an arbitrary `f64`
+pub fn value(u: &mut Unstructured) -> Result<f64> {
+    Ok(match u.int_in_range(0..=15u8)? {
+        0 => u.arbitrary()?,
+        1 => 0.0,
+        _ => {
+            let mantissa = f64::from(u.arbitrary::<i16>()?) / f64::from(i16::MAX);
+            mantissa * 10f64.powi(u.int_in_range(-6..=6)?)
+        }
+    })
+}
+
+/// A non-negative value, or rarely a negative or arbitrary one
+pub fn limit(u: &mut Unstructured) -> Result<f64> {
+    let value = value(u)?;
+    Ok(if u.ratio(1u8, 32u8)? {
+        value
+    } else {
+        value.abs()
+    })
+}
+
+fn values(
+    u: &mut Unstructured,
+    dofs: usize,
+    decode: fn(&mut Unstructured) -> Result<f64>,
+) -> Result<DataArrayOrVec<f64, 0>> {
+    (0..dofs).map(|_| decode(u)).collect()
+}
+
+/// An input with up to `MAX_DOFS` DoFs of the position or velocity interface
+pub fn input(u: &mut Unstructured) -> Result<InputParameter<0>> {
+    let dofs = u.int_in_range(1..=MAX_DOFS)?;
+    let mut input = InputParameter::new(Some(dofs));
+    input.current_position = values(u, dofs, value)?;
+    input.current_velocity = values(u, dofs, value)?;
+    input.current_acceleration = values(u, dofs, value)?;
+    input.target_position = values(u, dofs, value)?;
+    input.target_velocity = values(u, dofs, value)?;
+    input.target_acceleration = values(u, dofs, value)?;
+    input.max_velocity = values(u, dofs, limit)?;
+    input.max_acceleration = values(u, dofs, limit)?;
+    input.max_jerk = values(u, dofs, limit)?;
+    if u.arbitrary()? {
+        input.min_velocity = Some(values(u, dofs, |u| Ok(-limit(u)?))?);
+    }
+    if u.arbitrary()? {
+        input.min_acceleration = Some(values(u, dofs, |u| Ok(-limit(u)?))?);
+    }
+    if u.arbitrary()? {
+        input.minimum_duration = Some(limit(u)?);
+    }
+    input.control_interface = u
+        .choose(&[ControlInterface::Position, ControlInterface::Velocity])?
+        .clone();
+    input.synchronization = u
+        .choose(&[
+            Synchronization::Time,
+            Synchronization::TimeIfNecessary,
+            Synchronization::Phase,
+            Synchronization::None,
+        ])?
+        .clone();
+    if u.arbitrary()? {
+        input.duration_discretization = DurationDiscretization::Discrete;
+    }
+    Ok(input)
+}
+
+/// Whether the values of the input are within the range of the property tests, i.e. the limits
+/// between 1e-4 and 1e2 and the states at most 1e2. The precision of the trajectory is only
+/// checked in this range, as the rounding errors of f64 grow with the ratio of the magnitudes.
+pub fn is_in_tested_range(input: &InputParameter<0>) -> bool {
+    let limits = [
+        &input.max_velocity,
+        &input.max_acceleration,
+        &input.max_jerk,
+    ]
+    .into_iter()
+    .chain(&input.min_velocity)
+    .chain(&input.min_acceleration);
+    let states = [
+        &input.current_position,
+        &input.current_velocity,
+        &input.current_acceleration,
+        &input.target_position,
+        &input.target_velocity,
+        &input.target_acceleration,
+    ];
+    limits
+        .flat_map(|values| values.iter())
+        .all(|value| (1e-4..=1e2).contains(&value.abs()))
+        && states
+            .into_iter()
+            .flat_map(|values| values.iter())
+            .all(|value| value.abs() <= 1e2)
+}
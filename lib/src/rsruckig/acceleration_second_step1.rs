@@ -0,0 +1,47 @@
+//! Mathematical equations for Step 1 in second-order acceleration interface: Extremal profiles
+use crate::block::Block;
+use crate::profile::{ControlSigns, Profile, ReachedLimits};
+use crate::util::trace_strategy;
+
+#[derive(Debug)]
+pub struct AccelerationSecondOrderStep1 {
+    v_max: f64,
+    v_min: f64,
+    a_max: f64,
+    a_min: f64,
+}
+
+impl AccelerationSecondOrderStep1 {
+    pub fn new(v_max: f64, v_min: f64, a_max: f64, a_min: f64) -> Self {
+        Self {
+            v_max,
+            v_min,
+            a_max,
+            a_min,
+        }
+    }
+    pub fn get_profile(&mut self, input: &Profile, block: &mut Block) -> bool {
+        trace_strategy!("AccelerationSecondOrderStep1::get_profile");
+        let p = &mut block.p_min;
+        p.set_boundary_from_profile(input);
+
+        // The target acceleration is reached instantly
+        p.a[0] = p.af;
+        p.t = [0.0; 7];
+
+        if p.check_for_acceleration(
+            ControlSigns::UDDU,
+            ReachedLimits::None,
+            0.0,
+            self.v_max,
+            self.v_min,
+            self.a_max,
+            self.a_min,
+        ) {
+            block.t_min =
+                p.t_sum.last().unwrap() + p.pre_dwell + p.brake.duration + p.accel.duration;
+            return true;
+        }
+        false
+    }
+}
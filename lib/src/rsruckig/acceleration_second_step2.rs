@@ -0,0 +1,45 @@
+//! Mathematical equations for Step 2 in second-order acceleration interface: Time synchronization
+use crate::profile::{ControlSigns, Profile, ReachedLimits};
+use crate::util::trace_strategy;
+
+#[derive(Debug)]
+pub struct AccelerationSecondOrderStep2 {
+    tf: f64,
+    v_max: f64,
+    v_min: f64,
+    a_max: f64,
+    a_min: f64,
+}
+
+impl AccelerationSecondOrderStep2 {
+    pub fn new(tf: f64, v_max: f64, v_min: f64, a_max: f64, a_min: f64) -> Self {
+        Self {
+            tf,
+            v_max,
+            v_min,
+            a_max,
+            a_min,
+        }
+    }
+
+    pub fn get_profile(&mut self, profile: &mut Profile) -> bool {
+        trace_strategy!("AccelerationSecondOrderStep2::get_profile");
+        // Hold the target acceleration for the whole duration
+        profile.a[0] = profile.af;
+        profile.t = [self.tf, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+
+        if profile.check_for_acceleration(
+            ControlSigns::UDDU,
+            ReachedLimits::None,
+            0.0,
+            self.v_max,
+            self.v_min,
+            self.a_max,
+            self.a_min,
+        ) {
+            profile.pf = *profile.p.last().unwrap();
+            return true;
+        }
+        false
+    }
+}
@@ -0,0 +1,63 @@
+//! Mathematical equations for Step 1 in third-order acceleration interface: Extremal profiles
+use crate::block::Block;
+use crate::profile::{ControlSigns, Profile, ReachedLimits};
+use crate::util::trace_strategy;
+
+#[derive(Debug)]
+pub struct AccelerationThirdOrderStep1 {
+    v_max: f64,
+    v_min: f64,
+    a_max: f64,
+    a_min: f64,
+    j_max: f64,
+    ad: f64,
+}
+
+impl AccelerationThirdOrderStep1 {
+    pub fn new(
+        a0: f64,
+        af: f64,
+        v_max: f64,
+        v_min: f64,
+        a_max: f64,
+        a_min: f64,
+        j_max: f64,
+    ) -> Self {
+        Self {
+            v_max,
+            v_min,
+            a_max,
+            a_min,
+            j_max,
+            ad: af - a0,
+        }
+    }
+    pub fn get_profile(&mut self, input: &Profile, block: &mut Block) -> bool {
+        trace_strategy!("AccelerationThirdOrderStep1::get_profile");
+        let p = &mut block.p_min;
+        p.set_boundary_from_profile(input);
+
+        let jf = if self.ad > 0.0 {
+            self.j_max
+        } else {
+            -self.j_max
+        };
+        p.t = [0.0; 7];
+        p.t[0] = self.ad / jf;
+
+        if p.check_for_acceleration(
+            ControlSigns::UDDU,
+            ReachedLimits::None,
+            jf,
+            self.v_max,
+            self.v_min,
+            self.a_max,
+            self.a_min,
+        ) {
+            block.t_min =
+                p.t_sum.last().unwrap() + p.pre_dwell + p.brake.duration + p.accel.duration;
+            return true;
+        }
+        false
+    }
+}
@@ -0,0 +1,48 @@
+//! Mathematical equations for Step 2 in third-order acceleration interface: Time synchronization
+use crate::profile::{ControlSigns, Profile, ReachedLimits};
+use crate::util::trace_strategy;
+
+#[derive(Debug)]
+pub struct AccelerationThirdOrderStep2 {
+    tf: f64,
+    v_max: f64,
+    v_min: f64,
+    a_max: f64,
+    a_min: f64,
+    ad: f64,
+}
+
+impl AccelerationThirdOrderStep2 {
+    pub fn new(tf: f64, a0: f64, af: f64, v_max: f64, v_min: f64, a_max: f64, a_min: f64) -> Self {
+        Self {
+            tf,
+            v_max,
+            v_min,
+            a_max,
+            a_min,
+            ad: af - a0,
+        }
+    }
+
+    pub fn get_profile(&mut self, profile: &mut Profile) -> bool {
+        trace_strategy!("AccelerationThirdOrderStep2::get_profile");
+        // Ramp the acceleration over the whole duration, which keeps the jerk below the limit
+        // as the duration is at least the minimal one of step 1
+        let jf = self.ad / self.tf;
+        profile.t = [self.tf, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+
+        if profile.check_for_acceleration(
+            ControlSigns::UDDU,
+            ReachedLimits::None,
+            jf,
+            self.v_max,
+            self.v_min,
+            self.a_max,
+            self.a_min,
+        ) {
+            profile.pf = *profile.p.last().unwrap();
+            return true;
+        }
+        false
+    }
+}
@@ -0,0 +1,75 @@
+//! Calculation of a trajectory on a background thread.
+
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use crate::error::{RuckigError, RuckigErrorHandler};
+use crate::input_parameter::InputParameter;
+use crate::result::RuckigResult;
+use crate::ruckig::Ruckig;
+use crate::trajectory::Trajectory;
+
+type Outcome<const DOF: usize> = (Result<RuckigResult, RuckigError>, Option<Trajectory<DOF>>);
+
+/// Handle of a trajectory calculation on a background thread, see
+/// `Ruckig::calculate_in_background`. The control loop polls the handle without blocking.
+#[derive(Debug)]
+pub struct BackgroundCalculation<const DOF: usize> {
+    receiver: Receiver<Outcome<DOF>>,
+    /// The input of the calculation, starting from the state at `start_time`
+    pub(crate) input: InputParameter<DOF>,
+    pub(crate) start_time: f64,
+    pub(crate) outcome: Option<Outcome<DOF>>,
+}
+
+impl<const DOF: usize> BackgroundCalculation<DOF> {
+    /// Calculate the input with the given instance on a new thread
+    pub(crate) fn spawn<E: RuckigErrorHandler + Send + 'static>(
+        mut ruckig: Ruckig<DOF, E>,
+        input: InputParameter<DOF>,
+        start_time: f64,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let thread_input = input.clone();
+        thread::spawn(move || {
+            let mut trajectory = Trajectory::new_with_waypoints(
+                ruckig.max_number_of_waypoints,
+                (DOF == 0).then_some(ruckig.degrees_of_freedom),
+            );
+            let result = ruckig.calculate(&thread_input, &mut trajectory);
+            // The handle may have been dropped in the meantime
+            let _ = sender.send((result, Some(trajectory)));
+        });
+
+        Self {
+            receiver,
+            input,
+            start_time,
+            outcome: None,
+        }
+    }
+
+    /// Whether the calculation has finished, without blocking
+    pub fn is_finished(&mut self) -> bool {
+        if self.outcome.is_none() {
+            match self.receiver.try_recv() {
+                Ok(outcome) => self.outcome = Some(outcome),
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => {
+                    self.outcome = Some((
+                        Err(RuckigError::new(
+                            "background calculation ended without a result.",
+                        )),
+                        None,
+                    ))
+                }
+            }
+        }
+        self.outcome.is_some()
+    }
+
+    /// Time of the previous trajectory at which the calculated trajectory starts
+    pub fn get_start_time(&self) -> f64 {
+        self.start_time
+    }
+}
@@ -5,11 +5,17 @@ use std::f64;
 use std::fmt;
 use std::option::Option;
 
+/// The durations that a DoF can be synchronized to: from the time-optimal duration on, except
+/// within up to two blocked intervals
 #[derive(Clone, Default, Debug)]
 pub struct Block {
+    /// The time-optimal profile
     pub p_min: Profile,
+    /// The duration of the time-optimal profile
     pub t_min: f64,
+    /// The first interval of blocked durations, if any
     pub a: Option<Interval>,
+    /// The second interval of blocked durations, if any
     pub b: Option<Interval>,
 }
 
@@ -19,15 +25,16 @@ pub fn remove_profile(
     index: usize,
 ) {
     for i in index..(*valid_profile_counter - 1) {
-        valid_profiles[i] = valid_profiles[i + 1].clone();
+        valid_profiles[i] = valid_profiles[i + 1];
     }
     *valid_profile_counter -= 1;
 }
 
 impl Block {
     pub fn set_min_profile(&mut self, profile: &Profile) {
-        self.p_min = profile.clone();
+        self.p_min = *profile;
         self.t_min = self.p_min.t_sum.last().unwrap()
+            + self.p_min.pre_dwell
             + self.p_min.brake.duration
             + self.p_min.accel.duration;
         self.a = None;
@@ -46,7 +53,7 @@ impl Block {
         } else if *valid_profile_counter == 2 {
             if f64::abs(
                 valid_profiles[0].t_sum.last().unwrap() - valid_profiles[1].t_sum.last().unwrap(),
-            ) < 8.0 * std::f64::EPSILON
+            ) < 8.0 * f64::EPSILON
             {
                 block.set_min_profile(&valid_profiles[0]);
                 return true;
@@ -94,7 +101,7 @@ impl Block {
             } else {
                 return false;
             }
-        } else if *valid_profile_counter % 2 == 0 {
+        } else if valid_profile_counter.is_multiple_of(2) {
             return false;
         }
 
@@ -154,6 +161,27 @@ impl Block {
         false
     }
 
+    /// Remove the extremal profiles that don't fulfill the given condition, promoting the next
+    /// valid one to the minimal profile. Returns false if no valid profile is left.
+    pub fn retain_profiles<F: Fn(&Profile) -> bool>(&mut self, is_valid: F) -> bool {
+        if is_valid(&self.p_min) {
+            return true;
+        }
+
+        let a = self.a.take();
+        let b = self.b.take();
+        if let Some(a) = a.filter(|a| is_valid(&a.profile)) {
+            self.set_min_profile(&a.profile);
+            self.a = b;
+            return true;
+        }
+        if let Some(b) = b.filter(|b| is_valid(&b.profile)) {
+            self.set_min_profile(&b.profile);
+            return true;
+        }
+        false
+    }
+
     #[inline]
     pub fn is_blocked(&self, t: f64) -> bool {
         (t < self.t_min)
@@ -165,13 +193,33 @@ impl Block {
                 && t < self.b.as_ref().unwrap().right)
     }
 
+    /// The intervals of durations that aren't blocked, in ascending order. The last one is
+    /// unbounded, i.e. ends at infinity.
+    pub fn feasible_durations(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        let [a, b] = [&self.a, &self.b].map(|interval| interval.as_ref());
+        let bounds = [
+            Some(self.t_min),
+            a.map(|a| a.left),
+            a.map(|a| a.right),
+            b.map(|b| b.left),
+            b.map(|b| b.right),
+            Some(f64::INFINITY),
+        ]
+        .into_iter()
+        .flatten();
+        bounds
+            .clone()
+            .step_by(2)
+            .zip(bounds.skip(1).step_by(2))
+            .filter(|(left, right)| left <= right)
+    }
+
+    /// The extremal profile that is extended to a feasible duration `t`
     pub fn get_profile(&self, t: f64) -> &Profile {
-        if self.b.is_some() && t >= self.b.as_ref().unwrap().right {
-            &self.b.as_ref().unwrap().profile
-        } else if self.a.is_some() && t >= self.a.as_ref().unwrap().right {
-            &self.a.as_ref().unwrap().profile
-        } else {
-            &self.p_min
+        match (&self.a, &self.b) {
+            (_, Some(b)) if t >= b.right => &b.profile,
+            (Some(a), _) if t >= a.right => &a.profile,
+            _ => &self.p_min,
         }
     }
 }
@@ -189,10 +237,12 @@ impl fmt::Display for Block {
     }
 }
 
+/// An open interval of blocked durations
 #[derive(Debug, Clone, Default)]
 pub struct Interval {
     pub left: f64,
     pub right: f64,
+    /// The extremal profile with the duration `right`, which is extended to durations beyond
     pub profile: Profile,
 }
 
@@ -207,9 +257,11 @@ impl Interval {
 
     pub fn from_profiles(profile_left: &Profile, profile_right: &Profile) -> Self {
         let left_duration = *profile_left.t_sum.last().unwrap()
+            + profile_left.pre_dwell
             + profile_left.brake.duration
             + profile_left.accel.duration;
         let right_duration = *profile_right.t_sum.last().unwrap()
+            + profile_right.pre_dwell
             + profile_right.brake.duration
             + profile_right.accel.duration;
 
@@ -222,7 +274,7 @@ impl Interval {
         Self {
             left,
             right,
-            profile: profile.clone(),
+            profile: *profile,
         }
     }
 }
@@ -1,4 +1,4 @@
-use crate::util::integrate;
+use crate::util::{integrate, integrate_with_snap};
 use std::f64;
 
 const EPS: f64 = 2.2e-14;
@@ -13,11 +13,14 @@ fn v_at_a_zero(v0: f64, a0: f64, j: f64) -> f64 {
     v0 + (a0 * a0) / (2.0 * j)
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BrakeProfile {
     pub duration: f64,
     pub t: [f64; 2],
     pub j: [f64; 2],
+    /// Snap of each phase, which is only non-zero for a blending pre-trajectory
+    pub s: [f64; 2],
     pub a: [f64; 2],
     pub v: [f64; 2],
     pub p: [f64; 2],
@@ -29,12 +32,33 @@ impl BrakeProfile {
             duration: 0.0,
             t: [0.0; 2],
             j: [0.0; 2],
+            s: [0.0; 2],
             a: [0.0; 2],
             v: [0.0; 2],
             p: [0.0; 2],
         }
     }
 
+    /// Get the position, velocity, and acceleration at the time `t` since the start of the brake
+    /// trajectory. The time is clamped to the duration.
+    pub fn state_at(&self, t: f64) -> (f64, f64, f64) {
+        let t = t.clamp(0.0, self.duration);
+        let (index, t) = if t < self.t[0] || self.t[1] <= 0.0 {
+            (0, t)
+        } else {
+            (1, t - self.t[0])
+        };
+        let (p, v, a, _) = integrate_with_snap(
+            t,
+            self.p[index],
+            self.v[index],
+            self.a[index],
+            self.j[index],
+            self.s[index],
+        );
+        (p, v, a)
+    }
+
     fn acceleration_brake(
         &mut self,
         v0: f64,
@@ -110,6 +134,7 @@ impl BrakeProfile {
         self.t[1] = 0.0;
         self.j[0] = 0.0;
         self.j[1] = 0.0;
+        self.s = [0.0; 2];
 
         if j_max == 0.0 || a_max == 0.0 || a_min == 0.0 {
             return; // Ignore braking for zero-limits
@@ -142,6 +167,7 @@ impl BrakeProfile {
         self.t[1] = 0.0;
         self.j[0] = 0.0;
         self.j[1] = 0.0;
+        self.s = [0.0; 2];
         self.a[0] = 0.0;
         self.a[1] = 0.0;
 
@@ -163,6 +189,7 @@ impl BrakeProfile {
         self.t[1] = 0.0;
         self.j[0] = 0.0;
         self.j[1] = 0.0;
+        self.s = [0.0; 2];
 
         if j_max == 0.0 {
             return; // Ignore braking for zero-limits
@@ -170,10 +197,127 @@ impl BrakeProfile {
 
         if a0 > a_max {
             self.j[0] = -j_max;
-            self.t[0] = (a0 - a_max) / j_max + std::f64::EPSILON;
+            self.t[0] = (a0 - a_max) / j_max + f64::EPSILON;
         } else if a0 < a_min {
             self.j[0] = j_max;
-            self.t[0] = -(a0 - a_min) / j_max + std::f64::EPSILON;
+            self.t[0] = -(a0 - a_min) / j_max + f64::EPSILON;
+        }
+    }
+
+    /// Ramp down the jerk of a replaced trajectory linearly to zero within the duration, so that
+    /// the jerk is continuous where the trajectory is replaced and its magnitude only decreases.
+    /// Returns false and keeps the pre-trajectory empty if the velocity or acceleration would
+    /// exceed their limits, or the DoF would need to brake afterwards.
+    pub fn get_blending_trajectory(
+        &mut self,
+        v0: f64,
+        a0: f64,
+        j0: f64,
+        duration: f64,
+        v_max: f64,
+        v_min: f64,
+        a_max: f64,
+        a_min: f64,
+        j_max: f64,
+    ) -> bool {
+        self.t = [0.0; 2];
+        self.j = [0.0; 2];
+        self.s = [0.0; 2];
+        if j0 == 0.0 || duration <= 0.0 {
+            return false;
+        }
+
+        // The acceleration changes monotonically, so the velocity is extremal where it crosses
+        // zero, i.e. at t^2 - 2 T t - 2 a0 T / j0 = 0 within the window of duration T
+        let s = -j0 / duration;
+        let (_, v_end, a_end, _) = integrate_with_snap(duration, 0.0, v0, a0, j0, s);
+        let d = duration * duration + 2.0 * a0 * duration / j0;
+        let t_to_a_zero = if d >= 0.0 { duration - d.sqrt() } else { 0.0 };
+        let v_extremum = if 0.0 < t_to_a_zero && t_to_a_zero < duration {
+            integrate_with_snap(t_to_a_zero, 0.0, v0, a0, j0, s).1
+        } else {
+            v_end
+        };
+        let is_within_limits = [v_end, v_extremum]
+            .iter()
+            .all(|v| v_min <= *v && *v <= v_max)
+            && a_min <= a_end
+            && a_end <= a_max;
+        if !is_within_limits {
+            return false;
+        }
+
+        let mut brake = BrakeProfile::new();
+        brake.get_position_brake_trajectory(v_end, a_end, v_max, v_min, a_max, a_min, j_max);
+        if brake.t[0] > 0.0 || brake.t[1] > 0.0 {
+            return false;
+        }
+
+        self.t = [duration, 0.0];
+        self.j = [j0, 0.0];
+        self.s = [s, 0.0];
+        true
+    }
+
+    /// Turn the motion around with the smallest excursion, i.e. ramp the acceleration against
+    /// the velocity as fast as possible and hold it at its limit until the velocity vanishes.
+    /// Afterwards, the DoF can move back without leaving the range it has already passed.
+    pub fn get_turnaround_trajectory(
+        &mut self,
+        v0: f64,
+        a0: f64,
+        a_max: f64,
+        a_min: f64,
+        j_max: f64,
+    ) {
+        self.t = [0.0; 2];
+        self.j = [0.0; 2];
+        self.s = [0.0; 2];
+
+        if j_max == 0.0 || a_max == 0.0 || a_min == 0.0 {
+            return; // Ignore braking for zero-limits
+        }
+
+        // The direction of the motion, also if it only starts due to the acceleration
+        let (direction, a_limit) = if v0 > 0.0 || (v0 == 0.0 && a0 > 0.0) {
+            (1.0, -a_min)
+        } else if v0 < 0.0 || a0 < 0.0 {
+            (-1.0, a_max)
+        } else {
+            return;
+        };
+        let v = direction * v0;
+        let a = direction * a0;
+
+        let t_to_a_limit = ((a + a_limit) / j_max).max(0.0);
+        let v_at_a_limit = v_at_t(v, a, -j_max, t_to_a_limit);
+        self.j[0] = -direction * j_max;
+        if v_at_a_limit <= 0.0 {
+            self.t[0] = (a + (a * a + 2.0 * j_max * v).sqrt()) / j_max;
+        } else {
+            self.t[0] = t_to_a_limit;
+            self.t[1] = v_at_a_limit / a_limit;
+        }
+    }
+
+    /// Turn the motion around with the smallest excursion for infinite jerk limits, see
+    /// `get_turnaround_trajectory`
+    pub fn get_second_order_turnaround_trajectory(&mut self, v0: f64, a_max: f64, a_min: f64) {
+        self.t = [0.0; 2];
+        self.j = [0.0; 2];
+        self.s = [0.0; 2];
+        self.a = [0.0; 2];
+
+        if a_max == 0.0 || a_min == 0.0 {
+            return; // Ignore braking for zero-limits
+        }
+
+        if v0 > 0.0 {
+            self.a[0] = a_min;
+            self.t[0] = -v0 / a_min;
+        } else if v0 < 0.0 {
+            self.a[0] = a_max;
+            self.t[0] = -v0 / a_max;
         }
     }
 
@@ -182,6 +326,7 @@ impl BrakeProfile {
         self.t[1] = 0.0;
         self.j[0] = 0.0;
         self.j[1] = 0.0;
+        self.s = [0.0; 2];
     }
 
     pub fn finalize(&mut self, ps: &mut f64, vs: &mut f64, as_: &mut f64) {
@@ -194,7 +339,7 @@ impl BrakeProfile {
         self.p[0] = *ps;
         self.v[0] = *vs;
         self.a[0] = *as_;
-        let result = integrate(self.t[0], *ps, *vs, *as_, self.j[0]);
+        let result = integrate_with_snap(self.t[0], *ps, *vs, *as_, self.j[0], self.s[0]);
         *ps = result.0;
         *vs = result.1;
         *as_ = result.2;
@@ -204,7 +349,7 @@ impl BrakeProfile {
             self.p[1] = *ps;
             self.v[1] = *vs;
             self.a[1] = *as_;
-            let result = integrate(self.t[1], *ps, *vs, *as_, self.j[1]);
+            let result = integrate_with_snap(self.t[1], *ps, *vs, *as_, self.j[1], self.s[1]);
             *ps = result.0;
             *vs = result.1;
             *as_ = result.2;
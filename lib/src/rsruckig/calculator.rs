@@ -0,0 +1,104 @@
+//! Offline calculation of trajectories, like the `Calculator` class of the C++ version.
+
+use crate::calculator_target::TargetCalculator;
+use crate::error::{RuckigError, RuckigErrorHandler};
+use crate::input_parameter::InputParameter;
+use crate::result::RuckigResult;
+use crate::ruckig::Ruckig;
+use crate::settings::Settings;
+use crate::trajectory::Trajectory;
+
+/// Calculates trajectories into a `Trajectory` without the state of an online trajectory and
+/// without a control cycle, e.g. for planners and previews. As there is no control cycle,
+/// `DurationDiscretization::Discrete` is rejected, while `DiscreteStep` is supported.
+#[derive(Debug)]
+pub struct Calculator<const DOF: usize, E: RuckigErrorHandler> {
+    ruckig: Ruckig<DOF, E>,
+}
+
+impl<const DOF: usize, E: RuckigErrorHandler> Default for Calculator<DOF, E> {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl<const DOF: usize, E: RuckigErrorHandler> Calculator<DOF, E> {
+    pub fn new(degrees_of_freedom: Option<usize>) -> Self {
+        Self::new_with_waypoints(0, degrees_of_freedom)
+    }
+
+    /// Create a calculator that accepts up to `max_number_of_waypoints` intermediate positions
+    pub fn new_with_waypoints(
+        max_number_of_waypoints: usize,
+        degrees_of_freedom: Option<usize>,
+    ) -> Self {
+        Self {
+            ruckig: Ruckig::new_with_waypoints(max_number_of_waypoints, degrees_of_freedom, 0.0),
+        }
+    }
+
+    /// Create a calculator that behaves according to the given settings
+    pub fn with_settings(degrees_of_freedom: Option<usize>, settings: Settings) -> Self {
+        let mut calculator = Self::new(degrees_of_freedom);
+        calculator.ruckig.calculator.settings = settings;
+        calculator
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.ruckig.calculator.settings
+    }
+
+    pub fn settings_mut(&mut self) -> &mut Settings {
+        &mut self.ruckig.calculator.settings
+    }
+
+    /// The calculator of the state-to-state trajectories, e.g. for the DoF and stage of the last
+    /// failure, the relaxed limits, or the trace of the `debug-trace` feature
+    pub fn target_calculator(&self) -> &TargetCalculator<DOF> {
+        &self.ruckig.calculator
+    }
+
+    /// Validate the input for the trajectory calculation
+    pub fn validate_input(
+        &self,
+        input: &InputParameter<DOF>,
+        check_current_state_within_limits: bool,
+        check_target_state_within_limits: bool,
+    ) -> Result<bool, RuckigError> {
+        self.ruckig.validate_input(
+            input,
+            check_current_state_within_limits,
+            check_target_state_within_limits,
+        )
+    }
+
+    /// Calculate the trajectory of the input, see `Ruckig::calculate`
+    pub fn calculate(
+        &mut self,
+        input: &InputParameter<DOF>,
+        traj: &mut Trajectory<DOF>,
+    ) -> Result<RuckigResult, RuckigError> {
+        self.ruckig.calculate(input, traj)
+    }
+
+    /// Calculate the trajectory within a budget in microseconds, see
+    /// `Ruckig::calculate_with_budget`
+    pub fn calculate_with_budget(
+        &mut self,
+        input: &InputParameter<DOF>,
+        traj: &mut Trajectory<DOF>,
+        max_micros: f64,
+    ) -> Result<RuckigResult, RuckigError> {
+        self.ruckig.calculate_with_budget(input, traj, max_micros)
+    }
+
+    /// Calculate a time-optimal stop from the current state of the input, see
+    /// `Ruckig::calculate_stop`
+    pub fn calculate_stop(
+        &mut self,
+        input: &InputParameter<DOF>,
+        traj: &mut Trajectory<DOF>,
+    ) -> Result<RuckigResult, RuckigError> {
+        self.ruckig.calculate_stop(input, traj)
+    }
+}
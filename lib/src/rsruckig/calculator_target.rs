@@ -1,58 +1,215 @@
 //! Calculation of a state-to-state trajectory.
-use crate::error::{RuckigError, RuckigErrorHandler};
-use crate::util::DataArrayOrVec;
+use crate::error::{
+    DofState, LimitExcess, LoosenedTolerance, RuckigError, RuckigErrorHandler, RuckigWarning,
+};
+use crate::util::{DataArrayOrVec, Deadline};
 use crate::{
     block::Block,
-    input_parameter::{ControlInterface, DurationDiscretization, InputParameter, Synchronization},
-    position_first_step1::PositionFirstOrderStep1,
-    position_first_step2::PositionFirstOrderStep2,
-    position_second_step1::PositionSecondOrderStep1,
-    position_second_step2::PositionSecondOrderStep2,
-    position_third_step1::PositionThirdOrderStep1,
-    position_third_step2::PositionThirdOrderStep2,
-    profile::{ControlSigns, Direction, Profile, ReachedLimits},
-    result::RuckigResult,
-    trajectory::Trajectory,
-    velocity_second_step1::VelocitySecondOrderStep1,
-    velocity_second_step2::VelocitySecondOrderStep2,
-    velocity_third_step1::VelocityThirdOrderStep1,
-    velocity_third_step2::VelocityThirdOrderStep2,
+    input_parameter::{ControlInterface, InputParameter, Synchronization},
+    profile::{self, ControlSigns, Direction, Profile, ReachedLimits},
+    result::{CalculationStage, RuckigResult},
+    settings::{DurationRounding, Settings},
+    trajectory::{DofSynchronization, Trajectory},
 };
+#[cfg(feature = "second-order")]
+use crate::acceleration_second_step1::AccelerationSecondOrderStep1;
+#[cfg(feature = "second-order")]
+use crate::acceleration_second_step2::AccelerationSecondOrderStep2;
+#[cfg(feature = "third-order")]
+use crate::acceleration_third_step1::AccelerationThirdOrderStep1;
+#[cfg(feature = "third-order")]
+use crate::acceleration_third_step2::AccelerationThirdOrderStep2;
+#[cfg(feature = "first-order")]
+use crate::position_first_step1::PositionFirstOrderStep1;
+#[cfg(feature = "first-order")]
+use crate::position_first_step2::PositionFirstOrderStep2;
+#[cfg(feature = "second-order")]
+use crate::position_second_step1::PositionSecondOrderStep1;
+#[cfg(feature = "second-order")]
+use crate::position_second_step2::PositionSecondOrderStep2;
+#[cfg(feature = "third-order")]
+use crate::position_third_step1::PositionThirdOrderStep1;
+#[cfg(feature = "third-order")]
+use crate::position_third_step2::PositionThirdOrderStep2;
+#[cfg(feature = "first-order")]
+use crate::velocity_first_step1::VelocityFirstOrderStep1;
+#[cfg(feature = "first-order")]
+use crate::velocity_first_step2::VelocityFirstOrderStep2;
+#[cfg(feature = "second-order")]
+use crate::velocity_second_step1::VelocitySecondOrderStep1;
+#[cfg(feature = "second-order")]
+use crate::velocity_second_step2::VelocitySecondOrderStep2;
+#[cfg(feature = "third-order")]
+use crate::velocity_third_step1::VelocityThirdOrderStep1;
+#[cfg(feature = "third-order")]
+use crate::velocity_third_step2::VelocityThirdOrderStep2;
+#[cfg(feature = "debug-trace")]
+use crate::trace::{self, ProfileAttempt};
+
+// Tolerance of the position limits, matching the precision of the final position
+pub(crate) const POSITION_LIMIT_PRECISION: f64 = 1e-8;
+
+// Fraction of the maximal duration above which a warning is reported
+const DURATION_WARNING_RATIO: f64 = 0.9;
+
+// First relative relaxation of the limits in the relaxed mode, and its growth per retry
+const MINIMAL_RELAXATION: f64 = 1e-9;
+const RELAXATION_GROWTH: f64 = 1e3;
+
+// First relative reduction of the limits when step 2 is retried, and its growth per retry
+const STEP2_PERTURBATION: f64 = 1e-14;
+const STEP2_PERTURBATION_GROWTH: f64 = 10.0;
+
+/// Input of a DoF that determines its block in step 1
+#[derive(Debug, Clone, PartialEq)]
+struct Step1Input {
+    control_interface: ControlInterface,
+    turnaround: bool,
+    values: [f64; 17],
+}
+
+impl Step1Input {
+    fn new<const DOF: usize>(
+        inp: &InputParameter<DOF>,
+        dof: usize,
+        control_interface: &ControlInterface,
+        min_velocity: f64,
+        min_acceleration: f64,
+        max_relaxation: f64,
+        blending: (f64, f64),
+        turnaround: bool,
+    ) -> Self {
+        Self {
+            control_interface: control_interface.clone(),
+            turnaround,
+            values: [
+                inp.current_position[dof],
+                inp.current_velocity[dof],
+                inp.current_acceleration[dof],
+                inp.target_position[dof],
+                inp.target_velocity[dof],
+                inp.target_acceleration[dof],
+                inp.max_velocity[dof],
+                min_velocity,
+                inp.max_acceleration[dof],
+                min_acceleration,
+                inp.max_jerk[dof],
+                inp.max_position.as_ref().map_or(f64::INFINITY, |v| v[dof]),
+                inp.min_position.as_ref().map_or(f64::NEG_INFINITY, |v| v[dof]),
+                inp.pre_dwell.unwrap_or(0.0),
+                max_relaxation,
+                blending.0,
+                blending.1,
+            ],
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct TargetCalculator<const DOF: usize> {
-    eps: f64,
-    return_error_at_maximal_duration: bool,
+    pub settings: Settings,
     new_phase_control: DataArrayOrVec<f64, DOF>,
     pd: DataArrayOrVec<f64, DOF>,
+    /// Possible synchronization times, i.e. 3 * DoFs + 1 values. Like `roundings` and `idx`, it is
+    /// only allocated in the constructor, so that the calculation itself doesn't allocate.
     possible_t_syncs: Vec<f64>,
+    /// Time added to each possible t_sync by the discretization, negative if rounded down
+    roundings: Vec<f64>,
     idx: Vec<usize>,
     blocks: DataArrayOrVec<Block, DOF>,
     inp_min_velocity: DataArrayOrVec<f64, DOF>,
     inp_min_acceleration: DataArrayOrVec<f64, DOF>,
     inp_per_dof_control_interface: DataArrayOrVec<ControlInterface, DOF>,
     inp_per_dof_synchronization: DataArrayOrVec<Synchronization, DOF>,
+    /// Whether each DoF has the highest priority and keeps its time-optimal profile
+    prioritized: DataArrayOrVec<bool, DOF>,
+    /// Whether each DoF belongs to another synchronization group than the one being synchronized
+    excluded: DataArrayOrVec<bool, DOF>,
     pub degrees_of_freedom: usize,
+    /// The DoF that caused the latest calculation to fail, if the error refers to a single DoF
+    pub failed_dof: Option<usize>,
+    /// The stage in which the latest calculation failed
+    pub failed_stage: Option<CalculationStage>,
+    /// Relative relaxation of the limits of each DoF in the latest calculation
+    relaxations: DataArrayOrVec<f64, DOF>,
+    /// Largest fallback tolerance of the profile checks of each DoF in the latest calculation,
+    /// or zero for the default tolerances
+    check_tolerances: DataArrayOrVec<f64, DOF>,
+    /// Input, relaxation and check tolerance of the current block of each DoF, so that step 1
+    /// is only repeated for DoFs whose input changed since the last calculation
+    step1_inputs: DataArrayOrVec<Option<(Step1Input, f64, f64)>, DOF>,
+    /// The DoFs whose limits were relaxed since the last `Ruckig::calculate`, see
+    /// `Settings::max_relaxation`
+    pub relaxed_limits: Vec<LimitExcess>,
+    /// The DoFs whose check tolerances were loosened since the last `Ruckig::calculate`, see
+    /// `Settings::fallback_tolerances`
+    pub loosened_tolerances: Vec<LoosenedTolerance>,
+    /// The profiles that were checked in step 1 and 2 of the latest calculation, and why they
+    /// were rejected. DoFs whose step 1 is reused from the previous calculation have no step 1
+    /// attempts, and attempts beyond `trace::MAX_ATTEMPTS_PER_DOF` per DoF are dropped.
+    #[cfg(feature = "debug-trace")]
+    pub trace: Vec<ProfileAttempt>,
+    /// Time budget of the running calculation, checked before the profile of each DoF
+    pub(crate) deadline: Option<Deadline>,
+    /// Whether the running calculation replaces a trajectory in `Ruckig::update`, whose jerk
+    /// is blended, see `Settings::replan_blending`
+    pub(crate) is_blending: bool,
+    /// Jerk of each DoF of the replaced trajectory
+    pub(crate) blending_jerk: DataArrayOrVec<f64, DOF>,
+    /// Whether each DoF turns around with the smallest excursion before its profile, as its
+    /// direct profile leaves the position limits
+    turnarounds: DataArrayOrVec<bool, DOF>,
+    /// The position limits error of the running calculation, which is only reported if a
+    /// turnaround doesn't resolve it
+    position_limits_error: Option<RuckigError>,
 }
 
 impl<const DOF: usize> TargetCalculator<DOF> {
     pub fn new(dofs: Option<usize>) -> Self {
+        Self::with_settings(dofs, Settings::default())
+    }
+
+    pub fn with_settings(dofs: Option<usize>, settings: Settings) -> Self {
         Self {
             blocks: DataArrayOrVec::new(dofs, Block::default()),
             inp_min_velocity: DataArrayOrVec::new(dofs, 0.0),
             inp_min_acceleration: DataArrayOrVec::new(dofs, 0.0),
             inp_per_dof_control_interface: DataArrayOrVec::new(dofs, ControlInterface::default()),
             inp_per_dof_synchronization: DataArrayOrVec::new(dofs, Synchronization::default()),
+            prioritized: DataArrayOrVec::new(dofs, false),
+            excluded: DataArrayOrVec::new(dofs, false),
             new_phase_control: DataArrayOrVec::new(dofs, 0.0),
             pd: DataArrayOrVec::new(dofs, 0.0),
             possible_t_syncs: vec![0.0; 3 * dofs.unwrap_or(DOF) + 1],
+            roundings: vec![0.0; 3 * dofs.unwrap_or(DOF) + 1],
             idx: vec![0; 3 * dofs.unwrap_or(DOF) + 1],
-            eps: f64::EPSILON,
-            return_error_at_maximal_duration: true,
+            settings,
             degrees_of_freedom: dofs.unwrap_or(DOF),
+            failed_dof: None,
+            failed_stage: None,
+            relaxations: DataArrayOrVec::new(dofs, 0.0),
+            check_tolerances: DataArrayOrVec::new(dofs, 0.0),
+            step1_inputs: DataArrayOrVec::new(dofs, None),
+            relaxed_limits: Vec::with_capacity(dofs.unwrap_or(DOF)),
+            loosened_tolerances: Vec::with_capacity(dofs.unwrap_or(DOF)),
+            #[cfg(feature = "debug-trace")]
+            trace: Vec::with_capacity(trace::MAX_ATTEMPTS_PER_DOF * dofs.unwrap_or(DOF)),
+            deadline: None,
+            is_blending: false,
+            blending_jerk: DataArrayOrVec::new(dofs, 0.0),
+            turnarounds: DataArrayOrVec::new(dofs, false),
+            position_limits_error: None,
         }
     }
 
+    /// The blocks of all DoFs from step 1 of the latest calculation, i.e. their time-optimal
+    /// durations and the durations that they can't be synchronized to. A synchronized duration is
+    /// feasible if no block is blocked at it. A disabled DoF has a block of zero duration, and
+    /// the blocks are only complete if step 1 succeeded for all DoFs.
+    pub fn get_blocks(&self) -> &DataArrayOrVec<Block, DOF> {
+        &self.blocks
+    }
+
     // Allowing mutable reference to self for the sake of better performance.
     #[allow(clippy::wrong_self_convention)]
     fn is_input_collinear(
@@ -62,6 +219,7 @@ impl<const DOF: usize> TargetCalculator<DOF> {
         limiting_dof: usize,
     ) -> bool {
         // Check that vectors pd, v0, a0, vf, af are collinear
+        let eps = self.settings.eps;
         for dof in 0..self.degrees_of_freedom {
             self.pd[dof] = inp.target_position[dof] - inp.current_position[dof];
         }
@@ -74,24 +232,24 @@ impl<const DOF: usize> TargetCalculator<DOF> {
             }
 
             if self.inp_per_dof_control_interface[dof] == ControlInterface::Position
-                && self.pd[dof].abs() > self.eps
+                && self.pd[dof].abs() > eps
             {
                 scale_vector = Some(&self.pd);
                 scale_dof = Some(dof);
                 break;
-            } else if inp.current_velocity[dof].abs() > self.eps {
+            } else if inp.current_velocity[dof].abs() > eps {
                 scale_vector = Some(&inp.current_velocity);
                 scale_dof = Some(dof);
                 break;
-            } else if inp.current_acceleration[dof].abs() > self.eps {
+            } else if inp.current_acceleration[dof].abs() > eps {
                 scale_vector = Some(&inp.current_acceleration);
                 scale_dof = Some(dof);
                 break;
-            } else if inp.target_velocity[dof].abs() > self.eps {
+            } else if inp.target_velocity[dof].abs() > eps {
                 scale_vector = Some(&inp.target_velocity);
                 scale_dof = Some(dof);
                 break;
-            } else if inp.target_acceleration[dof].abs() > self.eps {
+            } else if inp.target_acceleration[dof].abs() > eps {
                 scale_vector = Some(&inp.target_acceleration);
                 scale_dof = Some(dof);
                 break;
@@ -130,11 +288,11 @@ impl<const DOF: usize> TargetCalculator<DOF> {
 
             let current_scale = scale_vector.unwrap()[dof];
             if (self.inp_per_dof_control_interface[dof] == ControlInterface::Position
-                && (self.pd[dof] - pd_scale * current_scale).abs() > self.eps)
-                || (inp.current_velocity[dof] - v0_scale * current_scale).abs() > self.eps
-                || (inp.current_acceleration[dof] - a0_scale * current_scale).abs() > self.eps
-                || (inp.target_velocity[dof] - vf_scale * current_scale).abs() > self.eps
-                || (inp.target_acceleration[dof] - af_scale * current_scale).abs() > self.eps
+                && (self.pd[dof] - pd_scale * current_scale).abs() > eps)
+                || (inp.current_velocity[dof] - v0_scale * current_scale).abs() > eps
+                || (inp.current_acceleration[dof] - a0_scale * current_scale).abs() > eps
+                || (inp.target_velocity[dof] - vf_scale * current_scale).abs() > eps
+                || (inp.target_acceleration[dof] - af_scale * current_scale).abs() > eps
             {
                 return false;
             }
@@ -145,12 +303,52 @@ impl<const DOF: usize> TargetCalculator<DOF> {
         true
     }
 
+    fn set_per_dof_synchronization(&mut self, inp: &InputParameter<DOF>) {
+        self.inp_per_dof_synchronization.fill(inp.synchronization.clone());
+        if let Some(per_dof_synchronization) = &inp.per_dof_synchronization {
+            for (dof, value) in per_dof_synchronization.iter().enumerate() {
+                *self.inp_per_dof_synchronization.get_mut(dof).unwrap() = value.clone();
+            }
+        }
+    }
+
+    /// Mark the enabled DoFs with the highest priority, if the priorities differ, and exclude
+    /// them from the synchronization. Returns the longest minimal duration of the marked DoFs
+    /// together with its DoF.
+    fn prioritize(&mut self, inp: &InputParameter<DOF>) -> Option<(f64, usize)> {
+        self.prioritized.fill(false);
+        let per_dof_priority = inp.per_dof_priority.as_ref()?;
+        let excluded = &self.excluded;
+        let enabled_dofs =
+            || (0..self.degrees_of_freedom).filter(|&dof| inp.enabled[dof] && !excluded[dof]);
+        let highest = enabled_dofs().map(|dof| per_dof_priority[dof]).max()?;
+        if enabled_dofs().all(|dof| per_dof_priority[dof] == highest) {
+            return None;
+        }
+
+        let mut prioritized_duration: Option<(f64, usize)> = None;
+        for dof in 0..self.degrees_of_freedom {
+            if !inp.enabled[dof] || self.excluded[dof] || per_dof_priority[dof] != highest {
+                continue;
+            }
+
+            self.prioritized[dof] = true;
+            self.inp_per_dof_synchronization[dof] = Synchronization::None;
+            let t_min = self.blocks[dof].t_min;
+            if prioritized_duration.is_none_or(|(t_priority, _)| t_min > t_priority) {
+                prioritized_duration = Some((t_min, dof));
+            }
+        }
+        prioritized_duration
+    }
+
     fn synchronize(
         &mut self,
         t_min: Option<f64>,
         t_sync: &mut f64,
         limiting_dof: &mut Option<usize>,
         profiles: &mut DataArrayOrVec<Profile, { DOF }>,
+        discretization_remainder: &mut f64,
         discrete_duration: bool,
         delta_time: f64,
     ) -> bool {
@@ -185,15 +383,29 @@ impl<const DOF: usize> TargetCalculator<DOF> {
         self.possible_t_syncs[3 * self.degrees_of_freedom] = t_min.unwrap_or(f64::INFINITY);
         any_interval |= t_min.is_some();
 
+        self.roundings.fill(0.0);
         if discrete_duration {
-            for possible_t_sync in &mut self.possible_t_syncs {
+            for (possible_t_sync, rounding) in
+                self.possible_t_syncs.iter_mut().zip(self.roundings.iter_mut())
+            {
                 if possible_t_sync.is_infinite() {
                     continue;
                 }
 
                 let remainder = *possible_t_sync % delta_time; // in [0, delta_time)
-                if remainder > self.eps {
-                    *possible_t_sync += delta_time - remainder;
+                if remainder > self.settings.discretization_tolerance {
+                    let round_up = match self.settings.duration_rounding {
+                        DurationRounding::Up => true,
+                        DurationRounding::Down => false,
+                        DurationRounding::Nearest => 2.0 * remainder >= delta_time,
+                    };
+                    if round_up {
+                        *rounding = delta_time - remainder;
+                        *possible_t_sync += delta_time - remainder;
+                    } else {
+                        *rounding = -remainder;
+                        *possible_t_sync -= remainder;
+                    }
                 }
             }
         }
@@ -211,32 +423,47 @@ impl<const DOF: usize> TargetCalculator<DOF> {
             self.idx[i] = i;
         }
 
-        // Sort the values in the range
-        self.idx[0..idx_end].sort_by(|&i, &j| {
+        // Sort the values in the range, and equally rounded values by their original value. The
+        // index breaks the remaining ties like a stable sort, but without its allocation.
+        self.idx[0..idx_end].sort_unstable_by(|&i, &j| {
             self.possible_t_syncs[i]
                 .partial_cmp(&self.possible_t_syncs[j])
                 .unwrap()
+                .then_with(|| self.roundings[j].partial_cmp(&self.roundings[i]).unwrap())
+                .then(i.cmp(&j))
         });
 
-        // Start at last tmin (or worse)
+        // Start at last tmin (or worse). A duration that was rounded down is mostly infeasible,
+        // and then the multiple above it is kept as fallback, unless a shorter duration follows.
+        let mut fallback: Option<(usize, f64, f64)> = None;
+        let mut selected: Option<(usize, f64, f64)> = None;
         for &i in &self.idx[(self.degrees_of_freedom - 1)..] {
             let possible_t_sync = self.possible_t_syncs[i];
-            let mut is_blocked = false;
-            for dof in 0..self.degrees_of_freedom {
-                if self.inp_per_dof_synchronization[dof] == Synchronization::None {
-                    continue; // inner dof loop
-                }
-                if self.blocks[dof].is_blocked(possible_t_sync) {
-                    is_blocked = true;
-                    break; // inner dof loop
-                }
+            if possible_t_sync.is_infinite() {
+                continue;
             }
-            if is_blocked || possible_t_sync < t_min.unwrap_or(0.0) || possible_t_sync.is_infinite()
-            {
+            if !self.is_feasible_t_sync(possible_t_sync, t_min) {
+                let rounded_up = possible_t_sync + delta_time;
+                if self.roundings[i] < 0.0
+                    && fallback.is_none_or(|(_, t_fallback, _)| rounded_up < t_fallback)
+                    && self.is_feasible_t_sync(rounded_up, t_min)
+                {
+                    fallback = Some((i, rounded_up, self.roundings[i] + delta_time));
+                }
                 continue;
             }
+            selected = Some((i, possible_t_sync, self.roundings[i]));
+            break;
+        }
+        if let Some((_, t_fallback, _)) = fallback {
+            if selected.is_none_or(|(_, t_selected, _)| t_fallback < t_selected) {
+                selected = fallback;
+            }
+        }
 
+        if let Some((i, possible_t_sync, rounding)) = selected {
             *t_sync = possible_t_sync;
+            *discretization_remainder = rounding;
             if i == 3 * self.degrees_of_freedom {
                 // Optional t_min
                 *limiting_dof = None;
@@ -245,25 +472,15 @@ impl<const DOF: usize> TargetCalculator<DOF> {
 
             let div = i / self.degrees_of_freedom;
             *limiting_dof = Some(i % self.degrees_of_freedom);
+            if self.excluded[i % self.degrees_of_freedom] {
+                // Keep the profile of another synchronization group
+                return true;
+            }
+            let block = &self.blocks[limiting_dof.unwrap()];
             match div {
-                0 => {
-                    profiles[limiting_dof.unwrap()] =
-                        self.blocks[limiting_dof.unwrap()].p_min.clone();
-                }
-                1 => {
-                    profiles[limiting_dof.unwrap()] = self.blocks[limiting_dof.unwrap()]
-                        .a
-                        .clone()
-                        .unwrap()
-                        .profile;
-                }
-                2 => {
-                    profiles[limiting_dof.unwrap()] = self.blocks[limiting_dof.unwrap()]
-                        .b
-                        .clone()
-                        .unwrap()
-                        .profile;
-                }
+                0 => profiles[limiting_dof.unwrap()] = block.p_min,
+                1 => profiles[limiting_dof.unwrap()] = block.a.as_ref().unwrap().profile,
+                2 => profiles[limiting_dof.unwrap()] = block.b.as_ref().unwrap().profile,
                 _ => {}
             }
             return true;
@@ -272,6 +489,59 @@ impl<const DOF: usize> TargetCalculator<DOF> {
         false
     }
 
+    /// Whether the duration is blocked for none of the synchronized DoFs and reaches the optional
+    /// minimum duration
+    fn is_feasible_t_sync(&self, t_sync: f64, t_min: Option<f64>) -> bool {
+        t_sync >= t_min.unwrap_or(0.0)
+            && (0..self.degrees_of_freedom).all(|dof| {
+                self.inp_per_dof_synchronization[dof] == Synchronization::None
+                    || !self.blocks[dof].is_blocked(t_sync)
+            })
+    }
+
+    /// Whether the profile stays within the (optional) position limits of the given DoF
+    pub(crate) fn is_within_position_limits(
+        inp: &InputParameter<DOF>,
+        dof: usize,
+        profile: &Profile,
+    ) -> bool {
+        if inp.max_position.is_none() && inp.min_position.is_none() {
+            return true;
+        }
+
+        let extrema = profile.get_position_extrema();
+        let p_max = inp.max_position.as_ref().map_or(f64::INFINITY, |v| v[dof]);
+        let p_min = inp.min_position.as_ref().map_or(f64::NEG_INFINITY, |v| v[dof]);
+        extrema.max <= p_max + POSITION_LIMIT_PRECISION
+            && extrema.min >= p_min - POSITION_LIMIT_PRECISION
+    }
+
+    /// Whether the DoF is moving and its trajectory isn't turned around yet, see
+    /// `BrakeProfile::get_turnaround_trajectory`
+    fn can_turn_around(&self, inp: &InputParameter<DOF>, dof: usize) -> bool {
+        !self.turnarounds[dof]
+            && self.inp_per_dof_control_interface[dof] == ControlInterface::Position
+            && inp.pre_dwell.unwrap_or(0.0) <= 0.0
+            && inp.max_acceleration[dof].is_finite()
+            && (inp.current_velocity[dof] != 0.0
+                || (inp.max_jerk[dof].is_finite() && inp.current_acceleration[dof] != 0.0))
+    }
+
+    /// Whether the running calculation has exceeded its time budget
+    fn is_interrupted(&self) -> bool {
+        self.deadline.as_ref().is_some_and(Deadline::is_exceeded)
+    }
+
+    /// Remember the failing DoF and stage before passing the error to the handler
+    pub(crate) fn fail<T: RuckigErrorHandler>(
+        &mut self,
+        error: RuckigError,
+    ) -> Result<RuckigResult, RuckigError> {
+        self.failed_dof = error.dof();
+        self.failed_stage = error.stage();
+        T::handle_calculator_error(error)
+    }
+
     /// Calculate the time-optimal waypoint-based trajectory.
     pub fn calculate<T: RuckigErrorHandler>(
         &mut self,
@@ -279,7 +549,141 @@ impl<const DOF: usize> TargetCalculator<DOF> {
         traj: &mut Trajectory<DOF>,
         delta_time: f64,
     ) -> Result<RuckigResult, RuckigError> {
+        self.failed_dof = None;
+        self.failed_stage = None;
+        self.relaxations.fill(0.0);
+        self.check_tolerances.fill(0.0);
+
+        self.turnarounds.fill(false);
+
+        #[cfg(feature = "debug-trace")]
+        trace::start(std::mem::take(&mut self.trace));
+        let result = loop {
+            let result = self.calculate_trajectory::<T>(inp, traj, delta_time);
+
+            // Check the synchronized profiles against the position limits
+            if result == Ok(RuckigResult::Working) {
+                self.position_limits_error = (0..self.degrees_of_freedom)
+                    .find(|&dof| {
+                        inp.enabled[dof]
+                            && !Self::is_within_position_limits(inp, dof, &traj.profiles[0][dof])
+                    })
+                    .map(|dof| RuckigError::PositionLimitsViolation {
+                        dof,
+                        state: DofState::from_input(inp, dof),
+                    });
+            }
+
+            // If a DoF leaves its position limits, turn it around first and calculate again
+            let Some(error) = self.position_limits_error.take() else {
+                break result;
+            };
+            match error.dof().filter(|&dof| self.can_turn_around(inp, dof)) {
+                Some(dof) => self.turnarounds[dof] = true,
+                None => break self.fail::<T>(error),
+            }
+        };
+        #[cfg(feature = "debug-trace")]
+        {
+            self.trace = trace::take();
+        }
+        let result = result?;
+        if result != RuckigResult::Working {
+            return Ok(result);
+        }
+
+        // Report how far the profiles of relaxed DoFs exceed their original limits
         for dof in 0..self.degrees_of_freedom {
+            if self.relaxations[dof] > 0.0 {
+                let excess = Self::get_limit_excess(inp, dof, self.relaxations[dof], traj);
+                T::handle_warning(RuckigWarning::RelaxedLimits(excess));
+                self.relaxed_limits.push(excess);
+            }
+        }
+
+        // Report the DoFs whose profiles were only found with loosened check tolerances
+        for dof in 0..self.degrees_of_freedom {
+            if self.check_tolerances[dof] > 0.0 {
+                let loosened = LoosenedTolerance {
+                    dof,
+                    tolerance: self.check_tolerances[dof],
+                };
+                T::handle_warning(RuckigWarning::LoosenedTolerance(loosened));
+                self.loosened_tolerances.push(loosened);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Magnitude by which the profile of the DoF exceeds its limits and misses its target state
+    fn get_limit_excess(
+        inp: &InputParameter<DOF>,
+        dof: usize,
+        relaxation: f64,
+        traj: &Trajectory<DOF>,
+    ) -> LimitExcess {
+        let p = &traj.profiles[0][dof];
+        let v_max = inp.max_velocity[dof];
+        let v_min = inp.min_velocity.as_ref().map_or(-v_max, |v| v[dof]);
+        let a_max = inp.max_acceleration[dof];
+        let a_min = inp.min_acceleration.as_ref().map_or(-a_max, |a| a[dof]);
+
+        let mut excess = LimitExcess {
+            dof,
+            relaxation,
+            velocity: 0.0,
+            acceleration: 0.0,
+            jerk: 0.0,
+            boundary: 0.0,
+        };
+        for i in 0..7 {
+            if p.t[i] <= 0.0 {
+                continue;
+            }
+
+            // The velocity is extremal at the phase boundaries or where the acceleration vanishes
+            let mut v_low = p.v[i].min(p.v[i + 1]);
+            let mut v_high = p.v[i].max(p.v[i + 1]);
+            if p.j[i] != 0.0 {
+                let t_extremum = -p.a[i] / p.j[i];
+                if 0.0 < t_extremum && t_extremum < p.t[i] {
+                    let v_extremum = p.v[i] + t_extremum * (p.a[i] + 0.5 * t_extremum * p.j[i]);
+                    v_low = v_low.min(v_extremum);
+                    v_high = v_high.max(v_extremum);
+                }
+            }
+
+            excess.velocity = excess.velocity.max(v_high - v_max).max(v_min - v_low);
+            excess.acceleration = excess
+                .acceleration
+                .max(p.a[i].max(p.a[i + 1]) - a_max)
+                .max(a_min - p.a[i].min(p.a[i + 1]));
+            excess.jerk = excess.jerk.max(p.j[i].abs() - inp.max_jerk[dof]);
+        }
+        excess.boundary = (p.p[7] - p.pf)
+            .abs()
+            .max((p.v[7] - p.vf).abs())
+            .max((p.a[7] - p.af).abs());
+        excess
+    }
+
+    fn calculate_trajectory<T: RuckigErrorHandler>(
+        &mut self,
+        inp: &InputParameter<DOF>,
+        traj: &mut Trajectory<DOF>,
+        delta_time: f64,
+    ) -> Result<RuckigResult, RuckigError> {
+        traj.resize(1);
+        traj.discretization_remainder = 0.0;
+        traj.snap_window = 0.0;
+        traj.synchronizations.fill(DofSynchronization::default());
+
+        for dof in 0..self.degrees_of_freedom {
+            if self.is_interrupted() {
+                return Ok(RuckigResult::Interrupted);
+            }
+
             let p = &mut traj.profiles[0][dof];
 
             self.inp_min_velocity[dof] = inp
@@ -292,21 +696,14 @@ impl<const DOF: usize> TargetCalculator<DOF> {
                 .as_ref()
                 .map_or(-inp.max_acceleration[dof], |v| v[dof]);
 
-            self.inp_per_dof_control_interface =
-                DataArrayOrVec::new(Some(self.degrees_of_freedom), inp.control_interface.clone());
+            self.inp_per_dof_control_interface.fill(inp.control_interface.clone());
             if let Some(per_dof_control_interface) = &inp.per_dof_control_interface {
                 for (dof, value) in per_dof_control_interface.iter().enumerate() {
                     *self.inp_per_dof_control_interface.get_mut(dof).unwrap() = value.clone();
                 }
             }
 
-            self.inp_per_dof_synchronization =
-                DataArrayOrVec::new(Some(self.degrees_of_freedom), inp.synchronization.clone());
-            if let Some(per_dof_synchronization) = &inp.per_dof_synchronization {
-                for (dof, value) in per_dof_synchronization.iter().enumerate() {
-                    *self.inp_per_dof_synchronization.get_mut(dof).unwrap() = value.clone();
-                }
-            }
+            self.set_per_dof_synchronization(inp);
 
             if !inp.enabled[dof] {
                 if let Some(last) = p.p.last_mut() {
@@ -321,10 +718,12 @@ impl<const DOF: usize> TargetCalculator<DOF> {
                 if let Some(last) = p.t_sum.last_mut() {
                     *last = 0.0;
                 }
+                p.pre_dwell = 0.0;
 
                 self.blocks[dof].t_min = 0.0;
                 self.blocks[dof].a = None;
                 self.blocks[dof].b = None;
+                self.step1_inputs[dof] = None;
                 continue;
             }
 
@@ -349,6 +748,15 @@ impl<const DOF: usize> TargetCalculator<DOF> {
                                 .unwrap_or(-inp.max_acceleration[dof]),
                             inp.max_jerk[dof],
                         );
+                        if self.turnarounds[dof] && p.brake.t == [0.0; 2] {
+                            p.brake.get_turnaround_trajectory(
+                                inp.current_velocity[dof],
+                                inp.current_acceleration[dof],
+                                inp.max_acceleration[dof],
+                                self.inp_min_acceleration[dof],
+                                inp.max_jerk[dof],
+                            );
+                        }
                     } else if !inp.max_acceleration[dof].is_infinite() {
                         p.brake.get_second_order_position_brake_trajectory(
                             inp.current_velocity[dof],
@@ -365,6 +773,13 @@ impl<const DOF: usize> TargetCalculator<DOF> {
                                 .cloned()
                                 .unwrap_or(-inp.max_acceleration[dof]),
                         );
+                        if self.turnarounds[dof] && p.brake.t == [0.0; 2] {
+                            p.brake.get_second_order_turnaround_trajectory(
+                                inp.current_velocity[dof],
+                                inp.max_acceleration[dof],
+                                self.inp_min_acceleration[dof],
+                            );
+                        }
                     }
                     p.set_boundary(
                         &inp.current_position[dof],
@@ -398,7 +813,17 @@ impl<const DOF: usize> TargetCalculator<DOF> {
                         inp.target_acceleration[dof],
                     );
                 }
-                _ => {}
+                ControlInterface::Acceleration => {
+                    // The acceleration is ramped from any current state, without a brake
+                    p.brake.get_second_order_velocity_brake_trajectory();
+                    p.set_boundary_for_velocity(
+                        inp.current_position[dof],
+                        inp.current_velocity[dof],
+                        inp.current_acceleration[dof],
+                        inp.target_velocity[dof],
+                        inp.target_acceleration[dof],
+                    );
+                }
             }
             // Finalize pre & post-trajectories
             if !inp.max_jerk[dof].is_infinite() {
@@ -406,85 +831,134 @@ impl<const DOF: usize> TargetCalculator<DOF> {
             } else if !inp.max_acceleration[dof].is_infinite() {
                 p.brake
                     .finalize_second_order(&mut p.p[0], &mut p.v[0], &mut p.a[0]);
+            } else {
+                p.brake.duration = 0.0;
             }
 
-            let mut found_profile = false;
-            match self.inp_per_dof_control_interface[dof] {
-                ControlInterface::Position => {
-                    if !inp.max_jerk[dof].is_infinite() {
-                        let mut step1 = PositionThirdOrderStep1::new(
-                            p.p[0],
-                            p.v[0],
-                            p.a[0],
-                            p.pf,
-                            p.vf,
-                            p.af,
-                            inp.max_velocity[dof],
-                            inp.min_velocity
-                                .as_ref()
-                                .map_or(-inp.max_velocity[dof], |v| v[dof]),
-                            inp.max_acceleration[dof],
-                            inp.min_acceleration
-                                .as_ref()
-                                .map_or(-inp.max_acceleration[dof], |v| v[dof]),
-                            inp.max_jerk[dof],
-                        );
-                        found_profile = step1.get_profile(p, &mut self.blocks[dof]);
-                    } else if !inp.max_acceleration[dof].is_infinite() {
-                        let mut step1 = PositionSecondOrderStep1::new(
-                            p.p[0],
-                            p.v[0],
-                            p.pf,
-                            p.vf,
-                            inp.max_velocity[dof],
-                            inp.min_velocity
-                                .as_ref()
-                                .map_or(-inp.max_velocity[dof], |v| v[dof]),
-                            inp.max_acceleration[dof],
-                            inp.min_acceleration
-                                .as_ref()
-                                .map_or(-inp.max_acceleration[dof], |v| v[dof]),
-                        );
-                        found_profile = step1.get_profile(p, &mut self.blocks[dof]);
-                    } else {
-                        let mut step1 = PositionFirstOrderStep1::new(
-                            p.p[0],
-                            p.pf,
-                            inp.max_velocity[dof],
-                            inp.min_velocity
-                                .as_ref()
-                                .map_or(-inp.max_velocity[dof], |v| v[dof]),
-                        );
-                        found_profile = step1.get_profile(p, &mut self.blocks[dof]);
+            // Hold the current state at rest before the motion, so that it is included in the
+            // duration of every DoF
+            p.pre_dwell = inp.pre_dwell.filter(|pre_dwell| *pre_dwell > 0.0).unwrap_or(0.0);
+
+            let mut blending = (0.0, 0.0);
+            if p.brake.duration > 0.0 && self.turnarounds[dof] {
+                // The turnaround takes the place of the blending pre-trajectory
+            } else if p.brake.duration > 0.0 {
+                #[cfg(feature = "log")]
+                log::warn!(
+                    "DoF {} violates its limits, a brake pre-trajectory of {} s is added.",
+                    dof,
+                    p.brake.duration
+                );
+                T::handle_warning(RuckigWarning::BrakeTrajectory {
+                    dof,
+                    duration: p.brake.duration,
+                });
+            } else if self.is_blending && p.pre_dwell == 0.0 && inp.max_jerk[dof].is_finite() {
+                // Ramp down the jerk of the replaced trajectory within the pre-trajectory, where
+                // only the position interface limits the velocity. A pre-dwell starts at rest
+                // anyway.
+                let (v_max, v_min) = match self.inp_per_dof_control_interface[dof] {
+                    ControlInterface::Position => {
+                        (inp.max_velocity[dof], self.inp_min_velocity[dof])
                     }
+                    _ => (f64::INFINITY, f64::NEG_INFINITY),
+                };
+                if p.brake.get_blending_trajectory(
+                    p.v[0],
+                    p.a[0],
+                    self.blending_jerk[dof],
+                    self.settings.replan_blending,
+                    v_max,
+                    v_min,
+                    inp.max_acceleration[dof],
+                    self.inp_min_acceleration[dof],
+                    inp.max_jerk[dof],
+                ) {
+                    p.brake.finalize(&mut p.p[0], &mut p.v[0], &mut p.a[0]);
+                    blending = (self.blending_jerk[dof], self.settings.replan_blending);
                 }
-                ControlInterface::Velocity => {
-                    if !inp.max_jerk[dof].is_infinite() {
-                        let mut step1 = VelocityThirdOrderStep1::new(
-                            p.v[0],
-                            p.a[0],
-                            p.vf,
-                            p.af,
-                            inp.max_acceleration[dof],
-                            inp.min_acceleration
-                                .as_ref()
-                                .map_or(-inp.max_acceleration[dof], |v| v[dof]),
-                            inp.max_jerk[dof],
-                        );
-                        found_profile = step1.get_profile(p, &mut self.blocks[dof]);
-                    } else {
-                        let mut step1 = VelocitySecondOrderStep1::new(
-                            p.v[0],
-                            p.vf,
-                            inp.max_acceleration[dof],
-                            inp.min_acceleration
-                                .as_ref()
-                                .map_or(-inp.max_acceleration[dof], |v| v[dof]),
-                        );
-                        found_profile = step1.get_profile(p, &mut self.blocks[dof]);
-                    }
+            }
+
+            // Reuse the block of the previous calculation if the input of the DoF is unchanged
+            let step1_input = Step1Input::new(
+                inp,
+                dof,
+                &self.inp_per_dof_control_interface[dof],
+                self.inp_min_velocity[dof],
+                self.inp_min_acceleration[dof],
+                self.settings.max_relaxation,
+                blending,
+                self.turnarounds[dof],
+            );
+            if let Some((_, relaxation, tolerance)) = self.step1_inputs[dof]
+                .as_ref()
+                .filter(|(cached, _, _)| *cached == step1_input)
+            {
+                self.relaxations[dof] = *relaxation;
+                self.check_tolerances[dof] = *tolerance;
+                traj.independent_min_durations[dof] = self.blocks[dof].t_min;
+                continue;
+            }
+            self.step1_inputs[dof] = None;
+
+            #[cfg(feature = "debug-trace")]
+            trace::begin(dof, CalculationStage::Step1);
+            let mut found_profile = calculate_step1_profile(
+                &self.inp_per_dof_control_interface[dof],
+                p,
+                &mut self.blocks[dof],
+                inp.max_velocity[dof],
+                self.inp_min_velocity[dof],
+                inp.max_acceleration[dof],
+                self.inp_min_acceleration[dof],
+                inp.max_jerk[dof],
+            );
+
+            // Retry with escalating check tolerances, if configured
+            for &tolerance in &self.settings.fallback_tolerances {
+                if found_profile {
+                    break;
+                }
+                let _loosened = profile::CheckToleranceGuard::loosen(tolerance);
+                found_profile = calculate_step1_profile(
+                    &self.inp_per_dof_control_interface[dof],
+                    p,
+                    &mut self.blocks[dof],
+                    inp.max_velocity[dof],
+                    self.inp_min_velocity[dof],
+                    inp.max_acceleration[dof],
+                    self.inp_min_acceleration[dof],
+                    inp.max_jerk[dof],
+                );
+                if found_profile {
+                    self.check_tolerances[dof] = tolerance;
+                }
+            }
+
+            // In the relaxed mode, retry with increasingly relaxed limits
+            self.relaxations[dof] = 0.0;
+            let mut relaxation = 0.0;
+            while !found_profile && relaxation < self.settings.max_relaxation {
+                relaxation = if relaxation == 0.0 {
+                    MINIMAL_RELAXATION
+                } else {
+                    relaxation * RELAXATION_GROWTH
+                }
+                .min(self.settings.max_relaxation);
+                let factor = 1.0 + relaxation;
+                found_profile = calculate_step1_profile(
+                    &self.inp_per_dof_control_interface[dof],
+                    p,
+                    &mut self.blocks[dof],
+                    factor * inp.max_velocity[dof],
+                    factor * self.inp_min_velocity[dof],
+                    factor * inp.max_acceleration[dof],
+                    factor * self.inp_min_acceleration[dof],
+                    factor * inp.max_jerk[dof],
+                );
+                if found_profile {
+                    self.relaxations[dof] = relaxation;
                 }
-                ControlInterface::Acceleration => {}
             }
 
             if !found_profile {
@@ -496,42 +970,126 @@ impl<const DOF: usize> TargetCalculator<DOF> {
                         == 0.0
                     || inp.max_jerk[dof] == 0.0;
                 if has_zero_limits {
-                    return T::handle_calculator_error(
-                        &format!(
-                            "zero limits conflict in step 1, dof: {} input: {}",
-                            dof, inp
-                        )
-                        .to_owned(),
-                        RuckigResult::ErrorZeroLimits,
-                    );
+                    return self.fail::<T>(RuckigError::ZeroLimitsConflict {
+                        dof,
+                        state: DofState::from_input(inp, dof),
+                    });
                 }
-                return T::handle_calculator_error(
-                    &format!("error in step 1, dof: {} input: {}", dof, inp).to_owned(),
-                    RuckigResult::ErrorExecutionTimeCalculation,
-                );
+                return self.fail::<T>(RuckigError::Step1Failure {
+                    dof,
+                    state: DofState::from_input(inp, dof),
+                });
+            }
+
+            // Skip extremal profiles that leave the position limits. The error is only reported by
+            // `calculate` if a turnaround of the DoF doesn't resolve it.
+            if !self.blocks[dof].retain_profiles(|profile| {
+                Self::is_within_position_limits(inp, dof, profile)
+            }) {
+                self.position_limits_error = Some(RuckigError::PositionLimitsConflict {
+                    dof,
+                    state: DofState::from_input(inp, dof),
+                });
+                return Ok(RuckigResult::ErrorPositionalLimits);
             }
 
             traj.independent_min_durations[dof] = self.blocks[dof].t_min;
+            self.step1_inputs[dof] = Some((
+                step1_input,
+                self.relaxations[dof],
+                self.check_tolerances[dof],
+            ));
         }
-        let discrete_duration = inp.duration_discretization == DurationDiscretization::Discrete;
+        let discretization_step = inp.duration_discretization.step(delta_time);
+        let discrete_duration = discretization_step.is_some();
+        let delta_time = discretization_step.unwrap_or(delta_time);
         if self.degrees_of_freedom == 1 && inp.minimum_duration.is_none() && !discrete_duration {
             traj.duration = self.blocks[0].t_min;
-            traj.profiles[0][0] = self.blocks[0].p_min.clone();
+            traj.profiles[0][0] = self.blocks[0].p_min;
             traj.cumulative_times[0] = traj.duration;
+            if inp.enabled[0] {
+                traj.synchronizations[0] = DofSynchronization {
+                    synchronization: match self.inp_per_dof_synchronization[0] {
+                        Synchronization::None => Synchronization::None,
+                        _ => Synchronization::Time,
+                    },
+                    is_limiting: true,
+                };
+            }
             return Ok(RuckigResult::Working);
         }
 
+        self.excluded.fill(false);
+        let Some(per_dof_group) = &inp.per_dof_group else {
+            return self.synchronize_group::<T>(inp, traj, delta_time, discrete_duration);
+        };
+
+        // Synchronize each group on its own, starting at the first enabled DoF of the group
+        let mut duration: f64 = 0.0;
+        let mut discretization_remainder = 0.0;
+        for first_dof in 0..self.degrees_of_freedom {
+            let group = per_dof_group[first_dof];
+            if !inp.enabled[first_dof]
+                || (0..first_dof).any(|dof| inp.enabled[dof] && per_dof_group[dof] == group)
+            {
+                continue;
+            }
+
+            self.set_per_dof_synchronization(inp);
+            for dof in 0..self.degrees_of_freedom {
+                self.excluded[dof] = inp.enabled[dof] && per_dof_group[dof] != group;
+                if self.excluded[dof] {
+                    self.inp_per_dof_synchronization[dof] = Synchronization::None;
+                }
+            }
+
+            let result = self.synchronize_group::<T>(inp, traj, delta_time, discrete_duration)?;
+            if result != RuckigResult::Working {
+                return Ok(result);
+            }
+            if traj.duration > duration {
+                duration = traj.duration;
+                discretization_remainder = traj.discretization_remainder;
+            }
+        }
+        self.excluded.fill(false);
+        traj.duration = duration;
+        traj.discretization_remainder = discretization_remainder;
+        traj.cumulative_times[0] = duration;
+        Ok(RuckigResult::Working)
+    }
+
+    /// Synchronize all DoFs that aren't excluded by another synchronization group
+    fn synchronize_group<T: RuckigErrorHandler>(
+        &mut self,
+        inp: &InputParameter<DOF>,
+        traj: &mut Trajectory<DOF>,
+        delta_time: f64,
+        discrete_duration: bool,
+    ) -> Result<RuckigResult, RuckigError> {
+        // The DoFs with the highest priority are excluded from the synchronization, but the
+        // other DoFs take at least as long as the slowest of them
+        let prioritized_duration = self.prioritize(inp);
+        let t_min = match prioritized_duration {
+            Some((t_priority, _)) => Some(
+                inp.minimum_duration
+                    .map_or(t_priority, |minimum_duration| minimum_duration.max(t_priority)),
+            ),
+            None => inp.minimum_duration,
+        };
+
         let mut limiting_dof: Option<usize> = None; // The DoF that doesn't need step 2
         let found_synchronization = self.synchronize(
-            inp.minimum_duration,
+            t_min,
             &mut traj.duration,
             &mut limiting_dof,
             &mut traj.profiles[0],
+            &mut traj.discretization_remainder,
             discrete_duration,
             delta_time,
         );
         if !found_synchronization {
-            let mut has_zero_limits = false;
+            let mut zero_limits_dof = None;
             for dof in 0..self.degrees_of_freedom {
                 if inp.max_acceleration[dof] == 0.0
                     || inp
@@ -541,41 +1099,100 @@ impl<const DOF: usize> TargetCalculator<DOF> {
                         == 0.0
                     || inp.max_jerk[dof] == 0.0
                 {
-                    has_zero_limits = true;
+                    zero_limits_dof = Some(dof);
                     break;
                 }
             }
 
-            if has_zero_limits {
-                return T::handle_calculator_error(
-                    &format!("zero limits conflict with other degrees of freedom in time synchronization {}", traj.duration),
-                    RuckigResult::ErrorZeroLimits);
+            if let Some(dof) = zero_limits_dof {
+                return self.fail::<T>(
+                    RuckigError::ZeroLimitsSynchronizationConflict {
+                        dof,
+                        t_sync: traj.duration,
+                    },
+                );
+            }
+            return self.fail::<T>(RuckigError::SynchronizationFailure {
+                t_sync: traj.duration,
+            });
+        }
+        #[cfg(feature = "log")]
+        if discrete_duration {
+            // Shortest duration that would have been found without the rounding
+            let continuous_duration = self
+                .possible_t_syncs
+                .iter()
+                .zip(self.roundings.iter())
+                .map(|(possible_t_sync, rounding)| possible_t_sync - rounding)
+                .filter(|t| t.is_finite() && self.is_feasible_t_sync(*t, t_min))
+                .fold(f64::INFINITY, f64::min);
+            if traj.duration - continuous_duration > delta_time {
+                log::debug!(
+                    "discrete duration {} s is more than one cycle longer than the duration {} s.",
+                    traj.duration,
+                    continuous_duration
+                );
             }
-            return T::handle_calculator_error(
-                &format!("error in time synchronization: {}", traj.duration),
-                RuckigResult::ErrorSynchronizationCalculation,
-            );
         }
+        if let Some((t_priority, dof)) = prioritized_duration {
+            if limiting_dof.is_none() && traj.duration == t_priority {
+                limiting_dof = Some(dof);
+            }
+        }
+
         // None Synchronization
         for dof in 0..self.degrees_of_freedom {
-            if inp.enabled[dof] && self.inp_per_dof_synchronization[dof] == Synchronization::None {
-                traj.profiles[0][dof] = self.blocks[dof].p_min.clone();
+            if inp.enabled[dof]
+                && !self.excluded[dof]
+                && self.inp_per_dof_synchronization[dof] == Synchronization::None
+            {
+                traj.profiles[0][dof] = self.blocks[dof].p_min;
                 if self.blocks[dof].t_min > traj.duration {
                     traj.duration = self.blocks[dof].t_min;
+                    traj.discretization_remainder = 0.0;
                     limiting_dof = Some(dof);
                 }
             }
         }
         traj.cumulative_times[0] = traj.duration;
 
-        if self.return_error_at_maximal_duration && traj.duration > 7.6e3 {
+        // The DoFs without synchronization keep their profile, the others are time synchronized
+        // unless they are phase synchronized or skipped below
+        for dof in 0..self.degrees_of_freedom {
+            if !inp.enabled[dof] || self.excluded[dof] {
+                continue;
+            }
+            let is_unsynchronized = self.prioritized[dof]
+                || self.inp_per_dof_synchronization[dof] == Synchronization::None
+                    && !discrete_duration;
+            traj.synchronizations[dof] = DofSynchronization {
+                synchronization: if is_unsynchronized {
+                    Synchronization::None
+                } else {
+                    Synchronization::Time
+                },
+                is_limiting: Some(dof) == limiting_dof,
+            };
+        }
+
+        let maximal_duration = self.settings.maximal_duration;
+        if self.settings.return_error_at_maximal_duration && traj.duration > maximal_duration {
             return Ok(RuckigResult::ErrorTrajectoryDuration);
         }
+        if traj.duration > DURATION_WARNING_RATIO * maximal_duration {
+            T::handle_warning(RuckigWarning::DurationNearLimit {
+                duration: traj.duration,
+                limit: maximal_duration,
+            });
+        }
 
         if (traj.duration - 0.0).abs() < f64::EPSILON {
             // Copy all profiles for end state
             for dof in 0..self.degrees_of_freedom {
-                traj.profiles[0][dof] = self.blocks[dof].p_min.clone();
+                if self.excluded[dof] {
+                    continue;
+                }
+                traj.profiles[0][dof] = self.blocks[dof].p_min;
             }
             return Ok(RuckigResult::Working);
         }
@@ -589,15 +1206,20 @@ impl<const DOF: usize> TargetCalculator<DOF> {
             return Ok(RuckigResult::Working);
         }
 
-        // Phase Synchronization
-        if let Some(limiting_dof_value) = limiting_dof {
+        // Phase Synchronization, copying the timing of the limiting DoF, which doesn't hold a
+        // duration rounded to the control cycle
+        if let Some(limiting_dof_value) = limiting_dof.filter(|_| !discrete_duration) {
             if self
                 .inp_per_dof_synchronization
                 .iter()
                 .any(|s| s == &Synchronization::Phase)
             {
-                let p_limiting = traj.profiles[0][limiting_dof_value].clone();
-                if self.is_input_collinear(inp, p_limiting.direction, limiting_dof_value) {
+                // Only the timing, signs, and limits of the limiting DoF are copied to the others
+                let t_limiting = traj.profiles[0][limiting_dof_value].t;
+                let control_signs_limiting = traj.profiles[0][limiting_dof_value].control_signs;
+                let direction_limiting = traj.profiles[0][limiting_dof_value].direction;
+                let limits_limiting = traj.profiles[0][limiting_dof_value].limits;
+                if self.is_input_collinear(inp, direction_limiting, limiting_dof_value) {
                     let mut found_time_synchronization = true;
                     for dof in 0..self.degrees_of_freedom {
                         if !inp.enabled[dof]
@@ -608,10 +1230,16 @@ impl<const DOF: usize> TargetCalculator<DOF> {
                         }
 
                         let p = &mut traj.profiles[0][dof];
-                        let t_profile = traj.duration - p.brake.duration - p.accel.duration;
+                        let t_profile =
+                            traj.duration - p.pre_dwell - p.brake.duration - p.accel.duration;
+                        #[cfg(feature = "debug-trace")]
+                        {
+                            trace::begin(dof, CalculationStage::Synchronization);
+                            trace::set_strategy("phase synchronization");
+                        }
 
-                        p.t = p_limiting.t; // Copy timing information from limiting DoF
-                        p.control_signs = p_limiting.control_signs.clone();
+                        p.t = t_limiting; // Copy timing information from limiting DoF
+                        p.control_signs = control_signs_limiting;
 
                         match self.inp_per_dof_control_interface[dof] {
                             ControlInterface::Position => match p.control_signs {
@@ -695,7 +1323,7 @@ impl<const DOF: usize> TargetCalculator<DOF> {
                                                 self.inp_min_acceleration[dof],
                                                 inp.max_jerk[dof],
                                             );
-                                    } else {
+                                    } else if !inp.max_acceleration[dof].is_infinite() {
                                         found_time_synchronization &= p
                                             .check_for_second_order_velocity_with_timing_a_limits(
                                                 ControlSigns::UDDU,
@@ -705,6 +1333,9 @@ impl<const DOF: usize> TargetCalculator<DOF> {
                                                 inp.max_acceleration[dof],
                                                 self.inp_min_acceleration[dof],
                                             );
+                                    } else {
+                                        // A velocity step can't be scaled to another profile
+                                        found_time_synchronization = false;
                                     }
                                 }
                                 ControlSigns::UDUD => {
@@ -719,7 +1350,7 @@ impl<const DOF: usize> TargetCalculator<DOF> {
                                                 self.inp_min_acceleration[dof],
                                                 inp.max_jerk[dof],
                                             );
-                                    } else {
+                                    } else if !inp.max_acceleration[dof].is_infinite() {
                                         found_time_synchronization &= p
                                             .check_for_second_order_velocity_with_timing_a_limits(
                                                 ControlSigns::UDUD,
@@ -729,13 +1360,19 @@ impl<const DOF: usize> TargetCalculator<DOF> {
                                                 inp.max_acceleration[dof],
                                                 self.inp_min_acceleration[dof],
                                             );
+                                    } else {
+                                        // A velocity step can't be scaled to another profile
+                                        found_time_synchronization = false;
                                     }
                                 }
                             },
-                            _ => {}
+                            ControlInterface::Acceleration => {
+                                // The acceleration interface is only time synchronized
+                                found_time_synchronization = false;
+                            }
                         }
 
-                        p.limits = p_limiting.limits; // After check method call to set correct limits
+                        p.limits = limits_limiting; // After check method call to set correct limits
                     }
 
                     if found_time_synchronization
@@ -744,126 +1381,169 @@ impl<const DOF: usize> TargetCalculator<DOF> {
                             .iter()
                             .all(|s| s == &Synchronization::Phase || s == &Synchronization::None)
                     {
+                        for dof in 0..self.degrees_of_freedom {
+                            if self.inp_per_dof_synchronization[dof] == Synchronization::Phase
+                                && inp.enabled[dof]
+                                && !self.excluded[dof]
+                            {
+                                traj.synchronizations[dof].synchronization =
+                                    Synchronization::Phase;
+                            }
+                        }
                         return Ok(RuckigResult::Working);
                     }
                 }
             }
         }
 
+        if self
+            .inp_per_dof_synchronization
+            .iter()
+            .any(|s| s == &Synchronization::Phase)
+        {
+            #[cfg(feature = "log")]
+            log::warn!("phase synchronization is not possible, using time synchronization.");
+            T::handle_warning(RuckigWarning::PhaseSynchronizationFallback);
+        }
+
         // Time Synchronization
         for dof in 0..self.degrees_of_freedom {
             let skip_synchronization = (Some(dof) == limiting_dof
                 || self.inp_per_dof_synchronization[dof] == Synchronization::None)
-                && !discrete_duration;
-            if !inp.enabled[dof] || skip_synchronization {
+                && !discrete_duration
+                || self.prioritized[dof];
+            if !inp.enabled[dof] || self.excluded[dof] || skip_synchronization {
                 continue;
             }
+            if self.is_interrupted() {
+                return Ok(RuckigResult::Interrupted);
+            }
 
             let p = &mut traj.profiles[0][dof];
-            let t_profile = traj.duration - p.brake.duration - p.accel.duration;
+            let t_profile = traj.duration - p.pre_dwell - p.brake.duration - p.accel.duration;
 
             if self.inp_per_dof_synchronization[dof] == Synchronization::TimeIfNecessary
-                && inp.target_velocity[dof].abs() < self.eps
-                && inp.target_acceleration[dof].abs() < self.eps
+                && inp.target_velocity[dof].abs() < self.settings.eps
+                && inp.target_acceleration[dof].abs() < self.settings.eps
             {
-                traj.profiles[0][dof] = self.blocks[dof].p_min.clone();
+                traj.profiles[0][dof] = self.blocks[dof].p_min;
+                traj.synchronizations[dof].synchronization = Synchronization::TimeIfNecessary;
                 continue;
             }
 
             // Check if the final time corresponds to an extremal profile calculated in step 1
-            if (t_profile - self.blocks[dof].t_min).abs() < 2.0 * self.eps {
-                traj.profiles[0][dof] = self.blocks[dof].p_min.clone();
+            if (t_profile - self.blocks[dof].t_min).abs() < 2.0 * self.settings.eps {
+                traj.profiles[0][dof] = self.blocks[dof].p_min;
                 continue;
             } else if let Some(a) = &self.blocks[dof].a {
-                if (t_profile - a.right).abs() < 2.0 * self.eps {
-                    traj.profiles[0][dof] = a.profile.clone();
+                if (t_profile - a.right).abs() < 2.0 * self.settings.eps {
+                    traj.profiles[0][dof] = a.profile;
                     continue;
                 }
             } else if let Some(b) = &self.blocks[dof].b {
-                if (t_profile - b.right).abs() < 2.0 * self.eps {
-                    traj.profiles[0][dof] = b.profile.clone();
+                if (t_profile - b.right).abs() < 2.0 * self.settings.eps {
+                    traj.profiles[0][dof] = b.profile;
                     continue;
                 }
             }
 
-            let mut found_time_synchronization = false;
-            match self.inp_per_dof_control_interface[dof] {
-                ControlInterface::Position => {
-                    if !inp.max_jerk[dof].is_infinite() {
-                        let mut step2 = PositionThirdOrderStep2::new(
-                            t_profile,
-                            p.p[0],
-                            p.v[0],
-                            p.a[0],
-                            p.pf,
-                            p.vf,
-                            p.af,
-                            inp.max_velocity[dof],
-                            self.inp_min_velocity[dof],
-                            inp.max_acceleration[dof],
-                            self.inp_min_acceleration[dof],
-                            inp.max_jerk[dof],
-                        );
-                        found_time_synchronization = step2.get_profile(p);
-                    } else if !inp.max_acceleration[dof].is_infinite() {
-                        let mut step2 = PositionSecondOrderStep2::new(
-                            t_profile,
-                            p.p[0],
-                            p.v[0],
-                            p.pf,
-                            p.vf,
-                            inp.max_velocity[dof],
-                            self.inp_min_velocity[dof],
-                            inp.max_acceleration[dof],
-                            self.inp_min_acceleration[dof],
-                        );
-                        found_time_synchronization = step2.get_profile(p);
-                    } else {
-                        let mut step2 = PositionFirstOrderStep2::new(
-                            t_profile,
-                            p.p[0],
-                            p.pf,
-                            inp.max_velocity[dof],
-                            self.inp_min_velocity[dof],
-                        );
-                        found_time_synchronization = step2.get_profile(p);
-                    }
+            let factor = 1.0 + self.relaxations[dof];
+            // Guess the shape of the synchronized profile from the time-optimal one
+            let guess = self
+                .settings
+                .guess_step2_profile
+                .then_some(self.blocks[dof].p_min.limits);
+            #[cfg(feature = "debug-trace")]
+            trace::begin(dof, CalculationStage::Step2);
+            let mut found_time_synchronization = calculate_step2_profile(
+                &self.inp_per_dof_control_interface[dof],
+                t_profile,
+                p,
+                guess,
+                factor * inp.max_velocity[dof],
+                factor * self.inp_min_velocity[dof],
+                factor * inp.max_acceleration[dof],
+                factor * self.inp_min_acceleration[dof],
+                factor * inp.max_jerk[dof],
+            );
+
+            // Retry with slightly reduced limits if the profile is missed due to the numerical
+            // precision
+            let mut perturbation = STEP2_PERTURBATION;
+            for _ in 0..self.settings.step2_retries {
+                if found_time_synchronization {
+                    break;
                 }
-                ControlInterface::Velocity => {
-                    if !inp.max_jerk[dof].is_infinite() {
-                        let mut step2 = VelocityThirdOrderStep2::new(
-                            t_profile,
-                            p.v[0],
-                            p.a[0],
-                            p.vf,
-                            p.af,
-                            inp.max_acceleration[dof],
-                            self.inp_min_acceleration[dof],
-                            inp.max_jerk[dof],
-                        );
-                        found_time_synchronization = step2.get_profile(p);
-                    } else {
-                        let mut step2 = VelocitySecondOrderStep2::new(
-                            t_profile,
-                            p.v[0],
-                            p.vf,
-                            inp.max_acceleration[dof],
-                            self.inp_min_acceleration[dof],
-                        );
-                        found_time_synchronization = step2.get_profile(p);
-                    }
+                let factor = (1.0 + self.relaxations[dof]) * (1.0 - perturbation);
+                found_time_synchronization = calculate_step2_profile(
+                    &self.inp_per_dof_control_interface[dof],
+                    t_profile,
+                    p,
+                    guess,
+                    factor * inp.max_velocity[dof],
+                    factor * self.inp_min_velocity[dof],
+                    factor * inp.max_acceleration[dof],
+                    factor * self.inp_min_acceleration[dof],
+                    factor * inp.max_jerk[dof],
+                );
+                perturbation *= STEP2_PERTURBATION_GROWTH;
+            }
+
+            // Retry with escalating check tolerances, if configured
+            for &tolerance in &self.settings.fallback_tolerances {
+                if found_time_synchronization {
+                    break;
+                }
+                let _loosened = profile::CheckToleranceGuard::loosen(tolerance);
+                found_time_synchronization = calculate_step2_profile(
+                    &self.inp_per_dof_control_interface[dof],
+                    t_profile,
+                    p,
+                    guess,
+                    factor * inp.max_velocity[dof],
+                    factor * self.inp_min_velocity[dof],
+                    factor * inp.max_acceleration[dof],
+                    factor * self.inp_min_acceleration[dof],
+                    factor * inp.max_jerk[dof],
+                );
+                if found_time_synchronization {
+                    self.check_tolerances[dof] = self.check_tolerances[dof].max(tolerance);
                 }
-                _ => {}
             }
 
-            if !found_time_synchronization {
-                return T::handle_calculator_error(
-                    &format!(
-                        "error in step 2 in dof: {} for t sync: {} input: {}",
-                        dof, traj.duration, inp
-                    ),
-                    RuckigResult::ErrorExecutionTimeCalculation,
+            // In the relaxed mode, retry with limits relaxed beyond those of step 1. As the profile
+            // is usually missed at the boundary, the check tolerances are loosened alike.
+            let mut relaxation = self.relaxations[dof];
+            while !found_time_synchronization && relaxation < self.settings.max_relaxation {
+                relaxation = if relaxation == 0.0 {
+                    MINIMAL_RELAXATION
+                } else {
+                    relaxation * RELAXATION_GROWTH
+                }
+                .min(self.settings.max_relaxation);
+                let factor = 1.0 + relaxation;
+                let _loosened = profile::CheckToleranceGuard::loosen(relaxation);
+                found_time_synchronization = calculate_step2_profile(
+                    &self.inp_per_dof_control_interface[dof],
+                    t_profile,
+                    p,
+                    guess,
+                    factor * inp.max_velocity[dof],
+                    factor * self.inp_min_velocity[dof],
+                    factor * inp.max_acceleration[dof],
+                    factor * self.inp_min_acceleration[dof],
+                    factor * inp.max_jerk[dof],
                 );
+                if found_time_synchronization {
+                    self.relaxations[dof] = relaxation;
+                }
+            }
+            if !found_time_synchronization {
+                return self.fail::<T>(RuckigError::Step2Failure {
+                    dof,
+                    t_sync: traj.duration,
+                    state: DofState::from_input(inp, dof),
+                });
             }
 
             // Uncomment the following line if you want to debug
@@ -873,3 +1553,305 @@ impl<const DOF: usize> TargetCalculator<DOF> {
         Ok(RuckigResult::Working)
     }
 }
+
+/// Calculate the extremal profiles of a DoF and their blocked intervals in step 1
+// Without all orders of profiles, some branches are compiled out
+#[cfg_attr(
+    not(all(
+        feature = "first-order",
+        feature = "second-order",
+        feature = "third-order"
+    )),
+    allow(unused_variables, clippy::needless_bool)
+)]
+fn calculate_step1_profile(
+    control_interface: &ControlInterface,
+    p: &mut Profile,
+    block: &mut Block,
+    max_velocity: f64,
+    min_velocity: f64,
+    max_acceleration: f64,
+    min_acceleration: f64,
+    max_jerk: f64,
+) -> bool {
+    match control_interface {
+        ControlInterface::Position => {
+            if !max_jerk.is_infinite() {
+                #[cfg(not(feature = "third-order"))]
+                return false;
+                #[cfg(feature = "third-order")]
+                {
+                    let mut step1 = PositionThirdOrderStep1::new(
+                        p.p[0],
+                        p.v[0],
+                        p.a[0],
+                        p.pf,
+                        p.vf,
+                        p.af,
+                        max_velocity,
+                        min_velocity,
+                        max_acceleration,
+                        min_acceleration,
+                        max_jerk,
+                    );
+                    step1.get_profile(p, block)
+                }
+            } else if !max_acceleration.is_infinite() {
+                #[cfg(not(feature = "second-order"))]
+                return false;
+                #[cfg(feature = "second-order")]
+                {
+                    let mut step1 = PositionSecondOrderStep1::new(
+                        p.p[0],
+                        p.v[0],
+                        p.pf,
+                        p.vf,
+                        max_velocity,
+                        min_velocity,
+                        max_acceleration,
+                        min_acceleration,
+                    );
+                    step1.get_profile(p, block)
+                }
+            } else {
+                #[cfg(not(feature = "first-order"))]
+                return false;
+                #[cfg(feature = "first-order")]
+                {
+                    let mut step1 =
+                        PositionFirstOrderStep1::new(p.p[0], p.pf, max_velocity, min_velocity);
+                    step1.get_profile(p, block)
+                }
+            }
+        }
+        ControlInterface::Velocity => {
+            if !max_jerk.is_infinite() {
+                #[cfg(not(feature = "third-order"))]
+                return false;
+                #[cfg(feature = "third-order")]
+                {
+                    let mut step1 = VelocityThirdOrderStep1::new(
+                        p.v[0],
+                        p.a[0],
+                        p.vf,
+                        p.af,
+                        max_acceleration,
+                        min_acceleration,
+                        max_jerk,
+                    );
+                    step1.get_profile(p, block)
+                }
+            } else if !max_acceleration.is_infinite() {
+                #[cfg(not(feature = "second-order"))]
+                return false;
+                #[cfg(feature = "second-order")]
+                {
+                    let mut step1 = VelocitySecondOrderStep1::new(
+                        p.v[0],
+                        p.vf,
+                        max_acceleration,
+                        min_acceleration,
+                    );
+                    step1.get_profile(p, block)
+                }
+            } else {
+                #[cfg(not(feature = "first-order"))]
+                return false;
+                #[cfg(feature = "first-order")]
+                {
+                    let mut step1 = VelocityFirstOrderStep1::new(max_velocity, min_velocity);
+                    step1.get_profile(p, block)
+                }
+            }
+        }
+        ControlInterface::Acceleration => {
+            if !max_jerk.is_infinite() {
+                #[cfg(not(feature = "third-order"))]
+                return false;
+                #[cfg(feature = "third-order")]
+                {
+                    let mut step1 = AccelerationThirdOrderStep1::new(
+                        p.a[0],
+                        p.af,
+                        max_velocity,
+                        min_velocity,
+                        max_acceleration,
+                        min_acceleration,
+                        max_jerk,
+                    );
+                    step1.get_profile(p, block)
+                }
+            } else {
+                #[cfg(not(feature = "second-order"))]
+                return false;
+                #[cfg(feature = "second-order")]
+                {
+                    let mut step1 = AccelerationSecondOrderStep1::new(
+                        max_velocity,
+                        min_velocity,
+                        max_acceleration,
+                        min_acceleration,
+                    );
+                    step1.get_profile(p, block)
+                }
+            }
+        }
+    }
+}
+
+/// Calculate the profile of a DoF for the given duration in step 2
+#[cfg_attr(
+    not(all(
+        feature = "first-order",
+        feature = "second-order",
+        feature = "third-order"
+    )),
+    allow(unused_variables, clippy::needless_bool)
+)]
+pub(crate) fn calculate_step2_profile(
+    control_interface: &ControlInterface,
+    t_profile: f64,
+    p: &mut Profile,
+    guess: Option<ReachedLimits>,
+    max_velocity: f64,
+    min_velocity: f64,
+    max_acceleration: f64,
+    min_acceleration: f64,
+    max_jerk: f64,
+) -> bool {
+    match control_interface {
+        ControlInterface::Position => {
+            if !max_jerk.is_infinite() {
+                #[cfg(not(feature = "third-order"))]
+                return false;
+                #[cfg(feature = "third-order")]
+                {
+                    let mut step2 = PositionThirdOrderStep2::new(
+                        t_profile,
+                        p.p[0],
+                        p.v[0],
+                        p.a[0],
+                        p.pf,
+                        p.vf,
+                        p.af,
+                        max_velocity,
+                        min_velocity,
+                        max_acceleration,
+                        min_acceleration,
+                        max_jerk,
+                    );
+                    step2.set_guess(guess);
+                    step2.get_profile(p)
+                }
+            } else if !max_acceleration.is_infinite() {
+                #[cfg(not(feature = "second-order"))]
+                return false;
+                #[cfg(feature = "second-order")]
+                {
+                    let mut step2 = PositionSecondOrderStep2::new(
+                        t_profile,
+                        p.p[0],
+                        p.v[0],
+                        p.pf,
+                        p.vf,
+                        max_velocity,
+                        min_velocity,
+                        max_acceleration,
+                        min_acceleration,
+                    );
+                    step2.get_profile(p)
+                }
+            } else {
+                #[cfg(not(feature = "first-order"))]
+                return false;
+                #[cfg(feature = "first-order")]
+                {
+                    let mut step2 = PositionFirstOrderStep2::new(
+                        t_profile,
+                        p.p[0],
+                        p.pf,
+                        max_velocity,
+                        min_velocity,
+                    );
+                    step2.get_profile(p)
+                }
+            }
+        }
+        ControlInterface::Velocity => {
+            if !max_jerk.is_infinite() {
+                #[cfg(not(feature = "third-order"))]
+                return false;
+                #[cfg(feature = "third-order")]
+                {
+                    let mut step2 = VelocityThirdOrderStep2::new(
+                        t_profile,
+                        p.v[0],
+                        p.a[0],
+                        p.vf,
+                        p.af,
+                        max_acceleration,
+                        min_acceleration,
+                        max_jerk,
+                    );
+                    step2.get_profile(p)
+                }
+            } else if !max_acceleration.is_infinite() {
+                #[cfg(not(feature = "second-order"))]
+                return false;
+                #[cfg(feature = "second-order")]
+                {
+                    let mut step2 = VelocitySecondOrderStep2::new(
+                        t_profile,
+                        p.v[0],
+                        p.vf,
+                        max_acceleration,
+                        min_acceleration,
+                    );
+                    step2.get_profile(p)
+                }
+            } else {
+                #[cfg(not(feature = "first-order"))]
+                return false;
+                #[cfg(feature = "first-order")]
+                {
+                    let mut step2 =
+                        VelocityFirstOrderStep2::new(t_profile, max_velocity, min_velocity);
+                    step2.get_profile(p)
+                }
+            }
+        }
+        ControlInterface::Acceleration => {
+            if !max_jerk.is_infinite() {
+                #[cfg(not(feature = "third-order"))]
+                return false;
+                #[cfg(feature = "third-order")]
+                {
+                    let mut step2 = AccelerationThirdOrderStep2::new(
+                        t_profile,
+                        p.a[0],
+                        p.af,
+                        max_velocity,
+                        min_velocity,
+                        max_acceleration,
+                        min_acceleration,
+                    );
+                    step2.get_profile(p)
+                }
+            } else {
+                #[cfg(not(feature = "second-order"))]
+                return false;
+                #[cfg(feature = "second-order")]
+                {
+                    let mut step2 = AccelerationSecondOrderStep2::new(
+                        t_profile,
+                        max_velocity,
+                        min_velocity,
+                        max_acceleration,
+                        min_acceleration,
+                    );
+                    step2.get_profile(p)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,420 @@
+//! Calculation of a trajectory through intermediate positions.
+use crate::calculator_target::TargetCalculator;
+use crate::error::{IgnoreErrorHandler, RuckigError, RuckigErrorHandler};
+use crate::input_parameter::InputParameter;
+use crate::result::RuckigResult;
+use crate::trajectory::Trajectory;
+use crate::util::{CompensatedSum, DataArrayOrVec};
+
+/// Number of blends, each with half the offset and velocity of the previous one, tried for an
+/// intermediate position
+const BLEND_ATTEMPTS: usize = 6;
+
+/// Number of intervals in which a section is sampled to measure its deviation
+const DEVIATION_SAMPLES: usize = 64;
+
+/// Chains state-to-state sections through the intermediate positions of the input. Every
+/// intermediate position is reached at rest, unless it has a blend tolerance, and each section
+/// is synchronized on its own. All scratch storage is allocated at construction, so that
+/// streaming waypoints into the input does not allocate within the control loop.
+#[derive(Debug)]
+pub struct WaypointsCalculator<const DOF: usize> {
+    section_input: InputParameter<DOF>,
+    section_trajectory: Trajectory<DOF>,
+    /// Input and trajectory of the following section while blending an intermediate position
+    blend_input: InputParameter<DOF>,
+    blend_trajectory: Trajectory<DOF>,
+    /// Direction of the velocity through a blended intermediate position
+    blend_direction: DataArrayOrVec<f64, DOF>,
+    /// Unit vector from a blended intermediate position into the inside of its corner
+    blend_offset: DataArrayOrVec<f64, DOF>,
+    sample: DataArrayOrVec<f64, DOF>,
+    pub degrees_of_freedom: usize,
+}
+
+impl<const DOF: usize> WaypointsCalculator<DOF> {
+    pub fn new(dofs: Option<usize>) -> Self {
+        let degrees_of_freedom = dofs.unwrap_or(DOF);
+        Self {
+            section_input: InputParameter::new(dofs),
+            section_trajectory: Trajectory::new(dofs),
+            blend_input: InputParameter::new(dofs),
+            blend_trajectory: Trajectory::new(dofs),
+            blend_direction: DataArrayOrVec::new(dofs, 0.0),
+            blend_offset: DataArrayOrVec::new(dofs, 0.0),
+            sample: DataArrayOrVec::new(dofs, 0.0),
+            degrees_of_freedom,
+        }
+    }
+
+    /// Copy all limits and settings that are shared by every section
+    fn prepare_section_input(&mut self, inp: &InputParameter<DOF>) {
+        let section = &mut self.section_input;
+        section.degrees_of_freedom = inp.degrees_of_freedom;
+        section.control_interface = inp.control_interface.clone();
+        section.synchronization = inp.synchronization.clone();
+        section.duration_discretization = inp.duration_discretization.clone();
+        section.current_position.clone_from(&inp.current_position);
+        section.current_velocity.clone_from(&inp.current_velocity);
+        section.current_acceleration.clone_from(&inp.current_acceleration);
+        section.max_position.clone_from(&inp.max_position);
+        section.min_position.clone_from(&inp.min_position);
+        section.max_velocity.clone_from(&inp.max_velocity);
+        section.max_acceleration.clone_from(&inp.max_acceleration);
+        section.max_jerk.clone_from(&inp.max_jerk);
+        section.min_velocity.clone_from(&inp.min_velocity);
+        section.min_acceleration.clone_from(&inp.min_acceleration);
+        section.enabled.clone_from(&inp.enabled);
+        section
+            .per_dof_control_interface
+            .clone_from(&inp.per_dof_control_interface);
+        section
+            .per_dof_synchronization
+            .clone_from(&inp.per_dof_synchronization);
+        section.per_dof_priority.clone_from(&inp.per_dof_priority);
+        section.per_dof_group.clone_from(&inp.per_dof_group);
+        section.minimum_duration = None;
+        section.pre_dwell = inp.pre_dwell;
+    }
+
+    /// Calculate the trajectory through all intermediate positions of the input.
+    pub fn calculate<E: RuckigErrorHandler>(
+        &mut self,
+        target_calculator: &mut TargetCalculator<DOF>,
+        inp: &InputParameter<DOF>,
+        traj: &mut Trajectory<DOF>,
+        delta_time: f64,
+    ) -> Result<RuckigResult, RuckigError> {
+        let number_of_sections = inp.intermediate_positions.len() + 1;
+        traj.resize(number_of_sections);
+        traj.duration = 0.0;
+        traj.discretization_remainder = 0.0;
+        traj.snap_window = 0.0;
+        for dof in 0..self.degrees_of_freedom {
+            traj.independent_min_durations[dof] = 0.0;
+        }
+
+        self.prepare_section_input(inp);
+
+        let mut duration = CompensatedSum::default();
+        for section in 0..number_of_sections {
+            if section > 0 {
+                // The pre-dwell belongs to the first section only
+                self.section_input.pre_dwell = None;
+
+                // Start from the end state of the previous section
+                let t_end = self.section_trajectory.get_duration();
+                self.section_trajectory.at_time(
+                    t_end,
+                    &mut Some(&mut self.section_input.current_position),
+                    &mut Some(&mut self.section_input.current_velocity),
+                    &mut Some(&mut self.section_input.current_acceleration),
+                    &mut None,
+                    &mut None,
+                );
+                for dof in 0..self.degrees_of_freedom {
+                    if inp.enabled[dof] {
+                        self.section_input.current_position[dof] =
+                            self.section_input.target_position[dof];
+                        self.section_input.current_velocity[dof] =
+                            self.section_input.target_velocity[dof];
+                        self.section_input.current_acceleration[dof] = 0.0;
+                    }
+                }
+            }
+
+            let mut is_blended = false;
+            if section + 1 < number_of_sections {
+                let waypoint = &inp.intermediate_positions[section];
+                for dof in 0..self.degrees_of_freedom {
+                    self.section_input.target_position[dof] = waypoint[dof];
+                    self.section_input.target_velocity[dof] = 0.0;
+                    self.section_input.target_acceleration[dof] = 0.0;
+                }
+                if inp
+                    .intermediate_blend_tolerances
+                    .get(section)
+                    .is_some_and(|tolerance| *tolerance > 0.0)
+                {
+                    is_blended = self.blend_intermediate_position(
+                        target_calculator,
+                        inp,
+                        section,
+                        delta_time,
+                    );
+                }
+            } else {
+                self.section_input
+                    .target_position
+                    .clone_from(&inp.target_position);
+                self.section_input
+                    .target_velocity
+                    .clone_from(&inp.target_velocity);
+                self.section_input
+                    .target_acceleration
+                    .clone_from(&inp.target_acceleration);
+
+                // The minimum duration refers to the complete trajectory
+                self.section_input.minimum_duration = inp
+                    .minimum_duration
+                    .map(|minimum_duration| minimum_duration - traj.duration)
+                    .filter(|remaining| *remaining > 0.0);
+            }
+
+            // A blended section keeps the trajectory calculated while blending
+            if !is_blended {
+                let result = target_calculator.calculate::<E>(
+                    &self.section_input,
+                    &mut self.section_trajectory,
+                    delta_time,
+                )?;
+                if result != RuckigResult::Working {
+                    return Ok(result);
+                }
+            }
+
+            traj.profiles[section].clone_from(&self.section_trajectory.profiles[0]);
+            duration.add(self.section_trajectory.get_duration());
+            traj.duration = duration.value();
+            traj.discretization_remainder += self.section_trajectory.discretization_remainder;
+            traj.cumulative_times[section] = traj.duration;
+            for dof in 0..self.degrees_of_freedom {
+                traj.independent_min_durations[dof] +=
+                    self.section_trajectory.independent_min_durations[dof];
+            }
+            traj.synchronizations.clone_from(&self.section_trajectory.synchronizations);
+        }
+
+        Ok(RuckigResult::Working)
+    }
+
+    /// Replace the target of the section, which ends at an intermediate position with a blend
+    /// tolerance, by a state that cuts the corner. The state lies on the bisector inside the
+    /// corner at the tolerance from the position, and is passed with a velocity along the mean
+    /// direction of the lines to and from the position. Offset and velocity are halved until both
+    /// adjacent sections deviate at most by the larger tolerance of their ends from their lines.
+    /// Returns whether a blend succeeded, and then the section trajectory holds the section. If
+    /// no blend succeeds, the position is reached exactly and at rest.
+    fn blend_intermediate_position(
+        &mut self,
+        target_calculator: &mut TargetCalculator<DOF>,
+        inp: &InputParameter<DOF>,
+        section: usize,
+        delta_time: f64,
+    ) -> bool {
+        let waypoints = &inp.intermediate_positions;
+        let tolerance = |index: usize| {
+            let tolerance = inp.intermediate_blend_tolerances.get(index).copied();
+            tolerance.filter(|_| index < waypoints.len()).unwrap_or(0.0)
+        };
+        let section_tolerance = tolerance(section).max(match section {
+            0 => 0.0,
+            _ => tolerance(section - 1),
+        });
+        let next_tolerance = tolerance(section).max(tolerance(section + 1));
+        let start = match section {
+            0 => &inp.current_position,
+            _ => &waypoints[section - 1],
+        };
+        let waypoint = &waypoints[section];
+        let next = waypoints.get(section + 1).unwrap_or(&inp.target_position);
+
+        let mut incoming = 0.0;
+        let mut outgoing = 0.0;
+        for dof in 0..self.degrees_of_freedom {
+            if inp.enabled[dof] {
+                incoming += (waypoint[dof] - start[dof]).powi(2);
+                outgoing += (next[dof] - waypoint[dof]).powi(2);
+            }
+        }
+        if incoming == 0.0 || outgoing == 0.0 {
+            return false;
+        }
+
+        // Mean of the unit vectors to and from the position, scaled to the velocity limits, and
+        // their difference, which points into the corner
+        let mut scale = f64::INFINITY;
+        let mut offset_length = 0.0;
+        for dof in 0..self.degrees_of_freedom {
+            self.blend_direction[dof] = 0.0;
+            self.blend_offset[dof] = 0.0;
+            if !inp.enabled[dof] {
+                continue;
+            }
+            let unit_incoming = (waypoint[dof] - start[dof]) / incoming.sqrt();
+            let unit_outgoing = (next[dof] - waypoint[dof]) / outgoing.sqrt();
+            let direction = (unit_incoming + unit_outgoing) / 2.0;
+            let v_max = inp.max_velocity[dof];
+            let v_min = inp.min_velocity.as_ref().map_or(-v_max, |v| v[dof]);
+            if direction > 0.0 {
+                scale = scale.min(v_max / direction);
+            } else if direction < 0.0 {
+                scale = scale.min(v_min / direction);
+            }
+            self.blend_direction[dof] = direction;
+            self.blend_offset[dof] = unit_outgoing - unit_incoming;
+            offset_length += self.blend_offset[dof].powi(2);
+        }
+        if !scale.is_finite() {
+            return false;
+        }
+        if offset_length > 0.0 {
+            let offset_length = offset_length.sqrt();
+            for dof in 0..self.degrees_of_freedom {
+                self.blend_offset[dof] /= offset_length;
+            }
+        }
+
+        // The candidates must not leave any trace of their failures or relaxations
+        let relaxed_limits = target_calculator.relaxed_limits.len();
+        let loosened_tolerances = target_calculator.loosened_tolerances.len();
+        let mut offset = tolerance(section);
+        let mut is_blended = false;
+        for _ in 0..BLEND_ATTEMPTS {
+            target_calculator.relaxed_limits.truncate(relaxed_limits);
+            target_calculator.loosened_tolerances.truncate(loosened_tolerances);
+            for dof in 0..self.degrees_of_freedom {
+                if inp.enabled[dof] {
+                    self.section_input.target_position[dof] =
+                        waypoint[dof] + offset * self.blend_offset[dof];
+                }
+                self.section_input.target_velocity[dof] = scale * self.blend_direction[dof];
+            }
+            if self.is_within_tolerances(
+                target_calculator,
+                inp,
+                section,
+                section_tolerance,
+                next_tolerance,
+                delta_time,
+            ) {
+                is_blended = true;
+                break;
+            }
+            offset /= 2.0;
+            scale /= 2.0;
+        }
+        if !is_blended {
+            self.section_input.target_position.clone_from(waypoint);
+            self.section_input.target_velocity.fill(0.0);
+            target_calculator.relaxed_limits.truncate(relaxed_limits);
+            target_calculator.loosened_tolerances.truncate(loosened_tolerances);
+        }
+        target_calculator.failed_dof = None;
+        target_calculator.failed_stage = None;
+        is_blended
+    }
+
+    /// Whether the section with its current target and the following section deviate at most by
+    /// their tolerances from the lines between the original positions. Only the relaxations of
+    /// the section itself are kept.
+    fn is_within_tolerances(
+        &mut self,
+        target_calculator: &mut TargetCalculator<DOF>,
+        inp: &InputParameter<DOF>,
+        section: usize,
+        section_tolerance: f64,
+        next_tolerance: f64,
+        delta_time: f64,
+    ) -> bool {
+        let result = target_calculator.calculate::<IgnoreErrorHandler>(
+            &self.section_input,
+            &mut self.section_trajectory,
+            delta_time,
+        );
+        let start = match section {
+            0 => &inp.current_position,
+            _ => &inp.intermediate_positions[section - 1],
+        };
+        let waypoint = &inp.intermediate_positions[section];
+        if result != Ok(RuckigResult::Working)
+            || Self::deviation(
+                &self.section_trajectory,
+                start,
+                waypoint,
+                &inp.enabled,
+                &mut self.sample,
+            ) > section_tolerance
+        {
+            return false;
+        }
+
+        // The following section starts at the position with the velocity of the candidate
+        let next_input = &mut self.blend_input;
+        next_input.clone_from(&self.section_input);
+        next_input.pre_dwell = None;
+        for dof in 0..self.degrees_of_freedom {
+            if inp.enabled[dof] {
+                next_input.current_position[dof] = self.section_input.target_position[dof];
+                next_input.current_velocity[dof] = self.section_input.target_velocity[dof];
+                next_input.current_acceleration[dof] = 0.0;
+            }
+        }
+        if let Some(next) = inp.intermediate_positions.get(section + 1) {
+            next_input.target_position.clone_from(next);
+            next_input.target_velocity.fill(0.0);
+            next_input.target_acceleration.fill(0.0);
+        } else {
+            next_input.target_position.clone_from(&inp.target_position);
+            next_input.target_velocity.clone_from(&inp.target_velocity);
+            next_input.target_acceleration.clone_from(&inp.target_acceleration);
+        }
+        let relaxed_limits = target_calculator.relaxed_limits.len();
+        let loosened_tolerances = target_calculator.loosened_tolerances.len();
+        let result = target_calculator.calculate::<IgnoreErrorHandler>(
+            &self.blend_input,
+            &mut self.blend_trajectory,
+            delta_time,
+        );
+        target_calculator.relaxed_limits.truncate(relaxed_limits);
+        target_calculator.loosened_tolerances.truncate(loosened_tolerances);
+        result == Ok(RuckigResult::Working)
+            && Self::deviation(
+                &self.blend_trajectory,
+                waypoint,
+                &self.blend_input.target_position,
+                &inp.enabled,
+                &mut self.sample,
+            ) <= next_tolerance
+    }
+
+    /// Largest distance of the enabled DoFs of the trajectory from the line segment between the
+    /// two positions, sampled over its duration
+    fn deviation(
+        trajectory: &Trajectory<DOF>,
+        start: &DataArrayOrVec<f64, DOF>,
+        end: &DataArrayOrVec<f64, DOF>,
+        enabled: &DataArrayOrVec<bool, DOF>,
+        sample: &mut DataArrayOrVec<f64, DOF>,
+    ) -> f64 {
+        let dofs = sample.len();
+        let mut length = 0.0;
+        for dof in (0..dofs).filter(|dof| enabled[*dof]) {
+            length += (end[dof] - start[dof]).powi(2);
+        }
+
+        let mut deviation: f64 = 0.0;
+        let duration = trajectory.get_duration();
+        for step in 0..=DEVIATION_SAMPLES {
+            let time = duration * step as f64 / DEVIATION_SAMPLES as f64;
+            trajectory.at_time(time, &mut Some(sample), &mut None, &mut None, &mut None, &mut None);
+
+            // Project the sample onto the segment
+            let mut projection = 0.0;
+            for dof in (0..dofs).filter(|dof| enabled[*dof]) {
+                projection += (sample[dof] - start[dof]) * (end[dof] - start[dof]);
+            }
+            let s = if length > 0.0 {
+                (projection / length).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let mut distance = 0.0;
+            for dof in (0..dofs).filter(|dof| enabled[*dof]) {
+                distance += (sample[dof] - start[dof] - s * (end[dof] - start[dof])).powi(2);
+            }
+            deviation = deviation.max(distance.sqrt());
+        }
+        deviation
+    }
+}
@@ -1,57 +1,398 @@
-use crate::result::RuckigResult;
+use crate::input_parameter::InputParameter;
+use crate::result::{CalculationStage, RuckigResult};
+use crate::validation::ValidationViolation;
 use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::VecDeque,
     error::Error,
     fmt::{self},
+    marker::PhantomData,
+    sync::atomic::{AtomicI32, AtomicUsize, Ordering},
+    sync::RwLock,
 };
 
-pub struct RuckigError {
-    message: String,
+/// Kinematic state and limits of a single DoF, as context of a calculation error
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DofState {
+    pub p0: f64,
+    pub v0: f64,
+    pub a0: f64,
+    pub pf: f64,
+    pub vf: f64,
+    pub af: f64,
+    pub v_max: f64,
+    pub v_min: f64,
+    pub a_max: f64,
+    pub a_min: f64,
+    pub j_max: f64,
+}
+
+impl DofState {
+    pub fn from_input<const DOF: usize>(input: &InputParameter<DOF>, dof: usize) -> Self {
+        let v_max = input.max_velocity[dof];
+        let a_max = input.max_acceleration[dof];
+        Self {
+            p0: input.current_position[dof],
+            v0: input.current_velocity[dof],
+            a0: input.current_acceleration[dof],
+            pf: input.target_position[dof],
+            vf: input.target_velocity[dof],
+            af: input.target_acceleration[dof],
+            v_max,
+            v_min: input.min_velocity.as_ref().map_or(-v_max, |v| v[dof]),
+            a_max,
+            a_min: input.min_acceleration.as_ref().map_or(-a_max, |a| a[dof]),
+            j_max: input.max_jerk[dof],
+        }
+    }
+}
+
+impl fmt::Display for DofState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "p0: {}, v0: {}, a0: {}, pf: {}, vf: {}, af: {}, v_max: {}, v_min: {}, a_max: {}, a_min: {}, j_max: {}",
+            self.p0,
+            self.v0,
+            self.a0,
+            self.pf,
+            self.vf,
+            self.af,
+            self.v_max,
+            self.v_min,
+            self.a_max,
+            self.a_min,
+            self.j_max
+        )
+    }
+}
+
+/// Error of the validation or the calculation. The variants carry the data needed to decide on a
+/// recovery, e.g. the failing DoF together with its kinematic state.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RuckigError {
+    /// The input violates a condition of the validation
+    InvalidInput(ValidationViolation),
+    /// The number of intermediate positions exceeds the maximum number of waypoints
+    TooManyWaypoints { count: usize, max: usize },
+    /// The control cycle is required for a discrete duration
+    InvalidDeltaTime { delta_time: f64 },
+    /// The speed factor is negative or not a number
+    InvalidSpeedFactor { speed_factor: f64 },
+    /// The target state of the tracker is not a valid number
+    InvalidTargetState { dof: usize },
+    /// The input or output has a different number of DoFs than the calculator
+    DofMismatch,
+    /// The requested duration is shorter than the duration of the trajectory
+    InvalidDuration { duration: f64, minimum: f64 },
+    /// No extremal profile was found in step 1
+    Step1Failure { dof: usize, state: DofState },
+    /// No extremal profile was found in step 1 due to zero limits
+    ZeroLimitsConflict { dof: usize, state: DofState },
+    /// All extremal profiles of step 1 leave the position limits
+    PositionLimitsConflict { dof: usize, state: DofState },
+    /// No common duration was found for the time synchronization
+    SynchronizationFailure { t_sync: f64 },
+    /// The zero limits of a DoF prevent the time synchronization with other DoFs
+    ZeroLimitsSynchronizationConflict { dof: usize, t_sync: f64 },
+    /// No profile was found in step 2 for the synchronized duration
+    Step2Failure {
+        dof: usize,
+        t_sync: f64,
+        state: DofState,
+    },
+    /// The synchronized profile leaves the position limits
+    PositionLimitsViolation { dof: usize, state: DofState },
+    /// The trajectory takes longer than the maximum duration of the input
+    MaximumDurationExceeded { duration: f64, maximum: f64 },
+    /// A background calculation finished after the time at which its trajectory starts
+    LateBackgroundCalculation { start_time: f64, time: f64 },
+    /// Error with a custom message, e.g. of a user-defined error handler. Static messages don't
+    /// allocate.
+    Other(Cow<'static, str>),
 }
 
 impl Error for RuckigError {}
 
 impl RuckigError {
-    pub fn new(message: String) -> RuckigError {
-        RuckigError {
-            message: format!("\n[rsruckig] {}\n", message),
+    pub fn new(message: impl Into<Cow<'static, str>>) -> RuckigError {
+        RuckigError::Other(message.into())
+    }
+
+    /// The result that corresponds to the error
+    pub fn result(&self) -> RuckigResult {
+        match self {
+            RuckigError::InvalidInput(_)
+            | RuckigError::TooManyWaypoints { .. }
+            | RuckigError::InvalidDeltaTime { .. }
+            | RuckigError::InvalidSpeedFactor { .. }
+            | RuckigError::InvalidTargetState { .. }
+            | RuckigError::InvalidDuration { .. } => RuckigResult::ErrorInvalidInput,
+            RuckigError::DofMismatch
+            | RuckigError::LateBackgroundCalculation { .. }
+            | RuckigError::Other(_) => RuckigResult::Error,
+            RuckigError::Step1Failure { .. } | RuckigError::Step2Failure { .. } => {
+                RuckigResult::ErrorExecutionTimeCalculation
+            }
+            RuckigError::ZeroLimitsConflict { .. }
+            | RuckigError::ZeroLimitsSynchronizationConflict { .. } => {
+                RuckigResult::ErrorZeroLimits
+            }
+            RuckigError::PositionLimitsConflict { .. }
+            | RuckigError::PositionLimitsViolation { .. } => RuckigResult::ErrorPositionalLimits,
+            RuckigError::SynchronizationFailure { .. } => {
+                RuckigResult::ErrorSynchronizationCalculation
+            }
+            RuckigError::MaximumDurationExceeded { .. } => RuckigResult::ErrorTrajectoryDuration,
+        }
+    }
+
+    /// The stage of the calculation in which the error occurred
+    pub fn stage(&self) -> Option<CalculationStage> {
+        match self {
+            RuckigError::InvalidInput(_)
+            | RuckigError::TooManyWaypoints { .. }
+            | RuckigError::InvalidDeltaTime { .. }
+            | RuckigError::InvalidSpeedFactor { .. }
+            | RuckigError::InvalidTargetState { .. }
+            | RuckigError::InvalidDuration { .. }
+            | RuckigError::DofMismatch => Some(CalculationStage::Validation),
+            RuckigError::Step1Failure { .. }
+            | RuckigError::ZeroLimitsConflict { .. }
+            | RuckigError::PositionLimitsConflict { .. } => Some(CalculationStage::Step1),
+            RuckigError::SynchronizationFailure { .. }
+            | RuckigError::ZeroLimitsSynchronizationConflict { .. }
+            | RuckigError::MaximumDurationExceeded { .. } => {
+                Some(CalculationStage::Synchronization)
+            }
+            RuckigError::Step2Failure { .. } | RuckigError::PositionLimitsViolation { .. } => {
+                Some(CalculationStage::Step2)
+            }
+            RuckigError::LateBackgroundCalculation { .. } | RuckigError::Other(_) => None,
+        }
+    }
+
+    /// The DoF that caused the error, if the error refers to a single DoF
+    pub fn dof(&self) -> Option<usize> {
+        match self {
+            RuckigError::InvalidInput(violation) => violation.dof,
+            RuckigError::InvalidTargetState { dof }
+            | RuckigError::Step1Failure { dof, .. }
+            | RuckigError::ZeroLimitsConflict { dof, .. }
+            | RuckigError::PositionLimitsConflict { dof, .. }
+            | RuckigError::ZeroLimitsSynchronizationConflict { dof, .. }
+            | RuckigError::Step2Failure { dof, .. }
+            | RuckigError::PositionLimitsViolation { dof, .. } => Some(*dof),
+            _ => None,
+        }
+    }
+
+    fn fmt_message(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuckigError::InvalidInput(violation) => write!(f, "{}", violation),
+            RuckigError::TooManyWaypoints { count, max } => write!(
+                f,
+                "the number of intermediate positions {} exceeds the maximum number of waypoints {}.",
+                count, max
+            ),
+            RuckigError::InvalidDeltaTime { delta_time } => write!(
+                f,
+                "delta time (control rate) parameter {} should be larger than zero.",
+                delta_time
+            ),
+            RuckigError::InvalidSpeedFactor { speed_factor } => write!(
+                f,
+                "speed factor {} should be larger than or equal to zero.",
+                speed_factor
+            ),
+            RuckigError::InvalidTargetState { dof } => write!(
+                f,
+                "target state of DoF {} should be a valid number.",
+                dof
+            ),
+            RuckigError::DofMismatch => {
+                write!(f, "mismatch in degrees of freedom (vector size).")
+            }
+            RuckigError::InvalidDuration { duration, minimum } => write!(
+                f,
+                "duration {} should be larger than or equal to the trajectory duration {}.",
+                duration, minimum
+            ),
+            RuckigError::Step1Failure { dof, state } => {
+                write!(f, "error in step 1, dof: {} state: {}", dof, state)
+            }
+            RuckigError::ZeroLimitsConflict { dof, state } => write!(
+                f,
+                "zero limits conflict in step 1, dof: {} state: {}",
+                dof, state
+            ),
+            RuckigError::PositionLimitsConflict { dof, state } => write!(
+                f,
+                "position limits conflict in step 1, dof: {} state: {}",
+                dof, state
+            ),
+            RuckigError::SynchronizationFailure { t_sync } => {
+                write!(f, "error in time synchronization: {}", t_sync)
+            }
+            RuckigError::ZeroLimitsSynchronizationConflict { dof, t_sync } => write!(
+                f,
+                "zero limits conflict with other degrees of freedom in time synchronization {}, dof: {}",
+                t_sync, dof
+            ),
+            RuckigError::Step2Failure { dof, t_sync, state } => write!(
+                f,
+                "error in step 2 in dof: {} for t sync: {} state: {}",
+                dof, t_sync, state
+            ),
+            RuckigError::PositionLimitsViolation { dof, state } => write!(
+                f,
+                "position limits violated in dof: {} state: {}",
+                dof, state
+            ),
+            RuckigError::MaximumDurationExceeded { duration, maximum } => write!(
+                f,
+                "trajectory duration {} exceeds the maximum duration {}.",
+                duration, maximum
+            ),
+            RuckigError::LateBackgroundCalculation { start_time, time } => write!(
+                f,
+                "background calculation finished at time {} after the start {} of its trajectory.",
+                time, start_time
+            ),
+            RuckigError::Other(message) => write!(f, "{}", message),
         }
     }
 }
 
 impl fmt::Display for RuckigError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        writeln!(f, "{}", self.message)
+        write!(f, "\n[rsruckig] ")?;
+        self.fmt_message(f)?;
+        writeln!(f, "\n")
     }
 }
 
-impl fmt::Debug for RuckigError {
+/// Magnitude by which a DoF exceeds its limits after they were relaxed to find a profile. All
+/// values are zero or positive, with zero meaning that the limit is kept.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LimitExcess {
+    pub dof: usize,
+    /// Relative relaxation of the limits that was needed to find a profile
+    pub relaxation: f64,
+    pub velocity: f64,
+    pub acceleration: f64,
+    pub jerk: f64,
+    /// Largest deviation of the final position, velocity, or acceleration from the target state
+    pub boundary: f64,
+}
+
+/// Tolerance of the profile checks that was needed to find the profiles of a DoF, see
+/// `Settings::fallback_tolerances`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoosenedTolerance {
+    pub dof: usize,
+    pub tolerance: f64,
+}
+
+/// Non-fatal situation in which the calculated trajectory differs from the requested one
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RuckigWarning {
+    /// Phase synchronization was requested but isn't possible, so the DoFs are time synchronized
+    PhaseSynchronizationFallback,
+    /// The trajectory duration is close to the maximal duration that is calculated
+    DurationNearLimit { duration: f64, limit: f64 },
+    /// The current state of the DoF violates its limits, so a brake trajectory is inserted first
+    BrakeTrajectory { dof: usize, duration: f64 },
+    /// No exact profile was found for the DoF, so its limits were relaxed
+    RelaxedLimits(LimitExcess),
+    /// No profile was found for the DoF with the default tolerances of the checks, so they
+    /// were loosened
+    LoosenedTolerance(LoosenedTolerance),
+    /// The trajectory takes longer than the maximum duration, which is accepted in relaxed mode
+    MaximumDurationExceeded { duration: f64, maximum: f64 },
+    /// The target state of the DoF can't be reached within its limits, so it was clamped to the
+    /// given velocity and acceleration
+    TargetStateClamped {
+        dof: usize,
+        velocity: f64,
+        acceleration: f64,
+    },
+}
+
+impl fmt::Display for RuckigWarning {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        writeln!(f, "RuckigError: {}", self.message)
+        match self {
+            RuckigWarning::PhaseSynchronizationFallback => write!(
+                f,
+                "phase synchronization is not possible, falling back to time synchronization."
+            ),
+            RuckigWarning::DurationNearLimit { duration, limit } => write!(
+                f,
+                "trajectory duration {} is close to the maximal duration {}.",
+                duration, limit
+            ),
+            RuckigWarning::BrakeTrajectory { dof, duration } => write!(
+                f,
+                "brake trajectory with duration {} is inserted for DoF {}.",
+                duration, dof
+            ),
+            RuckigWarning::RelaxedLimits(excess) => write!(
+                f,
+                "limits of DoF {} are relaxed by {}, exceeding the velocity by {}, the acceleration by {}, the jerk by {}, and missing the target state by {}.",
+                excess.dof,
+                excess.relaxation,
+                excess.velocity,
+                excess.acceleration,
+                excess.jerk,
+                excess.boundary
+            ),
+            RuckigWarning::LoosenedTolerance(loosened) => write!(
+                f,
+                "tolerances of the profile checks of DoF {} are loosened to {}.",
+                loosened.dof, loosened.tolerance
+            ),
+            RuckigWarning::MaximumDurationExceeded { duration, maximum } => write!(
+                f,
+                "trajectory duration {} exceeds the maximum duration {}.",
+                duration, maximum
+            ),
+            RuckigWarning::TargetStateClamped {
+                dof,
+                velocity,
+                acceleration,
+            } => write!(
+                f,
+                "target state of DoF {} is clamped to the velocity {} and acceleration {}.",
+                dof, velocity, acceleration
+            ),
+        }
     }
 }
 
 /// Trait for handling validation and calculator errors.
 /// Types that implement this trait decide how to respond to validation and calculator errors.
 pub trait RuckigErrorHandler {
-    fn handle_validation_error(message: &str) -> Result<bool, RuckigError>;
-    fn handle_calculator_error(
-        message: &str,
-        result: RuckigResult,
-    ) -> Result<RuckigResult, RuckigError>;
+    fn handle_validation_error(error: RuckigError) -> Result<bool, RuckigError>;
+    fn handle_calculator_error(error: RuckigError) -> Result<RuckigResult, RuckigError>;
+    /// Called for non-fatal situations, the calculation continues in any case
+    fn handle_warning(_warning: RuckigWarning) {}
 }
 
 #[derive(Debug, Default)]
 pub struct ThrowErrorHandler;
 
 impl RuckigErrorHandler for ThrowErrorHandler {
-    fn handle_validation_error(message: &str) -> Result<bool, RuckigError> {
-        Err(RuckigError::new(message.to_string()))
+    fn handle_validation_error(error: RuckigError) -> Result<bool, RuckigError> {
+        Err(error)
     }
-    fn handle_calculator_error(
-        message: &str,
-        result: RuckigResult,
-    ) -> Result<RuckigResult, RuckigError> {
-        Err(RuckigError::new(format!("{}: {:?}", message, result)))
+    fn handle_calculator_error(error: RuckigError) -> Result<RuckigResult, RuckigError> {
+        Err(error)
     }
 }
 
@@ -59,13 +400,274 @@ impl RuckigErrorHandler for ThrowErrorHandler {
 pub struct IgnoreErrorHandler;
 
 impl RuckigErrorHandler for IgnoreErrorHandler {
-    fn handle_validation_error(_message: &str) -> Result<bool, RuckigError> {
+    fn handle_validation_error(_error: RuckigError) -> Result<bool, RuckigError> {
         Ok(false)
     }
-    fn handle_calculator_error(
-        _message: &str,
-        result: RuckigResult,
-    ) -> Result<RuckigResult, RuckigError> {
+    fn handle_calculator_error(error: RuckigError) -> Result<RuckigResult, RuckigError> {
+        Ok(error.result())
+    }
+}
+
+/// Default number of errors kept by the `CollectErrorHandler`
+pub const COLLECTED_ERRORS_CAPACITY: usize = 64;
+
+struct CollectedErrors {
+    errors: VecDeque<RuckigError>,
+    capacity: usize,
+    total: usize,
+}
+
+thread_local! {
+    static COLLECTED_ERRORS: RefCell<CollectedErrors> = const { RefCell::new(CollectedErrors {
+        errors: VecDeque::new(),
+        capacity: COLLECTED_ERRORS_CAPACITY,
+        total: 0,
+    }) };
+}
+
+/// Records all errors while continuing like the `IgnoreErrorHandler`, e.g. for long-running soak
+/// tests. As the handler has no state, the errors are kept per thread in a ring buffer that keeps
+/// only the latest errors.
+#[derive(Debug, Default)]
+pub struct CollectErrorHandler;
+
+impl CollectErrorHandler {
+    fn record(error: RuckigError) {
+        COLLECTED_ERRORS.with(|collected| {
+            let mut collected = collected.borrow_mut();
+            if collected.capacity == 0 {
+                collected.total += 1;
+                return;
+            }
+            if collected.errors.len() == collected.capacity {
+                collected.errors.pop_front();
+            }
+            collected.errors.push_back(error);
+            collected.total += 1;
+        });
+    }
+
+    /// Number of errors that are kept in the ring buffer of the current thread. Older errors are
+    /// dropped first if the buffer is full.
+    pub fn set_capacity(capacity: usize) {
+        COLLECTED_ERRORS.with(|collected| {
+            let mut collected = collected.borrow_mut();
+            while collected.errors.len() > capacity {
+                collected.errors.pop_front();
+            }
+            collected.capacity = capacity;
+        });
+    }
+
+    /// The kept errors of the current thread, from the oldest to the latest
+    pub fn errors() -> Vec<RuckigError> {
+        COLLECTED_ERRORS.with(|collected| collected.borrow().errors.iter().cloned().collect())
+    }
+
+    /// Remove and return the kept errors of the current thread
+    pub fn take_errors() -> Vec<RuckigError> {
+        COLLECTED_ERRORS.with(|collected| collected.borrow_mut().errors.drain(..).collect())
+    }
+
+    /// Number of all errors recorded in the current thread, including the dropped ones
+    pub fn error_count() -> usize {
+        COLLECTED_ERRORS.with(|collected| collected.borrow().total)
+    }
+
+    /// Remove all kept errors and reset the error count of the current thread
+    pub fn clear() {
+        COLLECTED_ERRORS.with(|collected| {
+            let mut collected = collected.borrow_mut();
+            collected.errors.clear();
+            collected.total = 0;
+        });
+    }
+}
+
+impl RuckigErrorHandler for CollectErrorHandler {
+    fn handle_validation_error(error: RuckigError) -> Result<bool, RuckigError> {
+        Self::record(error);
+        Ok(false)
+    }
+    fn handle_calculator_error(error: RuckigError) -> Result<RuckigResult, RuckigError> {
+        let result = error.result();
+        Self::record(error);
         Ok(result)
     }
 }
+
+type ErrorCallback = Box<dyn Fn(&RuckigError) + Send + Sync>;
+
+static ERROR_CALLBACK: RwLock<Option<ErrorCallback>> = RwLock::new(None);
+
+/// Passes all errors to a closure, e.g. to log them to a telemetry system, and continues like the
+/// `IgnoreErrorHandler`. The callback is shared by all threads.
+#[derive(Debug, Default)]
+pub struct CallbackErrorHandler;
+
+impl CallbackErrorHandler {
+    /// Set the closure that is called with every error
+    pub fn set_callback<F: Fn(&RuckigError) + Send + Sync + 'static>(callback: F) {
+        *ERROR_CALLBACK
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(Box::new(callback));
+    }
+
+    /// Remove the closure, so that errors are ignored
+    pub fn clear_callback() {
+        *ERROR_CALLBACK
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+    }
+
+    fn call(error: &RuckigError) {
+        let callback = ERROR_CALLBACK
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(callback) = callback.as_ref() {
+            callback(error);
+        }
+    }
+}
+
+impl RuckigErrorHandler for CallbackErrorHandler {
+    fn handle_validation_error(error: RuckigError) -> Result<bool, RuckigError> {
+        Self::call(&error);
+        Ok(false)
+    }
+    fn handle_calculator_error(error: RuckigError) -> Result<RuckigResult, RuckigError> {
+        Self::call(&error);
+        Ok(error.result())
+    }
+}
+
+const NO_ERROR_DOF: usize = usize::MAX;
+
+/// Result code, DoF and count of the latest error, stored in atomics so that it can live in a
+/// `static`
+#[derive(Debug)]
+pub struct ErrorCode {
+    code: AtomicI32,
+    dof: AtomicUsize,
+    count: AtomicUsize,
+}
+
+impl ErrorCode {
+    pub const fn new() -> Self {
+        Self {
+            code: AtomicI32::new(RuckigResult::Working as i32),
+            dof: AtomicUsize::new(NO_ERROR_DOF),
+            count: AtomicUsize::new(0),
+        }
+    }
+
+    fn record(&self, error: &RuckigError) {
+        self.code.store(error.result() as i32, Ordering::Relaxed);
+        self.dof
+            .store(error.dof().unwrap_or(NO_ERROR_DOF), Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Result code and DoF of the latest error, if any
+    pub fn last_error(&self) -> Option<(RuckigResult, Option<usize>)> {
+        let code = self.code.load(Ordering::Relaxed);
+        let result = RuckigResult::try_from(code).ok()?;
+        if result == RuckigResult::Working {
+            return None;
+        }
+        let dof = self.dof.load(Ordering::Relaxed);
+        Some((result, (dof != NO_ERROR_DOF).then_some(dof)))
+    }
+
+    /// Number of all errors since the last reset
+    pub fn error_count(&self) -> usize {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Forget the latest error and reset the error count
+    pub fn reset(&self) {
+        self.code
+            .store(RuckigResult::Working as i32, Ordering::Relaxed);
+        self.dof.store(NO_ERROR_DOF, Ordering::Relaxed);
+        self.count.store(0, Ordering::Relaxed);
+    }
+}
+
+impl Default for ErrorCode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Where an `ErrorCodeHandler` keeps the latest error. Each storage type owns its own
+/// `ErrorCode`, e.g. one per axis group:
+///
+/// ```
+/// use rsruckig::error::{ErrorCode, ErrorCodeHandler, ErrorCodeStorage};
+///
+/// struct ArmErrors;
+/// impl ErrorCodeStorage for ArmErrors {
+///     fn error_code() -> &'static ErrorCode {
+///         static ERROR_CODE: ErrorCode = ErrorCode::new();
+///         &ERROR_CODE
+///     }
+/// }
+///
+/// assert_eq!(ErrorCodeHandler::<ArmErrors>::error_code().last_error(), None);
+/// ```
+pub trait ErrorCodeStorage {
+    fn error_code() -> &'static ErrorCode;
+}
+
+/// Storage of the default `ErrorCodeHandler`, which is shared by all of its users
+#[derive(Debug, Default)]
+pub struct SharedErrorCode;
+
+impl ErrorCodeStorage for SharedErrorCode {
+    fn error_code() -> &'static ErrorCode {
+        static ERROR_CODE: ErrorCode = ErrorCode::new();
+        &ERROR_CODE
+    }
+}
+
+/// Keeps only the result code and the DoF of the latest error, and continues like the
+/// `IgnoreErrorHandler`. Neither the handler nor the errors allocate, so it is suitable for
+/// real-time loops. The latest error is kept in the `ErrorCode` of the storage type `S`, so
+/// calculators that should report separately use handlers with different storages.
+#[derive(Debug, Default)]
+pub struct ErrorCodeHandler<S: ErrorCodeStorage = SharedErrorCode>(PhantomData<S>);
+
+impl<S: ErrorCodeStorage> ErrorCodeHandler<S> {
+    /// The storage of the latest error of this handler
+    pub fn error_code() -> &'static ErrorCode {
+        S::error_code()
+    }
+}
+
+impl ErrorCodeHandler {
+    /// Result code and DoF of the latest error of the shared storage, if any
+    pub fn last_error() -> Option<(RuckigResult, Option<usize>)> {
+        SharedErrorCode::error_code().last_error()
+    }
+
+    /// Number of all errors of the shared storage since the last reset
+    pub fn error_count() -> usize {
+        SharedErrorCode::error_code().error_count()
+    }
+
+    /// Forget the latest error of the shared storage and reset its error count
+    pub fn reset() {
+        SharedErrorCode::error_code().reset()
+    }
+}
+
+impl<S: ErrorCodeStorage> RuckigErrorHandler for ErrorCodeHandler<S> {
+    fn handle_validation_error(error: RuckigError) -> Result<bool, RuckigError> {
+        S::error_code().record(&error);
+        Ok(false)
+    }
+    fn handle_calculator_error(error: RuckigError) -> Result<RuckigResult, RuckigError> {
+        S::error_code().record(&error);
+        Ok(error.result())
+    }
+}
@@ -1,9 +1,12 @@
-use crate::error::{RuckigError, RuckigErrorHandler};
-use crate::util::{join, DataArrayOrVec};
+use crate::error::{RuckigError, RuckigErrorHandler, RuckigWarning};
+use crate::util::{circular_distance, join, DataArrayOrVec};
+use crate::validation::{InputQuantity, ValidationReport, ValidationViolation, ViolationKind};
+use std::collections::VecDeque;
 use std::fmt;
 use std::ops::Deref;
 
 #[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ControlInterface {
     #[default]
     Position,
@@ -12,6 +15,7 @@ pub enum ControlInterface {
 }
 
 #[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Synchronization {
     #[default]
     Time,
@@ -21,56 +25,281 @@ pub enum Synchronization {
 }
 
 #[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DurationDiscretization {
     #[default]
     Continuous,
+    /// The duration is a multiple of the control cycle
     Discrete,
+    /// The duration is a multiple of the given step in [s], independent of the control cycle
+    DiscreteStep(f64),
 }
 
-#[derive(Debug, Clone)]
+impl DurationDiscretization {
+    /// The step that the duration is a multiple of, if the duration is discrete
+    pub fn step(&self, delta_time: f64) -> Option<f64> {
+        match self {
+            DurationDiscretization::Continuous => None,
+            DurationDiscretization::Discrete => Some(delta_time),
+            DurationDiscretization::DiscreteStep(step) => Some(*step),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InputParameter<const DOF: usize> {
     pub degrees_of_freedom: usize,
     pub control_interface: ControlInterface,
     pub synchronization: Synchronization,
     pub duration_discretization: DurationDiscretization,
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_float"))]
     pub current_position: DataArrayOrVec<f64, DOF>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_float"))]
     pub current_velocity: DataArrayOrVec<f64, DOF>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_float"))]
     pub current_acceleration: DataArrayOrVec<f64, DOF>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_float"))]
     pub target_position: DataArrayOrVec<f64, DOF>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_float"))]
     pub target_velocity: DataArrayOrVec<f64, DOF>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_float"))]
     pub target_acceleration: DataArrayOrVec<f64, DOF>,
+    /// Intermediate positions between the current and the target state, each reached at rest
+    /// unless it has a blend tolerance. They are a queue, so that waypoints can be streamed.
+    pub intermediate_positions: VecDeque<DataArrayOrVec<f64, DOF>>,
+    /// Maximal deviation of the path from the straight lines through each intermediate
+    /// position, so that the position is passed with a velocity instead of at rest. A missing
+    /// entry or zero reaches the position exactly.
+    pub intermediate_blend_tolerances: VecDeque<f64>,
+    /// Optional position limits that the trajectory must not leave
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_float::option"))]
+    pub max_position: Option<DataArrayOrVec<f64, DOF>>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_float::option"))]
+    pub min_position: Option<DataArrayOrVec<f64, DOF>>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_float"))]
     pub max_velocity: DataArrayOrVec<f64, DOF>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_float"))]
     pub max_acceleration: DataArrayOrVec<f64, DOF>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_float"))]
     pub max_jerk: DataArrayOrVec<f64, DOF>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_float::option"))]
     pub min_velocity: Option<DataArrayOrVec<f64, DOF>>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_float::option"))]
     pub min_acceleration: Option<DataArrayOrVec<f64, DOF>>,
+    /// Optional snap limit of each DoF for trajectories to rest. The third-order trajectory is
+    /// smoothed by a moving average, which keeps its limits, so that its jerk is continuous. It
+    /// takes longer by the window of the average, see `Trajectory::get_snap_window`. An online
+    /// trajectory re-planned by `update` during the motion continues from its current jerk.
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_float::option"))]
+    pub max_snap: Option<DataArrayOrVec<f64, DOF>>,
     pub enabled: DataArrayOrVec<bool, DOF>,
     pub per_dof_control_interface: Option<DataArrayOrVec<ControlInterface, DOF>>,
     pub per_dof_synchronization: Option<DataArrayOrVec<Synchronization, DOF>>,
+    /// Optional priority of each DoF for the synchronization. The DoFs with the highest priority
+    /// keep their time-optimal profiles, while all other DoFs are synchronized to a duration that
+    /// is at least as long as theirs.
+    pub per_dof_priority: Option<DataArrayOrVec<u32, DOF>>,
+    /// Optional synchronization group of each DoF. The DoFs of each group are synchronized
+    /// independently of the other groups, and the trajectory lasts until all groups are finished.
+    pub per_dof_group: Option<DataArrayOrVec<usize, DOF>>,
+    /// Optional period of each circular DoF, e.g. 360.0 for a rotary axis in degrees. A circular
+    /// DoF moves to its target position along the shortest path, and the positions of the
+    /// trajectory are wrapped into `[0, period)`.
+    pub per_dof_period: Option<DataArrayOrVec<Option<f64>, DOF>>,
+    /// Optional tolerances of the position and velocity of each DoF around the target state.
+    /// `Ruckig::update` reports `Finished` as soon as all enabled DoFs are within them, e.g. to
+    /// blend into the next motion earlier.
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_float::option"))]
+    pub target_position_tolerance: Option<DataArrayOrVec<f64, DOF>>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_float::option"))]
+    pub target_velocity_tolerance: Option<DataArrayOrVec<f64, DOF>>,
     pub minimum_duration: Option<f64>,
+    /// Optional maximum duration of the trajectory including its dwell times. A longer trajectory
+    /// is an error, or reported as a warning in the relaxed mode of the settings.
+    pub maximum_duration: Option<f64>,
+    /// Optional time to hold the current state before the motion starts, which requires the
+    /// current state to be at rest
+    pub pre_dwell: Option<f64>,
+    /// Optional time to hold the target state after the motion has finished, which requires the
+    /// target state to be at rest
+    pub post_dwell: Option<f64>,
     pub interrupt_calculation_duration: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    max_number_of_waypoints: usize,
+}
+
+impl<const DOF: usize> Clone for InputParameter<DOF> {
+    fn clone(&self) -> Self {
+        Self {
+            degrees_of_freedom: self.degrees_of_freedom,
+            control_interface: self.control_interface.clone(),
+            synchronization: self.synchronization.clone(),
+            duration_discretization: self.duration_discretization.clone(),
+            current_position: self.current_position.clone(),
+            current_velocity: self.current_velocity.clone(),
+            current_acceleration: self.current_acceleration.clone(),
+            target_position: self.target_position.clone(),
+            target_velocity: self.target_velocity.clone(),
+            target_acceleration: self.target_acceleration.clone(),
+            intermediate_positions: self.intermediate_positions.clone(),
+            intermediate_blend_tolerances: self.intermediate_blend_tolerances.clone(),
+            max_position: self.max_position.clone(),
+            min_position: self.min_position.clone(),
+            max_velocity: self.max_velocity.clone(),
+            max_acceleration: self.max_acceleration.clone(),
+            max_jerk: self.max_jerk.clone(),
+            min_velocity: self.min_velocity.clone(),
+            min_acceleration: self.min_acceleration.clone(),
+            max_snap: self.max_snap.clone(),
+            enabled: self.enabled.clone(),
+            per_dof_control_interface: self.per_dof_control_interface.clone(),
+            per_dof_synchronization: self.per_dof_synchronization.clone(),
+            per_dof_priority: self.per_dof_priority.clone(),
+            per_dof_group: self.per_dof_group.clone(),
+            per_dof_period: self.per_dof_period.clone(),
+            target_position_tolerance: self.target_position_tolerance.clone(),
+            target_velocity_tolerance: self.target_velocity_tolerance.clone(),
+            minimum_duration: self.minimum_duration,
+            maximum_duration: self.maximum_duration,
+            pre_dwell: self.pre_dwell,
+            post_dwell: self.post_dwell,
+            interrupt_calculation_duration: self.interrupt_calculation_duration,
+            max_number_of_waypoints: self.max_number_of_waypoints,
+        }
+    }
+
+    // Reuse the existing storage, so that copying an input of the same shape doesn't allocate
+    fn clone_from(&mut self, source: &Self) {
+        let Self {
+            degrees_of_freedom,
+            control_interface,
+            synchronization,
+            duration_discretization,
+            current_position,
+            current_velocity,
+            current_acceleration,
+            target_position,
+            target_velocity,
+            target_acceleration,
+            intermediate_positions,
+            intermediate_blend_tolerances,
+            max_position,
+            min_position,
+            max_velocity,
+            max_acceleration,
+            max_jerk,
+            min_velocity,
+            min_acceleration,
+            max_snap,
+            enabled,
+            per_dof_control_interface,
+            per_dof_synchronization,
+            per_dof_priority,
+            per_dof_group,
+            per_dof_period,
+            target_position_tolerance,
+            target_velocity_tolerance,
+            minimum_duration,
+            maximum_duration,
+            pre_dwell,
+            post_dwell,
+            interrupt_calculation_duration,
+            max_number_of_waypoints,
+        } = source;
+        self.degrees_of_freedom = *degrees_of_freedom;
+        self.control_interface.clone_from(control_interface);
+        self.synchronization.clone_from(synchronization);
+        self.duration_discretization
+            .clone_from(duration_discretization);
+        self.current_position.clone_from(current_position);
+        self.current_velocity.clone_from(current_velocity);
+        self.current_acceleration.clone_from(current_acceleration);
+        self.target_position.clone_from(target_position);
+        self.target_velocity.clone_from(target_velocity);
+        self.target_acceleration.clone_from(target_acceleration);
+        self.intermediate_positions
+            .clone_from(intermediate_positions);
+        self.intermediate_blend_tolerances
+            .clone_from(intermediate_blend_tolerances);
+        self.max_position.clone_from(max_position);
+        self.min_position.clone_from(min_position);
+        self.max_velocity.clone_from(max_velocity);
+        self.max_acceleration.clone_from(max_acceleration);
+        self.max_jerk.clone_from(max_jerk);
+        self.min_velocity.clone_from(min_velocity);
+        self.min_acceleration.clone_from(min_acceleration);
+        self.max_snap.clone_from(max_snap);
+        self.enabled.clone_from(enabled);
+        self.per_dof_control_interface
+            .clone_from(per_dof_control_interface);
+        self.per_dof_synchronization
+            .clone_from(per_dof_synchronization);
+        self.per_dof_priority.clone_from(per_dof_priority);
+        self.per_dof_group.clone_from(per_dof_group);
+        self.per_dof_period.clone_from(per_dof_period);
+        self.target_position_tolerance
+            .clone_from(target_position_tolerance);
+        self.target_velocity_tolerance
+            .clone_from(target_velocity_tolerance);
+        self.minimum_duration = *minimum_duration;
+        self.maximum_duration = *maximum_duration;
+        self.pre_dwell = *pre_dwell;
+        self.post_dwell = *post_dwell;
+        self.interrupt_calculation_duration = *interrupt_calculation_duration;
+        self.max_number_of_waypoints = *max_number_of_waypoints;
+    }
 }
 
 impl<const DOF: usize> PartialEq for InputParameter<DOF> {
     fn eq(&self, other: &Self) -> bool {
+        self.equals_within_target_tolerance(other, 0.0)
+    }
+}
+
+impl<const DOF: usize> InputParameter<DOF> {
+    /// Whether the input equals the other one, except for target states that differ by at most
+    /// the tolerance, see `Settings::replan_tolerance`
+    pub(crate) fn equals_within_target_tolerance(&self, other: &Self, tolerance: f64) -> bool {
+        let close = |values: &DataArrayOrVec<f64, DOF>, others: &DataArrayOrVec<f64, DOF>| {
+            values.len() == others.len()
+                && values
+                    .iter()
+                    .zip(others.iter())
+                    .all(|(value, other)| value == other || (value - other).abs() <= tolerance)
+        };
+
         self.current_position == other.current_position
             && self.current_velocity == other.current_velocity
             && self.current_acceleration == other.current_acceleration
-            && self.target_position == other.target_position
-            && self.target_velocity == other.target_velocity
-            && self.target_acceleration == other.target_acceleration
+            && close(&self.target_position, &other.target_position)
+            && close(&self.target_velocity, &other.target_velocity)
+            && close(&self.target_acceleration, &other.target_acceleration)
+            && self.intermediate_positions == other.intermediate_positions
+            && self.intermediate_blend_tolerances == other.intermediate_blend_tolerances
+            && self.max_position == other.max_position
+            && self.min_position == other.min_position
             && self.max_velocity == other.max_velocity
             && self.max_acceleration == other.max_acceleration
             && self.max_jerk == other.max_jerk
             && self.enabled == other.enabled
             && self.minimum_duration == other.minimum_duration
+            && self.maximum_duration == other.maximum_duration
+            && self.pre_dwell == other.pre_dwell
+            && self.post_dwell == other.post_dwell
             && self.min_velocity == other.min_velocity
             && self.min_acceleration == other.min_acceleration
+            && self.max_snap == other.max_snap
             && self.control_interface == other.control_interface
             && self.synchronization == other.synchronization
             && self.duration_discretization == other.duration_discretization
             && self.per_dof_control_interface == other.per_dof_control_interface
             && self.per_dof_synchronization == other.per_dof_synchronization
+            && self.per_dof_priority == other.per_dof_priority
+            && self.per_dof_group == other.per_dof_group
+            && self.per_dof_period == other.per_dof_period
+            && self.target_position_tolerance == other.target_position_tolerance
+            && self.target_velocity_tolerance == other.target_velocity_tolerance
     }
 }
 
@@ -82,6 +311,12 @@ impl<const DOF: usize> Default for InputParameter<DOF> {
 
 impl<const DOF: usize> InputParameter<DOF> {
     pub fn new(dofs: Option<usize>) -> Self {
+        Self::new_with_waypoints(0, dofs)
+    }
+
+    /// Create an input with pre-allocated storage for up to `max_number_of_waypoints`
+    /// intermediate positions, which can then be streamed in with `push_intermediate_position`.
+    pub fn new_with_waypoints(max_number_of_waypoints: usize, dofs: Option<usize>) -> Self {
         Self {
             degrees_of_freedom: dofs.unwrap_or(DOF),
             control_interface: ControlInterface::Position,
@@ -93,17 +328,142 @@ impl<const DOF: usize> InputParameter<DOF> {
             target_position: DataArrayOrVec::<f64, DOF>::new(dofs, 0.0),
             target_velocity: DataArrayOrVec::<f64, DOF>::new(dofs, 0.0),
             target_acceleration: DataArrayOrVec::<f64, DOF>::new(dofs, 0.0),
+            intermediate_positions: VecDeque::with_capacity(max_number_of_waypoints),
+            intermediate_blend_tolerances: VecDeque::with_capacity(max_number_of_waypoints),
+            max_position: None,
+            min_position: None,
             max_velocity: DataArrayOrVec::<f64, DOF>::new(dofs, 0.0),
             max_acceleration: DataArrayOrVec::<f64, DOF>::new(dofs, f64::INFINITY),
             max_jerk: DataArrayOrVec::<f64, DOF>::new(dofs, f64::INFINITY),
             enabled: DataArrayOrVec::<bool, DOF>::new(dofs, true),
             min_velocity: None,
             min_acceleration: None,
+            max_snap: None,
             per_dof_control_interface: None,
             per_dof_synchronization: None,
+            per_dof_priority: None,
+            per_dof_group: None,
+            per_dof_period: None,
+            target_position_tolerance: None,
+            target_velocity_tolerance: None,
             minimum_duration: None,
+            maximum_duration: None,
+            pre_dwell: None,
+            post_dwell: None,
             interrupt_calculation_duration: None,
+            max_number_of_waypoints,
+        }
+    }
+
+    /// Append an intermediate position behind all other waypoints. Fails if the position doesn't
+    /// have a value for each DoF, or if the maximum number of waypoints given at construction is
+    /// already reached, so the storage never grows.
+    pub fn push_intermediate_position(
+        &mut self,
+        position: DataArrayOrVec<f64, DOF>,
+    ) -> Result<(), RuckigError> {
+        if position.len() != self.degrees_of_freedom {
+            return Err(RuckigError::DofMismatch);
+        }
+        if self.intermediate_positions.len() >= self.max_number_of_waypoints {
+            return Err(RuckigError::TooManyWaypoints {
+                count: self.intermediate_positions.len() + 1,
+                max: self.max_number_of_waypoints,
+            });
+        }
+        self.intermediate_positions.push_back(position);
+        Ok(())
+    }
+
+    /// Remove the next (first) intermediate position, e.g. after it has been reached, together
+    /// with its blend tolerance.
+    pub fn pop_intermediate_position(&mut self) -> Option<DataArrayOrVec<f64, DOF>> {
+        let position = self.intermediate_positions.pop_front()?;
+        self.intermediate_blend_tolerances.pop_front();
+        Some(position)
+    }
+
+    // Copy the values into the storage after checking that there is one per DoF
+    fn assign(
+        degrees_of_freedom: usize,
+        storage: &mut DataArrayOrVec<f64, DOF>,
+        values: &[f64],
+    ) -> Result<(), RuckigError> {
+        if values.len() != degrees_of_freedom {
+            return Err(RuckigError::DofMismatch);
+        }
+        storage.assign(values);
+        Ok(())
+    }
+
+    /// Set the current position from a slice with one value per DoF
+    pub fn set_current_position(&mut self, values: &[f64]) -> Result<(), RuckigError> {
+        Self::assign(self.degrees_of_freedom, &mut self.current_position, values)
+    }
+
+    /// Set the current velocity from a slice with one value per DoF
+    pub fn set_current_velocity(&mut self, values: &[f64]) -> Result<(), RuckigError> {
+        Self::assign(self.degrees_of_freedom, &mut self.current_velocity, values)
+    }
+
+    /// Set the current acceleration from a slice with one value per DoF
+    pub fn set_current_acceleration(&mut self, values: &[f64]) -> Result<(), RuckigError> {
+        Self::assign(
+            self.degrees_of_freedom,
+            &mut self.current_acceleration,
+            values,
+        )
+    }
+
+    /// Set the target position from a slice with one value per DoF
+    pub fn set_target_position(&mut self, values: &[f64]) -> Result<(), RuckigError> {
+        Self::assign(self.degrees_of_freedom, &mut self.target_position, values)
+    }
+
+    /// Set the target velocity from a slice with one value per DoF
+    pub fn set_target_velocity(&mut self, values: &[f64]) -> Result<(), RuckigError> {
+        Self::assign(self.degrees_of_freedom, &mut self.target_velocity, values)
+    }
+
+    /// Set the target acceleration from a slice with one value per DoF
+    pub fn set_target_acceleration(&mut self, values: &[f64]) -> Result<(), RuckigError> {
+        Self::assign(
+            self.degrees_of_freedom,
+            &mut self.target_acceleration,
+            values,
+        )
+    }
+
+    /// Set the maximum velocity from a slice with one value per DoF
+    pub fn set_max_velocity(&mut self, values: &[f64]) -> Result<(), RuckigError> {
+        Self::assign(self.degrees_of_freedom, &mut self.max_velocity, values)
+    }
+
+    /// Set the maximum acceleration from a slice with one value per DoF
+    pub fn set_max_acceleration(&mut self, values: &[f64]) -> Result<(), RuckigError> {
+        Self::assign(self.degrees_of_freedom, &mut self.max_acceleration, values)
+    }
+
+    /// Set the maximum jerk from a slice with one value per DoF
+    pub fn set_max_jerk(&mut self, values: &[f64]) -> Result<(), RuckigError> {
+        Self::assign(self.degrees_of_freedom, &mut self.max_jerk, values)
+    }
+
+    /// Set the current kinematic state from slices with one value per DoF each. On a length
+    /// mismatch, the input is left unchanged.
+    pub fn set_state(
+        &mut self,
+        position: &[f64],
+        velocity: &[f64],
+        acceleration: &[f64],
+    ) -> Result<(), RuckigError> {
+        let dofs = self.degrees_of_freedom;
+        if velocity.len() != dofs || acceleration.len() != dofs {
+            return Err(RuckigError::DofMismatch);
         }
+        Self::assign(dofs, &mut self.current_position, position)?;
+        Self::assign(dofs, &mut self.current_velocity, velocity)?;
+        Self::assign(dofs, &mut self.current_acceleration, acceleration)
     }
 
     #[inline]
@@ -111,81 +471,155 @@ impl<const DOF: usize> InputParameter<DOF> {
         v0 + (a0 * a0) / (2.0 * j)
     }
 
-    /// Validate the input for trajectory calculation
-    pub fn validate<E: RuckigErrorHandler>(
+    /// Check all conditions of the input in the order of the validation. Every violation is passed
+    /// to `on_violation`, which returns whether the check should continue.
+    fn check_input<F: FnMut(ValidationViolation) -> bool>(
         &self,
         check_current_state_within_limits: bool,
         check_target_state_within_limits: bool,
-    ) -> Result<bool, RuckigError> {
+        mut on_violation: F,
+    ) {
+        macro_rules! violation {
+            ($dof:expr, $quantity:expr, $kind:expr, $value:expr, $allowed:expr) => {
+                if !on_violation(ValidationViolation {
+                    dof: $dof,
+                    quantity: $quantity,
+                    kind: $kind,
+                    value: $value,
+                    allowed: $allowed,
+                }) {
+                    return;
+                }
+            };
+        }
+
+        // Kinematic states are expected to be finite, in contrast to limits
+        macro_rules! finite {
+            ($dof:expr, $quantity:expr, $value:expr) => {
+                if $value.is_nan() {
+                    violation!($dof, $quantity, ViolationKind::NotANumber, $value, None);
+                } else if $value.is_infinite() {
+                    violation!($dof, $quantity, ViolationKind::Infinite, $value, None);
+                }
+            };
+        }
+
         for dof in 0..self.degrees_of_freedom {
             let j_max = self.max_jerk[dof];
-            if j_max.is_nan() || j_max < 0.0 {
-                return E::handle_validation_error(&format!(
-                    "Maximum jerk limit {} of DoF {} should be larger than or equal to zero.",
-                    j_max, dof
-                ));
+            if j_max.is_nan() {
+                violation!(
+                    Some(dof),
+                    InputQuantity::MaxJerk,
+                    ViolationKind::NotANumber,
+                    j_max,
+                    None
+                );
+            } else if j_max < 0.0 {
+                violation!(
+                    Some(dof),
+                    InputQuantity::MaxJerk,
+                    ViolationKind::Negative,
+                    j_max,
+                    None
+                );
             }
 
             let a_max: f64 = self.max_acceleration[dof];
-            if a_max.is_nan() || a_max < 0.0 {
-                return E::handle_validation_error(&format!("maximum acceleration limit {} of DoF {} should be larger than or equal to zero.", a_max, dof));
+            if a_max.is_nan() {
+                violation!(
+                    Some(dof),
+                    InputQuantity::MaxAcceleration,
+                    ViolationKind::NotANumber,
+                    a_max,
+                    None
+                );
+            } else if a_max < 0.0 {
+                violation!(
+                    Some(dof),
+                    InputQuantity::MaxAcceleration,
+                    ViolationKind::Negative,
+                    a_max,
+                    None
+                );
             }
 
             let a_min: f64 = match &self.min_acceleration {
                 Some(min_acc) => min_acc.deref()[dof],
                 None => -self.max_acceleration.deref()[dof],
             };
-            if a_min.is_nan() || a_min > 0.0 {
-                return E::handle_validation_error(&format!("minimum acceleration limit {} of DoF {} should be smaller than or equal to zero.", a_min, dof));
+            if a_min.is_nan() {
+                violation!(
+                    Some(dof),
+                    InputQuantity::MinAcceleration,
+                    ViolationKind::NotANumber,
+                    a_min,
+                    None
+                );
+            } else if a_min > 0.0 {
+                violation!(
+                    Some(dof),
+                    InputQuantity::MinAcceleration,
+                    ViolationKind::Positive,
+                    a_min,
+                    None
+                );
             }
 
             let a0: f64 = self.current_acceleration[dof];
-            if a0.is_nan() {
-                return E::handle_validation_error(&format!(
-                    "current acceleration {} of DoF {} should be a valid number.",
-                    a0, dof
-                ));
-            }
+            finite!(Some(dof), InputQuantity::CurrentAcceleration, a0);
 
             let af: f64 = self.target_acceleration[dof];
-            if af.is_nan() {
-                return E::handle_validation_error(&format!(
-                    "target acceleration {} of DoF {} should be a valid number.",
-                    af, dof
-                ));
-            }
+            finite!(Some(dof), InputQuantity::TargetAcceleration, af);
 
             if check_current_state_within_limits {
                 if a0 > a_max {
-                    return E::handle_validation_error(&format!("current acceleration {} of DoF {} exceeds its maximum acceleration limit {}.", a0, dof, a_max));
+                    violation!(
+                        Some(dof),
+                        InputQuantity::CurrentAcceleration,
+                        ViolationKind::AboveMaximum,
+                        a0,
+                        Some(a_max)
+                    );
                 }
                 if a0 < a_min {
-                    return E::handle_validation_error(&format!("current acceleration {} of DoF {} undercuts its minimum acceleration limit {}.", a0, dof, a_min));
+                    violation!(
+                        Some(dof),
+                        InputQuantity::CurrentAcceleration,
+                        ViolationKind::BelowMinimum,
+                        a0,
+                        Some(a_min)
+                    );
                 }
             }
             if check_target_state_within_limits {
                 if af > a_max {
-                    return E::handle_validation_error(&format!("target acceleration {} of DoF {} exceeds its maximum acceleration limit {}.", af, dof, a_max));
+                    violation!(
+                        Some(dof),
+                        InputQuantity::TargetAcceleration,
+                        ViolationKind::AboveMaximum,
+                        af,
+                        Some(a_max)
+                    );
                 }
                 if af < a_min {
-                    return E::handle_validation_error(&format!("target acceleration {} of DoF {} undercuts its minimum acceleration limit {}.", af, dof, a_min));
+                    violation!(
+                        Some(dof),
+                        InputQuantity::TargetAcceleration,
+                        ViolationKind::BelowMinimum,
+                        af,
+                        Some(a_min)
+                    );
                 }
             }
 
             let v0 = self.current_velocity[dof];
-            if v0.is_nan() {
-                return E::handle_validation_error(&format!(
-                    "current velocity {} of DoF {} should be a valid number.",
-                    v0, dof
-                ));
-            }
+            finite!(Some(dof), InputQuantity::CurrentVelocity, v0);
             let vf = self.target_velocity[dof];
-            if vf.is_nan() {
-                return E::handle_validation_error(&format!(
-                    "target velocity {} of DoF {} should be a valid number.",
-                    vf, dof
-                ));
-            }
+            finite!(Some(dof), InputQuantity::TargetVelocity, vf);
+
+            // The current position is integrated for every control interface
+            let p0 = self.current_position[dof];
+            finite!(Some(dof), InputQuantity::CurrentPosition, p0);
 
             let control_interface_ = match &self.per_dof_control_interface {
                 Some(per_dof) => match per_dof.get(dof) {
@@ -195,25 +629,123 @@ impl<const DOF: usize> InputParameter<DOF> {
                 None => &self.control_interface,
             };
 
-            if let ControlInterface::Position = control_interface_ {
-                let p0 = self.current_position[dof];
-                if p0.is_nan() {
-                    return E::handle_validation_error(&format!(
-                        "current position {} of DoF {} should be a valid number.",
-                        p0, dof
-                    ));
+            // The limits select the order of the profiles, which may be compiled out
+            if self.enabled[dof] {
+                let (quantity, value, is_compiled) = if j_max.is_finite() {
+                    (InputQuantity::MaxJerk, j_max, cfg!(feature = "third-order"))
+                } else if a_max.is_finite() || *control_interface_ == ControlInterface::Acceleration
+                {
+                    (InputQuantity::MaxJerk, j_max, cfg!(feature = "second-order"))
+                } else {
+                    (InputQuantity::MaxAcceleration, a_max, cfg!(feature = "first-order"))
+                };
+                if !is_compiled {
+                    violation!(
+                        Some(dof),
+                        quantity,
+                        ViolationKind::OrderNotCompiled,
+                        value,
+                        None
+                    );
                 }
+            }
+
+            if let ControlInterface::Position = control_interface_ {
                 let pf = self.target_position[dof];
-                if pf.is_nan() {
-                    return E::handle_validation_error(&format!(
-                        "target position {} of DoF {} should be a valid number.",
-                        pf, dof
-                    ));
+                finite!(Some(dof), InputQuantity::TargetPosition, pf);
+
+                let p_max = self
+                    .max_position
+                    .as_ref()
+                    .map_or(f64::INFINITY, |max_position| max_position[dof]);
+                let p_min = self
+                    .min_position
+                    .as_ref()
+                    .map_or(f64::NEG_INFINITY, |min_position| min_position[dof]);
+                if p_max.is_nan() {
+                    violation!(
+                        Some(dof),
+                        InputQuantity::MaxPosition,
+                        ViolationKind::NotANumber,
+                        p_max,
+                        None
+                    );
+                } else if p_min.is_nan() {
+                    violation!(
+                        Some(dof),
+                        InputQuantity::MinPosition,
+                        ViolationKind::NotANumber,
+                        p_min,
+                        None
+                    );
+                } else if p_min > p_max {
+                    violation!(
+                        Some(dof),
+                        InputQuantity::MinPosition,
+                        ViolationKind::MinimumAboveMaximum,
+                        p_min,
+                        Some(p_max)
+                    );
+                }
+
+                if check_current_state_within_limits {
+                    if p0 > p_max {
+                        violation!(
+                            Some(dof),
+                            InputQuantity::CurrentPosition,
+                            ViolationKind::AboveMaximum,
+                            p0,
+                            Some(p_max)
+                        );
+                    }
+                    if p0 < p_min {
+                        violation!(
+                            Some(dof),
+                            InputQuantity::CurrentPosition,
+                            ViolationKind::BelowMinimum,
+                            p0,
+                            Some(p_min)
+                        );
+                    }
+                }
+                if check_target_state_within_limits {
+                    if pf > p_max {
+                        violation!(
+                            Some(dof),
+                            InputQuantity::TargetPosition,
+                            ViolationKind::AboveMaximum,
+                            pf,
+                            Some(p_max)
+                        );
+                    }
+                    if pf < p_min {
+                        violation!(
+                            Some(dof),
+                            InputQuantity::TargetPosition,
+                            ViolationKind::BelowMinimum,
+                            pf,
+                            Some(p_min)
+                        );
+                    }
                 }
 
                 let v_max = self.max_velocity[dof];
-                if v_max.is_nan() || v_max < 0.0 {
-                    return E::handle_validation_error(&format!("maximum velocity limit {} of DoF {} should be larger than or equal to zero.", v_max, dof));
+                if v_max.is_nan() {
+                    violation!(
+                        Some(dof),
+                        InputQuantity::MaxVelocity,
+                        ViolationKind::NotANumber,
+                        v_max,
+                        None
+                    );
+                } else if v_max < 0.0 {
+                    violation!(
+                        Some(dof),
+                        InputQuantity::MaxVelocity,
+                        ViolationKind::Negative,
+                        v_max,
+                        None
+                    );
                 }
 
                 let v_min = if let Some(min_velocity) = &self.min_velocity {
@@ -221,75 +753,613 @@ impl<const DOF: usize> InputParameter<DOF> {
                 } else {
                     -v_max
                 };
-                if v_min.is_nan() || v_min > 0.0 {
-                    return E::handle_validation_error(&format!("minimum velocity limit {} of DoF {} should be smaller than or equal to zero.", v_min, dof));
+                if v_min.is_nan() {
+                    violation!(
+                        Some(dof),
+                        InputQuantity::MinVelocity,
+                        ViolationKind::NotANumber,
+                        v_min,
+                        None
+                    );
+                } else if v_min > 0.0 {
+                    violation!(
+                        Some(dof),
+                        InputQuantity::MinVelocity,
+                        ViolationKind::Positive,
+                        v_min,
+                        None
+                    );
                 }
 
                 if check_current_state_within_limits {
                     if v0 > v_max {
-                        return E::handle_validation_error(&format!(
-                            "current velocity {} of DoF {} exceeds its maximum velocity limit {}.",
-                            v0, dof, v_max
-                        ));
+                        violation!(
+                            Some(dof),
+                            InputQuantity::CurrentVelocity,
+                            ViolationKind::AboveMaximum,
+                            v0,
+                            Some(v_max)
+                        );
                     }
                     if v0 < v_min {
-                        return E::handle_validation_error(&format!("current velocity {} of DoF {} undercuts its minimum velocity limit {}.", v0, dof, v_min));
+                        violation!(
+                            Some(dof),
+                            InputQuantity::CurrentVelocity,
+                            ViolationKind::BelowMinimum,
+                            v0,
+                            Some(v_min)
+                        );
                     }
                 }
                 if check_target_state_within_limits {
                     if vf > v_max {
-                        return E::handle_validation_error(&format!(
-                            "target velocity {} of DoF {} exceeds its maximum velocity limit {}.",
-                            vf, dof, v_max
-                        ));
+                        violation!(
+                            Some(dof),
+                            InputQuantity::TargetVelocity,
+                            ViolationKind::AboveMaximum,
+                            vf,
+                            Some(v_max)
+                        );
                     }
                     if vf < v_min {
-                        return E::handle_validation_error(&format!(
-                            "target velocity {} of DoF {} undercuts its minimum velocity limit {}.",
-                            vf, dof, v_min
-                        ));
+                        violation!(
+                            Some(dof),
+                            InputQuantity::TargetVelocity,
+                            ViolationKind::BelowMinimum,
+                            vf,
+                            Some(v_min)
+                        );
                     }
                 }
                 if check_current_state_within_limits {
-                    if a0 > 0.0
-                        && j_max > 0.0
-                        && InputParameter::<DOF>::v_at_a_zero(v0, a0, j_max) > v_max
-                    {
-                        return E::handle_validation_error(&format!("DoF {} will inevitably reach a velocity {} from the current kinematic state that will exceed its maximum velocity limit {}.", dof, InputParameter::<DOF>::v_at_a_zero(v0, a0, j_max), v_max));
+                    let v_peak = InputParameter::<DOF>::v_at_a_zero(v0, a0, j_max);
+                    if a0 > 0.0 && j_max > 0.0 && v_peak > v_max {
+                        violation!(
+                            Some(dof),
+                            InputQuantity::CurrentVelocity,
+                            ViolationKind::InevitablyAboveMaximumVelocity,
+                            v_peak,
+                            Some(v_max)
+                        );
                     }
-                    if a0 < 0.0
-                        && j_max > 0.0
-                        && InputParameter::<DOF>::v_at_a_zero(v0, a0, -j_max) < v_min
-                    {
-                        return E::handle_validation_error(&format!("DoF {} will inevitably reach a velocity {} from the current kinematic state that will undercut its minimum velocity limit {}.", dof, InputParameter::<DOF>::v_at_a_zero(v0, a0, -j_max), v_min));
+                    let v_peak = InputParameter::<DOF>::v_at_a_zero(v0, a0, -j_max);
+                    if a0 < 0.0 && j_max > 0.0 && v_peak < v_min {
+                        violation!(
+                            Some(dof),
+                            InputQuantity::CurrentVelocity,
+                            ViolationKind::InevitablyBelowMinimumVelocity,
+                            v_peak,
+                            Some(v_min)
+                        );
                     }
                 }
                 if check_target_state_within_limits {
-                    if af < 0.0
-                        && j_max > 0.0
-                        && InputParameter::<DOF>::v_at_a_zero(vf, af, j_max) > v_max
-                    {
-                        return E::handle_validation_error(&format!("DoF {} will inevitably have reached a velocity {} from the target kinematic state that will exceed its maximum velocity limit {}.", dof, InputParameter::<DOF>::v_at_a_zero(vf, af, j_max), v_max));
+                    let v_peak = InputParameter::<DOF>::v_at_a_zero(vf, af, j_max);
+                    if af < 0.0 && j_max > 0.0 && v_peak > v_max {
+                        violation!(
+                            Some(dof),
+                            InputQuantity::TargetVelocity,
+                            ViolationKind::InevitablyAboveMaximumVelocity,
+                            v_peak,
+                            Some(v_max)
+                        );
+                    }
+                    let v_peak = InputParameter::<DOF>::v_at_a_zero(vf, af, -j_max);
+                    if af > 0.0 && j_max > 0.0 && v_peak < v_min {
+                        violation!(
+                            Some(dof),
+                            InputQuantity::TargetVelocity,
+                            ViolationKind::InevitablyBelowMinimumVelocity,
+                            v_peak,
+                            Some(v_min)
+                        );
+                    }
+                }
+            } else if *control_interface_ == ControlInterface::Velocity
+                && a_max.is_infinite()
+                && check_target_state_within_limits
+            {
+                // The first-order velocity interface steps to the target velocity
+                let v_max = self.max_velocity[dof];
+                let v_min = self
+                    .min_velocity
+                    .as_ref()
+                    .map_or(-v_max, |min_velocity| min_velocity[dof]);
+                if vf > v_max {
+                    violation!(
+                        Some(dof),
+                        InputQuantity::TargetVelocity,
+                        ViolationKind::AboveMaximum,
+                        vf,
+                        Some(v_max)
+                    );
+                }
+                if vf < v_min {
+                    violation!(
+                        Some(dof),
+                        InputQuantity::TargetVelocity,
+                        ViolationKind::BelowMinimum,
+                        vf,
+                        Some(v_min)
+                    );
+                }
+            } else if *control_interface_ == ControlInterface::Acceleration {
+                // The acceleration interface has no target velocity, but keeps the velocity
+                // limits while it ramps the acceleration
+                if vf != 0.0 {
+                    violation!(
+                        Some(dof),
+                        InputQuantity::TargetVelocity,
+                        ViolationKind::UnsupportedControlInterface,
+                        vf,
+                        None
+                    );
+                }
+                let v_max = self.max_velocity[dof];
+                let v_min = self
+                    .min_velocity
+                    .as_ref()
+                    .map_or(-v_max, |min_velocity| min_velocity[dof]);
+                if check_current_state_within_limits {
+                    if v0 > v_max {
+                        violation!(
+                            Some(dof),
+                            InputQuantity::CurrentVelocity,
+                            ViolationKind::AboveMaximum,
+                            v0,
+                            Some(v_max)
+                        );
+                    }
+                    if v0 < v_min {
+                        violation!(
+                            Some(dof),
+                            InputQuantity::CurrentVelocity,
+                            ViolationKind::BelowMinimum,
+                            v0,
+                            Some(v_min)
+                        );
                     }
-                    if af > 0.0
-                        && j_max > 0.0
-                        && InputParameter::<DOF>::v_at_a_zero(vf, af, -j_max) < v_min
-                    {
-                        return E::handle_validation_error(&format!("DoF {} will inevitably have reached a velocity {} from the target kinematic state that will undercut its minimum velocity limit {}.", dof, InputParameter::<DOF>::v_at_a_zero(vf, af, -j_max), v_min));
+                }
+            }
+        }
+
+        for (i, position) in self.intermediate_positions.iter().enumerate() {
+            if position.len() != self.degrees_of_freedom {
+                violation!(
+                    None,
+                    InputQuantity::IntermediatePosition(i),
+                    ViolationKind::WrongLength,
+                    position.len() as f64,
+                    Some(self.degrees_of_freedom as f64)
+                );
+                continue;
+            }
+            for dof in 0..self.degrees_of_freedom {
+                finite!(
+                    Some(dof),
+                    InputQuantity::IntermediatePosition(i),
+                    position[dof]
+                );
+                let control_interface_ = match &self.per_dof_control_interface {
+                    Some(per_dof) => per_dof.get(dof).unwrap_or(&self.control_interface),
+                    None => &self.control_interface,
+                };
+                if *control_interface_ != ControlInterface::Position {
+                    violation!(
+                        Some(dof),
+                        InputQuantity::IntermediatePosition(i),
+                        ViolationKind::UnsupportedControlInterface,
+                        position[dof],
+                        None
+                    );
+                }
+            }
+        }
+
+        for (i, &tolerance) in self.intermediate_blend_tolerances.iter().enumerate() {
+            if tolerance.is_nan() {
+                violation!(
+                    None,
+                    InputQuantity::BlendTolerance(i),
+                    ViolationKind::NotANumber,
+                    tolerance,
+                    None
+                );
+            } else if tolerance < 0.0 {
+                violation!(
+                    None,
+                    InputQuantity::BlendTolerance(i),
+                    ViolationKind::Negative,
+                    tolerance,
+                    None
+                );
+            }
+        }
+
+        if let DurationDiscretization::DiscreteStep(step) = self.duration_discretization {
+            finite!(None, InputQuantity::DiscretizationStep, step);
+            if step <= 0.0 {
+                violation!(
+                    None,
+                    InputQuantity::DiscretizationStep,
+                    ViolationKind::NotPositive,
+                    step,
+                    None
+                );
+            }
+        }
+
+        if let Some(per_dof_period) = &self.per_dof_period {
+            for dof in 0..self.degrees_of_freedom {
+                let Some(period) = per_dof_period[dof] else {
+                    continue;
+                };
+                finite!(Some(dof), InputQuantity::Period, period);
+                if period <= 0.0 {
+                    violation!(
+                        Some(dof),
+                        InputQuantity::Period,
+                        ViolationKind::NotPositive,
+                        period,
+                        None
+                    );
+                }
+            }
+        }
+
+        for (quantity, tolerances) in [
+            (
+                InputQuantity::TargetPositionTolerance,
+                &self.target_position_tolerance,
+            ),
+            (
+                InputQuantity::TargetVelocityTolerance,
+                &self.target_velocity_tolerance,
+            ),
+        ] {
+            let Some(tolerances) = tolerances else {
+                continue;
+            };
+            for dof in 0..self.degrees_of_freedom {
+                finite!(Some(dof), quantity, tolerances[dof]);
+                if tolerances[dof] < 0.0 {
+                    violation!(
+                        Some(dof),
+                        quantity,
+                        ViolationKind::Negative,
+                        tolerances[dof],
+                        None
+                    );
+                }
+            }
+        }
+
+        for (quantity, dwell) in [
+            (InputQuantity::MinimumDuration, self.minimum_duration),
+            (InputQuantity::MaximumDuration, self.maximum_duration),
+            (InputQuantity::PreDwell, self.pre_dwell),
+            (InputQuantity::PostDwell, self.post_dwell),
+        ] {
+            if let Some(dwell) = dwell {
+                finite!(None, quantity, dwell);
+                if dwell < 0.0 {
+                    violation!(None, quantity, ViolationKind::Negative, dwell, None);
+                }
+            }
+        }
+
+        // A dwell holds a state at rest
+        let dwells = [
+            (
+                self.pre_dwell,
+                &self.current_velocity,
+                &self.current_acceleration,
+                InputQuantity::CurrentVelocity,
+                InputQuantity::CurrentAcceleration,
+            ),
+            (
+                self.post_dwell,
+                &self.target_velocity,
+                &self.target_acceleration,
+                InputQuantity::TargetVelocity,
+                InputQuantity::TargetAcceleration,
+            ),
+        ];
+        for (dwell, velocity, acceleration, velocity_quantity, acceleration_quantity) in dwells {
+            if !dwell.is_some_and(|dwell| dwell > 0.0) {
+                continue;
+            }
+            for dof in 0..self.degrees_of_freedom {
+                if !self.enabled[dof] {
+                    continue;
+                }
+                if velocity[dof] != 0.0 {
+                    violation!(
+                        Some(dof),
+                        velocity_quantity,
+                        ViolationKind::NotAtRest,
+                        velocity[dof],
+                        Some(0.0)
+                    );
+                }
+                if acceleration[dof] != 0.0 {
+                    violation!(
+                        Some(dof),
+                        acceleration_quantity,
+                        ViolationKind::NotAtRest,
+                        acceleration[dof],
+                        Some(0.0)
+                    );
+                }
+            }
+        }
+
+        // The moving average of a snap limit holds the target state after the motion, so it needs
+        // to be at rest
+        if let Some(max_snap) = &self.max_snap {
+            for dof in 0..self.degrees_of_freedom {
+                if !self.enabled[dof] {
+                    continue;
+                }
+                let s_max = max_snap[dof];
+                if s_max.is_nan() {
+                    violation!(
+                        Some(dof),
+                        InputQuantity::MaxSnap,
+                        ViolationKind::NotANumber,
+                        s_max,
+                        None
+                    );
+                } else if s_max <= 0.0 {
+                    violation!(
+                        Some(dof),
+                        InputQuantity::MaxSnap,
+                        ViolationKind::NotPositive,
+                        s_max,
+                        None
+                    );
+                }
+                let control_interface = match &self.per_dof_control_interface {
+                    Some(per_dof) => &per_dof[dof],
+                    None => &self.control_interface,
+                };
+                if *control_interface != ControlInterface::Position {
+                    violation!(
+                        Some(dof),
+                        InputQuantity::MaxSnap,
+                        ViolationKind::UnsupportedControlInterface,
+                        s_max,
+                        None
+                    );
+                }
+                for (quantity, value) in [
+                    (InputQuantity::TargetVelocity, self.target_velocity[dof]),
+                    (
+                        InputQuantity::TargetAcceleration,
+                        self.target_acceleration[dof],
+                    ),
+                ] {
+                    if value != 0.0 {
+                        violation!(
+                            Some(dof),
+                            quantity,
+                            ViolationKind::NotAtRest,
+                            value,
+                            Some(0.0)
+                        );
                     }
                 }
             }
         }
-        Ok(true)
+    }
+
+    /// The first violated condition of the validation, if any
+    pub fn first_violation(
+        &self,
+        check_current_state_within_limits: bool,
+        check_target_state_within_limits: bool,
+    ) -> Option<ValidationViolation> {
+        let mut first_violation = None;
+        self.check_input(
+            check_current_state_within_limits,
+            check_target_state_within_limits,
+            |violation| {
+                first_violation = Some(violation);
+                false
+            },
+        );
+        first_violation
+    }
+
+    /// Validate the input for trajectory calculation
+    pub fn validate<E: RuckigErrorHandler>(
+        &self,
+        check_current_state_within_limits: bool,
+        check_target_state_within_limits: bool,
+    ) -> Result<bool, RuckigError> {
+        match self.first_violation(
+            check_current_state_within_limits,
+            check_target_state_within_limits,
+        ) {
+            Some(violation) => E::handle_validation_error(RuckigError::InvalidInput(violation)),
+            None => Ok(true),
+        }
+    }
+
+    /// Clamp a target velocity and acceleration of the DoF to the closest state that can be
+    /// reached within the kinematic limits: the velocity within the velocity limits, and the
+    /// acceleration such that the velocity limits can still be kept.
+    pub(crate) fn reachable_target_state(
+        &self,
+        dof: usize,
+        velocity: f64,
+        acceleration: f64,
+    ) -> (f64, f64) {
+        // Non-finite targets are left to the validation
+        if !velocity.is_finite() || !acceleration.is_finite() {
+            return (velocity, acceleration);
+        }
+
+        let v_max = self.max_velocity[dof];
+        let v_min = self
+            .min_velocity
+            .as_ref()
+            .map_or(-v_max, |min_velocity| min_velocity[dof]);
+        let a_max = self.max_acceleration[dof];
+        let a_min = self
+            .min_acceleration
+            .as_ref()
+            .map_or(-a_max, |min_acceleration| min_acceleration[dof]);
+        let j_max = self.max_jerk[dof];
+
+        // Clamping with min/max instead of clamp(), as invalid limits are only rejected
+        // by the validation
+        let vf = velocity.max(v_min).min(v_max);
+        let mut af = acceleration.max(a_min).min(a_max);
+        if j_max.is_finite() && j_max > 0.0 {
+            af = af
+                .max(-(2.0 * j_max * (v_max - vf)).sqrt())
+                .min((2.0 * j_max * (vf - v_min)).sqrt());
+        }
+        (vf, af)
+    }
+
+    /// Whether the target velocity or acceleration of an enabled DoF can't be reached within
+    /// the kinematic limits
+    pub(crate) fn is_target_state_beyond_limits(&self) -> bool {
+        (0..self.degrees_of_freedom).any(|dof| {
+            let (vf, af) = (self.target_velocity[dof], self.target_acceleration[dof]);
+            self.enabled[dof] && self.reachable_target_state(dof, vf, af) != (vf, af)
+        })
+    }
+
+    /// Clamp the target velocity and acceleration of all enabled DoFs to the closest reachable
+    /// state, and report every clamped DoF as a warning
+    pub(crate) fn clamp_target_state<E: RuckigErrorHandler>(&mut self) {
+        for dof in 0..self.degrees_of_freedom {
+            if !self.enabled[dof] {
+                continue;
+            }
+
+            let (vf, af) = (self.target_velocity[dof], self.target_acceleration[dof]);
+            let (velocity, acceleration) = self.reachable_target_state(dof, vf, af);
+            if (velocity, acceleration) != (vf, af) {
+                self.target_velocity[dof] = velocity;
+                self.target_acceleration[dof] = acceleration;
+                E::handle_warning(RuckigWarning::TargetStateClamped {
+                    dof,
+                    velocity,
+                    acceleration,
+                });
+            }
+        }
+    }
+
+    /// Move the intermediate and target positions of circular DoFs by whole periods, so that
+    /// each of them is reached from the previous position along the shortest path
+    pub(crate) fn unwrap_circular_positions(&mut self) {
+        let Some(per_dof_period) = &self.per_dof_period else {
+            return;
+        };
+        for dof in 0..self.degrees_of_freedom {
+            let Some(period) = per_dof_period[dof] else {
+                continue;
+            };
+            let mut previous = self.current_position[dof];
+            for position in self
+                .intermediate_positions
+                .iter_mut()
+                .map(|intermediate_position| &mut intermediate_position[dof])
+                .chain(std::iter::once(&mut self.target_position[dof]))
+            {
+                *position = previous + circular_distance(*position - previous, period);
+                previous = *position;
+            }
+        }
+    }
+
+    /// Window of the moving average of a snap limit, which is fixed before the calculation if an
+    /// enabled DoF isn't at rest at the start, i.e. has a current velocity, acceleration, or jerk.
+    /// It covers a change between the negative and positive jerk limit or the current jerk.
+    pub(crate) fn leading_snap_window(
+        &self,
+        current_jerk: &DataArrayOrVec<f64, DOF>,
+    ) -> Option<f64> {
+        let max_snap = self.max_snap.as_ref()?;
+        let enabled = (0..self.degrees_of_freedom).filter(|&dof| self.enabled[dof]);
+        let is_at_rest = enabled.clone().all(|dof| {
+            self.current_velocity[dof] == 0.0
+                && self.current_acceleration[dof] == 0.0
+                && current_jerk[dof] == 0.0
+        });
+        if is_at_rest {
+            return None;
+        }
+
+        // A DoF without a jerk limit isn't smoothed, as for a trajectory from rest
+        Some(
+            enabled
+                .filter(|&dof| self.max_jerk[dof].is_finite())
+                .map(|dof| 2.0 * self.max_jerk[dof].max(current_jerk[dof].abs()) / max_snap[dof])
+                .fold(0.0, f64::max),
+        )
+    }
+
+    /// Move the current state of the enabled DoFs ahead by the window of a snap limit, so that
+    /// the moving average of the trajectory starts with the current state and jerk. Before the
+    /// start, the underlying trajectory continues backwards with the current jerk.
+    pub(crate) fn lead_snap_window(
+        &mut self,
+        window: f64,
+        current_jerk: &DataArrayOrVec<f64, DOF>,
+    ) {
+        for dof in 0..self.degrees_of_freedom {
+            if !self.enabled[dof] {
+                continue;
+            }
+            let (p0, v0, a0, j0) = (
+                self.current_position[dof],
+                self.current_velocity[dof],
+                self.current_acceleration[dof],
+                current_jerk[dof],
+            );
+            let a = a0 + j0 * window / 2.0;
+            let v = v0 + a0 * window / 2.0 + j0 * window * window / 12.0;
+            self.current_position[dof] = p0 + v * window / 2.0 - a * window * window / 6.0
+                + j0 * window * window * window / 24.0;
+            self.current_velocity[dof] = v;
+            self.current_acceleration[dof] = a;
+        }
+    }
+
+    /// Validate the input for trajectory calculation and report all violated conditions instead
+    /// of only the first one
+    pub fn validation_report(
+        &self,
+        check_current_state_within_limits: bool,
+        check_target_state_within_limits: bool,
+    ) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        self.check_input(
+            check_current_state_within_limits,
+            check_target_state_within_limits,
+            |violation| {
+                report.violations.push(violation);
+                true
+            },
+        );
+        report
     }
 }
 
 impl<const DOF: usize> fmt::Display for InputParameter<DOF> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        writeln!(f, "")?;
+        writeln!(f)?;
 
         if self.control_interface == ControlInterface::Velocity {
             writeln!(f, "inp.control_interface = ControlInterface.Velocity")?;
+        } else if self.control_interface == ControlInterface::Acceleration {
+            writeln!(f, "inp.control_interface = ControlInterface.Acceleration")?;
         }
         if self.synchronization == Synchronization::Phase {
             writeln!(f, "inp.synchronization = Synchronization.Phase")?;
@@ -301,6 +1371,12 @@ impl<const DOF: usize> fmt::Display for InputParameter<DOF> {
                 f,
                 "inp.duration_discretization = DurationDiscretization.Discrete"
             )?;
+        } else if let DurationDiscretization::DiscreteStep(step) = self.duration_discretization {
+            writeln!(
+                f,
+                "inp.duration_discretization = DurationDiscretization.DiscreteStep({})",
+                step
+            )?;
         }
 
         writeln!(
@@ -333,6 +1409,40 @@ impl<const DOF: usize> fmt::Display for InputParameter<DOF> {
             "inp.target_acceleration = [{}]",
             join::<DOF>(self.target_acceleration.deref(), true)
         )?;
+        if !self.intermediate_positions.is_empty() {
+            let positions: Vec<String> = self
+                .intermediate_positions
+                .iter()
+                .map(|position| format!("[{}]", join::<DOF>(position.deref(), true)))
+                .collect();
+            writeln!(f, "inp.intermediate_positions = [{}]", positions.join(", "))?;
+        }
+        if !self.intermediate_blend_tolerances.is_empty() {
+            let tolerances: Vec<String> = self
+                .intermediate_blend_tolerances
+                .iter()
+                .map(|tolerance| tolerance.to_string())
+                .collect();
+            writeln!(
+                f,
+                "inp.intermediate_blend_tolerances = [{}]",
+                tolerances.join(", ")
+            )?;
+        }
+        if let Some(max_pos) = &self.max_position {
+            writeln!(
+                f,
+                "inp.max_position = [{}]",
+                join::<DOF>(max_pos.deref(), true)
+            )?;
+        }
+        if let Some(min_pos) = &self.min_position {
+            writeln!(
+                f,
+                "inp.min_position = [{}]",
+                join::<DOF>(min_pos.deref(), true)
+            )?;
+        }
         writeln!(
             f,
             "inp.max_velocity = [{}]",
@@ -363,6 +1473,13 @@ impl<const DOF: usize> fmt::Display for InputParameter<DOF> {
                 join::<DOF>(min_acc.deref(), true)
             )?;
         }
+        if let Some(max_snap) = &self.max_snap {
+            writeln!(
+                f,
+                "inp.max_snap = [{}]",
+                join::<DOF>(max_snap.deref(), true)
+            )?;
+        }
 
         Ok(())
     }
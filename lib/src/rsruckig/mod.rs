@@ -1,39 +1,105 @@
 #![allow(clippy::too_many_arguments)]
 
+#[cfg(not(any(
+    feature = "first-order",
+    feature = "second-order",
+    feature = "third-order"
+)))]
+compile_error!("one of the features first-order, second-order or third-order is required");
+
+#[cfg(feature = "second-order")]
+pub mod acceleration_second_step1;
+#[cfg(feature = "second-order")]
+pub mod acceleration_second_step2;
+#[cfg(feature = "third-order")]
+pub mod acceleration_third_step1;
+#[cfg(feature = "third-order")]
+pub mod acceleration_third_step2;
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+pub mod background;
 pub mod block;
 pub mod brake;
+pub mod calculator;
 pub mod calculator_target;
+pub mod calculator_waypoints;
 pub mod error;
 pub mod input_parameter;
+pub mod motion_generator;
 pub mod output_parameter;
+#[cfg(feature = "first-order")]
 pub mod position_first_step1;
+#[cfg(feature = "first-order")]
 pub mod position_first_step2;
+#[cfg(feature = "second-order")]
 pub mod position_second_step1;
+#[cfg(feature = "second-order")]
 pub mod position_second_step2;
+#[cfg(feature = "third-order")]
 pub mod position_third_step1;
+#[cfg(feature = "third-order")]
 pub mod position_third_step2;
 pub mod profile;
 pub mod result;
 pub mod roots;
 pub mod ruckig;
+pub mod segment_planner;
+pub mod settings;
+#[cfg(feature = "debug-trace")]
+pub mod trace;
+pub mod tracker;
 pub mod trajectory;
+#[cfg(feature = "std")]
+pub mod trajectory_csv;
+#[cfg(feature = "ndarray")]
+pub mod trajectory_ndarray;
+#[cfg(feature = "plot")]
+pub mod trajectory_plot;
+#[cfg(feature = "uom")]
+pub mod units;
 pub mod util;
+pub mod validation;
+#[cfg(feature = "first-order")]
+pub mod velocity_first_step1;
+#[cfg(feature = "first-order")]
+pub mod velocity_first_step2;
+#[cfg(feature = "second-order")]
 pub mod velocity_second_step1;
+#[cfg(feature = "second-order")]
 pub mod velocity_second_step2;
+#[cfg(feature = "third-order")]
 pub mod velocity_third_step1;
+#[cfg(feature = "third-order")]
 pub mod velocity_third_step2;
 pub mod prelude {
+    #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+    pub use super::background::BackgroundCalculation;
+    pub use super::brake::BrakeProfile;
+    pub use super::calculator::Calculator;
     pub use super::daov_heap;
     pub use super::daov_stack;
-    pub use super::error::RuckigError;
-    pub use super::error::{IgnoreErrorHandler, ThrowErrorHandler};
+    pub use super::error::{LimitExcess, LoosenedTolerance, RuckigError, RuckigWarning};
+    pub use super::error::{
+        CallbackErrorHandler, CollectErrorHandler, ErrorCodeHandler, IgnoreErrorHandler,
+        ThrowErrorHandler,
+    };
     pub use super::input_parameter::{
         ControlInterface, DurationDiscretization, InputParameter, Synchronization,
     };
-    pub use super::output_parameter::OutputParameter;
-    pub use super::profile::Profile;
-    pub use super::result::RuckigResult;
+    pub use super::motion_generator::MotionGenerator;
+    pub use super::output_parameter::{OutputParameter, UpdateEvent};
+    pub use super::profile::{ControlSigns, Direction, Profile, ProfileInfo, ReachedLimits};
+    pub use super::result::{CalculationStage, RuckigResult};
     pub use super::ruckig::Ruckig;
-    pub use super::trajectory::Trajectory;
+    pub use super::segment_planner::SegmentPlanner;
+    pub use super::settings::{DurationRounding, Settings};
+    pub use super::tracker::{TargetState, Tracker};
+    pub use super::trajectory::{
+        DofSynchronization, PolynomialSegment, Trajectory, TrajectoryState,
+    };
+    #[cfg(feature = "std")]
+    pub use super::trajectory_csv::SampledTrajectory;
     pub use super::util::DataArrayOrVec;
+    pub use super::validation::{
+        InputQuantity, LimitViolation, ValidationReport, ValidationViolation, ViolationKind,
+    };
 }
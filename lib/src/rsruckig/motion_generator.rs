@@ -0,0 +1,70 @@
+//! Stateful wrapper around the online trajectory generation.
+
+use crate::error::{RuckigError, RuckigErrorHandler};
+use crate::input_parameter::InputParameter;
+use crate::output_parameter::OutputParameter;
+use crate::result::RuckigResult;
+use crate::ruckig::Ruckig;
+use crate::tracker::TargetState;
+
+/// Owns a `Ruckig` instance together with its input and output, and passes the new state of the
+/// output to the input after every cycle. The limits and the current state at the start are set
+/// through the public `input`, the target through `set_target`.
+#[derive(Debug)]
+pub struct MotionGenerator<const DOF: usize, E: RuckigErrorHandler> {
+    pub ruckig: Ruckig<DOF, E>,
+    /// Input of the next cycle
+    pub input: InputParameter<DOF>,
+    output: OutputParameter<DOF>,
+    result: RuckigResult,
+}
+
+impl<const DOF: usize, E: RuckigErrorHandler> Default for MotionGenerator<DOF, E> {
+    fn default() -> Self {
+        Self::new(None, 0.01)
+    }
+}
+
+impl<const DOF: usize, E: RuckigErrorHandler> MotionGenerator<DOF, E> {
+    pub fn new(degrees_of_freedom: Option<usize>, delta_time: f64) -> Self {
+        Self {
+            ruckig: Ruckig::new(degrees_of_freedom, delta_time),
+            input: InputParameter::new(degrees_of_freedom),
+            output: OutputParameter::new(degrees_of_freedom),
+            result: RuckigResult::Working,
+        }
+    }
+
+    /// Move towards a new target state, starting from the current state of the motion
+    pub fn set_target(&mut self, target: &TargetState<DOF>) {
+        self.input.target_position.clone_from(&target.position);
+        self.input.target_velocity.clone_from(&target.velocity);
+        self.input
+            .target_acceleration
+            .clone_from(&target.acceleration);
+        self.result = RuckigResult::Working;
+    }
+
+    /// Advance the motion by one control cycle and return the output with its new state. Once
+    /// the target is reached, the target state is held.
+    pub fn tick(&mut self) -> Result<&OutputParameter<DOF>, RuckigError> {
+        self.result = self.ruckig.update(&self.input, &mut self.output)?;
+        if matches!(self.result, RuckigResult::Working | RuckigResult::Finished) {
+            self.output.pass_to_input(&mut self.input);
+        }
+        Ok(&self.output)
+    }
+
+    /// Result of the last cycle, e.g. an error code with a non-throwing error handler
+    pub fn result(&self) -> RuckigResult {
+        self.result
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.result == RuckigResult::Finished
+    }
+
+    pub fn output(&self) -> &OutputParameter<DOF> {
+        &self.output
+    }
+}
@@ -1,14 +1,37 @@
 use std::fmt;
 use std::ops::Deref;
 
+use crate::error::{LimitExcess, RuckigError};
 use crate::input_parameter::InputParameter;
+use crate::result::CalculationStage;
 use crate::trajectory::Trajectory;
 use crate::util::{join, DataArrayOrVec};
+use crate::validation::{InputQuantity, ValidationViolation, ViolationKind};
+
+/// Event that happened within a single update, in the order of occurrence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UpdateEvent {
+    /// A new trajectory was calculated
+    NewCalculation,
+    /// The section with the given index was reached, which is the number of sections at the end
+    SectionChanged(usize),
+    /// The trajectory has finished
+    Finished,
+}
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OutputParameter<const DOF: usize> {
     pub degrees_of_freedom: usize,
     pub trajectory: Trajectory<DOF>,
+    /// The trajectory that the latest calculation replaced, if retained with
+    /// `retain_previous_trajectory`. A failed calculation overwrites it and keeps the active
+    /// trajectory instead.
+    pub previous_trajectory: Option<Trajectory<DOF>>,
+    /// Time of the previous trajectory when it was replaced, i.e. of the state from which the
+    /// new trajectory starts if the output was passed to the input
+    pub previous_time: f64,
     pub new_position: DataArrayOrVec<f64, DOF>,
     pub new_velocity: DataArrayOrVec<f64, DOF>,
     pub new_acceleration: DataArrayOrVec<f64, DOF>,
@@ -19,6 +42,16 @@ pub struct OutputParameter<const DOF: usize> {
     pub new_calculation: bool,
     pub was_calculation_interrupted: bool,
     pub calculation_duration: f64,
+    /// The DoF that caused the latest calculation to fail, if the error refers to a single DoF
+    pub failed_dof: Option<usize>,
+    /// The stage in which the latest calculation failed
+    pub failed_stage: Option<CalculationStage>,
+    /// How far the DoFs of the trajectory exceed their limits after they were relaxed, see
+    /// `Settings::max_relaxation`. Empty if the trajectory keeps all limits.
+    pub relaxed_limits: Vec<LimitExcess>,
+    /// All events of the last update. Every skipped section is reported, and the end of a
+    /// trajectory only in the cycle in which it is reached.
+    pub events: Vec<UpdateEvent>,
 }
 
 impl<const DOF: usize> Default for OutputParameter<DOF> {
@@ -29,9 +62,17 @@ impl<const DOF: usize> Default for OutputParameter<DOF> {
 
 impl<const DOF: usize> OutputParameter<DOF> {
     pub fn new(dofs: Option<usize>) -> Self {
+        Self::new_with_waypoints(0, dofs)
+    }
+
+    /// Create an output whose trajectory has pre-allocated sections for up to
+    /// `max_number_of_waypoints` intermediate positions.
+    pub fn new_with_waypoints(max_number_of_waypoints: usize, dofs: Option<usize>) -> Self {
         Self {
             degrees_of_freedom: dofs.unwrap_or(DOF),
-            trajectory: Trajectory::new(dofs),
+            trajectory: Trajectory::new_with_waypoints(max_number_of_waypoints, dofs),
+            previous_trajectory: None,
+            previous_time: 0.0,
             new_position: DataArrayOrVec::new(dofs, 0.0),
             new_velocity: DataArrayOrVec::new(dofs, 0.0),
             new_acceleration: DataArrayOrVec::new(dofs, 0.0),
@@ -42,12 +83,69 @@ impl<const DOF: usize> OutputParameter<DOF> {
             new_calculation: false,
             was_calculation_interrupted: false,
             calculation_duration: 0.0,
+            failed_dof: None,
+            failed_stage: None,
+            relaxed_limits: Vec::with_capacity(dofs.unwrap_or(DOF)),
+            // Room for a new calculation, every section change, and the end
+            events: Vec::with_capacity(max_number_of_waypoints + 3),
         }
     }
+
+    /// Keep the previous trajectory when a new calculation replaces it, e.g. to compare the old
+    /// and new commands or to log the abandoned plan. Its storage is allocated here with the
+    /// same number of sections as a copy of the current trajectory, so that the replacement
+    /// doesn't allocate.
+    pub fn retain_previous_trajectory(&mut self) {
+        self.previous_trajectory = Some(self.trajectory.clone());
+        self.previous_time = self.time;
+    }
+
+    /// Whether the new state of the DoF is within the brake trajectory at the start of the
+    /// trajectory, see `Trajectory::get_brake_profile`
+    pub fn is_braking(&self, dof: usize) -> bool {
+        self.trajectory
+            .get_brake_profile(dof)
+            .is_some_and(|brake| {
+                let brake_start = self.trajectory.get_profiles()[0][dof].pre_dwell;
+                self.new_section == 0
+                    && brake_start <= self.time
+                    && self.time < brake_start + brake.duration
+            })
+    }
+
     pub fn pass_to_input(&self, input: &mut InputParameter<DOF>) {
-        input.current_position = self.new_position.clone();
-        input.current_velocity = self.new_velocity.clone();
-        input.current_acceleration = self.new_acceleration.clone();
+        input.current_position.clone_from(&self.new_position);
+        input.current_velocity.clone_from(&self.new_velocity);
+        input.current_acceleration.clone_from(&self.new_acceleration);
+    }
+
+    /// Pass the new state to the input like `pass_to_input`, but blend the new position with the
+    /// measured position, e.g. of an encoder. An `alpha` of zero keeps the commanded position
+    /// and an `alpha` of one takes over the measured position, while the velocity and
+    /// acceleration are passed as commanded. A deviation from the trajectory leads to a new
+    /// calculation in the next cycle. Fails without changing the input if the measured position
+    /// doesn't have a value for each DoF.
+    pub fn pass_to_input_with_feedback(
+        &self,
+        input: &mut InputParameter<DOF>,
+        measured_position: &[f64],
+        alpha: f64,
+    ) -> Result<(), RuckigError> {
+        if measured_position.len() != self.degrees_of_freedom {
+            return Err(RuckigError::InvalidInput(ValidationViolation {
+                dof: None,
+                quantity: InputQuantity::MeasuredPosition,
+                kind: ViolationKind::WrongLength,
+                value: measured_position.len() as f64,
+                allowed: Some(self.degrees_of_freedom as f64),
+            }));
+        }
+
+        self.pass_to_input(input);
+        for (dof, measured) in measured_position.iter().enumerate() {
+            input.current_position[dof] += alpha * (measured - self.new_position[dof]);
+        }
+        Ok(())
     }
 }
 
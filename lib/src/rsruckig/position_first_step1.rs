@@ -1,6 +1,7 @@
 //! Mathematical equations for Step 1 in first-order position interface: Extremal profiles
 use crate::block::Block;
 use crate::profile::{ControlSigns, Profile, ReachedLimits};
+use crate::util::trace_strategy;
 
 #[derive(Debug)]
 pub struct PositionFirstOrderStep1 {
@@ -18,6 +19,7 @@ impl PositionFirstOrderStep1 {
         }
     }
     pub fn get_profile(&mut self, input: &Profile, block: &mut Block) -> bool {
+        trace_strategy!("PositionFirstOrderStep1::get_profile");
         let p = &mut block.p_min;
         p.set_boundary_from_profile(input);
 
@@ -35,7 +37,8 @@ impl PositionFirstOrderStep1 {
         p.t[6] = 0.0;
 
         if p.check_for_first_order(vf, ControlSigns::UDDU, ReachedLimits::Vel) {
-            block.t_min = p.t_sum.last().unwrap() + p.brake.duration + p.accel.duration;
+            block.t_min =
+                p.t_sum.last().unwrap() + p.pre_dwell + p.brake.duration + p.accel.duration;
             return true;
         }
         false
@@ -1,5 +1,6 @@
 //! Mathematical equations for Step 2 in first-order position interface: Time synchronization
 use crate::profile::{ControlSigns, Profile, ReachedLimits};
+use crate::util::trace_strategy;
 
 #[derive(Debug)]
 pub struct PositionFirstOrderStep2 {
@@ -20,6 +21,7 @@ impl PositionFirstOrderStep2 {
     }
 
     pub fn get_profile(&mut self, profile: &mut Profile) -> bool {
+        trace_strategy!("PositionFirstOrderStep2::get_profile");
         let vf = self.pd / self.tf;
 
         profile.t[0] = 0.0;
@@ -2,6 +2,7 @@
 use crate::{
     block::{Block, Interval},
     profile::{ControlSigns, Profile, ReachedLimits},
+    util::trace_strategy,
 };
 
 #[derive(Debug)]
@@ -45,7 +46,7 @@ impl PositionSecondOrderStep1 {
     #[inline]
     fn add_profile(&mut self, profile: &mut Profile) {
         if self.current_index < 3 {
-            self.valid_profiles[self.current_index] = profile.clone();
+            self.valid_profiles[self.current_index] = *profile;
             self.current_index += 1;
             self.valid_profiles[self.current_index].set_boundary_from_profile(profile);
         }
@@ -60,6 +61,7 @@ impl PositionSecondOrderStep1 {
         a_min: f64,
         _: bool,
     ) {
+        trace_strategy!("PositionSecondOrderStep1::time_acc0");
         profile.t[0] = (-self.v0 + v_max) / a_max;
         profile.t[1] = (a_min * self.v0 * self.v0 - a_max * self.vf * self.vf)
             / (2.0 * a_max * a_min * v_max)
@@ -91,6 +93,7 @@ impl PositionSecondOrderStep1 {
         a_min: f64,
         return_after_found: bool,
     ) {
+        trace_strategy!("PositionSecondOrderStep1::time_none");
         let mut h1 =
             (a_max * self.vf * self.vf - a_min * self.v0 * self.v0 - 2.0 * a_max * a_min * self.pd)
                 / (a_max - a_min);
@@ -99,7 +102,7 @@ impl PositionSecondOrderStep1 {
 
             // Solution 1
             {
-                let mut profile = self.valid_profiles[self.current_index].clone();
+                let mut profile = self.valid_profiles[self.current_index];
                 profile.t[0] = -(self.v0 + h1) / a_max;
                 profile.t[1] = 0.0;
                 profile.t[2] = (self.vf + h1) / a_min;
@@ -125,7 +128,7 @@ impl PositionSecondOrderStep1 {
 
             // Solution 2
             {
-                let mut profile = self.valid_profiles[self.current_index].clone();
+                let mut profile = self.valid_profiles[self.current_index];
                 profile.t[0] = (-self.v0 + h1) / a_max;
                 profile.t[1] = 0.0;
                 profile.t[2] = (self.vf - h1) / a_min;
@@ -155,6 +158,7 @@ impl PositionSecondOrderStep1 {
         _: f64,
         _: f64,
     ) -> bool {
+        trace_strategy!("PositionSecondOrderStep1::time_all_single_step");
         if f64::abs(self.vf - self.v0) > f64::EPSILON {
             return false;
         }
@@ -207,13 +211,15 @@ impl PositionSecondOrderStep1 {
     }
 
     pub fn get_profile(&mut self, input: &Profile, block: &mut Block) -> bool {
+        trace_strategy!("PositionSecondOrderStep1::get_profile");
         // Zero-limits special case
         if self._v_max == 0.0 && self._v_min == 0.0 {
             let p = &mut block.p_min;
             p.set_boundary_from_profile(input);
 
             if self.time_all_single_step(p, self._v_max, self._v_min, self._a_max, self._a_min) {
-                block.t_min = p.t_sum.last().unwrap() + p.brake.duration + p.accel.duration;
+                block.t_min =
+                    p.t_sum.last().unwrap() + p.pre_dwell + p.brake.duration + p.accel.duration;
                 if f64::abs(self.v0) > f64::EPSILON {
                     block.a = Some(Interval::new(block.t_min, f64::INFINITY));
                 }
@@ -224,7 +230,7 @@ impl PositionSecondOrderStep1 {
 
         self.valid_profiles[0].set_boundary_from_profile(input);
         self.current_index = 0;
-        let mut profile = self.valid_profiles[0].clone();
+        let mut profile = self.valid_profiles[0];
 
         if f64::abs(self.vf) < f64::EPSILON {
             // There is no blocked interval when self.vf==0.0, so return after first found profile
@@ -1,5 +1,6 @@
 //! Mathematical equations for Step 2 in second-order position interface: Time synchronization
 use crate::profile::{ControlSigns, Profile, ReachedLimits};
+use crate::util::trace_strategy;
 
 #[derive(Debug)]
 pub struct PositionSecondOrderStep2 {
@@ -49,6 +50,7 @@ impl PositionSecondOrderStep2 {
         a_min: f64,
         _: bool,
     ) -> bool {
+        trace_strategy!("PositionSecondOrderStep2::time_acc0");
         // UD Solution 1/2
         {
             let h1 = f64::sqrt(
@@ -142,6 +144,7 @@ impl PositionSecondOrderStep2 {
         a_min: f64,
         _: bool,
     ) -> bool {
+        trace_strategy!("PositionSecondOrderStep2::time_none");
         if f64::abs(self.v0) < f64::EPSILON
             && f64::abs(self.vf) < f64::EPSILON
             && f64::abs(self.pd) < f64::EPSILON
@@ -214,6 +217,7 @@ impl PositionSecondOrderStep2 {
     }
 
     pub fn get_profile(&mut self, profile: &mut Profile) -> bool {
+        trace_strategy!("PositionSecondOrderStep2::get_profile");
         // Test all cases to get ones that match
         // However we should guess which one is correct and try them first...
         if self.pd > 0.0 {
@@ -2,6 +2,7 @@
 use crate::block::{Block, Interval};
 use crate::profile::{ControlSigns, Profile, ReachedLimits};
 use crate::roots;
+use crate::util::trace_strategy;
 
 #[derive(Debug, Default)]
 pub struct PositionThirdOrderStep1 {
@@ -100,6 +101,7 @@ impl PositionThirdOrderStep1 {
         j_max: f64,
         _: bool,
     ) {
+        trace_strategy!("PositionThirdOrderStep1::time_all_vel");
         let profile = &mut self.valid_profiles[self.current_index];
         // ACC0_ACC1_VEL
         profile.t[0] = (-self.a0 + a_max) / j_max;
@@ -244,6 +246,7 @@ impl PositionThirdOrderStep1 {
         j_max: f64,
         return_after_found: bool,
     ) {
+        trace_strategy!("PositionThirdOrderStep1::time_acc0_acc1");
         let mut h1 = (3. * (self.af_p4 * a_max - self.a0_p4 * a_min)
             + a_max
                 * a_min
@@ -332,6 +335,7 @@ impl PositionThirdOrderStep1 {
         j_max: f64,
         return_after_found: bool,
     ) {
+        trace_strategy!("PositionThirdOrderStep1::time_all_none_acc0_acc1");
         let j_max_j_max = j_max * j_max;
         // NONE UDDU / UDUD Strategy: t7 == 0 (equals UDDU), this one is in particular prone to numerical issues
         let h2_none = (self.a0_a0 - self.af_af) / (2.0 * j_max) + (self.vf - self.v0);
@@ -605,6 +609,7 @@ impl PositionThirdOrderStep1 {
         a_min: f64,
         j_max: f64,
     ) {
+        trace_strategy!("PositionThirdOrderStep1::time_acc1_vel_two_step");
         let profile = &mut self.valid_profiles[self.current_index];
         profile.t[0] = 0.0;
         profile.t[1] = 0.0;
@@ -639,6 +644,7 @@ impl PositionThirdOrderStep1 {
     }
 
     fn time_acc0_two_step(&mut self, v_max: f64, v_min: f64, a_max: f64, a_min: f64, j_max: f64) {
+        trace_strategy!("PositionThirdOrderStep1::time_acc0_two_step");
         {
             // Two step
             let profile = &mut self.valid_profiles[self.current_index];
@@ -775,6 +781,7 @@ impl PositionThirdOrderStep1 {
     }
 
     fn time_vel_two_step(&mut self, v_max: f64, v_min: f64, a_max: f64, a_min: f64, j_max: f64) {
+        trace_strategy!("PositionThirdOrderStep1::time_vel_two_step");
         let h1 = f64::sqrt(self.af_af / (2.0 * self.j_max_j_max) + (v_max - self.vf) / j_max);
         // Four step
         {
@@ -838,6 +845,7 @@ impl PositionThirdOrderStep1 {
     }
 
     fn time_none_two_step(&mut self, v_max: f64, v_min: f64, a_max: f64, a_min: f64, j_max: f64) {
+        trace_strategy!("PositionThirdOrderStep1::time_none_two_step");
         // Two step
         {
             let profile = &mut self.valid_profiles[self.current_index];
@@ -900,6 +908,7 @@ impl PositionThirdOrderStep1 {
         a_min: f64,
         _: f64,
     ) -> bool {
+        trace_strategy!("PositionThirdOrderStep1::time_all_single_step");
         if f64::abs(self.af - self.a0) > f64::EPSILON {
             return false;
         }
@@ -977,6 +986,7 @@ impl PositionThirdOrderStep1 {
     }
 
     pub fn get_profile(&mut self, input: &Profile, block: &mut Block) -> bool {
+        trace_strategy!("PositionThirdOrderStep1::get_profile");
         // Zero-limits special case
         if self._j_max == 0.0 || self._a_max == 0.0 || self._a_min == 0.0 {
             let p = &mut block.p_min;
@@ -991,7 +1001,8 @@ impl PositionThirdOrderStep1 {
                 self._j_max,
             ) {
                 // [p.t_sum.len() - 1] instead of C++ back()
-                block.t_min = p.t_sum[p.t_sum.len() - 1] + p.brake.duration + p.accel.duration;
+                block.t_min =
+                    p.t_sum[p.t_sum.len() - 1] + p.pre_dwell + p.brake.duration + p.accel.duration;
                 if f64::abs(self.v0) > f64::EPSILON || f64::abs(self.a0) > f64::EPSILON {
                     block.a = Some(Interval::new(block.t_min, f64::INFINITY));
                 }
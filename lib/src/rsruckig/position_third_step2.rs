@@ -4,6 +4,7 @@ use arrayvec::ArrayVec;
 use crate::{
     profile::{ControlSigns, Profile, ReachedLimits},
     roots::*,
+    util::trace_strategy,
 };
 
 pub struct PositionThirdOrderStep2 {
@@ -42,6 +43,8 @@ pub struct PositionThirdOrderStep2 {
     g1: f64,
     g2: f64,
     minimize_jerk: bool,
+    /// Limits that the profile is expected to reach, e.g. those of the time-optimal profile
+    guess: Option<ReachedLimits>,
 }
 
 impl PositionThirdOrderStep2 {
@@ -121,9 +124,16 @@ impl PositionThirdOrderStep2 {
             g1,
             g2,
             minimize_jerk: false,
+            guess: None,
         }
     }
 
+    /// Try the profiles with the given reached limits first. If several profiles match the
+    /// duration, this may select another one than the default order.
+    pub fn set_guess(&mut self, guess: Option<ReachedLimits>) {
+        self.guess = guess;
+    }
+
     fn time_acc0_acc1_vel(
         &mut self,
         profile: &mut Profile,
@@ -133,6 +143,7 @@ impl PositionThirdOrderStep2 {
         a_min: f64,
         j_max: f64,
     ) -> bool {
+        trace_strategy!("PositionThirdOrderStep2::time_acc0_acc1_vel");
         // Profile UDDU, Solution 1
         if (2.0 * (a_max - a_min) + self.ad) / j_max < self.tf {
             let h1 = f64::sqrt(
@@ -250,6 +261,7 @@ impl PositionThirdOrderStep2 {
         a_min: f64,
         j_max: f64,
     ) -> bool {
+        trace_strategy!("PositionThirdOrderStep2::time_acc1_vel");
         // Profile UDDU
         {
             let ph1 = self.a0_a0 + self.af_af
@@ -419,6 +431,7 @@ impl PositionThirdOrderStep2 {
         a_min: f64,
         j_max: f64,
     ) -> bool {
+        trace_strategy!("PositionThirdOrderStep2::time_acc0_vel");
         if self.tf < f64::max((-self.a0 + a_max) / j_max, 0.0) + f64::max(a_max / j_max, 0.0) {
             return false;
         }
@@ -610,6 +623,7 @@ impl PositionThirdOrderStep2 {
         a_min: f64,
         j_max: f64,
     ) -> bool {
+        trace_strategy!("PositionThirdOrderStep2::time_vel");
         let tz_min = f64::max(0.0, -self.a0 / j_max);
         let tz_max = f64::min((self.tf - self.a0 / j_max) / 2.0, (a_max - self.a0) / j_max);
 
@@ -979,6 +993,7 @@ impl PositionThirdOrderStep2 {
         a_min: f64,
         _: f64,
     ) -> bool {
+        trace_strategy!("PositionThirdOrderStep2::time_acc0_acc1");
         if f64::abs(self.a0) < f64::EPSILON && f64::abs(self.af) < f64::EPSILON {
             let h1 = 2.0 * a_min * self.g1
                 + self.vd_vd
@@ -1084,6 +1099,7 @@ impl PositionThirdOrderStep2 {
         a_min: f64,
         j_max: f64,
     ) -> bool {
+        trace_strategy!("PositionThirdOrderStep2::time_acc1");
         // a3 != 0.0
         // Case UDDU
         {
@@ -1315,6 +1331,7 @@ impl PositionThirdOrderStep2 {
         a_min: f64,
         j_max: f64,
     ) -> bool {
+        trace_strategy!("PositionThirdOrderStep2::time_acc0");
         // UDUD
         {
             let h1 = f64::sqrt(
@@ -1442,6 +1459,7 @@ impl PositionThirdOrderStep2 {
         a_min: f64,
         j_max: f64,
     ) -> bool {
+        trace_strategy!("PositionThirdOrderStep2::time_none");
         if f64::abs(self.v0) < f64::EPSILON
             && f64::abs(self.a0) < f64::EPSILON
             && f64::abs(self.af) < f64::EPSILON
@@ -2207,6 +2225,7 @@ impl PositionThirdOrderStep2 {
         a_min: f64,
         j_max: f64,
     ) -> bool {
+        trace_strategy!("PositionThirdOrderStep2::time_none_smooth");
         {
             let h0 = self.ad_ad + 2.0 * j_max * (self.a0 * self.tf - self.vd);
             let h1a = 2.0 * (self.a0_p3 - self.af_p3)
@@ -2444,8 +2463,8 @@ impl PositionThirdOrderStep2 {
     }
 
     pub fn get_profile(&mut self, profile: &mut Profile) -> bool {
-        // Test all cases to get ones that match
-        // However we should guess which one is correct and try them first...
+        trace_strategy!("PositionThirdOrderStep2::get_profile");
+        // Test all cases to get ones that match, starting with the most likely ones
         let up_first = self.pd > self.tf * self.v0;
         let v_max = if up_first { self._v_max } else { self._v_min };
         let v_min = if up_first { self._v_min } else { self._v_max };
@@ -2460,6 +2479,33 @@ impl PositionThirdOrderStep2 {
             return true;
         }
 
+        // If the guess doesn't reach the velocity limit, the profile most likely won't either
+        if matches!(
+            self.guess,
+            Some(
+                ReachedLimits::Acc0Acc1
+                    | ReachedLimits::Acc0
+                    | ReachedLimits::Acc1
+                    | ReachedLimits::None
+            )
+        ) {
+            return self.time_without_velocity_limit(profile, v_max, v_min, a_max, a_min, j_max)
+                || self.time_with_velocity_limit(profile, v_max, v_min, a_max, a_min, j_max);
+        }
+
+        self.time_with_velocity_limit(profile, v_max, v_min, a_max, a_min, j_max)
+            || self.time_without_velocity_limit(profile, v_max, v_min, a_max, a_min, j_max)
+    }
+
+    fn time_with_velocity_limit(
+        &mut self,
+        profile: &mut Profile,
+        v_max: f64,
+        v_min: f64,
+        a_max: f64,
+        a_min: f64,
+        j_max: f64,
+    ) -> bool {
         self.time_acc0_acc1_vel(profile, v_max, v_min, a_max, a_min, j_max)
             || self.time_vel(profile, v_max, v_min, a_max, a_min, j_max)
             || self.time_acc0_vel(profile, v_max, v_min, a_max, a_min, j_max)
@@ -2468,7 +2514,18 @@ impl PositionThirdOrderStep2 {
             || self.time_vel(profile, v_min, v_max, a_min, a_max, -j_max)
             || self.time_acc0_vel(profile, v_min, v_max, a_min, a_max, -j_max)
             || self.time_acc1_vel(profile, v_min, v_max, a_min, a_max, -j_max)
-            || self.time_acc0_acc1(profile, v_max, v_min, a_max, a_min, j_max)
+    }
+
+    fn time_without_velocity_limit(
+        &mut self,
+        profile: &mut Profile,
+        v_max: f64,
+        v_min: f64,
+        a_max: f64,
+        a_min: f64,
+        j_max: f64,
+    ) -> bool {
+        self.time_acc0_acc1(profile, v_max, v_min, a_max, a_min, j_max)
             || self.time_acc0(profile, v_max, v_min, a_max, a_min, j_max)
             || self.time_acc1(profile, v_max, v_min, a_max, a_min, j_max)
             || self.time_none(profile, v_max, v_min, a_max, a_min, j_max)
@@ -1,19 +1,100 @@
 use crate::brake::BrakeProfile;
 use crate::roots;
-use crate::util::integrate;
+#[cfg(feature = "debug-trace")]
+use crate::trace::{self, CheckFailure};
+use crate::util::{integrate, integrate_with_snap, CompensatedSum};
+use std::cell::Cell;
 use std::fmt;
 
-static V_EPS: f64 = 1e-12;
-static A_EPS: f64 = 1e-12;
-static J_EPS: f64 = 1e-12;
+/// Tolerances of the checks of a profile: of the kinematic limits, and of the final state
+#[derive(Debug, Clone, Copy)]
+struct Tolerances {
+    v_eps: f64,
+    a_eps: f64,
+    j_eps: f64,
+    p_precision: f64,
+    v_precision: f64,
+    a_precision: f64,
+}
+
+impl Tolerances {
+    const DEFAULT: Tolerances = Tolerances {
+        v_eps: 1e-12,
+        a_eps: 1e-12,
+        j_eps: 1e-12,
+        p_precision: 1e-8,
+        v_precision: 1e-8,
+        a_precision: 1e-10,
+    };
+
+    /// The default tolerances, each loosened to at least `tolerance`
+    fn loosened(tolerance: f64) -> Tolerances {
+        let default = Tolerances::DEFAULT;
+        Tolerances {
+            v_eps: default.v_eps.max(tolerance),
+            a_eps: default.a_eps.max(tolerance),
+            j_eps: default.j_eps.max(tolerance),
+            p_precision: default.p_precision.max(tolerance),
+            v_precision: default.v_precision.max(tolerance),
+            a_precision: default.a_precision.max(tolerance),
+        }
+    }
+}
+
+// The step types check their profiles without knowing the settings, so a loosened tolerance is
+// set per thread for the duration of a calculation
+thread_local! {
+    static TOLERANCES: Cell<Tolerances> = const { Cell::new(Tolerances::DEFAULT) };
+}
 
-static P_PRECISION: f64 = 1e-8;
-static V_PRECISION: f64 = 1e-8;
-static A_PRECISION: f64 = 1e-10;
+fn tolerances() -> Tolerances {
+    TOLERANCES.with(Cell::get)
+}
+
+/// Loosens the tolerances of the profile checks of the current thread to at least `tolerance`,
+/// e.g. as a fallback for marginal numerical cases. The previous tolerances are restored when the
+/// guard is dropped, also if the calculation unwinds.
+pub(crate) struct CheckToleranceGuard {
+    previous: Tolerances,
+}
+
+impl CheckToleranceGuard {
+    pub(crate) fn loosen(tolerance: f64) -> Self {
+        let previous =
+            TOLERANCES.with(|tolerances| tolerances.replace(Tolerances::loosened(tolerance)));
+        Self { previous }
+    }
+}
+
+impl Drop for CheckToleranceGuard {
+    fn drop(&mut self) {
+        TOLERANCES.with(|tolerances| tolerances.set(self.previous));
+    }
+}
 
 static T_MAX: f64 = 1e12;
 
+/// Reject the profile in a check, recording the failed check in the trace of the `debug-trace`
+/// feature
+macro_rules! reject {
+    ($control_signs:expr, $limits:expr, $failure:expr) => {{
+        #[cfg(feature = "debug-trace")]
+        trace::record($control_signs, $limits, Some($failure));
+        return false;
+    }};
+}
+
+/// Accept the profile in a check, recording it in the trace of the `debug-trace` feature
+macro_rules! accept {
+    ($control_signs:expr, $limits:expr) => {{
+        #[cfg(feature = "debug-trace")]
+        trace::record($control_signs, $limits, None);
+        return true;
+    }};
+}
+
 #[derive(Debug, Default, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReachedLimits {
     Acc0Acc1Vel,
     Vel,
@@ -26,14 +107,16 @@ pub enum ReachedLimits {
     None,
 }
 
-#[derive(Debug, Default, PartialEq, Clone)]
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     #[default]
     UP,
     DOWN,
 }
 
-#[derive(Debug, Default, PartialEq, Clone)]
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ControlSigns {
     #[default]
     UDDU,
@@ -41,6 +124,7 @@ pub enum ControlSigns {
 }
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Bound {
     // The extreme position
     pub min: f64,
@@ -50,8 +134,20 @@ pub struct Bound {
     pub t_max: f64,
 }
 
+/// Shape of the profile of a DoF, as chosen by the calculation
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProfileInfo {
+    pub limits: ReachedLimits,
+    pub control_signs: ControlSigns,
+    pub direction: Direction,
+    /// Sign of the jerk in each of the seven phases, zero if the jerk is zero
+    pub jerk_signs: [i8; 7],
+}
+
 /// The state profile for position, velocity, acceleration and jerk for a single DoF
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Profile {
     pub t: [f64; 7],
     pub t_sum: [f64; 7],
@@ -60,6 +156,9 @@ pub struct Profile {
     pub v: [f64; 8],
     pub p: [f64; 8],
 
+    /// Time at rest in the current state before the brake pre-trajectory
+    pub pre_dwell: f64,
+
     /// Brake sub-profiles
     pub brake: BrakeProfile,
     pub accel: BrakeProfile,
@@ -75,6 +174,53 @@ pub struct Profile {
 }
 
 impl Profile {
+    /// Get the name of the profile in the format of the C++ version, e.g. `UP_VEL_UDUD`, to
+    /// compare logs of both versions
+    pub fn name(&self) -> String {
+        self.to_string()
+    }
+
+    /// Sum up the phase durations into `t_sum`, or return false if a duration is negative or the
+    /// sum isn't finite, e.g. for durations that overflow with subnormal limits
+    fn set_cumulative_times(&mut self) -> bool {
+        let mut sum = CompensatedSum::default();
+        for i in 0..7 {
+            if self.t[i] < 0.0 {
+                return false;
+            }
+            sum.add(self.t[i]);
+            self.t_sum[i] = sum.value();
+        }
+        self.t_sum[6].is_finite()
+    }
+
+    /// Why `set_cumulative_times` failed
+    #[cfg(feature = "debug-trace")]
+    fn duration_failure(&self) -> CheckFailure {
+        match self.t.iter().position(|&t| t < 0.0) {
+            Some(phase) => CheckFailure::NegativeDuration { phase },
+            None => CheckFailure::DurationTooLong,
+        }
+    }
+
+    /// Get the shape of the profile
+    pub fn info(&self) -> ProfileInfo {
+        ProfileInfo {
+            limits: self.limits,
+            control_signs: self.control_signs,
+            direction: self.direction,
+            jerk_signs: self.j.map(|j| {
+                if j > 0.0 {
+                    1
+                } else if j < 0.0 {
+                    -1
+                } else {
+                    0
+                }
+            }),
+        }
+    }
+
     pub fn check_for_velocity(
         &mut self,
         control_signs: ControlSigns,
@@ -83,26 +229,22 @@ impl Profile {
         a_max: f64,
         a_min: f64,
     ) -> bool {
-        if self.t[0] < 0.0 {
-            return false;
+        let tolerances = tolerances();
+        if !self.set_cumulative_times() {
+            reject!(control_signs, limits, self.duration_failure());
         }
 
-        self.t_sum[0] = self.t[0];
-        for i in 0..6 {
-            if self.t[i + 1] < 0.0 {
-                return false;
-            }
-
-            self.t_sum[i + 1] = self.t_sum[i] + self.t[i + 1];
-        }
-
-        if (limits == ReachedLimits::Acc0) && self.t[1] < std::f64::EPSILON {
-            return false;
+        if (limits == ReachedLimits::Acc0) && self.t[1] < f64::EPSILON {
+            reject!(
+                control_signs,
+                limits,
+                CheckFailure::LimitPhaseMissing { phase: 1 }
+            );
         }
 
         if *self.t_sum.last().unwrap() > T_MAX {
             // Use T_PRECISION for numerical reasons.
-            return false;
+            reject!(control_signs, limits, CheckFailure::DurationTooLong);
         }
 
         if control_signs == ControlSigns::UDDU {
@@ -144,23 +286,35 @@ impl Profile {
             Direction::DOWN
         };
         let a_upp_lim = match self.direction {
-            Direction::UP => a_max + A_EPS,
-            Direction::DOWN => a_min + A_EPS,
+            Direction::UP => a_max + tolerances.a_eps,
+            Direction::DOWN => a_min + tolerances.a_eps,
         };
         let a_low_lim = match self.direction {
-            Direction::UP => a_min - A_EPS,
-            Direction::DOWN => a_max - A_EPS,
+            Direction::UP => a_min - tolerances.a_eps,
+            Direction::DOWN => a_max - tolerances.a_eps,
         };
 
-        // For Velocity limit checks. Here I'm using V_PRECISION and A_PRECISION for clarity.
-        (self.v.last().unwrap() - self.vf).abs() < V_PRECISION
-            && (self.a.last().unwrap() - self.af).abs() < A_PRECISION
-            && self.a[1] >= a_low_lim
-            && self.a[3] >= a_low_lim
-            && self.a[5] >= a_low_lim
-            && self.a[1] <= a_upp_lim
-            && self.a[3] <= a_upp_lim
-            && self.a[5] <= a_upp_lim
+        // For Velocity limit checks. Here I'm using the velocity and acceleration precisions for
+        // clarity.
+        let reaches_velocity = (self.v.last().unwrap() - self.vf).abs() < tolerances.v_precision;
+        if !reaches_velocity {
+            reject!(control_signs, limits, CheckFailure::TargetVelocity);
+        }
+        let reaches_acceleration =
+            (self.a.last().unwrap() - self.af).abs() < tolerances.a_precision;
+        if !reaches_acceleration {
+            reject!(control_signs, limits, CheckFailure::TargetAcceleration);
+        }
+        for phase in [0, 2, 4] {
+            if !(a_low_lim..=a_upp_lim).contains(&self.a[phase + 1]) {
+                reject!(
+                    control_signs,
+                    limits,
+                    CheckFailure::AccelerationLimit { phase }
+                );
+            }
+        }
+        accept!(control_signs, limits)
     }
 
     #[inline]
@@ -188,8 +342,12 @@ impl Profile {
         a_min: f64,
         j_max: f64,
     ) -> bool {
-        jf.abs() < j_max.abs() + J_EPS
-            && self.check_for_velocity_with_timing(tf, control_signs, limits, jf, a_max, a_min)
+        let tolerances = tolerances();
+        let within_jerk = jf.abs() < j_max.abs() + tolerances.j_eps;
+        if !within_jerk {
+            reject!(control_signs, limits, CheckFailure::JerkLimit);
+        }
+        self.check_for_velocity_with_timing(tf, control_signs, limits, jf, a_max, a_min)
     }
 
     #[inline]
@@ -216,9 +374,14 @@ impl Profile {
         limits: ReachedLimits,
         a_up: f64,
     ) -> bool {
+        let tolerances = tolerances();
         // ReachedLimits::ACC0
         if self.t[1] < 0.0 {
-            return false;
+            reject!(
+                control_signs,
+                limits,
+                CheckFailure::NegativeDuration { phase: 1 }
+            );
         }
 
         self.t_sum = [
@@ -226,7 +389,7 @@ impl Profile {
         ];
         //self.t_sum.fill(self.t[1]);
         if *self.t_sum.last().unwrap_or(&0.0) > T_MAX {
-            return false;
+            reject!(control_signs, limits, CheckFailure::DurationTooLong);
         }
 
         self.j = [0.0; 7];
@@ -248,7 +411,12 @@ impl Profile {
             Direction::DOWN
         };
 
-        (self.v.last().unwrap_or(&0.0) - self.vf).abs() < V_PRECISION
+        let reaches_velocity =
+            (self.v.last().unwrap_or(&0.0) - self.vf).abs() < tolerances.v_precision;
+        if !reaches_velocity {
+            reject!(control_signs, limits, CheckFailure::TargetVelocity);
+        }
+        accept!(control_signs, limits)
     }
 
     #[inline]
@@ -272,9 +440,17 @@ impl Profile {
         a_max: f64,
         a_min: f64,
     ) -> bool {
-        a_min - A_EPS < a_up
-            && a_up < a_max + A_EPS
-            && self.check_for_second_order_velocity_with_timing(_tf, control_signs, limits, a_up)
+        let tolerances = tolerances();
+        let within_acceleration =
+            a_min - tolerances.a_eps < a_up && a_up < a_max + tolerances.a_eps;
+        if !within_acceleration {
+            reject!(
+                control_signs,
+                limits,
+                CheckFailure::AccelerationLimit { phase: 1 }
+            );
+        }
+        self.check_for_second_order_velocity_with_timing(_tf, control_signs, limits, a_up)
     }
 
     #[inline]
@@ -289,16 +465,9 @@ impl Profile {
         a_max: f64,
         a_min: f64,
     ) -> bool {
-        if self.t[0] < 0.0 {
-            return false;
-        }
-
-        self.t_sum[0] = self.t[0];
-        for i in 0..6 {
-            if self.t[i + 1] < 0.0 {
-                return false;
-            }
-            self.t_sum[i + 1] = self.t_sum[i] + self.t[i + 1];
+        let tolerances = tolerances();
+        if !self.set_cumulative_times() {
+            reject!(control_signs, limits, self.duration_failure());
         }
 
         if matches!(
@@ -309,23 +478,35 @@ impl Profile {
                 | ReachedLimits::Vel
         ) && self.t[3] < f64::EPSILON
         {
-            return false;
+            reject!(
+                control_signs,
+                limits,
+                CheckFailure::LimitPhaseMissing { phase: 3 }
+            );
         }
 
         if matches!(limits, ReachedLimits::Acc0 | ReachedLimits::Acc0Acc1)
             && self.t[1] < f64::EPSILON
         {
-            return false;
+            reject!(
+                control_signs,
+                limits,
+                CheckFailure::LimitPhaseMissing { phase: 1 }
+            );
         }
 
         if matches!(limits, ReachedLimits::Acc1 | ReachedLimits::Acc0Acc1)
             && self.t[5] < f64::EPSILON
         {
-            return false;
+            reject!(
+                control_signs,
+                limits,
+                CheckFailure::LimitPhaseMissing { phase: 5 }
+            );
         }
 
         if self.t_sum.last().unwrap_or(&0.0) > &T_MAX {
-            return false;
+            reject!(control_signs, limits, CheckFailure::DurationTooLong);
         }
 
         self.j = if control_signs == ControlSigns::UDDU {
@@ -360,12 +541,12 @@ impl Profile {
             v_max
         } else {
             v_min
-        } + V_EPS;
+        } + tolerances.v_eps;
         let v_low_lim = if self.direction == Direction::UP {
             v_min
         } else {
             v_max
-        } - V_EPS;
+        } - tolerances.v_eps;
 
         for i in 0..7 {
             self.a[i + 1] = self.a[i] + self.t[i] * self.j[i];
@@ -388,10 +569,8 @@ impl Profile {
 
             if set_limits {
                 match limits {
-                    ReachedLimits::Acc1 => {
-                        if i == 2 {
-                            self.a[3] = a_min;
-                        }
+                    ReachedLimits::Acc1 if i == 2 => {
+                        self.a[3] = a_min;
                     }
                     ReachedLimits::Acc0Acc1 => {
                         if i == 0 {
@@ -408,7 +587,11 @@ impl Profile {
             if i > 1 && self.a[i + 1] * self.a[i] < -f64::EPSILON {
                 let v_a_zero = self.v[i] - (self.a[i] * self.a[i]) / (2.0 * self.j[i]);
                 if v_a_zero > v_upp_lim || v_a_zero < v_low_lim {
-                    return false;
+                    reject!(
+                        control_signs,
+                        limits,
+                        CheckFailure::VelocityLimit { phase: i }
+                    );
                 }
             }
         }
@@ -420,22 +603,43 @@ impl Profile {
             a_max
         } else {
             a_min
-        } + A_EPS;
+        } + tolerances.a_eps;
         let a_low_lim = if self.direction == Direction::UP {
             a_min
         } else {
             a_max
-        } - A_EPS;
+        } - tolerances.a_eps;
 
-        (self.p.last().unwrap_or(&0.0) - self.pf).abs() < P_PRECISION
-            && (self.v.last().unwrap_or(&0.0) - self.vf).abs() < V_PRECISION
-            && (self.a.last().unwrap_or(&0.0) - self.af).abs() < A_PRECISION
-            && [self.a[1], self.a[3], self.a[5]]
-                .iter()
-                .all(|&x| x >= a_low_lim && x <= a_upp_lim)
-            && [self.v[3], self.v[4], self.v[5], self.v[6]]
-                .iter()
-                .all(|&x| x <= v_upp_lim && x >= v_low_lim)
+        let reaches_position =
+            (self.p.last().unwrap_or(&0.0) - self.pf).abs() < tolerances.p_precision;
+        if !reaches_position {
+            reject!(control_signs, limits, CheckFailure::TargetPosition);
+        }
+        let reaches_velocity =
+            (self.v.last().unwrap_or(&0.0) - self.vf).abs() < tolerances.v_precision;
+        if !reaches_velocity {
+            reject!(control_signs, limits, CheckFailure::TargetVelocity);
+        }
+        let reaches_acceleration =
+            (self.a.last().unwrap_or(&0.0) - self.af).abs() < tolerances.a_precision;
+        if !reaches_acceleration {
+            reject!(control_signs, limits, CheckFailure::TargetAcceleration);
+        }
+        for phase in [0, 2, 4] {
+            if !(a_low_lim..=a_upp_lim).contains(&self.a[phase + 1]) {
+                reject!(
+                    control_signs,
+                    limits,
+                    CheckFailure::AccelerationLimit { phase }
+                );
+            }
+        }
+        for phase in 2..6 {
+            if !(v_low_lim..=v_upp_lim).contains(&self.v[phase + 1]) {
+                reject!(control_signs, limits, CheckFailure::VelocityLimit { phase });
+            }
+        }
+        accept!(control_signs, limits)
     }
 
     #[inline]
@@ -468,8 +672,12 @@ impl Profile {
         a_min: f64,
         j_max: f64,
     ) -> bool {
-        (jf.abs() < j_max.abs() + J_EPS)
-            && self.check_with_timing(control_signs, limits, jf, v_max, v_min, a_max, a_min)
+        let tolerances = tolerances();
+        let within_jerk = jf.abs() < j_max.abs() + tolerances.j_eps;
+        if !within_jerk {
+            reject!(control_signs, limits, CheckFailure::JerkLimit);
+        }
+        self.check_with_timing(control_signs, limits, jf, v_max, v_min, a_max, a_min)
     }
 
     #[inline]
@@ -480,8 +688,9 @@ impl Profile {
         self.af = profile.af;
         self.vf = profile.vf;
         self.pf = profile.pf;
-        self.brake = profile.brake.clone();
-        self.accel = profile.accel.clone();
+        self.pre_dwell = profile.pre_dwell;
+        self.brake = profile.brake;
+        self.accel = profile.accel;
     }
 
     #[inline]
@@ -512,21 +721,13 @@ impl Profile {
         v_max: f64,
         v_min: f64,
     ) -> bool {
-        if self.t[0] < 0.0 {
-            return false;
-        }
-
-        self.t_sum[0] = self.t[0];
-        for i in 0..6 {
-            if self.t[i + 1] < 0.0 {
-                return false;
-            }
-
-            self.t_sum[i + 1] = self.t_sum[i] + self.t[i + 1];
+        let tolerances = tolerances();
+        if !self.set_cumulative_times() {
+            reject!(control_signs, limits, self.duration_failure());
         }
 
         if *self.t_sum.last().unwrap_or(&0.0) > T_MAX {
-            return false;
+            reject!(control_signs, limits, CheckFailure::DurationTooLong);
         }
 
         self.j = [0.0; 7];
@@ -565,14 +766,14 @@ impl Profile {
         };
 
         let v_upp_lim = if self.direction == Direction::UP {
-            v_max + V_EPS
+            v_max + tolerances.v_eps
         } else {
-            v_min + V_EPS
+            v_min + tolerances.v_eps
         };
         let v_low_lim = if self.direction == Direction::UP {
-            v_min - V_EPS
+            v_min - tolerances.v_eps
         } else {
-            v_max - V_EPS
+            v_max - tolerances.v_eps
         };
 
         for i in 0..7 {
@@ -583,10 +784,22 @@ impl Profile {
         self.control_signs = control_signs;
         self.limits = limits;
 
-        (self.p.last().unwrap_or(&0.0) - self.pf).abs() < P_PRECISION
-            && (self.v.last().unwrap_or(&0.0) - self.vf).abs() < P_PRECISION
-            && self.v[2..=7].iter().all(|&v| v <= v_upp_lim)
-            && self.v[2..=7].iter().all(|&v| v >= v_low_lim)
+        let reaches_position =
+            (self.p.last().unwrap_or(&0.0) - self.pf).abs() < tolerances.p_precision;
+        if !reaches_position {
+            reject!(control_signs, limits, CheckFailure::TargetPosition);
+        }
+        let reaches_velocity =
+            (self.v.last().unwrap_or(&0.0) - self.vf).abs() < tolerances.p_precision;
+        if !reaches_velocity {
+            reject!(control_signs, limits, CheckFailure::TargetVelocity);
+        }
+        for phase in 1..7 {
+            if !(v_low_lim..=v_upp_lim).contains(&self.v[phase + 1]) {
+                reject!(control_signs, limits, CheckFailure::VelocityLimit { phase });
+            }
+        }
+        accept!(control_signs, limits)
     }
 
     #[inline]
@@ -618,19 +831,32 @@ impl Profile {
         a_max: f64,
         a_min: f64,
     ) -> bool {
-        (a_min - A_EPS < a_up)
-            && (a_up < a_max + A_EPS)
-            && (a_min - A_EPS < a_down)
-            && (a_down < a_max + A_EPS)
-            && self.check_for_second_order_with_timing(
+        let tolerances = tolerances();
+        let within_up = a_min - tolerances.a_eps < a_up && a_up < a_max + tolerances.a_eps;
+        if !within_up {
+            reject!(
                 control_signs,
                 limits,
-                tf,
-                a_up,
-                a_down,
-                v_max,
-                v_min,
-            )
+                CheckFailure::AccelerationLimit { phase: 0 }
+            );
+        }
+        let within_down = a_min - tolerances.a_eps < a_down && a_down < a_max + tolerances.a_eps;
+        if !within_down {
+            reject!(
+                control_signs,
+                limits,
+                CheckFailure::AccelerationLimit { phase: 2 }
+            );
+        }
+        self.check_for_second_order_with_timing(
+            control_signs,
+            limits,
+            tf,
+            a_up,
+            a_down,
+            v_max,
+            v_min,
+        )
     }
 
     // For first-order position interface
@@ -641,13 +867,18 @@ impl Profile {
         control_signs: ControlSigns,
         limits: ReachedLimits,
     ) -> bool {
+        let tolerances = tolerances();
         if self.t[3] < 0.0 {
-            return false;
+            reject!(
+                control_signs,
+                limits,
+                CheckFailure::NegativeDuration { phase: 3 }
+            );
         }
 
         self.t_sum = [0.0, 0.0, 0.0, self.t[3], self.t[3], self.t[3], self.t[3]];
         if *self.t_sum.last().unwrap_or(&0.0) > T_MAX {
-            return false;
+            reject!(control_signs, limits, CheckFailure::DurationTooLong);
         }
 
         self.j = [0.0; 7];
@@ -675,7 +906,12 @@ impl Profile {
             Direction::DOWN
         };
 
-        (self.p.last().unwrap_or(&0.0) - self.pf).abs() < P_PRECISION
+        let reaches_position =
+            (self.p.last().unwrap_or(&0.0) - self.pf).abs() < tolerances.p_precision;
+        if !reaches_position {
+            reject!(control_signs, limits, CheckFailure::TargetPosition);
+        }
+        accept!(control_signs, limits)
     }
 
     #[inline]
@@ -699,9 +935,153 @@ impl Profile {
         v_max: f64,
         v_min: f64,
     ) -> bool {
-        (v_min - V_EPS < v_up)
-            && (v_up < v_max + V_EPS)
-            && self.check_for_first_order_with_timing(control_signs, limits, tf, v_up)
+        let tolerances = tolerances();
+        let within_velocity = v_min - tolerances.v_eps < v_up && v_up < v_max + tolerances.v_eps;
+        if !within_velocity {
+            reject!(
+                control_signs,
+                limits,
+                CheckFailure::VelocityLimit { phase: 3 }
+            );
+        }
+        self.check_for_first_order_with_timing(control_signs, limits, tf, v_up)
+    }
+
+    // For first-order velocity interface
+    #[inline]
+    pub fn check_for_first_order_velocity(
+        &mut self,
+        control_signs: ControlSigns,
+        limits: ReachedLimits,
+        v_max: f64,
+        v_min: f64,
+    ) -> bool {
+        let tolerances = tolerances();
+        if self.t[3] < 0.0 {
+            reject!(
+                control_signs,
+                limits,
+                CheckFailure::NegativeDuration { phase: 3 }
+            );
+        }
+
+        self.t_sum = [0.0, 0.0, 0.0, self.t[3], self.t[3], self.t[3], self.t[3]];
+        if *self.t_sum.last().unwrap_or(&0.0) > T_MAX {
+            reject!(control_signs, limits, CheckFailure::DurationTooLong);
+        }
+
+        let within_velocity =
+            v_min - tolerances.v_eps < self.vf && self.vf < v_max + tolerances.v_eps;
+        if !within_velocity {
+            reject!(
+                control_signs,
+                limits,
+                CheckFailure::VelocityLimit { phase: 0 }
+            );
+        }
+
+        self.direction = if self.vf >= self.v[0] {
+            Direction::UP
+        } else {
+            Direction::DOWN
+        };
+        self.j = [0.0; 7];
+        self.a = [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, self.af];
+        self.v = [self.vf; 8];
+
+        for i in 0..7 {
+            self.p[i + 1] = self.p[i] + self.t[i] * (self.v[i] + self.t[i] * self.a[i] / 2.0);
+        }
+
+        self.control_signs = control_signs;
+        self.limits = limits;
+        accept!(control_signs, limits)
+    }
+
+    // For acceleration interface
+    #[inline]
+    pub fn check_for_acceleration(
+        &mut self,
+        control_signs: ControlSigns,
+        limits: ReachedLimits,
+        jf: f64,
+        v_max: f64,
+        v_min: f64,
+        a_max: f64,
+        a_min: f64,
+    ) -> bool {
+        let tolerances = tolerances();
+        if self.t[0] < 0.0 {
+            reject!(
+                control_signs,
+                limits,
+                CheckFailure::NegativeDuration { phase: 0 }
+            );
+        }
+
+        self.t_sum = [self.t[0]; 7];
+        if *self.t_sum.last().unwrap_or(&0.0) > T_MAX {
+            reject!(control_signs, limits, CheckFailure::DurationTooLong);
+        }
+
+        let within_acceleration =
+            a_min - tolerances.a_eps < self.af && self.af < a_max + tolerances.a_eps;
+        if !within_acceleration {
+            reject!(
+                control_signs,
+                limits,
+                CheckFailure::AccelerationLimit { phase: 0 }
+            );
+        }
+
+        self.j = [
+            if self.t[0] > 0.0 { jf } else { 0.0 },
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+        ];
+        for i in 0..7 {
+            (self.p[i + 1], self.v[i + 1], self.a[i + 1]) =
+                integrate(self.t[i], self.p[i], self.v[i], self.a[i], self.j[i]);
+        }
+
+        // The velocity is extremal at the ends of the ramp or where the acceleration crosses zero
+        let mut v_low = self.v[0].min(self.v[7]);
+        let mut v_high = self.v[0].max(self.v[7]);
+        if self.j[0] != 0.0 {
+            let t_extremum = -self.a[0] / self.j[0];
+            if 0.0 < t_extremum && t_extremum < self.t[0] {
+                let v_extremum =
+                    self.v[0] + t_extremum * (self.a[0] + 0.5 * t_extremum * self.j[0]);
+                v_low = v_low.min(v_extremum);
+                v_high = v_high.max(v_extremum);
+            }
+        }
+        if v_high > v_max + tolerances.v_eps || v_low < v_min - tolerances.v_eps {
+            reject!(
+                control_signs,
+                limits,
+                CheckFailure::VelocityLimit { phase: 0 }
+            );
+        }
+
+        self.control_signs = control_signs;
+        self.limits = limits;
+        self.direction = if jf >= 0.0 {
+            Direction::UP
+        } else {
+            Direction::DOWN
+        };
+
+        let reaches_acceleration =
+            (self.a.last().unwrap_or(&0.0) - self.af).abs() < tolerances.a_precision;
+        if !reaches_acceleration {
+            reject!(control_signs, limits, CheckFailure::TargetAcceleration);
+        }
+        accept!(control_signs, limits)
     }
 
     pub fn check_position_extremum(
@@ -756,6 +1136,39 @@ impl Profile {
         }
     }
 
+    /// Check a phase with a constant snap, whose velocity is cubic, for position extrema
+    fn check_snap_step_for_position_extremum(
+        t_sum: f64,
+        t: f64,
+        state: (f64, f64, f64, f64),
+        s: f64,
+        ext: &mut Bound,
+    ) {
+        let (p, v, a, j) = state;
+        Self::check_step_for_position_extremum(t_sum, t, p, v, a, 0.0, ext);
+        for t_ext in roots::solve_cub(s / 6.0, j / 2.0, a, v) {
+            if 0.0 < t_ext && t_ext < t {
+                let (p_ext, _, a_ext, _) = integrate_with_snap(t_ext, p, v, a, j, s);
+                if a_ext > 0.0 && p_ext < ext.min {
+                    ext.min = p_ext;
+                    ext.t_min = t_sum + t_ext;
+                } else if a_ext < 0.0 && p_ext > ext.max {
+                    ext.max = p_ext;
+                    ext.t_max = t_sum + t_ext;
+                }
+            }
+        }
+    }
+
+    /// Kinematic state at the start, before the brake pre-trajectory if there is one
+    pub fn get_start_state(&self) -> (f64, f64, f64) {
+        if self.brake.duration > 0.0 {
+            (self.brake.p[0], self.brake.v[0], self.brake.a[0])
+        } else {
+            (self.p[0], self.v[0], self.a[0])
+        }
+    }
+
     pub fn get_position_extrema(&self) -> Bound {
         let mut extrema = Bound {
             min: f64::INFINITY,
@@ -764,9 +1177,22 @@ impl Profile {
             t_max: 0.0,
         };
 
-        if self.brake.duration > 0.0 && self.brake.t[0] > 0.0 {
+        // The pre-dwell holds the start position, which the first phase includes
+        let t_brake = self.pre_dwell;
+        if self.brake.duration > 0.0 && self.brake.s[0] != 0.0 {
+            // A blending pre-trajectory consists of a single phase with a snap
+            let brake = &self.brake;
+            let state = (brake.p[0], brake.v[0], brake.a[0], brake.j[0]);
+            Self::check_snap_step_for_position_extremum(
+                t_brake,
+                brake.t[0],
+                state,
+                brake.s[0],
+                &mut extrema,
+            );
+        } else if self.brake.duration > 0.0 && self.brake.t[0] > 0.0 {
             Self::check_step_for_position_extremum(
-                0.0,
+                t_brake,
                 self.brake.t[0],
                 self.brake.p[0],
                 self.brake.v[0],
@@ -776,7 +1202,7 @@ impl Profile {
             );
             if self.brake.t[1] > 0.0 {
                 Self::check_step_for_position_extremum(
-                    self.brake.t[0],
+                    t_brake + self.brake.t[0],
                     self.brake.t[1],
                     self.brake.p[1],
                     self.brake.v[1],
@@ -793,7 +1219,7 @@ impl Profile {
                 t_current_sum = self.t_sum[i - 1];
             }
             Self::check_step_for_position_extremum(
-                t_current_sum + self.brake.duration,
+                t_current_sum + t_brake + self.brake.duration,
                 self.t[i],
                 self.p[i],
                 self.v[i],
@@ -805,11 +1231,11 @@ impl Profile {
 
         if self.pf < extrema.min {
             extrema.min = self.pf;
-            extrema.t_min = self.t_sum.last().unwrap_or(&0.0) + self.brake.duration;
+            extrema.t_min = self.t_sum.last().unwrap_or(&0.0) + t_brake + self.brake.duration;
         }
         if self.pf > extrema.max {
             extrema.max = self.pf;
-            extrema.t_max = self.t_sum.last().unwrap_or(&0.0) + self.brake.duration;
+            extrema.t_max = self.t_sum.last().unwrap_or(&0.0) + t_brake + self.brake.duration;
         }
 
         extrema
@@ -848,31 +1274,57 @@ impl Profile {
     }
 }
 
-impl fmt::Display for Profile {
+impl fmt::Display for Direction {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut result = String::new();
-
-        match self.direction {
-            Direction::UP => result.push_str("UP_"),
-            Direction::DOWN => result.push_str("DOWN_"),
+        match self {
+            Direction::UP => write!(f, "UP"),
+            Direction::DOWN => write!(f, "DOWN"),
         }
+    }
+}
 
-        match self.limits {
-            ReachedLimits::Acc0Acc1Vel => result.push_str("ACC0_ACC1_VEL"),
-            ReachedLimits::Vel => result.push_str("VEL"),
-            ReachedLimits::Acc0 => result.push_str("ACC0"),
-            ReachedLimits::Acc1 => result.push_str("ACC1"),
-            ReachedLimits::Acc0Acc1 => result.push_str("ACC0_ACC1"),
-            ReachedLimits::Acc0Vel => result.push_str("ACC0_VEL"),
-            ReachedLimits::Acc1Vel => result.push_str("ACC1_VEL"),
-            ReachedLimits::None => result.push_str("NONE"),
+impl fmt::Display for ReachedLimits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReachedLimits::Acc0Acc1Vel => write!(f, "ACC0_ACC1_VEL"),
+            ReachedLimits::Vel => write!(f, "VEL"),
+            ReachedLimits::Acc0 => write!(f, "ACC0"),
+            ReachedLimits::Acc1 => write!(f, "ACC1"),
+            ReachedLimits::Acc0Acc1 => write!(f, "ACC0_ACC1"),
+            ReachedLimits::Acc0Vel => write!(f, "ACC0_VEL"),
+            ReachedLimits::Acc1Vel => write!(f, "ACC1_VEL"),
+            ReachedLimits::None => write!(f, "NONE"),
         }
+    }
+}
 
-        match self.control_signs {
-            ControlSigns::UDDU => result.push_str("_UDDU"),
-            ControlSigns::UDUD => result.push_str("_UDUD"),
+impl fmt::Display for ControlSigns {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ControlSigns::UDDU => write!(f, "UDDU"),
+            ControlSigns::UDUD => write!(f, "UDUD"),
         }
+    }
+}
 
-        write!(f, "{}", result)
+/// Same name as the profile, e.g. `UP_ACC0_ACC1_VEL_UDDU`
+impl fmt::Display for ProfileInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}_{}_{}",
+            self.direction, self.limits, self.control_signs
+        )
+    }
+}
+
+/// Name of the profile in the format of the C++ version, e.g. `UP_ACC0_ACC1_VEL_UDDU`
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}_{}_{}",
+            self.direction, self.limits, self.control_signs
+        )
     }
 }
@@ -1,14 +1,51 @@
 /// Result type of Ruckig's update function
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RuckigResult {
     Working = 0,                    // The trajectory is calculated normally
     Finished = 1,                   // The trajectory has reached its final position
+    Interrupted = 2,                // The calculation exceeded its time budget
     Error = -1,                     // Unclassified error
     ErrorInvalidInput = -100,       // Error in the input parameter
     ErrorTrajectoryDuration = -101, // The trajectory duration exceeds its numerical limits
-    ErrorPositionalLimits = -102, // The trajectory exceeds the given positional limits (only in Ruckig Pro)
+    ErrorPositionalLimits = -102, // The trajectory exceeds the given positional limits
     // ErrorNoPhaseSynchronization = -103, // The trajectory cannot be phase synchronized
     ErrorZeroLimits = -104, // The trajectory is not valid due to a conflict with zero limits
     ErrorExecutionTimeCalculation = -110, // Error during the extremel time calculation (Step 1)
     ErrorSynchronizationCalculation = -111, // Error during the synchronization calculation (Step 2)
 }
+
+/// Stage of the calculation in which an error occurred
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CalculationStage {
+    /// Validation of the input
+    Validation,
+    /// Calculation of the extremal profiles of each DoF (step 1)
+    Step1,
+    /// Search for the synchronized duration
+    Synchronization,
+    /// Calculation of the synchronized profile of each DoF (step 2)
+    Step2,
+}
+
+impl TryFrom<i32> for RuckigResult {
+    type Error = i32;
+
+    /// Convert an error code, e.g. of `RuckigResult::ErrorInvalidInput as i32`, back to the result
+    fn try_from(code: i32) -> Result<Self, i32> {
+        match code {
+            0 => Ok(RuckigResult::Working),
+            1 => Ok(RuckigResult::Finished),
+            2 => Ok(RuckigResult::Interrupted),
+            -1 => Ok(RuckigResult::Error),
+            -100 => Ok(RuckigResult::ErrorInvalidInput),
+            -101 => Ok(RuckigResult::ErrorTrajectoryDuration),
+            -102 => Ok(RuckigResult::ErrorPositionalLimits),
+            -104 => Ok(RuckigResult::ErrorZeroLimits),
+            -110 => Ok(RuckigResult::ErrorExecutionTimeCalculation),
+            -111 => Ok(RuckigResult::ErrorSynchronizationCalculation),
+            _ => Err(code),
+        }
+    }
+}
@@ -1,13 +1,59 @@
-use arrayvec::ArrayVec;
+//! Real roots of polynomials, as used by the profile calculations. The closed-form solvers for
+//! cubic and quartic equations return only the non-negative roots, as these are durations, while
+//! `shrink_interval` finds a root of a polynomial of any order within a bracketing interval.
+//! Polynomials are given by their coefficients, starting with the highest order.
+pub use arrayvec::ArrayVec;
 
 const COS_120: f64 = -0.50;
 const SIN_120: f64 = 0.866_025_403_784_438_6;
+/// Step size below which `shrink_interval` stops refining a root
 pub const TOLERANCE: f64 = 1e-14;
 
+// Maximal number of Newton steps when refining a root
+const REFINEMENT_ITERATIONS: usize = 4;
+
+// Transcendental functions differ in their last bits between the math libraries of platforms, so
+// with the `libm` feature they are calculated by the same implementation everywhere. The square
+// root and fused multiply-add are exactly rounded by IEEE 754 and therefore identical already.
+#[cfg(feature = "libm")]
+use libm::{acos, atan2, cbrt, cos, sin};
+
+#[cfg(not(feature = "libm"))]
+#[inline]
+fn cbrt(x: f64) -> f64 {
+    x.cbrt()
+}
+
+#[cfg(not(feature = "libm"))]
+#[inline]
+fn sin(x: f64) -> f64 {
+    x.sin()
+}
+
+#[cfg(not(feature = "libm"))]
+#[inline]
+fn cos(x: f64) -> f64 {
+    x.cos()
+}
+
+#[cfg(not(feature = "libm"))]
+#[inline]
+fn acos(x: f64) -> f64 {
+    x.acos()
+}
+
+#[cfg(not(feature = "libm"))]
+#[inline]
+fn atan2(y: f64, x: f64) -> f64 {
+    y.atan2(x)
+}
+
+/// Square of a value
 pub fn pow2<T: std::ops::Mul<Output = T> + Copy>(v: T) -> T {
     v * v
 }
 
+/// Set of at most `N` distinct values in the order of their insertion
 #[derive(Debug)]
 pub struct Set<T, const N: usize> {
     pub data: ArrayVec<T, N>,
@@ -26,10 +72,12 @@ impl<T: Copy + Default + PartialEq, const N: usize> Set<T, N> {
         }
     }
 
+    /// The values in the order of their insertion
     pub fn begin(&self) -> &[T] {
         &self.data[..]
     }
 
+    /// Insert the value unless it is already contained
     pub fn insert(&mut self, value: T) {
         if !self.data.contains(&value) {
             self.data.push(value);
@@ -64,6 +112,8 @@ impl<'a, T, const N: usize> IntoIterator for &'a mut Set<T, N> {
     }
 }
 
+/// Set of at most `N` distinct non-negative roots. Iterating by value or mutable reference
+/// yields them in ascending order.
 #[derive(Debug)]
 pub struct PositiveSet<const N: usize>(pub Set<f64, N>);
 
@@ -72,12 +122,14 @@ impl<const N: usize> PositiveSet<N> {
         PositiveSet(Set::new())
     }
 
+    /// Insert the value if it is non-negative and not already contained
     pub fn insert(&mut self, value: f64) {
         if value >= 0.0 {
             self.0.insert(value);
         }
     }
 
+    /// The roots in the order of their insertion
     pub fn get_data(&self) -> &[f64] {
         self.0.begin()
     }
@@ -119,12 +171,85 @@ impl<const N: usize> Default for PositiveSet<N> {
     }
 }
 
-/// Calculate all roots of a*x^3 + b*x^2 + c*x + d = 0
+/// Sum of two values and its exact rounding error
+#[inline]
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    let b_virtual = sum - a;
+    (sum, (a - (sum - b_virtual)) + (b - b_virtual))
+}
+
+/// Product of two values and its exact rounding error
+#[inline]
+fn two_prod(a: f64, b: f64) -> (f64, f64) {
+    let product = a * b;
+    (product, a.mul_add(b, -product))
+}
+
+/// Evaluate the polynomial with the given coefficients (highest order first) and its derivative.
+/// The value is calculated by the compensated Horner scheme, which is as accurate as evaluating
+/// in double-double precision and rounding the result.
+pub fn poly_eval_compensated(coeffs: &[f64], x: f64) -> (f64, f64) {
+    let mut value = coeffs[0];
+    let mut error = 0.0;
+    let mut derivative = 0.0;
+    for &coeff in &coeffs[1..] {
+        derivative = derivative * x + value;
+        let (product, product_error) = two_prod(value, x);
+        let (sum, sum_error) = two_sum(product, coeff);
+        error = error * x + (product_error + sum_error);
+        value = sum;
+    }
+    (value + error, derivative)
+}
+
+/// Refine a simple root of the polynomial with the given coefficients (highest order first) by
+/// Newton steps on its accurately evaluated residual. A step is only taken if it reduces the
+/// residual, so that the root never gets worse.
+pub fn refine_root(coeffs: &[f64], mut x: f64) -> f64 {
+    let (mut residual, mut derivative) = poly_eval_compensated(coeffs, x);
+    for _ in 0..REFINEMENT_ITERATIONS {
+        if residual == 0.0 || derivative == 0.0 || !derivative.is_finite() {
+            break;
+        }
+
+        let x_new = x - residual / derivative;
+        let (residual_new, derivative_new) = poly_eval_compensated(coeffs, x_new);
+        if residual_new.abs() >= residual.abs() {
+            break;
+        }
+        x = x_new;
+        residual = residual_new;
+        derivative = derivative_new;
+    }
+    x
+}
+
+#[cfg(feature = "extended-precision")]
+fn refine_roots<const N: usize>(roots: PositiveSet<N>, coeffs: &[f64]) -> PositiveSet<N> {
+    let mut refined = PositiveSet::new();
+    for &root in roots.get_data() {
+        refined.insert(refine_root(coeffs, root));
+    }
+    refined
+}
+
+/// Calculate the non-negative roots of a*x^3 + b*x^2 + c*x + d = 0, which may degenerate to a
+/// quadratic or linear equation. With the `extended-precision` feature, the roots are refined
+/// with an accurately evaluated residual.
 #[inline]
 pub fn solve_cub(a: f64, b: f64, c: f64, d: f64) -> PositiveSet<3> {
+    let roots = solve_cub_unrefined(a, b, c, d);
+    #[cfg(feature = "extended-precision")]
+    let roots = refine_roots(roots, &[a, b, c, d]);
+    roots
+}
+
+#[inline]
+fn solve_cub_unrefined(a: f64, b: f64, c: f64, d: f64) -> PositiveSet<3> {
     let mut roots = PositiveSet::new();
 
-    if d.abs() < std::f64::EPSILON {
+    if d.abs() < f64::EPSILON {
         // First solution is x = 0
         roots.insert(0.0);
 
@@ -135,9 +260,9 @@ pub fn solve_cub(a: f64, b: f64, c: f64, d: f64) -> PositiveSet<3> {
         let b = a;
         let _a = 0.0;
 
-        if b.abs() < std::f64::EPSILON {
+        if b.abs() < f64::EPSILON {
             // Linear equation
-            if c.abs() > std::f64::EPSILON {
+            if c.abs() > f64::EPSILON {
                 roots.insert(-tmp / c);
             }
         } else {
@@ -150,10 +275,10 @@ pub fn solve_cub(a: f64, b: f64, c: f64, d: f64) -> PositiveSet<3> {
                 roots.insert((-c - y) * inv2b);
             }
         }
-    } else if a.abs() < std::f64::EPSILON {
-        if b.abs() < std::f64::EPSILON {
+    } else if a.abs() < f64::EPSILON {
+        if b.abs() < f64::EPSILON {
             // Linear equation
-            if c.abs() > std::f64::EPSILON {
+            if c.abs() > f64::EPSILON {
                 roots.insert(-d / c);
             }
         } else {
@@ -182,7 +307,7 @@ pub fn solve_cub(a: f64, b: f64, c: f64, d: f64) -> PositiveSet<3> {
             let uuu = -halfq + y;
             let vvv = -halfq - y;
             let www = if uuu.abs() > vvv.abs() { uuu } else { vvv };
-            let w = www.cbrt();
+            let w = cbrt(www);
             roots.insert(w - p / (3.0 * w) - bover3a);
         } else if yy < -f64::EPSILON {
             // Sqrt is negative: three real solutions
@@ -193,7 +318,7 @@ pub fn solve_cub(a: f64, b: f64, c: f64, d: f64) -> PositiveSet<3> {
 
             // Convert to polar form
             if x.abs() > f64::EPSILON {
-                theta = y.atan2(x);
+                theta = atan2(y, x);
                 r = (x * x - yy).sqrt();
             } else {
                 // Vertical line
@@ -202,10 +327,10 @@ pub fn solve_cub(a: f64, b: f64, c: f64, d: f64) -> PositiveSet<3> {
             }
             // Calculate cube root
             theta /= 3.0;
-            r = 2.0 * r.cbrt();
+            r = 2.0 * cbrt(r);
             // Convert to complex coordinate
-            let ux = theta.cos() * r;
-            let uyi = theta.sin() * r;
+            let ux = cos(theta) * r;
+            let uyi = sin(theta) * r;
 
             roots.insert(ux - bover3a);
             roots.insert(ux * COS_120 - uyi * SIN_120 - bover3a);
@@ -213,7 +338,7 @@ pub fn solve_cub(a: f64, b: f64, c: f64, d: f64) -> PositiveSet<3> {
         } else {
             // Sqrt is zero: two real solutions
             let www = -halfq;
-            let w = 2.0 * www.cbrt();
+            let w = 2.0 * cbrt(www);
 
             roots.insert(w - bover3a);
             roots.insert(w * COS_120 - bover3a);
@@ -222,9 +347,9 @@ pub fn solve_cub(a: f64, b: f64, c: f64, d: f64) -> PositiveSet<3> {
     roots
 }
 
-// Solve resolvent eqaution of corresponding Quartic equation
-// The input x must be of length 3
-// Number of zeros are returned
+/// Solve the resolvent cubic x^3 + a*x^2 + b*x + c = 0 of a quartic equation. If three real roots
+/// exist, they are written to `x` and 3 is returned. Otherwise, `x[0]` is the real root and the
+/// complex pair is `x[1] ± i*x[2]`, and 1 is returned, or 2 if the pair is real and double.
 #[inline]
 pub fn solve_resolvent(x: &mut [f64; 3], a: f64, b: f64, c: f64) -> usize {
     let a = a / 3.0;
@@ -236,18 +361,18 @@ pub fn solve_resolvent(x: &mut [f64; 3], a: f64, b: f64, c: f64) -> usize {
 
     if r2 < q3 {
         let q_sqrt = q.sqrt();
-        let t = (r / (q * q_sqrt)).min(1.0).max(-1.0);
+        let t = (r / (q * q_sqrt)).clamp(-1.0, 1.0);
         q = -2.0 * q_sqrt;
 
-        let theta = t.acos() / 3.0;
-        let ux = theta.cos() * q;
-        let uyi = (theta).sin() * q;
+        let theta = acos(t) / 3.0;
+        let ux = cos(theta) * q;
+        let uyi = sin(theta) * q;
         x[0] = ux - a;
         x[1] = ux * COS_120 - uyi * SIN_120 - a;
         x[2] = ux * COS_120 + uyi * SIN_120 - a;
         3
     } else {
-        let mut a_ = (-r.abs() - (r2 - q3).sqrt()).cbrt();
+        let mut a_ = cbrt(-r.abs() - (r2 - q3).sqrt());
         if r < 0.0 {
             a_ = -a_;
         }
@@ -256,7 +381,7 @@ pub fn solve_resolvent(x: &mut [f64; 3], a: f64, b: f64, c: f64) -> usize {
         x[0] = (a_ + b_) - a;
         x[1] = -(a_ + b_) / 2.0 - a;
         x[2] = 3.0_f64.sqrt() * (a_ - b_) / 2.0;
-        if x[2].abs() < std::f64::EPSILON {
+        if x[2].abs() < f64::EPSILON {
             x[2] = x[1];
             2
         } else {
@@ -265,20 +390,30 @@ pub fn solve_resolvent(x: &mut [f64; 3], a: f64, b: f64, c: f64) -> usize {
     }
 }
 
-/// Calculate all roots of the monic quartic equation: x^4 + a*x^3 + b*x^2 + c*x + d = 0
+/// Calculate the non-negative roots of the monic quartic equation x^4 + a*x^3 + b*x^2 + c*x + d = 0.
+/// With the `extended-precision` feature, the roots are refined with an accurately evaluated
+/// residual.
 #[inline]
 pub fn solve_quart_monic_coeffs(a: f64, b: f64, c: f64, d: f64) -> PositiveSet<4> {
+    let roots = solve_quart_monic_coeffs_unrefined(a, b, c, d);
+    #[cfg(feature = "extended-precision")]
+    let roots = refine_roots(roots, &[1.0, a, b, c, d]);
+    roots
+}
+
+#[inline]
+fn solve_quart_monic_coeffs_unrefined(a: f64, b: f64, c: f64, d: f64) -> PositiveSet<4> {
     let mut roots = PositiveSet::new();
 
     let a_squared = a * a;
     let four_b = 4.0 * b;
 
-    if d.abs() < std::f64::EPSILON {
-        if c.abs() < std::f64::EPSILON {
+    if d.abs() < f64::EPSILON {
+        if c.abs() < f64::EPSILON {
             roots.insert(0.0);
 
             let d_ = a_squared - four_b;
-            if d_.abs() < std::f64::EPSILON {
+            if d_.abs() < f64::EPSILON {
                 roots.insert(-a / 2.0);
             } else if d_ > 0.0 {
                 let sqrt_d = d_.sqrt();
@@ -288,9 +423,9 @@ pub fn solve_quart_monic_coeffs(a: f64, b: f64, c: f64, d: f64) -> PositiveSet<4
             return roots;
         }
 
-        if a.abs() < std::f64::EPSILON && b.abs() < std::f64::EPSILON {
+        if a.abs() < f64::EPSILON && b.abs() < f64::EPSILON {
             roots.insert(0.0);
-            roots.insert(-c.cbrt());
+            roots.insert(-cbrt(c));
             return roots;
         }
     }
@@ -361,12 +496,14 @@ pub fn solve_quart_monic_coeffs(a: f64, b: f64, c: f64, d: f64) -> PositiveSet<4
     roots
 }
 
+/// Calculate the non-negative roots of the monic quartic equation with the coefficients
+/// `[a, b, c, d]`, see `solve_quart_monic_coeffs`
 #[inline]
 pub fn solve_quart_monic_arr(polynom: &[f64; 4]) -> PositiveSet<4> {
     solve_quart_monic_coeffs(polynom[0], polynom[1], polynom[2], polynom[3])
 }
 
-// Currently Rust doesn't support const generics, so using ArrayVec instead of array
+/// Coefficients of the derivative of the polynomial
 #[inline]
 pub fn poly_deri<const N: usize>(coeffs: &ArrayVec<f64, N>) -> ArrayVec<f64, N> {
     let mut deriv = ArrayVec::<f64, N>::new();
@@ -377,6 +514,8 @@ pub fn poly_deri<const N: usize>(coeffs: &ArrayVec<f64, N>) -> ArrayVec<f64, N>
     deriv
 }
 
+/// Coefficients of the derivative of the monic polynomial, divided by its order so that it is
+/// monic again
 #[inline]
 pub fn poly_monic_deri<const N: usize>(monic_coeffs: &ArrayVec<f64, N>) -> ArrayVec<f64, N> {
     let mut deriv = ArrayVec::<f64, N>::new();
@@ -388,13 +527,14 @@ pub fn poly_monic_deri<const N: usize>(monic_coeffs: &ArrayVec<f64, N>) -> Array
     deriv
 }
 
+/// Evaluate the polynomial at x by the Horner scheme
 #[inline]
 pub fn poly_eval<const N: usize>(p: &ArrayVec<f64, N>, x: f64) -> f64 {
     let mut result = 0.0;
     let n = p.len();
-    if x.abs() < std::f64::EPSILON {
+    if x.abs() < f64::EPSILON {
         result = p[n - 1];
-    } else if (x - 1.0).abs() < std::f64::EPSILON {
+    } else if (x - 1.0).abs() < f64::EPSILON {
         result = p.iter().sum();
     } else {
         let mut xn = 1.0;
@@ -406,18 +546,72 @@ pub fn poly_eval<const N: usize>(p: &ArrayVec<f64, N>, x: f64) -> f64 {
     result
 }
 
-// Wrapper for poly_eval with default value for MAX_ITS
+/// Find the real roots of the polynomial of any degree within the interval `[l, h]` in ascending
+/// order. The roots of its derivatives split the interval into monotonic pieces, in which each
+/// sign change is shrunk to a root. Roots of even multiplicity are only found if the polynomial
+/// vanishes exactly at them.
+pub fn solve_poly<const N: usize>(p: &ArrayVec<f64, N>, l: f64, h: f64) -> ArrayVec<f64, N> {
+    let mut roots = ArrayVec::<f64, N>::new();
+    let leading = p.iter().position(|coeff| *coeff != 0.0).unwrap_or(p.len());
+    if p.len() - leading < 2 {
+        return roots; // Constant polynomial
+    }
+
+    // All derivatives down to the linear one
+    let mut derivatives = ArrayVec::<ArrayVec<f64, N>, N>::new();
+    derivatives.push(p[leading..].iter().copied().collect());
+    while derivatives[derivatives.len() - 1].len() > 2 {
+        let derivative = poly_deri(&derivatives[derivatives.len() - 1]);
+        derivatives.push(derivative);
+    }
+
+    let linear = &derivatives[derivatives.len() - 1];
+    let root = -linear[1] / linear[0];
+    if l <= root && root <= h {
+        roots.push(root);
+    }
+
+    // The roots of each derivative bound the monotonic pieces of the next higher polynomial
+    for polynom in derivatives.iter().rev().skip(1) {
+        let critical_points = std::mem::take(&mut roots);
+        let mut lower = l;
+        let mut value_lower = poly_eval(polynom, l);
+        if value_lower == 0.0 {
+            roots.push(l);
+        }
+        for upper in critical_points.into_iter().chain(std::iter::once(h)) {
+            let value_upper = poly_eval(polynom, upper);
+            if value_upper == 0.0 {
+                if roots.last() != Some(&upper) {
+                    roots.push(upper);
+                }
+            } else if value_lower * value_upper < 0.0 {
+                roots.push(shrink_interval_default(polynom, lower, upper));
+            }
+            lower = upper;
+            value_lower = value_upper;
+        }
+    }
+    roots
+}
+
+/// Find a root of the polynomial within the interval `[l, h]` with at most 128 iterations, see
+/// `shrink_interval`
 #[inline]
 pub fn shrink_interval_default<const N: usize>(p: &ArrayVec<f64, N>, l: f64, h: f64) -> f64 {
     shrink_interval::<N, 128>(p, l, h)
 }
 
+/// Find a root of the polynomial within the interval `[l, h]`, whose values at the bounds have
+/// opposite signs. Newton steps are combined with bisection, so that the root stays bracketed,
+/// for at most `MAX_ITS` iterations or until the step falls below `TOLERANCE`.
 #[inline]
 pub fn shrink_interval<const N: usize, const MAX_ITS: usize>(
     p: &ArrayVec<f64, N>,
     mut l: f64,
     mut h: f64,
 ) -> f64 {
+    let (lower, upper) = (l.min(h), l.max(h));
     let deriv = poly_deri(p);
     let fl = poly_eval(p, l);
     let fh = poly_eval(p, h);
@@ -443,7 +637,7 @@ pub fn shrink_interval<const N: usize, const MAX_ITS: usize>(
             dxold = dx;
             dx = (h - l) / 2.0;
             rts = l + dx;
-            if (l - rts).abs() < std::f64::EPSILON {
+            if (l - rts).abs() < f64::EPSILON {
                 break;
             }
         } else {
@@ -451,7 +645,7 @@ pub fn shrink_interval<const N: usize, const MAX_ITS: usize>(
             dx = f / df;
             let temp = rts;
             rts -= dx;
-            if (temp - rts).abs() < std::f64::EPSILON {
+            if (temp - rts).abs() < f64::EPSILON {
                 break;
             }
         }
@@ -469,5 +663,6 @@ pub fn shrink_interval<const N: usize, const MAX_ITS: usize>(
         }
     }
 
-    rts
+    // Newton steps may leave the interval by rounding
+    rts.max(lower).min(upper)
 }
@@ -1,21 +1,297 @@
 //! Main implementation for the Ruckig algorithm.
 
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+use crate::background::BackgroundCalculation;
 use crate::calculator_target::TargetCalculator;
-use crate::error::{RuckigError, RuckigErrorHandler};
-use crate::input_parameter::{DurationDiscretization, InputParameter};
-use crate::output_parameter::OutputParameter;
-use crate::result::RuckigResult;
+use crate::calculator_waypoints::WaypointsCalculator;
+use crate::error::{RuckigError, RuckigErrorHandler, RuckigWarning};
+use crate::input_parameter::{ControlInterface, DurationDiscretization, InputParameter};
+use crate::output_parameter::{OutputParameter, UpdateEvent};
+use crate::result::{CalculationStage, RuckigResult};
+use crate::settings::Settings;
 use crate::trajectory::Trajectory;
+use crate::util::{circular_distance, duration_from_secs, DataArrayOrVec, Deadline, Stopwatch};
 use std::marker::PhantomData;
-use std::time::Instant;
+use std::time::Duration;
+
+/// Share of the acceleration limit that the trajectory keeps during a transition of the speed
+/// factor
+const ACCELERATION_SHARE: f64 = 0.75;
+
+/// Share of the jerk limit that the trajectory keeps during a transition of the speed factor
+const JERK_SHARE: f64 = 0.5;
+
+/// Phase of the speed factor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SpeedFactorPhase {
+    /// The speed factor is constant
+    Steady,
+    /// The trajectory brakes into the reduced limits of a transition with its full jerk limit,
+    /// while the speed factor is still constant
+    Braking,
+    /// The speed factor changes along its own trajectory
+    Transitioning,
+}
+
+/// Speed factor of the online trajectory. Changes of the speed factor are planned as a 1-DoF
+/// third-order trajectory themselves. Before a transition, the trajectory is re-planned with a
+/// share of its acceleration limit and brakes into it. During the transition, it also keeps only
+/// a share of its jerk limit, and the rate and change of rate of the speed factor are limited
+/// such that the additional acceleration and jerk of the scaled trajectory fit into the remaining
+/// share. Together with a speed factor of at most one, the scaled trajectory stays within the
+/// kinematic limits.
+#[derive(Debug)]
+struct SpeedFactor {
+    target: f64,
+    planned_target: f64,
+    phase: SpeedFactorPhase,
+    time: f64,
+    /// Time of the trajectory at which its brake into the reduced limits has finished
+    brake_end: f64,
+    /// Whether the trajectory needs to be re-planned with the limits of the current phase, i.e.
+    /// at the start and at the end of a transition
+    needs_replan: bool,
+    input: InputParameter<1>,
+    calculator: TargetCalculator<1>,
+    trajectory: Trajectory<1>,
+}
+
+impl SpeedFactor {
+    fn new() -> Self {
+        Self {
+            target: 1.0,
+            planned_target: 1.0,
+            phase: SpeedFactorPhase::Steady,
+            time: 0.0,
+            brake_end: 0.0,
+            needs_replan: false,
+            input: InputParameter::new(None),
+            calculator: TargetCalculator::new(None),
+            trajectory: Trajectory::new(None),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.target = 1.0;
+        self.planned_target = 1.0;
+        self.phase = SpeedFactorPhase::Steady;
+        self.time = 0.0;
+        self.brake_end = 0.0;
+        self.needs_replan = false;
+    }
+
+    /// Speed factor and its first and second time derivative
+    fn state(&self) -> (f64, f64, f64) {
+        if self.phase != SpeedFactorPhase::Transitioning {
+            return (self.planned_target, 0.0, 0.0);
+        }
+
+        let state = self.trajectory.state_at(self.time);
+        (
+            state.position[0].max(0.0),
+            state.velocity[0],
+            state.acceleration[0],
+        )
+    }
+
+    fn advance(&mut self, delta_time: f64) {
+        self.time += delta_time;
+        if self.phase == SpeedFactorPhase::Transitioning
+            && self.time >= self.trajectory.get_duration()
+        {
+            self.phase = SpeedFactorPhase::Steady;
+            self.needs_replan = true;
+        }
+    }
+
+    fn is_unscaled(&self) -> bool {
+        self.phase != SpeedFactorPhase::Transitioning && self.planned_target == 1.0
+    }
+
+    /// Advance the phase before the trajectory is updated at the given time. A changed target
+    /// first lets the trajectory brake into the reduced limits, after which the transition
+    /// starts. Each change of the phase re-plans the trajectory with its limits.
+    fn prepare(&mut self, time: f64) {
+        let phase = match self.phase {
+            SpeedFactorPhase::Steady if self.target != self.planned_target => {
+                SpeedFactorPhase::Braking
+            }
+            SpeedFactorPhase::Braking if self.target == self.planned_target => {
+                SpeedFactorPhase::Steady
+            }
+            SpeedFactorPhase::Braking if time >= self.brake_end => SpeedFactorPhase::Transitioning,
+            phase => phase,
+        };
+        if phase != self.phase {
+            self.phase = phase;
+            self.time = 0.0;
+            self.needs_replan = true;
+        }
+    }
+
+    /// The velocity and acceleration limit of the DoF that hold in both directions
+    fn symmetric_limits<const DOF: usize>(input: &InputParameter<DOF>, dof: usize) -> (f64, f64) {
+        let v_max = input.max_velocity[dof].max(
+            input
+                .min_velocity
+                .as_ref()
+                .map_or(0.0, |min_velocity| -min_velocity[dof]),
+        );
+        let a_max = input.max_acceleration[dof].min(
+            input
+                .min_acceleration
+                .as_ref()
+                .map_or(f64::INFINITY, |min_acceleration| -min_acceleration[dof]),
+        );
+        (v_max, a_max)
+    }
+
+    /// Reduce the limits of the input, in the time of the trajectory, to those of the current
+    /// phase. While braking, the velocity limits keep a margin for the velocity that the
+    /// acceleration adds once the jerk limit is reduced during the transition.
+    fn reserve_headroom<const DOF: usize>(&self, input: &mut InputParameter<DOF>) {
+        if self.phase == SpeedFactorPhase::Steady {
+            return;
+        }
+
+        for dof in 0..input.degrees_of_freedom {
+            if !input.enabled[dof] {
+                continue;
+            }
+            let (v_max, a_max) = Self::symmetric_limits(input, dof);
+            let j_max = input.max_jerk[dof];
+            input.max_acceleration[dof] *= ACCELERATION_SHARE;
+            if let Some(min_acceleration) = input.min_acceleration.as_mut() {
+                min_acceleration[dof] *= ACCELERATION_SHARE;
+            }
+            if self.phase == SpeedFactorPhase::Transitioning {
+                input.max_jerk[dof] *= JERK_SHARE;
+                continue;
+            }
+
+            let a_reduced = ACCELERATION_SHARE * a_max;
+            let margin = (a_reduced * a_reduced / (2.0 * j_max)).min(0.5 * v_max);
+            input.max_velocity[dof] -= margin;
+            if let Some(min_velocity) = input.min_velocity.as_mut() {
+                min_velocity[dof] += margin;
+            }
+        }
+    }
+
+    /// Remember when the brake of the trajectory, which was just re-planned with the reduced
+    /// limits, has finished
+    fn set_brake_end<const DOF: usize>(
+        &mut self,
+        input: &InputParameter<DOF>,
+        traj: &Trajectory<DOF>,
+    ) {
+        if self.phase != SpeedFactorPhase::Braking {
+            return;
+        }
+
+        self.brake_end = (0..input.degrees_of_freedom)
+            .filter(|&dof| input.enabled[dof])
+            .map(|dof| {
+                let p = &traj.profiles[0][dof];
+                p.pre_dwell + p.brake.duration
+            })
+            .fold(0.0, f64::max);
+    }
+
+    /// Plan the transition to a changed target from the current state of the speed factor,
+    /// once the trajectory has braked into the reduced limits
+    fn plan<const DOF: usize, E: RuckigErrorHandler>(
+        &mut self,
+        input: &InputParameter<DOF>,
+        delta_time: f64,
+    ) -> Result<RuckigResult, RuckigError> {
+        if self.phase != SpeedFactorPhase::Transitioning || self.target == self.planned_target {
+            return Ok(RuckigResult::Working);
+        }
+
+        // A DoF at its velocity limit gains the rate times the velocity limit as acceleration,
+        // and the change of rate times the velocity limit as jerk. The acceleration of the
+        // trajectory and the rate of the speed factor add the jerk `3 a s ds`, which may take
+        // half of the remaining jerk.
+        let mut max_rate = f64::INFINITY;
+        let mut max_rate_change = f64::INFINITY;
+        let mut ramp_rate = f64::INFINITY;
+        for dof in 0..input.degrees_of_freedom {
+            if !input.enabled[dof] {
+                continue;
+            }
+            let (v_max, a_max) = Self::symmetric_limits(input, dof);
+            if v_max <= 0.0 {
+                continue;
+            }
+            let j_max = input.max_jerk[dof];
+            let a_reduced = ACCELERATION_SHARE * a_max;
+            let j_remaining = (1.0 - JERK_SHARE) * j_max;
+            let rate = ((a_max - a_reduced) / v_max).min(j_remaining / (6.0 * a_reduced));
+            max_rate = max_rate.min(rate);
+            max_rate_change = max_rate_change.min((j_remaining - 3.0 * a_reduced * rate) / v_max);
+            // The change of rate ramps up as fast as the acceleration of the DoF
+            ramp_rate = ramp_rate.min(j_max / a_max);
+        }
+
+        // A transition that has just started begins with the constant speed factor
+        let (speed_factor, rate, rate_change) = if self.time > 0.0 {
+            self.state()
+        } else {
+            (self.planned_target, 0.0, 0.0)
+        };
+        self.planned_target = self.target;
+        self.time = 0.0;
+        // Without acceleration limits, the speed factor changes instantly, and the trajectory
+        // gets its full limits back
+        if !max_rate.is_finite() {
+            self.phase = SpeedFactorPhase::Steady;
+            self.needs_replan = true;
+            return Ok(RuckigResult::Working);
+        }
+
+        self.input.current_position[0] = speed_factor;
+        self.input.current_velocity[0] = rate;
+        self.input.current_acceleration[0] = rate_change;
+        self.input.target_position[0] = self.target;
+        self.input.max_velocity[0] = max_rate;
+        self.input.max_acceleration[0] = max_rate_change;
+        self.input.max_jerk[0] = max_rate_change * ramp_rate;
+        let result =
+            self.calculator
+                .calculate::<E>(&self.input, &mut self.trajectory, delta_time)?;
+        if result != RuckigResult::Working {
+            self.phase = SpeedFactorPhase::Steady;
+        }
+        Ok(result)
+    }
+}
 
 #[derive(Debug)]
 pub struct Ruckig<const DOF: usize, E: RuckigErrorHandler> {
     current_input: InputParameter<DOF>,
     current_input_initialized: bool,
     pub calculator: TargetCalculator<DOF>,
+    waypoints_calculator: WaypointsCalculator<DOF>,
     pub degrees_of_freedom: usize,
     pub delta_time: f64,
+    pub max_number_of_waypoints: usize,
+    speed_factor: SpeedFactor,
+    /// Scratch input for re-planning in the time of a scaled trajectory, which is taken out while
+    /// it is calculated
+    scaled_input: Option<InputParameter<DOF>>,
+    /// Scratch input for unwrapping circular positions and clamping the target state, which is
+    /// taken out while it is calculated
+    prepared_input: Option<InputParameter<DOF>>,
+    /// Scratch input of `calculate_stop`, which is taken out while it is calculated
+    stop_input: Option<InputParameter<DOF>>,
+    /// Jerk of the online trajectory from which a snap-limited trajectory continues while it is
+    /// re-planned by `update`, and zero otherwise
+    current_jerk: DataArrayOrVec<f64, DOF>,
+    /// The speed factor to continue with after a pause
+    paused_speed_factor: Option<f64>,
+    /// Time of the host clock at the last `update_at`
+    last_update_time: Option<f64>,
     _error_handler: PhantomData<E>,
 }
 
@@ -27,18 +303,201 @@ impl<const DOF: usize, E: RuckigErrorHandler> Default for Ruckig<DOF, E> {
 
 impl<const DOF: usize, E: RuckigErrorHandler> Ruckig<DOF, E> {
     pub fn new(degrees_of_freedom: Option<usize>, delta_time: f64) -> Self {
+        Self::new_with_waypoints(0, degrees_of_freedom, delta_time)
+    }
+
+    /// Create an instance with the control cycle given as a duration
+    pub fn new_with_cycle(degrees_of_freedom: Option<usize>, cycle: Duration) -> Self {
+        Self::new(degrees_of_freedom, cycle.as_secs_f64())
+    }
+
+    /// Create an instance whose calculation behaves according to the given settings
+    pub fn with_settings(
+        degrees_of_freedom: Option<usize>,
+        delta_time: f64,
+        settings: Settings,
+    ) -> Self {
+        let mut ruckig = Self::new(degrees_of_freedom, delta_time);
+        ruckig.calculator.settings = settings;
+        ruckig
+    }
+
+    /// Create an instance that accepts up to `max_number_of_waypoints` intermediate positions.
+    /// Together with `InputParameter::new_with_waypoints` and `OutputParameter::new_with_waypoints`,
+    /// all section buffers are allocated here, so that waypoints can be pushed and popped
+    /// between `update` calls without allocation.
+    pub fn new_with_waypoints(
+        max_number_of_waypoints: usize,
+        degrees_of_freedom: Option<usize>,
+        delta_time: f64,
+    ) -> Self {
         Self {
-            current_input: InputParameter::new(degrees_of_freedom),
+            current_input: InputParameter::new_with_waypoints(
+                max_number_of_waypoints,
+                degrees_of_freedom,
+            ),
             current_input_initialized: false,
             calculator: TargetCalculator::new(degrees_of_freedom),
+            waypoints_calculator: WaypointsCalculator::new(degrees_of_freedom),
             degrees_of_freedom: degrees_of_freedom.unwrap_or(DOF),
             delta_time,
+            max_number_of_waypoints,
+            speed_factor: SpeedFactor::new(),
+            scaled_input: Some(InputParameter::new_with_waypoints(
+                max_number_of_waypoints,
+                degrees_of_freedom,
+            )),
+            prepared_input: Some(InputParameter::new_with_waypoints(
+                max_number_of_waypoints,
+                degrees_of_freedom,
+            )),
+            stop_input: Some(InputParameter::new_with_waypoints(
+                max_number_of_waypoints,
+                degrees_of_freedom,
+            )),
+            current_jerk: DataArrayOrVec::new(degrees_of_freedom, 0.0),
+            paused_speed_factor: None,
+            last_update_time: None,
             _error_handler: PhantomData,
         }
     }
 
+    /// Clear the state of the online trajectory, so that the next `update` calculates a new
+    /// trajectory from its input. The speed factor is reset to one, a pause is cancelled, and the
+    /// host clock of `update_at` restarts, while all buffers are kept for the next motion.
     pub fn reset(&mut self) {
         self.current_input_initialized = false;
+        self.speed_factor.reset();
+        self.paused_speed_factor = None;
+        self.last_update_time = None;
+        self.calculator.failed_dof = None;
+        self.calculator.failed_stage = None;
+    }
+
+    /// Continue an online trajectory that was calculated before, e.g. after restoring the input
+    /// and output parameters from a snapshot. Pass the input of the last `update` call after
+    /// `pass_to_input`, so that the next `update` continues the trajectory of the restored output
+    /// without re-planning.
+    pub fn restore(&mut self, input: &InputParameter<DOF>) {
+        self.current_input = input.clone();
+        self.current_input_initialized = true;
+    }
+
+    /// Change the number of DoFs of an instance with heap-allocated DoFs, e.g. between two
+    /// programs. All buffers are reallocated once while the settings of the calculator are kept,
+    /// and the next `update` calculates a new trajectory.
+    pub fn resize_dofs(&mut self, degrees_of_freedom: usize) {
+        assert!(
+            DOF == 0,
+            "the number of DoFs can only be changed for heap-allocated DoFs"
+        );
+
+        self.current_input = InputParameter::new_with_waypoints(
+            self.max_number_of_waypoints,
+            Some(degrees_of_freedom),
+        );
+        self.current_input_initialized = false;
+        self.scaled_input = Some(InputParameter::new_with_waypoints(
+            self.max_number_of_waypoints,
+            Some(degrees_of_freedom),
+        ));
+        self.prepared_input = Some(InputParameter::new_with_waypoints(
+            self.max_number_of_waypoints,
+            Some(degrees_of_freedom),
+        ));
+        self.stop_input = Some(InputParameter::new_with_waypoints(
+            self.max_number_of_waypoints,
+            Some(degrees_of_freedom),
+        ));
+        self.current_jerk = DataArrayOrVec::new(Some(degrees_of_freedom), 0.0);
+        let settings = std::mem::take(&mut self.calculator.settings);
+        self.calculator = TargetCalculator::new(Some(degrees_of_freedom));
+        self.calculator.settings = settings;
+        self.waypoints_calculator = WaypointsCalculator::new(Some(degrees_of_freedom));
+        self.degrees_of_freedom = degrees_of_freedom;
+    }
+
+    /// Change the control cycle between `update` calls. A trajectory with a discrete duration is
+    /// re-planned from its current state on the next `update`, so that its duration becomes a
+    /// multiple of the new control cycle.
+    pub fn set_delta_time(&mut self, delta_time: f64) {
+        if delta_time != self.delta_time
+            && self.current_input.duration_discretization == DurationDiscretization::Discrete
+        {
+            self.current_input_initialized = false;
+        }
+        self.delta_time = delta_time;
+    }
+
+    /// Change the control cycle given as a duration, see `set_delta_time`
+    pub fn set_cycle(&mut self, cycle: Duration) {
+        self.set_delta_time(cycle.as_secs_f64());
+    }
+
+    /// Get the control cycle as a duration
+    pub fn get_cycle(&self) -> Duration {
+        duration_from_secs(self.delta_time)
+    }
+
+    /// Scale the speed of the online trajectory by the given factor (feedrate override), e.g. 0.5
+    /// for half of the speed. The trajectory continues along the same path, while the factor
+    /// changes smoothly within the next `update` calls. Within the kinematic limits of the input,
+    /// the speed factor should be between zero and one; a factor of zero halts the motion. A
+    /// negative factor or NaN is passed to the error handler and doesn't change the speed factor.
+    pub fn set_speed_factor(&mut self, speed_factor: f64) -> Result<(), RuckigError> {
+        if speed_factor.is_nan() || speed_factor < 0.0 {
+            E::handle_validation_error(RuckigError::InvalidSpeedFactor { speed_factor })?;
+            return Ok(());
+        }
+
+        match self.paused_speed_factor.as_mut() {
+            Some(paused_speed_factor) => *paused_speed_factor = speed_factor,
+            None => self.speed_factor.target = speed_factor,
+        }
+        Ok(())
+    }
+
+    /// Ramp the online trajectory down to standstill along its path within the next `update`
+    /// calls, while the trajectory keeps its target. A speed factor set during the pause is
+    /// applied when resuming.
+    pub fn pause(&mut self) {
+        if self.paused_speed_factor.is_none() {
+            self.paused_speed_factor = Some(self.speed_factor.target);
+            self.speed_factor.target = 0.0;
+        }
+    }
+
+    /// Continue a paused trajectory towards its target with the speed factor before the pause
+    pub fn resume(&mut self) {
+        if let Some(speed_factor) = self.paused_speed_factor.take() {
+            self.speed_factor.target = speed_factor;
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_speed_factor.is_some()
+    }
+
+    /// The current speed factor, which might still be in transition to the last set one
+    pub fn get_speed_factor(&self) -> f64 {
+        self.speed_factor.state().0
+    }
+
+    /// Convert the current state of the input from real time into the time of the trajectory
+    /// that is scaled by the speed factor. A halted state is taken as it is.
+    fn to_trajectory_time(&self, input: &mut InputParameter<DOF>) {
+        let (speed_factor, speed_factor_rate, _) = self.speed_factor.state();
+        if speed_factor <= 0.0 {
+            return;
+        }
+
+        for dof in 0..self.degrees_of_freedom {
+            let velocity = input.current_velocity[dof] / speed_factor;
+            input.current_acceleration[dof] = (input.current_acceleration[dof]
+                - velocity * speed_factor_rate)
+                / (speed_factor * speed_factor);
+            input.current_velocity[dof] = velocity;
+        }
     }
 
     /// Validate the input as well as the Ruckig instance for trajectory calculation
@@ -55,13 +514,19 @@ impl<const DOF: usize, E: RuckigErrorHandler> Ruckig<DOF, E> {
             return Ok(false);
         }
 
+        if input.intermediate_positions.len() > self.max_number_of_waypoints {
+            return E::handle_validation_error(RuckigError::TooManyWaypoints {
+                count: input.intermediate_positions.len(),
+                max: self.max_number_of_waypoints,
+            });
+        }
+
         if self.delta_time <= 0.0
-            && input.duration_discretization != DurationDiscretization::Continuous
+            && input.duration_discretization == DurationDiscretization::Discrete
         {
-            return E::handle_validation_error(&format!(
-                "delta time (control rate) parameter {} should be larger than zero.",
-                self.delta_time
-            ));
+            return E::handle_validation_error(RuckigError::InvalidDeltaTime {
+                delta_time: self.delta_time,
+            });
         }
 
         Ok(true)
@@ -72,9 +537,350 @@ impl<const DOF: usize, E: RuckigErrorHandler> Ruckig<DOF, E> {
         input: &InputParameter<DOF>,
         traj: &mut Trajectory<DOF>,
     ) -> Result<RuckigResult, RuckigError> {
-        self.validate_input(input, false, true)?;
+        let stopwatch = Stopwatch::start();
+        let clamp_target_state =
+            self.calculator.settings.clamp_target_state && input.is_target_state_beyond_limits();
+        let leads_snap_window = input.leading_snap_window(&self.current_jerk).is_some();
+        let result = if input.per_dof_period.is_some() || clamp_target_state || leads_snap_window {
+            let mut prepared_input = self.prepared_input.take().unwrap_or_default();
+            prepared_input.clone_from(input);
+            let result = self.prepare_and_calculate(&mut prepared_input, traj);
+            self.prepared_input = Some(prepared_input);
+            result
+        } else {
+            traj.periods.clone_from(&input.per_dof_period);
+            self.calculate_prepared(input, traj)
+        };
+        traj.calculation_duration = stopwatch.elapsed_microseconds();
+        result
+    }
+
+    /// Unwrap the circular positions, clamp the target state, and lead the current state by the
+    /// window of a snap limit of a scratch input in place, and calculate the trajectory of it
+    fn prepare_and_calculate(
+        &mut self,
+        input: &mut InputParameter<DOF>,
+        traj: &mut Trajectory<DOF>,
+    ) -> Result<RuckigResult, RuckigError> {
+        traj.periods.clone_from(&input.per_dof_period);
+        input.unwrap_circular_positions();
+        if self.calculator.settings.clamp_target_state && input.is_target_state_beyond_limits() {
+            input.clamp_target_state::<E>();
+        }
+        if let Some(window) = input.leading_snap_window(&self.current_jerk) {
+            input.lead_snap_window(window, &self.current_jerk);
+        }
+        self.calculate_prepared(input, traj)
+    }
+
+    /// Calculate many independent trajectories, e.g. candidate motions of a planner, reusing the
+    /// scratch buffers of this instance. Each input is calculated into the trajectory at the same
+    /// index, and its result is written to the same index of the results, so that a failing input
+    /// doesn't stop the batch. As with `update`, the caller owns all buffers, so that the batch
+    /// doesn't allocate. Surplus items of the longer slices are ignored.
+    pub fn calculate_batch(
+        &mut self,
+        inputs: &[InputParameter<DOF>],
+        trajectories: &mut [Trajectory<DOF>],
+        results: &mut [Result<RuckigResult, RuckigError>],
+    ) {
+        for ((input, traj), result) in inputs.iter().zip(trajectories).zip(results) {
+            *result = self.calculate(input, traj);
+        }
+    }
+
+    /// Calculate many independent trajectories like `calculate_batch`, but in parallel on the
+    /// rayon thread pool. Rayon splits the batch into parts, and each part creates its own
+    /// instance with the settings of this one, which is reused for the inputs of that part. As
+    /// there are usually more parts than worker threads, a small batch may spend more time on
+    /// creating instances than on the calculations.
+    #[cfg(all(feature = "rayon", not(target_arch = "wasm32")))]
+    pub fn calculate_batch_parallel(
+        &self,
+        inputs: &[InputParameter<DOF>],
+        trajectories: &mut [Trajectory<DOF>],
+        results: &mut [Result<RuckigResult, RuckigError>],
+    ) {
+        use rayon::prelude::*;
+
+        let max_number_of_waypoints = self.max_number_of_waypoints;
+        let degrees_of_freedom = (DOF == 0).then_some(self.degrees_of_freedom);
+        let delta_time = self.delta_time;
+        let settings = &self.calculator.settings;
+        inputs
+            .par_iter()
+            .zip(trajectories.par_iter_mut())
+            .zip(results.par_iter_mut())
+            .for_each_init(
+                || {
+                    let mut ruckig = Self::new_with_waypoints(
+                        max_number_of_waypoints,
+                        degrees_of_freedom,
+                        delta_time,
+                    );
+                    ruckig.calculator.settings = settings.clone();
+                    ruckig
+                },
+                |ruckig, ((input, traj), result)| *result = ruckig.calculate(input, traj),
+            );
+    }
+
+    /// Calculate the trajectory for the input on a background thread, e.g. if the calculation
+    /// may take longer than the control cycle. The new trajectory starts at the state that the
+    /// current trajectory of the output reaches after the lead time, so that the control loop
+    /// continues with `update` in the meantime, and `switch_to_background` switches over. It isn't
+    /// available on `wasm32` targets, which can't spawn threads.
+    #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+    pub fn calculate_in_background(
+        &self,
+        input: &InputParameter<DOF>,
+        output: &OutputParameter<DOF>,
+        lead_time: f64,
+    ) -> BackgroundCalculation<DOF>
+    where
+        E: Send + 'static,
+    {
+        let start_time = output.time + lead_time;
+        let mut background_input = input.clone();
+        output.trajectory.at_time(
+            start_time,
+            &mut Some(&mut background_input.current_position),
+            &mut Some(&mut background_input.current_velocity),
+            &mut Some(&mut background_input.current_acceleration),
+            &mut None,
+            &mut None,
+        );
+
+        let mut worker = Self::new_with_waypoints(
+            self.max_number_of_waypoints,
+            (DOF == 0).then_some(self.degrees_of_freedom),
+            self.delta_time,
+        );
+        worker.calculator.settings = self.calculator.settings.clone();
+        BackgroundCalculation::spawn(worker, background_input, start_time)
+    }
+
+    /// Switch the output to the trajectory of a finished background calculation in the cycle in
+    /// which the current trajectory reaches its start, and call this before every `update`
+    /// meanwhile. It returns `None` while waiting, `Working` after switching, and the error if
+    /// the calculation failed or finished too late. Afterwards, the input of `update` needs the
+    /// target of the background calculation, so that the trajectory continues without
+    /// re-planning.
+    #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+    pub fn switch_to_background(
+        &mut self,
+        calculation: &mut BackgroundCalculation<DOF>,
+        output: &mut OutputParameter<DOF>,
+    ) -> Option<Result<RuckigResult, RuckigError>> {
+        if !calculation.is_finished() {
+            return None;
+        }
+
+        let start_time = calculation.start_time;
+        if matches!(calculation.outcome, Some((Ok(RuckigResult::Working), _)))
+            && output.time + self.delta_time < start_time
+        {
+            return None;
+        }
+
+        let (result, trajectory) = calculation.outcome.take()?;
+        if result != Ok(RuckigResult::Working) {
+            return Some(result);
+        }
+        if output.time > start_time {
+            return Some(E::handle_calculator_error(
+                RuckigError::LateBackgroundCalculation {
+                    start_time,
+                    time: output.time,
+                },
+            ));
+        }
+
+        if let Some(trajectory) = trajectory {
+            let previous = std::mem::replace(&mut output.trajectory, trajectory);
+            if let Some(previous_trajectory) = &mut output.previous_trajectory {
+                *previous_trajectory = previous;
+                output.previous_time = start_time;
+            }
+        }
+        output.time -= start_time;
+        output.new_section = 0;
+        self.current_input.clone_from(&calculation.input);
+        output.pass_to_input(&mut self.current_input);
+        self.current_input_initialized = true;
+        Some(Ok(RuckigResult::Working))
+    }
+
+    /// Calculate the trajectory, but stop with `RuckigResult::Interrupted` once the calculation
+    /// takes longer than the budget in microseconds, e.g. to keep the control cycle. The budget
+    /// is checked before the profile of each DoF, and the trajectory is incomplete after an
+    /// interruption. Without a clock, i.e. without the `std` feature or on
+    /// `wasm32-unknown-unknown`, the budget is never exceeded.
+    pub fn calculate_with_budget(
+        &mut self,
+        input: &InputParameter<DOF>,
+        traj: &mut Trajectory<DOF>,
+        max_micros: f64,
+    ) -> Result<RuckigResult, RuckigError> {
+        self.calculator.deadline = Some(Deadline::start(max_micros));
+        let result = self.calculate(input, traj);
+        self.calculator.deadline = None;
+        result
+    }
+
+    /// Calculate the trajectory of an input whose circular positions are already unwrapped, whose
+    /// target state is already clamped, and whose current state is already led by the window of
+    /// a snap limit
+    fn calculate_prepared(
+        &mut self,
+        input: &InputParameter<DOF>,
+        traj: &mut Trajectory<DOF>,
+    ) -> Result<RuckigResult, RuckigError> {
+        self.calculator.failed_dof = None;
+        self.calculator.failed_stage = None;
+        self.calculator.relaxed_limits.clear();
+        self.calculator.loosened_tolerances.clear();
+        if !self.validate_input(input, false, true)? {
+            return Ok(RuckigResult::ErrorInvalidInput);
+        }
+
+        let result = if input.intermediate_positions.is_empty() {
+            self.calculator
+                .calculate::<E>(input, traj, self.delta_time)?
+        } else {
+            self.waypoints_calculator.calculate::<E>(
+                &mut self.calculator,
+                input,
+                traj,
+                self.delta_time,
+            )?
+        };
 
-        self.calculator.calculate::<E>(input, traj, self.delta_time)
+        if result == RuckigResult::Working {
+            if let Some(post_dwell) = input.post_dwell {
+                traj.extend_by(post_dwell);
+            }
+            if let Some(max_snap) = &input.max_snap {
+                let leading_window = input.leading_snap_window(&self.current_jerk);
+                traj.limit_snap(max_snap, &input.enabled, leading_window, &self.current_jerk);
+            }
+
+            if let Some(maximum) = input.maximum_duration {
+                let duration = traj.get_duration();
+                if duration > maximum {
+                    if self.calculator.settings.max_relaxation > 0.0 {
+                        E::handle_warning(RuckigWarning::MaximumDurationExceeded {
+                            duration,
+                            maximum,
+                        });
+                    } else {
+                        return self
+                            .calculator
+                            .fail::<E>(RuckigError::MaximumDurationExceeded { duration, maximum });
+                    }
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Calculate a time-optimal stop to standstill from the current state of the input, e.g. for
+    /// an emergency stop. The target state, intermediate positions, position limits, minimum
+    /// and maximum duration, dwell times, and snap limit of the input are ignored, and the input is
+    /// not modified.
+    pub fn calculate_stop(
+        &mut self,
+        input: &InputParameter<DOF>,
+        traj: &mut Trajectory<DOF>,
+    ) -> Result<RuckigResult, RuckigError> {
+        let mut stop_input = self.stop_input.take().unwrap_or_default();
+        stop_input.clone_from(input);
+        stop_input.control_interface = ControlInterface::Velocity;
+        stop_input.intermediate_positions.clear();
+        stop_input.intermediate_blend_tolerances.clear();
+        stop_input.minimum_duration = None;
+        stop_input.maximum_duration = None;
+        stop_input.pre_dwell = None;
+        stop_input.post_dwell = None;
+        stop_input.target_velocity.fill(0.0);
+        stop_input.target_acceleration.fill(0.0);
+        // The ignored per-DoF fields are put back afterwards, so that the next copy reuses them
+        let per_dof_control_interface = stop_input.per_dof_control_interface.take();
+        let max_position = stop_input.max_position.take();
+        let min_position = stop_input.min_position.take();
+        let max_snap = stop_input.max_snap.take();
+        let stopwatch = Stopwatch::start();
+        let result = self.prepare_and_calculate(&mut stop_input, traj);
+        traj.calculation_duration = stopwatch.elapsed_microseconds();
+        stop_input.per_dof_control_interface = per_dof_control_interface;
+        stop_input.max_position = max_position;
+        stop_input.min_position = min_position;
+        stop_input.max_snap = max_snap;
+        self.stop_input = Some(stop_input);
+        result
+    }
+
+    /// Whether the new state of all enabled DoFs is within the tolerances around the target
+    fn is_within_target_tolerance(
+        input: &InputParameter<DOF>,
+        output: &OutputParameter<DOF>,
+    ) -> bool {
+        if input.target_position_tolerance.is_none() && input.target_velocity_tolerance.is_none() {
+            return false;
+        }
+
+        (0..output.degrees_of_freedom).all(|dof| {
+            if !input.enabled[dof] {
+                return true;
+            }
+
+            let mut position_error = output.new_position[dof] - input.target_position[dof];
+            if let Some(period) = input
+                .per_dof_period
+                .as_ref()
+                .and_then(|periods| periods[dof])
+            {
+                position_error = circular_distance(position_error, period);
+            }
+            let velocity_error = output.new_velocity[dof] - input.target_velocity[dof];
+            input
+                .target_position_tolerance
+                .as_ref()
+                .is_none_or(|tolerance| position_error.abs() <= tolerance[dof])
+                && input
+                    .target_velocity_tolerance
+                    .as_ref()
+                    .is_none_or(|tolerance| velocity_error.abs() <= tolerance[dof])
+        })
+    }
+
+    /// Whether the output has passed the end of the trajectory or reached the target tolerances
+    fn is_finished(input: &InputParameter<DOF>, output: &OutputParameter<DOF>) -> bool {
+        output.time > output.trajectory.get_duration()
+            || Self::is_within_target_tolerance(input, output)
+    }
+
+    /// Write the DoF and stage of the failed calculation into the output. Both are unknown for
+    /// results that don't refer to a stage, e.g. errors of a custom error handler.
+    fn report_failure(
+        &self,
+        input: &InputParameter<DOF>,
+        result: &Result<RuckigResult, RuckigError>,
+        output: &mut OutputParameter<DOF>,
+    ) {
+        (output.failed_dof, output.failed_stage) = match result {
+            _ if self.calculator.failed_stage.is_some() => {
+                (self.calculator.failed_dof, self.calculator.failed_stage)
+            }
+            Err(error) => (error.dof(), error.stage()),
+            Ok(RuckigResult::ErrorInvalidInput) => (
+                input
+                    .first_violation(false, true)
+                    .and_then(|violation| violation.dof),
+                Some(CalculationStage::Validation),
+            ),
+            Ok(_) => (None, None),
+        };
     }
 
     pub fn update(
@@ -82,48 +888,198 @@ impl<const DOF: usize, E: RuckigErrorHandler> Ruckig<DOF, E> {
         input: &InputParameter<DOF>,
         output: &mut OutputParameter<DOF>,
     ) -> Result<RuckigResult, RuckigError> {
-        let start = Instant::now();
+        self.update_with_dt(input, self.delta_time, output)
+    }
+
+    /// Update with the time measured by the host clock in [s] instead of the fixed control
+    /// cycle, so that scheduling jitter doesn't accumulate into a drift of the trajectory. The
+    /// first call after the construction or a `reset` advances by the control cycle.
+    pub fn update_at(
+        &mut self,
+        input: &InputParameter<DOF>,
+        now: f64,
+        output: &mut OutputParameter<DOF>,
+    ) -> Result<RuckigResult, RuckigError> {
+        let elapsed = self
+            .last_update_time
+            .map_or(self.delta_time, |last_update_time| {
+                (now - last_update_time).max(0.0)
+            });
+        self.last_update_time = Some(now);
+        self.update_with_dt(input, elapsed, output)
+    }
+
+    /// Update with an explicit time step given as a duration, see `update_with_dt`
+    pub fn update_with_duration(
+        &mut self,
+        input: &InputParameter<DOF>,
+        time_step: Duration,
+        output: &mut OutputParameter<DOF>,
+    ) -> Result<RuckigResult, RuckigError> {
+        self.update_with_dt(input, time_step.as_secs_f64(), output)
+    }
+
+    /// Update with an explicit time step in [s] for this control cycle, e.g. for a double cycle
+    /// of a fieldbus master. The trajectory advances by exactly the given time.
+    pub fn update_with_dt(
+        &mut self,
+        input: &InputParameter<DOF>,
+        time_step: f64,
+        output: &mut OutputParameter<DOF>,
+    ) -> Result<RuckigResult, RuckigError> {
+        let stopwatch = Stopwatch::start();
 
         if self.degrees_of_freedom == 0
             && (self.degrees_of_freedom != input.degrees_of_freedom
                 || self.degrees_of_freedom != output.degrees_of_freedom)
         {
-            return E::handle_calculator_error(
-                "mismatch in degrees of freedom (vector size).",
-                RuckigResult::Error,
-            );
+            return E::handle_calculator_error(RuckigError::DofMismatch);
         }
 
         output.new_calculation = false;
+        output.events.clear();
 
-        let result = Ok(RuckigResult::Working);
-        if !self.current_input_initialized || *input != self.current_input {
-            self.calculate(input, &mut output.trajectory)?;
+        // The trajectory gets the reduced limits before a transition of the speed factor
+        self.speed_factor.prepare(output.time);
 
-            self.current_input = input.clone();
+        let replan_tolerance = self.calculator.settings.replan_tolerance;
+        if !self.current_input_initialized
+            || !input.equals_within_target_tolerance(&self.current_input, replan_tolerance)
+            || self.speed_factor.needs_replan
+        {
+            // A retained previous trajectory lends its storage to the new one, and they are
+            // swapped after a successful calculation
+            let replaces_trajectory =
+                self.current_input_initialized && output.previous_trajectory.is_some();
+            self.calculator.is_blending = self.current_input_initialized
+                && self.calculator.settings.replan_blending > 0.0
+                && input.intermediate_positions.is_empty();
+            if self.calculator.is_blending {
+                // The jerk in the time of the trajectory, also with a speed factor
+                output.trajectory.at_time(
+                    output.time,
+                    &mut None,
+                    &mut None,
+                    &mut None,
+                    &mut Some(&mut self.calculator.blending_jerk),
+                    &mut None,
+                );
+            }
+            if self.current_input_initialized && input.max_snap.is_some() {
+                // A snap-limited trajectory continues from the jerk in the time of the trajectory
+                output.trajectory.at_time(
+                    output.time,
+                    &mut None,
+                    &mut None,
+                    &mut None,
+                    &mut Some(&mut self.current_jerk),
+                    &mut None,
+                );
+            }
+            let traj = match &mut output.previous_trajectory {
+                Some(previous_trajectory) if replaces_trajectory => previous_trajectory,
+                _ => &mut output.trajectory,
+            };
+            let result = if self.speed_factor.is_unscaled()
+                && self.speed_factor.phase == SpeedFactorPhase::Steady
+            {
+                self.calculate(input, traj)
+            } else {
+                let mut scaled_input = self.scaled_input.take().unwrap_or_default();
+                scaled_input.clone_from(input);
+                self.to_trajectory_time(&mut scaled_input);
+                self.speed_factor.reserve_headroom(&mut scaled_input);
+                let result = self.calculate(&scaled_input, traj);
+                self.scaled_input = Some(scaled_input);
+                result
+            };
+            self.calculator.is_blending = false;
+            self.current_jerk.fill(0.0);
+            if result != Ok(RuckigResult::Working) {
+                self.report_failure(input, &result, output);
+                output.calculation_duration = stopwatch.elapsed_microseconds();
+                return result;
+            }
+            output.failed_dof = None;
+            output.failed_stage = None;
+            output
+                .relaxed_limits
+                .clone_from(&self.calculator.relaxed_limits);
+            if let Some(previous_trajectory) = output
+                .previous_trajectory
+                .as_mut()
+                .filter(|_| replaces_trajectory)
+            {
+                std::mem::swap(&mut output.trajectory, previous_trajectory);
+                output.previous_time = output.time;
+            }
+
+            self.current_input.clone_from(input);
             self.current_input_initialized = true;
+            self.speed_factor.needs_replan = false;
+            self.speed_factor.set_brake_end(input, &output.trajectory);
             output.time = 0.0;
+            output.new_section = 0;
             output.new_calculation = true;
+            output.events.push(UpdateEvent::NewCalculation);
+        }
+
+        let result = self.speed_factor.plan::<DOF, E>(input, self.delta_time)?;
+        if result != RuckigResult::Working {
+            return Ok(result);
         }
+        let result = Ok(RuckigResult::Working);
+        let was_finished = !output.new_calculation && Self::is_finished(input, output);
 
         let old_section = output.new_section;
-        output.time += self.delta_time;
+        let mut new_section = Some(old_section);
+        if self.speed_factor.is_unscaled() {
+            output.time += time_step;
+        } else {
+            // The trajectory time advances with the integral of the speed factor, which the
+            // corrected trapezoidal rule integrates exactly within each phase of the transition
+            let (speed_factor, speed_factor_rate, _) = self.speed_factor.state();
+            self.speed_factor.advance(time_step);
+            let (new_speed_factor, new_speed_factor_rate, _) = self.speed_factor.state();
+            let scaled_time_step = 0.5 * time_step * (speed_factor + new_speed_factor)
+                + time_step * time_step / 12.0 * (speed_factor_rate - new_speed_factor_rate);
+            output.time += scaled_time_step.max(0.0);
+        }
         output.trajectory.at_time(
             output.time,
             &mut Some(&mut output.new_position),
             &mut Some(&mut output.new_velocity),
             &mut Some(&mut output.new_acceleration),
             &mut Some(&mut output.new_jerk),
-            &mut Some(output.new_section),
+            &mut new_section,
         );
+        output.new_section = new_section.unwrap_or(old_section);
         output.did_section_change = output.new_section > old_section; // Report only forward section changes
+        for section in old_section + 1..=output.new_section {
+            output.events.push(UpdateEvent::SectionChanged(section));
+        }
+
+        if !self.speed_factor.is_unscaled() {
+            // Chain rule of the trajectory along the scaled time
+            let (s, ds, dds) = self.speed_factor.state();
+            for dof in 0..self.degrees_of_freedom {
+                let v = output.new_velocity[dof];
+                let a = output.new_acceleration[dof];
+                let j = output.new_jerk[dof];
+                output.new_velocity[dof] = v * s;
+                output.new_acceleration[dof] = a * s * s + v * ds;
+                output.new_jerk[dof] = j * s * s * s + 3.0 * a * s * ds + v * dds;
+            }
+        }
 
-        let stop = Instant::now();
-        output.calculation_duration = (stop.duration_since(start).as_nanos() as f64) / 1000.0;
+        output.calculation_duration = stopwatch.elapsed_microseconds();
 
         output.pass_to_input(&mut self.current_input);
 
-        if output.time > output.trajectory.get_duration() {
+        if Self::is_finished(input, output) {
+            if !was_finished {
+                output.events.push(UpdateEvent::Finished);
+            }
             return Ok(RuckigResult::Finished);
         }
 
@@ -0,0 +1,139 @@
+//! Planning of a continuous trajectory through a sequence of target states.
+
+use crate::calculator::Calculator;
+use crate::error::{RuckigError, RuckigErrorHandler};
+use crate::input_parameter::InputParameter;
+use crate::result::RuckigResult;
+use crate::settings::Settings;
+use crate::tracker::TargetState;
+use crate::trajectory::Trajectory;
+use crate::util::CompensatedSum;
+
+/// Chains state-to-state trajectories through an ordered list of target states into a single
+/// `Trajectory` with a section per target state. Each segment starts with the final state of
+/// the previous one, so that the trajectory is continuous, and is time-optimal on its own.
+/// Unlike intermediate positions, which are reached at rest, the target states may pass their
+/// positions with a velocity and acceleration.
+///
+/// The limits, synchronization and interfaces of the input hold for every segment, the pre-dwell
+/// precedes the first and the post-dwell follows the last one. The target state, intermediate
+/// positions, minimum and maximum duration, and snap limit of the input are ignored.
+#[derive(Debug)]
+pub struct SegmentPlanner<const DOF: usize, E: RuckigErrorHandler> {
+    calculator: Calculator<DOF, E>,
+    segment_input: InputParameter<DOF>,
+    segment_trajectory: Trajectory<DOF>,
+    pub degrees_of_freedom: usize,
+}
+
+impl<const DOF: usize, E: RuckigErrorHandler> Default for SegmentPlanner<DOF, E> {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl<const DOF: usize, E: RuckigErrorHandler> SegmentPlanner<DOF, E> {
+    pub fn new(degrees_of_freedom: Option<usize>) -> Self {
+        Self::with_settings(degrees_of_freedom, Settings::default())
+    }
+
+    /// Create a planner whose segments are calculated according to the given settings
+    pub fn with_settings(degrees_of_freedom: Option<usize>, settings: Settings) -> Self {
+        Self {
+            calculator: Calculator::with_settings(degrees_of_freedom, settings),
+            segment_input: InputParameter::new(degrees_of_freedom),
+            segment_trajectory: Trajectory::new(degrees_of_freedom),
+            degrees_of_freedom: degrees_of_freedom.unwrap_or(DOF),
+        }
+    }
+
+    /// The calculator of the segments, e.g. for the DoF and stage of the last failure
+    pub fn calculator(&self) -> &Calculator<DOF, E> {
+        &self.calculator
+    }
+
+    /// Plan the trajectory from the current state of the input through all target states. The
+    /// trajectory gets a section per target state, whose end times are its intermediate
+    /// durations. If a segment fails, its result is returned and the trajectory is incomplete.
+    pub fn plan(
+        &mut self,
+        input: &InputParameter<DOF>,
+        targets: &[TargetState<DOF>],
+        traj: &mut Trajectory<DOF>,
+    ) -> Result<RuckigResult, RuckigError> {
+        if targets.is_empty() {
+            return E::handle_calculator_error(RuckigError::new(
+                "the segment planner requires at least one target state",
+            ));
+        }
+
+        traj.resize(targets.len());
+        traj.duration = 0.0;
+        traj.discretization_remainder = 0.0;
+        traj.calculation_duration = 0.0;
+        traj.snap_window = 0.0;
+        traj.periods.clone_from(&input.per_dof_period);
+        for dof in 0..self.degrees_of_freedom {
+            traj.independent_min_durations[dof] = 0.0;
+        }
+
+        self.segment_input.clone_from(input);
+        self.segment_input.intermediate_positions.clear();
+        self.segment_input.intermediate_blend_tolerances.clear();
+        self.segment_input.minimum_duration = None;
+        self.segment_input.maximum_duration = None;
+        self.segment_input.post_dwell = None;
+        self.segment_input.max_snap = None;
+
+        let mut duration = CompensatedSum::default();
+        for (segment, target) in targets.iter().enumerate() {
+            if segment > 0 {
+                // Start from the final state of the previous segment
+                self.segment_input.pre_dwell = None;
+                let t_end = self.segment_trajectory.get_duration();
+                self.segment_trajectory.at_time(
+                    t_end,
+                    &mut Some(&mut self.segment_input.current_position),
+                    &mut Some(&mut self.segment_input.current_velocity),
+                    &mut Some(&mut self.segment_input.current_acceleration),
+                    &mut None,
+                    &mut None,
+                );
+            }
+            self.segment_input
+                .target_position
+                .clone_from(&target.position);
+            self.segment_input
+                .target_velocity
+                .clone_from(&target.velocity);
+            self.segment_input
+                .target_acceleration
+                .clone_from(&target.acceleration);
+
+            let result = self
+                .calculator
+                .calculate(&self.segment_input, &mut self.segment_trajectory)?;
+            traj.calculation_duration += self.segment_trajectory.calculation_duration;
+            if result != RuckigResult::Working {
+                return Ok(result);
+            }
+
+            traj.profiles[segment].clone_from(&self.segment_trajectory.profiles[0]);
+            duration.add(self.segment_trajectory.get_duration());
+            traj.duration = duration.value();
+            traj.discretization_remainder += self.segment_trajectory.discretization_remainder;
+            traj.cumulative_times[segment] = traj.duration;
+            for dof in 0..self.degrees_of_freedom {
+                traj.independent_min_durations[dof] +=
+                    self.segment_trajectory.independent_min_durations[dof];
+            }
+            traj.synchronizations
+                .clone_from(&self.segment_trajectory.synchronizations);
+        }
+
+        if let Some(post_dwell) = input.post_dwell {
+            traj.extend_by(post_dwell);
+        }
+        Ok(RuckigResult::Working)
+    }
+}
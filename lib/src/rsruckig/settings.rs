@@ -0,0 +1,93 @@
+//! Tunable behavior of the trajectory calculation.
+
+/// Direction in which a duration is rounded to a multiple of the discretization step
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DurationRounding {
+    /// Stretch the trajectory to the next multiple, so that it ends exactly on a step
+    #[default]
+    Up,
+    /// Round to the previous multiple if all DoFs can reach their target within it, and to the
+    /// next multiple otherwise
+    Down,
+    /// Round up if the next multiple is at most half a step away, and down otherwise, with the
+    /// same fallback to the next multiple as `Down`
+    Nearest,
+}
+
+/// Numerical and behavioral settings of the calculation. The defaults match the behavior of
+/// the reference implementation, except for the retries of step 2 where it fails.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Settings {
+    /// Whether trajectories longer than `maximal_duration` are rejected with
+    /// `RuckigResult::ErrorTrajectoryDuration`
+    pub return_error_at_maximal_duration: bool,
+    /// Maximal trajectory duration in [s], limited by the numerical precision
+    pub maximal_duration: f64,
+    /// Tolerance below which a kinematic value counts as zero, e.g. for phase synchronization
+    pub eps: f64,
+    /// Remainder below which a duration counts as a multiple of the control cycle for discrete
+    /// durations. Larger remainders are rounded according to `duration_rounding`.
+    pub discretization_tolerance: f64,
+    /// Rounding of discrete durations, which always end on a multiple of the step. The time that
+    /// was added by rounding up, or removed by rounding down as a negative value, is reported by
+    /// `Trajectory::get_discretization_remainder`.
+    pub duration_rounding: DurationRounding,
+    /// Maximal relative relaxation of the kinematic limits of a DoF in the relaxed mode. If no
+    /// exact profile is found in step 1 or step 2, the limits are relaxed step by step up to this
+    /// fraction, in step 2 together with the tolerances of the profile checks, and the remaining
+    /// excess is reported. Zero disables the relaxed mode, so that the calculation fails instead.
+    pub max_relaxation: f64,
+    /// Whether a target velocity or acceleration beyond the kinematic limits is clamped to the
+    /// closest reachable state instead of failing the validation. Every clamped DoF is reported
+    /// as `RuckigWarning::TargetStateClamped`.
+    pub clamp_target_state: bool,
+    /// Largest change of the target position, velocity, and acceleration that doesn't lead to a
+    /// new calculation in `Ruckig::update`, e.g. to ignore the noise of a streamed target. The
+    /// trajectory then continues towards the target of the last calculation.
+    pub replan_tolerance: f64,
+    /// Whether the time synchronization of third-order position profiles first tries the
+    /// profile shapes that match the time-optimal profile of the DoF. This is faster on
+    /// average, but if several profiles match the synchronized duration, another one than
+    /// with the default order may be selected.
+    pub guess_step2_profile: bool,
+    /// Number of retries of the time synchronization of a DoF whose profile isn't found for the
+    /// synchronized duration due to the numerical precision. Each retry shrinks the kinematic
+    /// limits of the DoF by a tenfold larger fraction, starting at 1e-14, so that the limits
+    /// are kept. Zero fails at once with `RuckigError::Step2Failure`.
+    pub step2_retries: usize,
+    /// Escalating tolerances of the profile checks, e.g. `vec![1e-9, 1e-6]`, that are tried in
+    /// turn for a DoF whose profile isn't found with the default tolerances. A tolerance loosens
+    /// both the kinematic limits (1e-12 by default) and the precision of the final state (1e-8
+    /// for the position and velocity, 1e-10 for the acceleration) to at least its value. The
+    /// tolerance that succeeded is reported as `RuckigWarning::LoosenedTolerance`. Empty
+    /// disables the fallback.
+    pub fallback_tolerances: Vec<f64>,
+    /// Duration in [s] of the blending window when `Ruckig::update` replaces a trajectory
+    /// mid-motion. Within the window, the jerk is ramped down linearly from the current jerk of
+    /// the replaced trajectory to zero before the new trajectory starts, so that the commanded
+    /// jerk is continuous at the switch and doesn't jump from one limit to the opposite one. A
+    /// DoF isn't blended if it would exceed its limits in the window, and not for trajectories
+    /// with intermediate positions. Zero disables the blending.
+    pub replan_blending: f64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            return_error_at_maximal_duration: true,
+            maximal_duration: 7.6e3,
+            eps: f64::EPSILON,
+            discretization_tolerance: f64::EPSILON,
+            duration_rounding: DurationRounding::Up,
+            max_relaxation: 0.0,
+            clamp_target_state: false,
+            replan_tolerance: 0.0,
+            guess_step2_profile: false,
+            step2_retries: 8,
+            fallback_tolerances: Vec::new(),
+            replan_blending: 0.0,
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Record of the profile strategies attempted by the latest calculation, for debugging e.g.
+//! `ErrorExecutionTimeCalculation`. Only available with the `debug-trace` feature.
+use crate::profile::{ControlSigns, ReachedLimits};
+use crate::result::CalculationStage;
+use std::{cell::RefCell, fmt};
+
+/// Number of attempts per DoF that the trace holds. It is allocated with the calculator, so that
+/// the calculation stays free of allocations, and further attempts are dropped.
+pub const MAX_ATTEMPTS_PER_DOF: usize = 64;
+
+/// Check of a profile that rejected it
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CheckFailure {
+    /// A phase has a negative duration
+    NegativeDuration { phase: usize },
+    /// The duration is longer than supported, or not finite
+    DurationTooLong,
+    /// The phase that should hold the reached limit has no duration
+    LimitPhaseMissing { phase: usize },
+    /// The jerk exceeds its limit
+    JerkLimit,
+    /// The acceleration exceeds its limits at the end of the phase
+    AccelerationLimit { phase: usize },
+    /// The velocity exceeds its limits at the end of the phase, or where the acceleration
+    /// crosses zero within it
+    VelocityLimit { phase: usize },
+    /// The final position misses the target
+    TargetPosition,
+    /// The final velocity misses the target
+    TargetVelocity,
+    /// The final acceleration misses the target
+    TargetAcceleration,
+}
+
+impl fmt::Display for CheckFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckFailure::NegativeDuration { phase } => {
+                write!(f, "negative duration of phase {}", phase)
+            }
+            CheckFailure::DurationTooLong => write!(f, "duration too long"),
+            CheckFailure::LimitPhaseMissing { phase } => {
+                write!(f, "no duration of limit phase {}", phase)
+            }
+            CheckFailure::JerkLimit => write!(f, "jerk limit exceeded"),
+            CheckFailure::AccelerationLimit { phase } => {
+                write!(f, "acceleration limit exceeded in phase {}", phase)
+            }
+            CheckFailure::VelocityLimit { phase } => {
+                write!(f, "velocity limit exceeded in phase {}", phase)
+            }
+            CheckFailure::TargetPosition => write!(f, "target position missed"),
+            CheckFailure::TargetVelocity => write!(f, "target velocity missed"),
+            CheckFailure::TargetAcceleration => write!(f, "target acceleration missed"),
+        }
+    }
+}
+
+/// A candidate profile of a DoF and the result of its check
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ProfileAttempt {
+    pub dof: usize,
+    /// `Step1` for the extremal profiles, `Step2` for the synchronized ones, and
+    /// `Synchronization` for the profiles with the timing of the limiting DoF
+    pub stage: CalculationStage,
+    /// The step type and its method that calculated the profile, e.g.
+    /// `PositionThirdOrderStep1::time_acc0_acc1`, or `phase synchronization`
+    pub strategy: &'static str,
+    pub control_signs: ControlSigns,
+    pub limits: ReachedLimits,
+    /// The check that rejected the profile, or `None` if it is valid
+    pub failure: Option<CheckFailure>,
+}
+
+impl fmt::Display for ProfileAttempt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "DoF {} {:?} {} {:?} {:?}: ",
+            self.dof, self.stage, self.strategy, self.limits, self.control_signs
+        )?;
+        match &self.failure {
+            Some(failure) => write!(f, "{}", failure),
+            None => write!(f, "valid"),
+        }
+    }
+}
+
+struct Recorder {
+    /// Whether a calculation is recorded, so that profiles checked outside of it don't pile up
+    active: bool,
+    dof: usize,
+    stage: CalculationStage,
+    strategy: &'static str,
+    attempts: Vec<ProfileAttempt>,
+}
+
+// The checks of the profiles don't know their DoF, so the attempts are recorded per thread
+thread_local! {
+    static RECORDER: RefCell<Recorder> = const { RefCell::new(Recorder {
+        active: false,
+        dof: 0,
+        stage: CalculationStage::Step1,
+        strategy: "",
+        attempts: Vec::new(),
+    }) };
+}
+
+/// Start recording a calculation on this thread into the buffer, dropping its previous attempts
+pub(crate) fn start(mut buffer: Vec<ProfileAttempt>) {
+    buffer.clear();
+    RECORDER.with(|recorder| {
+        let mut recorder = recorder.borrow_mut();
+        recorder.active = true;
+        recorder.attempts = buffer;
+    });
+}
+
+/// Start recording the attempts of a DoF in a stage
+pub(crate) fn begin(dof: usize, stage: CalculationStage) {
+    RECORDER.with(|recorder| {
+        let mut recorder = recorder.borrow_mut();
+        recorder.dof = dof;
+        recorder.stage = stage;
+        recorder.strategy = "";
+    });
+}
+
+pub(crate) fn set_strategy(strategy: &'static str) {
+    RECORDER.with(|recorder| recorder.borrow_mut().strategy = strategy);
+}
+
+pub(crate) fn record(
+    control_signs: ControlSigns,
+    limits: ReachedLimits,
+    failure: Option<CheckFailure>,
+) {
+    RECORDER.with(|recorder| {
+        let mut recorder = recorder.borrow_mut();
+        if !recorder.active || recorder.attempts.len() == recorder.attempts.capacity() {
+            return;
+        }
+        let attempt = ProfileAttempt {
+            dof: recorder.dof,
+            stage: recorder.stage,
+            strategy: recorder.strategy,
+            control_signs,
+            limits,
+            failure,
+        };
+        recorder.attempts.push(attempt);
+    });
+}
+
+/// Stop recording and take the buffer with the attempts of the calculation
+pub(crate) fn take() -> Vec<ProfileAttempt> {
+    RECORDER.with(|recorder| {
+        let mut recorder = recorder.borrow_mut();
+        recorder.active = false;
+        std::mem::take(&mut recorder.attempts)
+    })
+}
@@ -0,0 +1,486 @@
+//! Online tracking of a streamed target state.
+
+use crate::calculator_target::{TargetCalculator, POSITION_LIMIT_PRECISION};
+use crate::error::{IgnoreErrorHandler, RuckigError, RuckigErrorHandler};
+use crate::input_parameter::InputParameter;
+use crate::output_parameter::{OutputParameter, UpdateEvent};
+use crate::result::RuckigResult;
+use crate::trajectory::Trajectory;
+use crate::util::{DataArrayOrVec, Stopwatch};
+use std::marker::PhantomData;
+
+/// Kinematic state of the target that is followed by the `Tracker`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TargetState<const DOF: usize> {
+    pub position: DataArrayOrVec<f64, DOF>,
+    pub velocity: DataArrayOrVec<f64, DOF>,
+    pub acceleration: DataArrayOrVec<f64, DOF>,
+}
+
+impl<const DOF: usize> Default for TargetState<DOF> {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl<const DOF: usize> TargetState<DOF> {
+    pub fn new(dofs: Option<usize>) -> Self {
+        Self {
+            position: DataArrayOrVec::new(dofs, 0.0),
+            velocity: DataArrayOrVec::new(dofs, 0.0),
+            acceleration: DataArrayOrVec::new(dofs, 0.0),
+        }
+    }
+}
+
+/// Largest velocity towards a position limit from which a DoF at zero acceleration can stop
+/// within the distance, with a constant acceleration phase if the velocity is large enough
+fn stop_velocity(distance: f64, a_max: f64, j_max: f64) -> f64 {
+    if distance <= 0.0 {
+        return 0.0;
+    }
+    if !j_max.is_finite() {
+        return (2.0 * a_max * distance).sqrt();
+    }
+
+    // Without reaching the acceleration limit, the distance is v * sqrt(v / j_max)
+    let v = (distance * distance * j_max).cbrt();
+    if !a_max.is_finite() || v <= a_max * a_max / j_max {
+        return v;
+    }
+    // Otherwise it is v^2 / (2 a_max) + v a_max / (2 j_max)
+    let b = a_max * a_max / j_max;
+    0.5 * (-b + (b * b + 8.0 * a_max * distance).sqrt())
+}
+
+/// Whether a DoF moving with the velocity and acceleration towards a position limit at the
+/// distance can't stop at the limit anymore after the next control cycle
+fn passes_limit(v: f64, a: f64, distance: f64, a_max: f64, j_max: f64, delta_time: f64) -> bool {
+    let mut distance = distance - v.max(0.0) * delta_time;
+    let mut v = v;
+    if a > 0.0 && j_max.is_finite() {
+        // The acceleration towards the limit is reduced to zero first
+        distance -= v * a / j_max + a * a * a / (3.0 * j_max * j_max);
+        v += a * a / (2.0 * j_max);
+    }
+    v > stop_velocity(distance, a_max, j_max)
+}
+
+/// Follows a target state that is streamed anew in every control cycle, e.g. from a sensor.
+///
+/// The target may change at every update and may violate the kinematic limits. It is first
+/// clamped to the closest reachable state, then the trajectory is planned relative to the target
+/// moving with constant acceleration. This way, noise that puts the target slightly behind does
+/// not lead to a detour, and the velocity, acceleration and jerk limits hold exactly. If the
+/// target can't be caught up with, e.g. as it moves at the velocity limit, or if it would pass a
+/// velocity or position limit while accelerating, the tracker falls back to a state-to-state
+/// trajectory towards the current target state. A target that changes by at most
+/// `Settings::replan_tolerance` from its motion at the last calculation doesn't lead to a new
+/// calculation, so that the tracker ignores its noise. The trajectory of the output is not
+/// changed, as the planned motion is relative to the target. All buffers are allocated at
+/// construction.
+#[derive(Debug)]
+pub struct Tracker<const DOF: usize, E: RuckigErrorHandler> {
+    input: InputParameter<DOF>,
+    target: TargetState<DOF>,
+    trajectory: Trajectory<DOF>,
+    /// Input of the last update, whose current state is the state of the output
+    current_input: InputParameter<DOF>,
+    current_input_initialized: bool,
+    /// Whether the trajectory of the last calculation is relative to the target
+    is_relative: bool,
+    pub calculator: TargetCalculator<DOF>,
+    pub degrees_of_freedom: usize,
+    pub delta_time: f64,
+    _error_handler: PhantomData<E>,
+}
+
+impl<const DOF: usize, E: RuckigErrorHandler> Default for Tracker<DOF, E> {
+    fn default() -> Self {
+        Self::new(None, 0.01)
+    }
+}
+
+impl<const DOF: usize, E: RuckigErrorHandler> Tracker<DOF, E> {
+    pub fn new(degrees_of_freedom: Option<usize>, delta_time: f64) -> Self {
+        let mut input = InputParameter::new(degrees_of_freedom);
+        input.min_velocity = Some(DataArrayOrVec::new(degrees_of_freedom, 0.0));
+        input.min_acceleration = Some(DataArrayOrVec::new(degrees_of_freedom, 0.0));
+
+        Self {
+            input,
+            target: TargetState::new(degrees_of_freedom),
+            trajectory: Trajectory::new(degrees_of_freedom),
+            current_input: InputParameter::new(degrees_of_freedom),
+            current_input_initialized: false,
+            is_relative: false,
+            calculator: TargetCalculator::new(degrees_of_freedom),
+            degrees_of_freedom: degrees_of_freedom.unwrap_or(DOF),
+            delta_time,
+            _error_handler: PhantomData,
+        }
+    }
+
+    /// Copy the limits and settings that are independent of the target into the scratch input
+    fn prepare_input(&mut self, input: &InputParameter<DOF>) {
+        let scratch = &mut self.input;
+        scratch.degrees_of_freedom = input.degrees_of_freedom;
+        scratch.control_interface = input.control_interface.clone();
+        scratch.synchronization = input.synchronization.clone();
+        scratch.duration_discretization = input.duration_discretization.clone();
+        scratch.max_jerk.clone_from(&input.max_jerk);
+        scratch.enabled.clone_from(&input.enabled);
+        scratch
+            .per_dof_control_interface
+            .clone_from(&input.per_dof_control_interface);
+        scratch
+            .per_dof_synchronization
+            .clone_from(&input.per_dof_synchronization);
+        scratch.per_dof_priority.clone_from(&input.per_dof_priority);
+        scratch.per_dof_group.clone_from(&input.per_dof_group);
+        scratch.minimum_duration = None;
+    }
+
+    /// Clamp the target to the closest state that can be reached within the kinematic limits:
+    /// the position within the position limits, the velocity within the velocity limits, and the
+    /// acceleration such that the velocity limits can still be kept. Towards a position limit,
+    /// the velocity and acceleration are clamped such that the target can still stop at the limit
+    /// after the next control cycle.
+    fn set_reachable_target(&mut self, input: &InputParameter<DOF>, target: &TargetState<DOF>) {
+        for dof in 0..self.degrees_of_freedom {
+            let p_max = input
+                .max_position
+                .as_ref()
+                .map_or(f64::INFINITY, |max_position| max_position[dof]);
+            let p_min = input
+                .min_position
+                .as_ref()
+                .map_or(f64::NEG_INFINITY, |min_position| min_position[dof]);
+            let (mut vf, mut af) =
+                input.reachable_target_state(dof, target.velocity[dof], target.acceleration[dof]);
+            let position = target.position[dof].max(p_min).min(p_max);
+
+            let a_max = input.max_acceleration[dof];
+            let a_min = input
+                .min_acceleration
+                .as_ref()
+                .map_or(-a_max, |min_acceleration| min_acceleration[dof]);
+            let j_max = input.max_jerk[dof];
+            let dt = self.delta_time;
+            if passes_limit(vf, af, p_max - position, -a_min, j_max, dt) {
+                vf = stop_velocity(p_max - position - vf * dt, -a_min, j_max).min(vf);
+                af = af.min(0.0);
+            } else if passes_limit(-vf, -af, position - p_min, a_max, j_max, dt) {
+                vf = (-stop_velocity(position - p_min + vf * dt, a_max, j_max)).max(vf);
+                af = af.max(0.0);
+            }
+
+            self.target.position[dof] = position;
+            self.target.velocity[dof] = vf;
+            self.target.acceleration[dof] = af;
+        }
+    }
+
+    /// Set up the scratch input relative to the target moving with constant acceleration. The
+    /// velocity limits are shifted by the initial velocity of the target only, see
+    /// `keeps_velocity_limits`.
+    fn set_relative_input(&mut self, input: &InputParameter<DOF>) {
+        let scratch = &mut self.input;
+        scratch.max_position = None;
+        scratch.min_position = None;
+        for dof in 0..self.degrees_of_freedom {
+            let v_max = input.max_velocity[dof];
+            let v_min = input
+                .min_velocity
+                .as_ref()
+                .map_or(-v_max, |min_velocity| min_velocity[dof]);
+            let a_max = input.max_acceleration[dof];
+            let a_min = input
+                .min_acceleration
+                .as_ref()
+                .map_or(-a_max, |min_acceleration| min_acceleration[dof]);
+            let vt = self.target.velocity[dof];
+            let at = self.target.acceleration[dof];
+
+            scratch.current_position[dof] = input.current_position[dof] - self.target.position[dof];
+            scratch.current_velocity[dof] = input.current_velocity[dof] - vt;
+            scratch.current_acceleration[dof] = input.current_acceleration[dof] - at;
+            scratch.target_position[dof] = 0.0;
+            scratch.target_velocity[dof] = 0.0;
+            scratch.target_acceleration[dof] = 0.0;
+            scratch.max_velocity[dof] = v_max - vt;
+            scratch.max_acceleration[dof] = a_max - at;
+            if let Some(min_velocity) = &mut scratch.min_velocity {
+                min_velocity[dof] = v_min - vt;
+            }
+            if let Some(min_acceleration) = &mut scratch.min_acceleration {
+                min_acceleration[dof] = a_min - at;
+            }
+        }
+    }
+
+    /// Check the velocity limits of the relative trajectory in the absolute frame. The velocity
+    /// of an accelerating target changes over time, so it is added to the velocity of each phase
+    /// and of the final state, which the trajectory keeps after a DoF reached its target.
+    fn keeps_velocity_limits(&self, input: &InputParameter<DOF>) -> bool {
+        const V_TOLERANCE: f64 = 1e-9;
+
+        let profiles = &self.trajectory.get_profiles()[0];
+        (0..self.degrees_of_freedom).all(|dof| {
+            let vt = self.target.velocity[dof];
+            let at = self.target.acceleration[dof];
+            if at == 0.0 {
+                return true;
+            }
+
+            let v_max = input.max_velocity[dof] + V_TOLERANCE;
+            let v_min = input
+                .min_velocity
+                .as_ref()
+                .map_or(-input.max_velocity[dof], |min_velocity| min_velocity[dof])
+                - V_TOLERANCE;
+            let within_limits = |v: f64| v_min <= v && v <= v_max;
+
+            let p = &profiles[dof];
+            let brake = (0..2).map(|i| (p.brake.t[i], p.brake.v[i], p.brake.a[i], p.brake.j[i]));
+            let phases = (0..7).map(|i| (p.t[i], p.v[i], p.a[i], p.j[i]));
+            let mut t_start = 0.0;
+            for (duration, v, a, j) in brake.chain(phases).filter(|phase| phase.0 > 0.0) {
+                let velocity = |t: f64| vt + at * (t_start + t) + v + (a + 0.5 * j * t) * t;
+                let t_extremum = if j != 0.0 { -(a + at) / j } else { -1.0 };
+                let is_extremum_inside = 0.0 < t_extremum && t_extremum < duration;
+                if !within_limits(velocity(0.0))
+                    || !within_limits(velocity(duration))
+                    || (is_extremum_inside && !within_limits(velocity(t_extremum)))
+                {
+                    return false;
+                }
+                t_start += duration;
+            }
+            within_limits(vt + at * self.trajectory.get_duration())
+        })
+    }
+
+    /// Check the position limits of the relative trajectory in the absolute frame. The motion of
+    /// the target is added to the start state of each phase, which keeps its jerk, so that the
+    /// extrema of the shifted profile are the extrema of the absolute motion.
+    fn keeps_position_limits(&self, input: &InputParameter<DOF>) -> bool {
+        if input.max_position.is_none() && input.min_position.is_none() {
+            return true;
+        }
+
+        let profiles = &self.trajectory.get_profiles()[0];
+        (0..self.degrees_of_freedom).all(|dof| {
+            let pt = self.target.position[dof];
+            let vt = self.target.velocity[dof];
+            let at = self.target.acceleration[dof];
+            let shift = |t: f64, p: &mut f64, v: &mut f64, a: &mut f64| {
+                *p += pt + (vt + 0.5 * at * t) * t;
+                *v += vt + at * t;
+                *a += at;
+            };
+
+            let mut profile = profiles[dof];
+            let mut t_start = profile.pre_dwell;
+            for i in 0..2 {
+                let brake = &mut profile.brake;
+                shift(t_start, &mut brake.p[i], &mut brake.v[i], &mut brake.a[i]);
+                t_start += brake.t[i];
+            }
+            for i in 0..8 {
+                let t = t_start + if i > 0 { profile.t_sum[i - 1] } else { 0.0 };
+                shift(t, &mut profile.p[i], &mut profile.v[i], &mut profile.a[i]);
+            }
+            let t_end = t_start + profile.t_sum[6];
+            shift(t_end, &mut profile.pf, &mut profile.vf, &mut profile.af);
+
+            // After the relative trajectory, the DoF moves with the target until it is sampled
+            let p_max = input
+                .max_position
+                .as_ref()
+                .map_or(f64::INFINITY, |v| v[dof]);
+            let p_min = input
+                .min_position
+                .as_ref()
+                .map_or(f64::NEG_INFINITY, |v| v[dof]);
+            let target_position = |t: f64| pt + (vt + 0.5 * at * t) * t;
+            let t_extremum = if at != 0.0 { -vt / at } else { -1.0 };
+            let is_extremum_inside = t_end < t_extremum && t_extremum < self.delta_time;
+            let follows_within_limits = [self.delta_time.max(t_end), t_extremum]
+                .into_iter()
+                .take(if is_extremum_inside { 2 } else { 1 })
+                .map(target_position)
+                .all(|p| {
+                    p_min - POSITION_LIMIT_PRECISION <= p && p <= p_max + POSITION_LIMIT_PRECISION
+                });
+            follows_within_limits
+                && TargetCalculator::is_within_position_limits(input, dof, &profile)
+        })
+    }
+
+    /// Whether the target differs by at most the tolerance from the motion of the target of the
+    /// last calculation at the given time since then
+    fn is_target_within_tolerance(
+        &self,
+        target: &TargetState<DOF>,
+        time: f64,
+        tolerance: f64,
+    ) -> bool {
+        (0..self.degrees_of_freedom).all(|dof| {
+            let pt = self.target.position[dof];
+            let vt = self.target.velocity[dof];
+            let at = self.target.acceleration[dof];
+            (target.position[dof] - (pt + (vt + 0.5 * at * time) * time)).abs() <= tolerance
+                && (target.velocity[dof] - (vt + at * time)).abs() <= tolerance
+                && (target.acceleration[dof] - at).abs() <= tolerance
+        })
+    }
+
+    /// Set up the scratch input for a state-to-state trajectory towards the current target state
+    fn set_absolute_input(&mut self, input: &InputParameter<DOF>) {
+        let scratch = &mut self.input;
+        scratch.current_position.clone_from(&input.current_position);
+        scratch.current_velocity.clone_from(&input.current_velocity);
+        scratch
+            .current_acceleration
+            .clone_from(&input.current_acceleration);
+        scratch.target_position.clone_from(&self.target.position);
+        scratch.target_velocity.clone_from(&self.target.velocity);
+        scratch
+            .target_acceleration
+            .clone_from(&self.target.acceleration);
+        scratch.max_position.clone_from(&input.max_position);
+        scratch.min_position.clone_from(&input.min_position);
+        scratch.max_velocity.clone_from(&input.max_velocity);
+        scratch.max_acceleration.clone_from(&input.max_acceleration);
+        for dof in 0..self.degrees_of_freedom {
+            if let Some(min_velocity) = &mut scratch.min_velocity {
+                min_velocity[dof] = input
+                    .min_velocity
+                    .as_ref()
+                    .map_or(-input.max_velocity[dof], |min_velocity| min_velocity[dof]);
+            }
+            if let Some(min_acceleration) = &mut scratch.min_acceleration {
+                min_acceleration[dof] = input
+                    .min_acceleration
+                    .as_ref()
+                    .map_or(-input.max_acceleration[dof], |min_acceleration| {
+                        min_acceleration[dof]
+                    });
+            }
+        }
+    }
+
+    /// Calculate a trajectory from the current state of the input towards the given target
+    /// state and write the state after one control cycle into the output. Returns `Finished`
+    /// once the target is followed exactly.
+    pub fn update(
+        &mut self,
+        target: &TargetState<DOF>,
+        input: &InputParameter<DOF>,
+        output: &mut OutputParameter<DOF>,
+    ) -> Result<RuckigResult, RuckigError> {
+        let stopwatch = Stopwatch::start();
+
+        if self.degrees_of_freedom == 0
+            && (self.degrees_of_freedom != input.degrees_of_freedom
+                || self.degrees_of_freedom != output.degrees_of_freedom)
+        {
+            return E::handle_calculator_error(RuckigError::DofMismatch);
+        }
+
+        for dof in 0..self.degrees_of_freedom {
+            if target.position[dof].is_nan()
+                || target.velocity[dof].is_nan()
+                || target.acceleration[dof].is_nan()
+            {
+                E::handle_validation_error(RuckigError::InvalidTargetState { dof })?;
+                return Ok(RuckigResult::ErrorInvalidInput);
+            }
+        }
+
+        // The trajectory relative to the target continues as long as the target follows its
+        // motion at the last calculation within the tolerance
+        let replan_tolerance = self.calculator.settings.replan_tolerance;
+        let continues_trajectory = self.current_input_initialized
+            && self.is_relative
+            && replan_tolerance > 0.0
+            && *input == self.current_input
+            && self.is_target_within_tolerance(target, output.time, replan_tolerance);
+
+        output.new_section = 0;
+        output.did_section_change = false;
+        output.events.clear();
+        if continues_trajectory {
+            output.new_calculation = false;
+            output.time += self.delta_time;
+        } else {
+            self.current_input_initialized = false;
+            self.prepare_input(input);
+            self.set_reachable_target(input, target);
+            self.set_relative_input(input);
+
+            let relative_result = self.calculator.calculate::<IgnoreErrorHandler>(
+                &self.input,
+                &mut self.trajectory,
+                self.delta_time,
+            )?;
+            self.is_relative = relative_result == RuckigResult::Working
+                && self.keeps_velocity_limits(input)
+                && self.keeps_position_limits(input);
+            if !self.is_relative {
+                self.set_absolute_input(input);
+                if !self.input.validate::<E>(false, true)? {
+                    return Ok(RuckigResult::ErrorInvalidInput);
+                }
+
+                let result = self.calculator.calculate::<E>(
+                    &self.input,
+                    &mut self.trajectory,
+                    self.delta_time,
+                )?;
+                if result != RuckigResult::Working {
+                    return Ok(result);
+                }
+            }
+
+            output.new_calculation = true;
+            output.events.push(UpdateEvent::NewCalculation);
+            output.time = self.delta_time;
+            self.current_input.clone_from(input);
+            self.current_input_initialized = true;
+        }
+        self.trajectory.at_time(
+            output.time,
+            &mut Some(&mut output.new_position),
+            &mut Some(&mut output.new_velocity),
+            &mut Some(&mut output.new_acceleration),
+            &mut Some(&mut output.new_jerk),
+            &mut None,
+        );
+        if self.is_relative {
+            let t = output.time;
+            for dof in 0..self.degrees_of_freedom {
+                let (pt, vt, at) = (
+                    self.target.position[dof],
+                    self.target.velocity[dof],
+                    self.target.acceleration[dof],
+                );
+                output.new_position[dof] += pt + (vt + 0.5 * at * t) * t;
+                output.new_velocity[dof] += vt + at * t;
+                output.new_acceleration[dof] += at;
+            }
+        }
+
+        output.calculation_duration = stopwatch.elapsed_microseconds();
+        output.pass_to_input(&mut self.current_input);
+
+        if output.time > self.trajectory.get_duration() {
+            output.events.push(UpdateEvent::Finished);
+            return Ok(RuckigResult::Finished);
+        }
+
+        Ok(RuckigResult::Working)
+    }
+}
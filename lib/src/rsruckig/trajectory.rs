@@ -1,14 +1,172 @@
+use crate::brake::BrakeProfile;
+use crate::calculator_target::calculate_step2_profile;
+use crate::error::{DofState, RuckigError, RuckigErrorHandler};
+use crate::input_parameter::{ControlInterface, InputParameter, Synchronization};
 use crate::profile::Bound;
-use crate::profile::Profile;
-use crate::util::{integrate, DataArrayOrVec};
+use crate::profile::{Profile, ProfileInfo};
+use crate::result::RuckigResult;
+use crate::roots;
+use crate::util::{
+    circular_distance, duration_from_secs, integrate, integrate_with_snap, DataArrayOrVec,
+};
+use crate::validation::{InputQuantity, LimitViolation};
+use std::time::Duration;
+
+/// Relative tolerance of the limits and target states in `Trajectory::check_limits`
+pub const CHECK_TOLERANCE: f64 = 1e-8;
+
+/// Number of phases with a constant jerk that a phase with a snap, i.e. of a blending
+/// pre-trajectory, is split into when a trajectory is built from its segments
+const SNAP_SEGMENT_PIECES: usize = 16;
+
+/// Kinematic state of a trajectory at a given time
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrajectoryState<const DOF: usize> {
+    pub position: DataArrayOrVec<f64, DOF>,
+    pub velocity: DataArrayOrVec<f64, DOF>,
+    pub acceleration: DataArrayOrVec<f64, DOF>,
+    pub jerk: DataArrayOrVec<f64, DOF>,
+    /// Index of the section, equal to the number of sections after the end of the trajectory
+    pub section: usize,
+}
+
+/// Position polynomial of a single phase of a DoF. It is cubic, except for the phases of a
+/// snap-limited trajectory, whose snap is constant within each phase.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PolynomialSegment {
+    /// Start time of the phase within the trajectory
+    pub t_start: f64,
+    /// End time of the phase within the trajectory
+    pub t_end: f64,
+    /// Coefficients of the position `c[0] + c[1] t + c[2] t^2 + c[3] t^3 + c[4] t^4`, with the
+    /// time `t` relative to the start of the phase
+    pub coefficients: [f64; 5],
+}
+
+impl PolynomialSegment {
+    fn new(t_start: f64, duration: f64, p: f64, v: f64, a: f64, j: f64) -> Self {
+        Self::with_snap(t_start, duration, p, v, a, j, 0.0)
+    }
+
+    fn with_snap(t_start: f64, duration: f64, p: f64, v: f64, a: f64, j: f64, s: f64) -> Self {
+        Self {
+            t_start,
+            t_end: t_start + duration,
+            coefficients: [p, v, a / 2.0, j / 6.0, s / 24.0],
+        }
+    }
+
+    /// Position at the time `t` relative to the start of the phase
+    pub fn position(&self, t: f64) -> f64 {
+        let c = &self.coefficients;
+        c[0] + t * (c[1] + t * (c[2] + t * (c[3] + t * c[4])))
+    }
+
+    /// Velocity at the time `t` relative to the start of the phase
+    pub fn velocity(&self, t: f64) -> f64 {
+        let c = &self.coefficients;
+        c[1] + t * (2.0 * c[2] + t * (3.0 * c[3] + t * 4.0 * c[4]))
+    }
+
+    /// Acceleration at the time `t` relative to the start of the phase
+    pub fn acceleration(&self, t: f64) -> f64 {
+        let c = &self.coefficients;
+        2.0 * c[2] + t * (6.0 * c[3] + t * 12.0 * c[4])
+    }
+
+    /// Jerk at the time `t` relative to the start of the phase
+    pub fn jerk(&self, t: f64) -> f64 {
+        let c = &self.coefficients;
+        6.0 * c[3] + t * 24.0 * c[4]
+    }
+
+    /// Constant snap of the phase
+    pub fn snap(&self) -> f64 {
+        24.0 * self.coefficients[4]
+    }
+
+    /// Integral of the position from the start of the phase to the time `t` relative to it
+    fn position_integral(&self, t: f64) -> f64 {
+        let c = &self.coefficients;
+        t * (c[0] + t * (c[1] / 2.0 + t * (c[2] / 3.0 + t * (c[3] / 4.0 + t * c[4] / 5.0))))
+    }
+
+    /// Times relative to the start of the phase where the velocity changes its sign, in
+    /// increasing order
+    fn velocity_sign_changes(&self) -> impl Iterator<Item = f64> {
+        let c = &self.coefficients;
+        let duration = self.t_end - self.t_start;
+        roots::solve_cub(4.0 * c[4], 3.0 * c[3], 2.0 * c[2], c[1])
+            .into_iter()
+            .filter(move |&t| 0.0 < t && t < duration)
+    }
+
+    /// Distance travelled within the phase
+    fn path_length(&self) -> f64 {
+        let mut length = 0.0;
+        let mut previous_position = self.position(0.0);
+        for t in self
+            .velocity_sign_changes()
+            .chain(std::iter::once(self.t_end - self.t_start))
+        {
+            let position = self.position(t);
+            length += (position - previous_position).abs();
+            previous_position = position;
+        }
+        length
+    }
+}
+
+/// Synchronization that the calculation applied to a DoF
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DofSynchronization {
+    /// `Phase` if the DoF is phase synchronized, `Time` if it is time synchronized, also after a
+    /// failed phase synchronization, `TimeIfNecessary` if it keeps its time-optimal profile as
+    /// it comes to rest, and `None` if it isn't synchronized, e.g. as it is disabled or
+    /// prioritized
+    pub synchronization: Synchronization,
+    /// Whether the DoF determines the duration of the trajectory, or of its group
+    pub is_limiting: bool,
+}
+
+impl Default for DofSynchronization {
+    fn default() -> Self {
+        Self {
+            synchronization: Synchronization::None,
+            is_limiting: false,
+        }
+    }
+}
 
 // We'll use Vec<T> instead of CustomVector<T, DOF>
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Trajectory<const DOF: usize> {
+    /// Profiles of each section. Only the first `cumulative_times.len()` sections are active,
+    /// the remaining ones are pre-allocated storage for intermediate waypoints.
     pub profiles: Vec<DataArrayOrVec<Profile, DOF>>,
     pub duration: f64,
-    pub cumulative_times: DataArrayOrVec<f64, DOF>,
+    /// Cumulative end time of each active section
+    pub cumulative_times: Vec<f64>,
     pub independent_min_durations: DataArrayOrVec<f64, DOF>,
+    pub(crate) synchronizations: DataArrayOrVec<DofSynchronization, DOF>,
+    pub(crate) discretization_remainder: f64,
+    /// Duration of the calculation of the trajectory in [µs]
+    pub(crate) calculation_duration: f64,
+    /// Periods of circular DoFs, into which the sampled positions are wrapped
+    pub(crate) periods: Option<DataArrayOrVec<Option<f64>, DOF>>,
+    /// Window of the moving average of a snap-limited trajectory, or zero
+    pub(crate) snap_window: f64,
+    /// Time by which the window of a snap-limited trajectory ends after the sampled time, so
+    /// that the profiles describe the underlying trajectory from this long before the start.
+    /// It is only non-zero for cropped and reversed snap-limited trajectories.
+    pub(crate) snap_lead: f64,
+    /// Jerk with which the underlying trajectory of a snap-limited DoF continues backwards before
+    /// its start, so that the moving average starts with the current jerk
+    pub(crate) snap_start_jerk: DataArrayOrVec<f64, DOF>,
     position_extrema: DataArrayOrVec<Bound, DOF>,
     degrees_of_freedom: usize,
 }
@@ -18,8 +176,15 @@ impl<const DOF: usize> Default for Trajectory<DOF> {
         Self {
             profiles: vec![DataArrayOrVec::new(None, Profile::default())],
             duration: Default::default(),
-            cumulative_times: DataArrayOrVec::new(None, 0.0),
+            cumulative_times: vec![0.0],
             independent_min_durations: DataArrayOrVec::new(None, 0.0),
+            synchronizations: DataArrayOrVec::new(None, DofSynchronization::default()),
+            discretization_remainder: 0.0,
+            calculation_duration: 0.0,
+            periods: None,
+            snap_window: 0.0,
+            snap_lead: 0.0,
+            snap_start_jerk: DataArrayOrVec::new(None, 0.0),
             position_extrema: DataArrayOrVec::new(None, Bound::default()),
             degrees_of_freedom: DOF,
         }
@@ -28,47 +193,202 @@ impl<const DOF: usize> Default for Trajectory<DOF> {
 
 impl<const DOF: usize> Trajectory<DOF> {
     pub fn new(dofs: Option<usize>) -> Self {
+        Self::new_with_waypoints(0, dofs)
+    }
+
+    /// Create a trajectory with pre-allocated storage for up to `max_number_of_waypoints`
+    /// intermediate positions, so that calculating a trajectory with waypoints does not allocate.
+    pub fn new_with_waypoints(max_number_of_waypoints: usize, dofs: Option<usize>) -> Self {
+        let mut cumulative_times = Vec::with_capacity(max_number_of_waypoints + 1);
+        cumulative_times.push(0.0);
         Self {
-            profiles: vec![DataArrayOrVec::<Profile, DOF>::new(
-                dofs,
-                Profile::default(),
-            )],
+            profiles: vec![
+                DataArrayOrVec::<Profile, DOF>::new(dofs, Profile::default());
+                max_number_of_waypoints + 1
+            ],
             duration: 0.0,
-            cumulative_times: DataArrayOrVec::new(dofs, 0.0),
+            cumulative_times,
             independent_min_durations: DataArrayOrVec::new(dofs, 0.0),
+            synchronizations: DataArrayOrVec::new(dofs, DofSynchronization::default()),
+            discretization_remainder: 0.0,
+            calculation_duration: 0.0,
+            periods: None,
+            snap_window: 0.0,
+            snap_lead: 0.0,
+            snap_start_jerk: DataArrayOrVec::new(dofs, 0.0),
             position_extrema: DataArrayOrVec::new(dofs, Bound::default()),
             degrees_of_freedom: dofs.unwrap_or(DOF),
         }
     }
-    pub fn state_to_integrate_from<F>(
-        &self,
-        time: f64,
-        new_section: &mut usize,
-        mut set_integrate: F,
-    ) where
-        F: FnMut(usize, f64, f64, f64, f64, f64),
-    {
-        let degrees_of_freedom = self.profiles[0].len();
 
-        if time >= self.duration {
-            *new_section = self.profiles.len();
-            let profiles_dof = &self.profiles.last().unwrap();
-            for dof in 0..degrees_of_freedom {
-                let t_pre = if self.profiles.len() > 1 {
-                    self.cumulative_times[self.cumulative_times.len() - 2]
-                } else {
-                    profiles_dof[dof].brake.duration
-                };
-                let t_diff = time - (t_pre + profiles_dof[dof].t_sum.last().unwrap());
-                set_integrate(
-                    dof,
-                    t_diff,
-                    *profiles_dof[dof].p.last().unwrap(),
-                    *profiles_dof[dof].v.last().unwrap(),
-                    *profiles_dof[dof].a.last().unwrap(),
+    /// Number of active sections, i.e. the number of intermediate positions plus one
+    pub fn get_number_of_sections(&self) -> usize {
+        self.cumulative_times.len()
+    }
+
+    /// The period of a circular DoF
+    fn period(&self, dof: usize) -> Option<f64> {
+        self.periods.as_ref().and_then(|periods| periods[dof])
+    }
+
+    /// Wrap the position of a circular DoF into `[0, period)`
+    fn wrap_position(&self, dof: usize, position: f64) -> f64 {
+        match self.period(dof) {
+            Some(period) => position.rem_euclid(period),
+            None => position,
+        }
+    }
+
+    /// Set the number of active sections. Storage is only allocated if the number exceeds
+    /// the pre-allocated sections.
+    pub(crate) fn resize(&mut self, number_of_sections: usize) {
+        while self.profiles.len() < number_of_sections {
+            self.profiles.push(DataArrayOrVec::new(
+                Some(self.degrees_of_freedom),
+                Profile::default(),
+            ));
+        }
+        self.cumulative_times.resize(number_of_sections, 0.0);
+    }
+
+    /// Hold the final state of the last section for the given time after the motion
+    pub(crate) fn extend_by(&mut self, hold_duration: f64) {
+        self.duration += hold_duration;
+        if let Some(section_end) = self.cumulative_times.last_mut() {
+            *section_end += hold_duration;
+        }
+    }
+
+    /// Smooth a trajectory to rest by a moving average, whose window is long enough that the
+    /// snap, i.e. the change of the jerk within the window divided by its duration, is within the
+    /// limit of each enabled DoF. The trajectory is extended by the window. A trajectory that
+    /// doesn't start at rest was planned from its current state led by the given window, see
+    /// `InputParameter::lead_snap_window`, and continues backwards with the current jerk.
+    pub(crate) fn limit_snap(
+        &mut self,
+        max_snap: &DataArrayOrVec<f64, DOF>,
+        enabled: &DataArrayOrVec<bool, DOF>,
+        leading_window: Option<f64>,
+        current_jerk: &DataArrayOrVec<f64, DOF>,
+    ) {
+        let window = match leading_window {
+            Some(window) => {
+                self.snap_start_jerk.copy_from_slice(current_jerk);
+                window
+            }
+            None => {
+                self.snap_start_jerk.fill(0.0);
+                let mut window: f64 = 0.0;
+                for dof in (0..self.degrees_of_freedom).filter(|&dof| enabled[dof]) {
+                    // The jerk is zero at rest before and after the motion
+                    let (j_min, j_max) =
+                        self.profile_segments(dof)
+                            .fold((0.0, 0.0), |(j_min, j_max), segment| {
+                                let j = segment.jerk(0.0);
+                                (j.min(j_min), j.max(j_max))
+                            });
+                    window = window.max((j_max - j_min) / max_snap[dof]);
+                }
+                window
+            }
+        };
+        if window > 0.0 {
+            self.extend_by(window);
+            self.snap_window = window;
+        }
+    }
+
+    /// Integral of the position of a DoF of the underlying trajectory from zero to the time, and
+    /// the position, velocity, acceleration and jerk at the time. Before the motion, the DoF
+    /// continues backwards with its start jerk, i.e. it is at rest for a trajectory from rest, and
+    /// afterwards it continues with its final state and zero jerk.
+    fn position_integral(&self, dof: usize, time: f64) -> (f64, f64, f64, f64, f64) {
+        let number_of_sections = self.get_number_of_sections();
+        let p = &self.profiles[number_of_sections - 1][dof];
+        let mut integral = 0.0;
+        let mut end = PolynomialSegment::new(0.0, 0.0, p.p[7], p.v[7], p.a[7], 0.0);
+        for segment in self.profile_segments(dof) {
+            let duration = segment.t_end - segment.t_start;
+            let t = time - segment.t_start;
+            if t < 0.0 {
+                let before = PolynomialSegment::new(
                     0.0,
+                    0.0,
+                    segment.position(0.0),
+                    segment.velocity(0.0),
+                    segment.acceleration(0.0),
+                    self.snap_start_jerk[dof],
+                );
+                return (
+                    before.position_integral(time),
+                    before.position(time),
+                    before.velocity(time),
+                    before.acceleration(time),
+                    before.jerk(time),
+                );
+            }
+            if t < duration {
+                integral += segment.position_integral(t);
+                return (
+                    integral,
+                    segment.position(t),
+                    segment.velocity(t),
+                    segment.acceleration(t),
+                    segment.jerk(t),
                 );
             }
+            integral += segment.position_integral(duration);
+            end = PolynomialSegment::new(
+                segment.t_end,
+                0.0,
+                segment.position(duration),
+                segment.velocity(duration),
+                segment.acceleration(duration),
+                0.0,
+            );
+        }
+        let t = time - end.t_start;
+        (
+            integral + end.position_integral(t),
+            end.position(t),
+            end.velocity(t),
+            end.acceleration(t),
+            0.0,
+        )
+    }
+
+    /// Kinematic state of a DoF of a snap-limited trajectory, i.e. the moving average of its
+    /// underlying third-order profiles and its derivatives, including the snap. The position
+    /// isn't wrapped.
+    fn smoothed_state(&self, dof: usize, time: f64) -> (f64, f64, f64, f64, f64) {
+        let window = self.snap_window;
+        let (integral, p, v, a, j) = self.position_integral(dof, time + self.snap_lead);
+        let (integral_before, p_before, v_before, a_before, j_before) =
+            self.position_integral(dof, time + self.snap_lead - window);
+        (
+            (integral - integral_before) / window,
+            (p - p_before) / window,
+            (v - v_before) / window,
+            (a - a_before) / window,
+            (j - j_before) / window,
+        )
+    }
+
+    /// Kinematic state of a DoF of a snap-limited trajectory, see `smoothed_state`
+    fn snap_limited_state(&self, dof: usize, time: f64) -> (f64, f64, f64, f64) {
+        let (p, v, a, j, _) = self.smoothed_state(dof, time);
+        (self.wrap_position(dof, p), v, a, j)
+    }
+
+    pub fn state_to_integrate_from<F>(&self, time: f64, new_section: &mut usize, set_integrate: F)
+    where
+        F: FnMut(usize, f64, f64, f64, f64, f64),
+    {
+        let number_of_sections = self.get_number_of_sections();
+
+        if time >= self.duration {
+            *new_section = number_of_sections;
+            self.state_to_integrate_from_end(time, set_integrate);
             return;
         }
 
@@ -78,30 +398,80 @@ impl<const DOF: usize> Trajectory<DOF> {
             .position(|&t| t > time)
             .unwrap_or(self.cumulative_times.len());
         *new_section = new_section_index;
+        self.state_to_integrate_from_section(time, new_section_index, set_integrate);
+    }
+
+    /// State to integrate from after the end of the trajectory
+    fn state_to_integrate_from_end<F>(&self, time: f64, mut set_integrate: F)
+    where
+        F: FnMut(usize, f64, f64, f64, f64, f64),
+    {
+        let degrees_of_freedom = self.profiles[0].len();
+        let number_of_sections = self.get_number_of_sections();
+        let profiles_dof = &self.profiles[number_of_sections - 1];
+        for dof in 0..degrees_of_freedom {
+            let t_pre = if number_of_sections > 1 {
+                self.cumulative_times[self.cumulative_times.len() - 2]
+            } else {
+                profiles_dof[dof].pre_dwell + profiles_dof[dof].brake.duration
+            };
+            let t_diff = time - (t_pre + profiles_dof[dof].t_sum.last().unwrap());
+            set_integrate(
+                dof,
+                t_diff,
+                *profiles_dof[dof].p.last().unwrap(),
+                *profiles_dof[dof].v.last().unwrap(),
+                *profiles_dof[dof].a.last().unwrap(),
+                0.0,
+            );
+        }
+    }
+
+    /// State to integrate from within the given section
+    fn state_to_integrate_from_section<F>(&self, time: f64, section: usize, mut set_integrate: F)
+    where
+        F: FnMut(usize, f64, f64, f64, f64, f64),
+    {
         let mut t_diff = time;
-        if new_section_index > 0 {
-            t_diff -= self.cumulative_times[new_section_index - 1];
+        if section > 0 {
+            t_diff -= self.cumulative_times[section - 1];
         }
 
-        for dof in 0..degrees_of_freedom {
-            let p = &self.profiles[*new_section][dof];
+        // Iterate over the profiles as a slice, as indexing into DataArrayOrVec matches its
+        // variant at every access. The DoFs are integrated one by one: the lookup of their phases
+        // dominates, and gathering them into SIMD lanes made the `at_time` benchmark slower.
+        let profiles: &[Profile] = &self.profiles[section];
+        for (dof, p) in profiles.iter().enumerate() {
             let mut t_diff_dof = t_diff;
 
+            // Pre-dwell in the start state, i.e. of the brake pre-trajectory if there is one
+            if section == 0 && p.pre_dwell > 0.0 {
+                if t_diff_dof < p.pre_dwell {
+                    let (position, velocity, acceleration) = p.get_start_state();
+                    set_integrate(dof, 0.0, position, velocity, acceleration, 0.0);
+                    continue;
+                }
+                t_diff_dof -= p.pre_dwell;
+            }
+
             // Brake pre-trajectory
-            if *new_section == 0 && p.brake.duration > 0.0 {
+            if section == 0 && p.brake.duration > 0.0 {
                 if t_diff_dof < p.brake.duration {
                     let index = if t_diff_dof < p.brake.t[0] { 0 } else { 1 };
                     if index > 0 {
                         t_diff_dof -= p.brake.t[index - 1];
                     }
-                    set_integrate(
-                        dof,
+                    // The phases of a blending pre-trajectory have a snap, so pass their state
+                    // at the time itself
+                    let (position, velocity, acceleration, jerk) = integrate_with_snap(
                         t_diff_dof,
                         p.brake.p[index],
                         p.brake.v[index],
                         p.brake.a[index],
                         p.brake.j[index],
+                        p.brake.s[index],
                     );
+                    set_integrate(dof, 0.0, position, velocity, acceleration, jerk);
                     continue;
                 } else {
                     t_diff_dof -= p.brake.duration;
@@ -151,11 +521,41 @@ impl<const DOF: usize> Trajectory<DOF> {
     ) {
         new_section.get_or_insert(0);
 
+        if self.snap_window > 0.0 {
+            *new_section = Some(
+                self.cumulative_times
+                    .iter()
+                    .position(|&t| t > time + self.snap_lead)
+                    .unwrap_or(self.cumulative_times.len()),
+            );
+            for dof in 0..self.degrees_of_freedom {
+                let (p, v, a, j) = self.snap_limited_state(dof, time);
+                if let Some(new_position) = new_position {
+                    new_position[dof] = p;
+                }
+                if let Some(new_velocity) = new_velocity {
+                    new_velocity[dof] = v;
+                }
+                if let Some(new_acceleration) = new_acceleration {
+                    new_acceleration[dof] = a;
+                }
+                if let Some(new_jerk) = new_jerk {
+                    new_jerk[dof] = j;
+                }
+            }
+            return;
+        }
+
         if let Some(ref mut section_value) = new_section {
+            // Write into slices, as indexing into DataArrayOrVec matches its variant at every access
+            let mut new_position = new_position.as_deref_mut().map(|v| -> &mut [f64] { v });
+            let mut new_velocity = new_velocity.as_deref_mut().map(|v| -> &mut [f64] { v });
+            let mut new_acceleration = new_acceleration.as_deref_mut().map(|v| -> &mut [f64] { v });
+            let mut new_jerk = new_jerk.as_deref_mut().map(|v| -> &mut [f64] { v });
             self.state_to_integrate_from(time, section_value, |dof, t, p, v, a, j| {
                 let (pos, vel, acc) = integrate(t, p, v, a, j);
                 if let Some(ref mut pos_vec) = new_position {
-                    pos_vec[dof] = pos;
+                    pos_vec[dof] = self.wrap_position(dof, pos);
                 }
 
                 if let Some(ref mut vel_vec) = new_velocity {
@@ -173,42 +573,912 @@ impl<const DOF: usize> Trajectory<DOF> {
         }
     }
 
-    pub fn get_profiles(&self) -> &Vec<DataArrayOrVec<Profile, { DOF }>> {
-        &self.profiles
+    /// Get the kinematic state at a time given as a duration, see `at_time`
+    pub fn at_duration(
+        &self,
+        time: Duration,
+        new_position: &mut Option<&mut DataArrayOrVec<f64, DOF>>,
+        new_velocity: &mut Option<&mut DataArrayOrVec<f64, DOF>>,
+        new_acceleration: &mut Option<&mut DataArrayOrVec<f64, DOF>>,
+        new_jerk: &mut Option<&mut DataArrayOrVec<f64, DOF>>,
+        new_section: &mut Option<usize>,
+    ) {
+        self.at_time(
+            time.as_secs_f64(),
+            new_position,
+            new_velocity,
+            new_acceleration,
+            new_jerk,
+            new_section,
+        );
+    }
+
+    /// Get the kinematic state of the trajectory at a time given as a duration, see `state_at`
+    pub fn state_at_duration(&self, time: Duration) -> TrajectoryState<DOF> {
+        self.state_at(time.as_secs_f64())
+    }
+
+    /// Get the kinematic state of the trajectory at a given time. Unlike `at_time`, this allocates
+    /// the state for a dynamic number of DoFs.
+    pub fn state_at(&self, time: f64) -> TrajectoryState<DOF> {
+        let dofs = Some(self.degrees_of_freedom);
+        let mut state = TrajectoryState {
+            position: DataArrayOrVec::new(dofs, 0.0),
+            velocity: DataArrayOrVec::new(dofs, 0.0),
+            acceleration: DataArrayOrVec::new(dofs, 0.0),
+            jerk: DataArrayOrVec::new(dofs, 0.0),
+            section: 0,
+        };
+        let mut section = None;
+        self.at_time(
+            time,
+            &mut Some(&mut state.position),
+            &mut Some(&mut state.velocity),
+            &mut Some(&mut state.acceleration),
+            &mut Some(&mut state.jerk),
+            &mut section,
+        );
+        state.section = section.unwrap_or(0);
+        state
+    }
+
+    /// Evaluate the trajectory at many times at once. The buffers hold the state of all DoFs at
+    /// each time one after another, i.e. `times.len() * degrees_of_freedom` values. The section is
+    /// searched incrementally, so the evaluation is fastest for increasing times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of any buffer is not `times.len() * degrees_of_freedom`.
+    pub fn sample_into(
+        &self,
+        times: &[f64],
+        positions: &mut [f64],
+        velocities: &mut [f64],
+        accelerations: &mut [f64],
+    ) {
+        let dofs = self.degrees_of_freedom;
+        let number_of_values = times.len() * dofs;
+        assert_eq!(
+            positions.len(),
+            number_of_values,
+            "size of the position buffer"
+        );
+        assert_eq!(
+            velocities.len(),
+            number_of_values,
+            "size of the velocity buffer"
+        );
+        assert_eq!(
+            accelerations.len(),
+            number_of_values,
+            "size of the acceleration buffer"
+        );
+
+        let number_of_sections = self.get_number_of_sections();
+        let mut section = 0;
+        let mut previous_time = f64::NEG_INFINITY;
+        for (i, &time) in times.iter().enumerate() {
+            let offset = i * dofs;
+            if self.snap_window > 0.0 {
+                for dof in 0..dofs {
+                    let (p, v, a, _) = self.snap_limited_state(dof, time);
+                    positions[offset + dof] = p;
+                    velocities[offset + dof] = v;
+                    accelerations[offset + dof] = a;
+                }
+                continue;
+            }
+            let mut set_state = |dof: usize, t: f64, p: f64, v: f64, a: f64, j: f64| {
+                let (pos, vel, acc) = integrate(t, p, v, a, j);
+                positions[offset + dof] = self.wrap_position(dof, pos);
+                velocities[offset + dof] = vel;
+                accelerations[offset + dof] = acc;
+            };
+
+            if time >= self.duration {
+                self.state_to_integrate_from_end(time, &mut set_state);
+                continue;
+            }
+
+            if time < previous_time {
+                section = 0;
+            }
+            previous_time = time;
+            while section + 1 < number_of_sections && self.cumulative_times[section] <= time {
+                section += 1;
+            }
+            self.state_to_integrate_from_section(time, section, &mut set_state);
+        }
+    }
+
+    /// Get the position polynomials of all phases for each DoF, covering the trajectory from zero
+    /// to its duration. Phases without duration are skipped. If a DoF reaches its target before
+    /// the end of a section, a segment of constant jerk zero fills the remaining time. The
+    /// phases of a snap-limited trajectory are the pieces of its moving average, which change
+    /// wherever the start or the end of the window crosses a phase of the underlying trajectory.
+    pub fn to_piecewise_polynomials(&self) -> Vec<Vec<PolynomialSegment>> {
+        (0..self.degrees_of_freedom)
+            .map(|dof| self.segments(dof).collect())
+            .collect()
+    }
+
+    /// The position polynomials of all phases of a DoF as sampled, see `to_piecewise_polynomials`
+    fn segments(&self, dof: usize) -> impl Iterator<Item = PolynomialSegment> + '_ {
+        let is_snap_limited = self.snap_window > 0.0;
+        let profile_segments = (!is_snap_limited).then(|| self.profile_segments(dof));
+        let smoothed_segments = is_snap_limited.then(|| self.smoothed_segments(dof));
+        profile_segments
+            .into_iter()
+            .flatten()
+            .chain(smoothed_segments.into_iter().flatten())
+    }
+
+    /// The quartic pieces of the moving average of a snap-limited DoF
+    fn smoothed_segments(&self, dof: usize) -> impl Iterator<Item = PolynomialSegment> + '_ {
+        let lead = self.snap_lead;
+        let window = self.snap_window;
+        let mut boundaries: Vec<f64> = self
+            .profile_segments(dof)
+            .flat_map(|segment| [segment.t_start - lead, segment.t_start - lead + window])
+            .filter(|&t| 0.0 < t && t < self.duration)
+            .chain([0.0, self.duration])
+            .collect();
+        boundaries.sort_by(|a, b| a.total_cmp(b));
+        boundaries.dedup_by(|a, b| *a - *b < 1e-12);
+        if let Some(last) = boundaries.last_mut() {
+            *last = self.duration;
+        }
+
+        (1..boundaries.len()).map(move |i| {
+            let (t_start, t_end) = (boundaries[i - 1], boundaries[i]);
+            let (p, v, a, j, _) = self.smoothed_state(dof, t_start);
+            // The snap is constant between the boundaries, so take it in their middle
+            let (_, _, _, _, s) = self.smoothed_state(dof, (t_start + t_end) / 2.0);
+            PolynomialSegment::with_snap(t_start, t_end - t_start, p, v, a, j, s)
+        })
+    }
+
+    /// The position polynomials of all phases of a DoF of the underlying trajectory, i.e. of its
+    /// profiles
+    fn profile_segments(&self, dof: usize) -> impl Iterator<Item = PolynomialSegment> + '_ {
+        self.get_profiles()
+            .iter()
+            .enumerate()
+            .flat_map(move |(section, profiles)| {
+                let p = &profiles[dof];
+                let section_start = match section {
+                    0 => 0.0,
+                    _ => self.cumulative_times[section - 1],
+                };
+
+                // Pre-dwell and brake pre-trajectory, followed by the phases of the profile
+                let (p0, v0, a0) = p.get_start_state();
+                let dwell = (section == 0).then_some((p.pre_dwell, p0, v0, a0, 0.0, 0.0));
+                let brake = (0..2).filter(move |_| section == 0).map(move |i| {
+                    let brake = &p.brake;
+                    let (t, p, v, a) = (brake.t[i], brake.p[i], brake.v[i], brake.a[i]);
+                    (t, p, v, a, brake.j[i], brake.s[i])
+                });
+                let phases = (0..7).map(move |i| (p.t[i], p.p[i], p.v[i], p.a[i], p.j[i], 0.0));
+                let phases = dwell
+                    .into_iter()
+                    .chain(brake)
+                    .chain(phases)
+                    .filter(|phase| phase.0 > 0.0);
+                let phases_end = phases.clone().fold(section_start, |t, phase| t + phase.0);
+                let mut t_start = section_start;
+                let segments = phases.map(move |(duration, p, v, a, j, s)| {
+                    let segment = PolynomialSegment::with_snap(t_start, duration, p, v, a, j, s);
+                    t_start += duration;
+                    segment
+                });
+
+                // Ignore numerical differences between the sum of the phases and the section
+                let section_end = self.cumulative_times[section];
+                let hold = (section_end - phases_end > 1e-12).then(|| {
+                    PolynomialSegment::new(
+                        phases_end,
+                        section_end - phases_end,
+                        p.p[7],
+                        p.v[7],
+                        p.a[7],
+                        0.0,
+                    )
+                });
+                segments.chain(hold)
+            })
+    }
+
+    /// Get the distance travelled by a single DoF, i.e. the integral of its absolute velocity
+    pub fn path_length(&self, dof: usize) -> f64 {
+        self.segments(dof)
+            .map(|segment| segment.path_length())
+            .sum()
+    }
+
+    /// Get the Euclidean length of the path of the given DoFs, e.g. of the axes of a tool center
+    /// point. As the norm of the velocities has no closed-form integral in general, it is
+    /// integrated by Gauss-Legendre quadrature between all phase boundaries and velocity sign
+    /// changes of the DoFs.
+    pub fn euclidean_path_length(&self, dofs: &[usize]) -> f64 {
+        // Nodes and weights of the 5-point Gauss-Legendre quadrature on [-1, 1]
+        const NODES: [f64; 5] = [
+            0.0,
+            -0.538_469_310_105_683,
+            0.538_469_310_105_683,
+            -0.906_179_845_938_664,
+            0.906_179_845_938_664,
+        ];
+        const WEIGHTS: [f64; 5] = [
+            0.568_888_888_888_889,
+            0.478_628_670_499_366,
+            0.478_628_670_499_366,
+            0.236_926_885_056_189,
+            0.236_926_885_056_189,
+        ];
+
+        if let [dof] = dofs {
+            return self.path_length(*dof);
+        }
+
+        let polynomials: Vec<Vec<PolynomialSegment>> = dofs
+            .iter()
+            .map(|&dof| self.segments(dof).collect())
+            .collect();
+        let mut boundaries = vec![0.0, self.duration];
+        for segments in &polynomials {
+            for segment in segments {
+                boundaries.push(segment.t_end);
+                boundaries.extend(segment.velocity_sign_changes().map(|t| segment.t_start + t));
+            }
+        }
+        boundaries.retain(|&t| t <= self.duration);
+        boundaries.sort_by(|a, b| a.total_cmp(b));
+        boundaries.dedup();
+
+        let mut cursors = vec![0; dofs.len()];
+        let mut length = 0.0;
+        for interval in boundaries.windows(2) {
+            let (t_start, t_end) = (interval[0], interval[1]);
+            let half_width = (t_end - t_start) / 2.0;
+            let center = (t_start + t_end) / 2.0;
+            let segments: Vec<&PolynomialSegment> = polynomials
+                .iter()
+                .zip(cursors.iter_mut())
+                .map(|(segments, cursor)| {
+                    while *cursor + 1 < segments.len() && segments[*cursor].t_end <= center {
+                        *cursor += 1;
+                    }
+                    &segments[*cursor]
+                })
+                .collect();
+
+            for (node, weight) in NODES.iter().zip(WEIGHTS) {
+                let time = center + half_width * node;
+                let speed = segments
+                    .iter()
+                    .map(|segment| segment.velocity(time - segment.t_start).powi(2))
+                    .sum::<f64>()
+                    .sqrt();
+                length += weight * half_width * speed;
+            }
+        }
+        length
+    }
+
+    /// Build a trajectory of the given duration from the polynomial segments of each DoF, which
+    /// start at zero and cover the duration and the snap lead without gaps. The phases of all
+    /// DoFs are split at the boundaries of every DoF and packed into sections of up to seven
+    /// phases. The trajectory keeps the snap window, and the end states are only used if the
+    /// segments have no duration. A segment with a snap is approximated by phases with the jerk
+    /// at their center, which keeps the acceleration exact.
+    fn with_segments(
+        &self,
+        segments: &[Vec<PolynomialSegment>],
+        duration: f64,
+        snap_lead: f64,
+        end_states: &[(f64, f64, f64)],
+    ) -> Self {
+        let dofs = self.degrees_of_freedom;
+        let underlying_duration = duration + snap_lead;
+        let mut boundaries: Vec<f64> = segments
+            .iter()
+            .flatten()
+            .flat_map(|segment| {
+                let pieces = if segment.snap() != 0.0 {
+                    SNAP_SEGMENT_PIECES
+                } else {
+                    1
+                };
+                let duration = segment.t_end - segment.t_start;
+                (1..pieces)
+                    .map(move |k| segment.t_start + duration * k as f64 / pieces as f64)
+                    .chain(std::iter::once(segment.t_end))
+            })
+            .filter(|&t| 0.0 < t && t < underlying_duration)
+            .chain([0.0, underlying_duration])
+            .collect();
+        boundaries.sort_by(|a, b| a.total_cmp(b));
+        boundaries.dedup_by(|a, b| *a - *b < 1e-12);
+        if let Some(last) = boundaries.last_mut() {
+            *last = underlying_duration;
+        }
+
+        let number_of_phases = boundaries.len() - 1;
+        let number_of_sections = number_of_phases.div_ceil(7).max(1);
+        let mut trajectory = Self {
+            profiles: vec![DataArrayOrVec::new(Some(dofs), Profile::default()); number_of_sections],
+            duration,
+            cumulative_times: Vec::with_capacity(number_of_sections),
+            independent_min_durations: self.independent_min_durations.clone(),
+            synchronizations: self.synchronizations.clone(),
+            discretization_remainder: 0.0,
+            calculation_duration: 0.0,
+            periods: self.periods.clone(),
+            snap_window: self.snap_window,
+            snap_lead,
+            snap_start_jerk: self.snap_start_jerk.clone(),
+            position_extrema: DataArrayOrVec::new(Some(dofs), Bound::default()),
+            degrees_of_freedom: dofs,
+        };
+
+        let mut cursors = vec![0; dofs];
+        for section in 0..number_of_sections {
+            let phases = &boundaries[7 * section..=(7 * section + 7).min(number_of_phases)];
+            trajectory.cumulative_times.push(phases[phases.len() - 1]);
+            for (dof, cursor) in cursors.iter_mut().enumerate() {
+                let p = &mut trajectory.profiles[section][dof];
+                (p.p[0], p.v[0], p.a[0]) = end_states[dof];
+                for (i, interval) in phases.windows(2).enumerate() {
+                    let dof_segments = &segments[dof];
+                    let center = (interval[0] + interval[1]) / 2.0;
+                    while *cursor + 1 < dof_segments.len() && dof_segments[*cursor].t_end <= center
+                    {
+                        *cursor += 1;
+                    }
+
+                    // Start each phase from its segment to avoid accumulating integration errors
+                    let segment = &dof_segments[*cursor];
+                    let t = interval[0] - segment.t_start;
+                    p.t[i] = interval[1] - interval[0];
+                    p.p[i] = segment.position(t);
+                    p.v[i] = segment.velocity(t);
+                    p.a[i] = segment.acceleration(t);
+                    p.j[i] = segment.jerk(center - segment.t_start);
+                }
+
+                for i in 0..7 {
+                    if i + 1 >= phases.len() - 1 {
+                        (p.p[i + 1], p.v[i + 1], p.a[i + 1]) =
+                            integrate(p.t[i], p.p[i], p.v[i], p.a[i], p.j[i]);
+                    }
+                    p.t_sum[i] = if i > 0 { p.t_sum[i - 1] } else { 0.0 } + p.t[i];
+                }
+                p.pf = p.p[7];
+                p.vf = p.v[7];
+                p.af = p.a[7];
+            }
+        }
+        trajectory
+    }
+
+    /// Get a trajectory that plays the motion backwards, e.g. to retract along the path of the
+    /// approach. Its velocity and jerk are negated. The sections of the reversed trajectory don't
+    /// correspond to the intermediate positions anymore.
+    pub fn reversed(&self) -> Self {
+        // The underlying trajectory is reversed as a whole, so that the window of a snap-limited
+        // trajectory ends one window after the sampled time, and the DoFs continue backwards from
+        // their start states for the remaining window
+        let window = self.snap_window;
+        let underlying_end = self.duration + self.snap_lead;
+        let underlying_duration = self.duration + window;
+        let segments: Vec<Vec<PolynomialSegment>> = (0..self.degrees_of_freedom)
+            .map(|dof| {
+                let dof_segments: Vec<PolynomialSegment> = self.profile_segments(dof).collect();
+                let before = dof_segments.first().map(|segment| {
+                    PolynomialSegment::new(
+                        underlying_end,
+                        underlying_duration - underlying_end,
+                        segment.position(0.0),
+                        -segment.velocity(0.0),
+                        segment.acceleration(0.0),
+                        -self.snap_start_jerk[dof],
+                    )
+                });
+                let before = before.filter(|before| before.t_end - before.t_start > 1e-12);
+                dof_segments
+                    .iter()
+                    .rev()
+                    .map(|segment| {
+                        let d = segment.t_end - segment.t_start;
+                        PolynomialSegment::with_snap(
+                            underlying_end - segment.t_end,
+                            d,
+                            segment.position(d),
+                            -segment.velocity(d),
+                            segment.acceleration(d),
+                            -segment.jerk(d),
+                            segment.snap(),
+                        )
+                    })
+                    .chain(before)
+                    .collect()
+            })
+            .collect();
+
+        let start = self.state_at(0.0);
+        let end_states: Vec<(f64, f64, f64)> = (0..self.degrees_of_freedom)
+            .map(|dof| {
+                (
+                    start.position[dof],
+                    -start.velocity[dof],
+                    start.acceleration[dof],
+                )
+            })
+            .collect();
+        // The reversed trajectory starts from the target state at rest
+        let mut reversed = self.with_segments(&segments, self.duration, window, &end_states);
+        reversed.snap_start_jerk.fill(0.0);
+        reversed
+    }
+
+    /// Get the part of the trajectory between two times, shifted to start at zero, e.g. to hand
+    /// over a partially executed motion. Its boundary states are the states of the trajectory at
+    /// these times, which are clamped to the duration. The sections of the cropped trajectory
+    /// don't correspond to the intermediate positions anymore.
+    pub fn crop(&self, t_start: f64, t_end: f64) -> Self {
+        assert!(
+            t_start <= t_end,
+            "start time {} should be before the end time {}",
+            t_start,
+            t_end
+        );
+        let t_start = t_start.clamp(0.0, self.duration);
+        let t_end = t_end.clamp(0.0, self.duration);
+
+        // A snap-limited trajectory keeps the underlying trajectory within the window before the
+        // start, as far as it exists
+        let lead = (t_start + self.snap_lead).min(self.snap_window);
+        let underlying_start = t_start + self.snap_lead - lead;
+        let underlying_end = t_end + self.snap_lead;
+        let segments: Vec<Vec<PolynomialSegment>> = (0..self.degrees_of_freedom)
+            .map(|dof| {
+                self.profile_segments(dof)
+                    .filter(|segment| {
+                        segment.t_end > underlying_start && segment.t_start < underlying_end
+                    })
+                    .map(|segment| {
+                        let start = segment.t_start.max(underlying_start);
+                        let t = start - segment.t_start;
+                        PolynomialSegment::with_snap(
+                            start - underlying_start,
+                            segment.t_end.min(underlying_end) - start,
+                            segment.position(t),
+                            segment.velocity(t),
+                            segment.acceleration(t),
+                            segment.jerk(t),
+                            segment.snap(),
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let end = self.state_at(t_end);
+        let end_states: Vec<(f64, f64, f64)> = (0..self.degrees_of_freedom)
+            .map(|dof| (end.position[dof], end.velocity[dof], end.acceleration[dof]))
+            .collect();
+        self.with_segments(&segments, t_end - t_start, lead, &end_states)
+    }
+
+    /// Extend the calculated trajectory to a longer duration by re-running step 2 for each DoF
+    /// of the last section. The kinematic limits are taken from the input that the trajectory was
+    /// calculated with. DoFs that are disabled or not synchronized keep their profiles.
+    pub fn stretch_to_duration<E: RuckigErrorHandler>(
+        &mut self,
+        input: &InputParameter<DOF>,
+        new_duration: f64,
+    ) -> Result<RuckigResult, RuckigError> {
+        if new_duration.is_nan() || new_duration < self.duration {
+            return E::handle_validation_error(RuckigError::InvalidDuration {
+                duration: new_duration,
+                minimum: self.duration,
+            })
+            .map(|_| RuckigResult::ErrorInvalidInput);
+        }
+
+        let section = self.get_number_of_sections() - 1;
+        let section_start = if section > 0 {
+            self.cumulative_times[section - 1]
+        } else {
+            0.0
+        };
+        // Keep the trajectory unchanged if any DoF fails
+        let mut profiles = self.profiles[section].clone();
+        for dof in 0..self.degrees_of_freedom {
+            let synchronization = input
+                .per_dof_synchronization
+                .as_ref()
+                .map_or(&input.synchronization, |v| &v[dof]);
+            if !input.enabled[dof] || *synchronization == Synchronization::None {
+                continue;
+            }
+
+            let control_interface = input
+                .per_dof_control_interface
+                .as_ref()
+                .map_or(&input.control_interface, |v| &v[dof]);
+            let p = &mut profiles[dof];
+            let t_profile =
+                new_duration - section_start - p.pre_dwell - p.brake.duration - p.accel.duration;
+            if !calculate_step2_profile(
+                control_interface,
+                t_profile,
+                p,
+                None,
+                input.max_velocity[dof],
+                input
+                    .min_velocity
+                    .as_ref()
+                    .map_or(-input.max_velocity[dof], |v| v[dof]),
+                input.max_acceleration[dof],
+                input
+                    .min_acceleration
+                    .as_ref()
+                    .map_or(-input.max_acceleration[dof], |v| v[dof]),
+                input.max_jerk[dof],
+            ) {
+                return E::handle_calculator_error(RuckigError::Step2Failure {
+                    dof,
+                    t_sync: new_duration,
+                    state: DofState::from_input(input, dof),
+                });
+            }
+        }
+
+        self.profiles[section] = profiles;
+        self.duration = new_duration;
+        self.cumulative_times[section] = new_duration;
+        Ok(RuckigResult::Working)
+    }
+
+    /// Sample the trajectory every `resolution` seconds and at its end, and report every
+    /// kinematic limit of the input that is exceeded, as well as every target state that isn't
+    /// reached at the end. Limits and states are compared with a relative tolerance of
+    /// `CHECK_TOLERANCE`. The velocity and acceleration limits aren't checked during a brake
+    /// trajectory, as the current state may exceed them.
+    pub fn check_limits(
+        &self,
+        input: &InputParameter<DOF>,
+        resolution: f64,
+    ) -> Vec<LimitViolation> {
+        assert!(resolution > 0.0, "resolution should be positive");
+
+        let exceeds =
+            |value: f64, limit: f64| value > limit + CHECK_TOLERANCE * limit.abs().max(1.0);
+        let misses = |value: f64, target: f64| {
+            (value - target).abs() > CHECK_TOLERANCE * target.abs().max(1.0)
+        };
+
+        let mut violations = Vec::new();
+        let number_of_samples = (self.duration / resolution).ceil() as usize;
+        for i in 0..=number_of_samples {
+            let time = (i as f64 * resolution).min(self.duration);
+            let state = self.state_at(time);
+            for dof in 0..self.degrees_of_freedom {
+                if !input.enabled[dof] {
+                    continue;
+                }
+
+                let control_interface = input
+                    .per_dof_control_interface
+                    .as_ref()
+                    .map_or(&input.control_interface, |v| &v[dof]);
+                let mut report = |quantity: InputQuantity, value: f64, allowed: f64| {
+                    violations.push(LimitViolation {
+                        time,
+                        dof,
+                        quantity,
+                        value,
+                        allowed,
+                    });
+                };
+
+                let j = state.jerk[dof];
+                if exceeds(j.abs(), input.max_jerk[dof]) {
+                    report(InputQuantity::MaxJerk, j, input.max_jerk[dof]);
+                }
+                // The window of a snap-limited trajectory averages over the brake trajectory
+                let window_start = time + self.snap_lead - self.snap_window;
+                let profile = &self.profiles[0][dof];
+                if window_start < profile.pre_dwell + profile.brake.duration {
+                    continue;
+                }
+
+                if *control_interface != ControlInterface::Acceleration {
+                    let a = state.acceleration[dof];
+                    let a_max = input.max_acceleration[dof];
+                    let a_min = input.min_acceleration.as_ref().map_or(-a_max, |v| v[dof]);
+                    if exceeds(a, a_max) {
+                        report(InputQuantity::MaxAcceleration, a, a_max);
+                    }
+                    if exceeds(-a, -a_min) {
+                        report(InputQuantity::MinAcceleration, a, a_min);
+                    }
+                }
+
+                if *control_interface == ControlInterface::Position {
+                    let v = state.velocity[dof];
+                    let v_max = input.max_velocity[dof];
+                    let v_min = input.min_velocity.as_ref().map_or(-v_max, |v| v[dof]);
+                    if exceeds(v, v_max) {
+                        report(InputQuantity::MaxVelocity, v, v_max);
+                    }
+                    if exceeds(-v, -v_min) {
+                        report(InputQuantity::MinVelocity, v, v_min);
+                    }
+
+                    let p = state.position[dof];
+                    if let Some(p_max) = input.max_position.as_ref().map(|v| v[dof]) {
+                        if exceeds(p, p_max) {
+                            report(InputQuantity::MaxPosition, p, p_max);
+                        }
+                    }
+                    if let Some(p_min) = input.min_position.as_ref().map(|v| v[dof]) {
+                        if exceeds(-p, -p_min) {
+                            report(InputQuantity::MinPosition, p, p_min);
+                        }
+                    }
+                }
+
+                if i == number_of_samples {
+                    // Compare circular positions by their shortest distance
+                    let target_position = input.target_position[dof];
+                    let position = match self.period(dof) {
+                        Some(period) => {
+                            target_position
+                                + circular_distance(state.position[dof] - target_position, period)
+                        }
+                        None => state.position[dof],
+                    };
+                    if *control_interface == ControlInterface::Position
+                        && misses(position, target_position)
+                    {
+                        report(InputQuantity::TargetPosition, position, target_position);
+                    }
+                    if *control_interface != ControlInterface::Acceleration
+                        && misses(state.velocity[dof], input.target_velocity[dof])
+                    {
+                        report(
+                            InputQuantity::TargetVelocity,
+                            state.velocity[dof],
+                            input.target_velocity[dof],
+                        );
+                    }
+                    if misses(state.acceleration[dof], input.target_acceleration[dof]) {
+                        report(
+                            InputQuantity::TargetAcceleration,
+                            state.acceleration[dof],
+                            input.target_acceleration[dof],
+                        );
+                    }
+                }
+            }
+        }
+        violations
+    }
+
+    /// Get the reached limits, control signs, direction, and jerk signs of the profile of a DoF
+    /// in the given section
+    pub fn get_profile_info(&self, section: usize, dof: usize) -> Option<ProfileInfo> {
+        if section >= self.get_number_of_sections() || dof >= self.degrees_of_freedom {
+            return None;
+        }
+        Some(self.profiles[section][dof].info())
+    }
+
+    /// Get the brake trajectory at the start of a DoF, which is inserted if its current state
+    /// exceeds or will inevitably exceed the kinematic limits
+    pub fn get_brake_profile(&self, dof: usize) -> Option<&BrakeProfile> {
+        if dof >= self.degrees_of_freedom {
+            return None;
+        }
+        let brake = &self.profiles[0][dof].brake;
+        (brake.duration > 0.0).then_some(brake)
+    }
+
+    /// Get the acceleration trajectory at the end of a DoF. It is reserved for target states
+    /// outside of the kinematic limits, which are not supported, so it is always `None` for now.
+    pub fn get_accel_profile(&self, dof: usize) -> Option<&BrakeProfile> {
+        if dof >= self.degrees_of_freedom {
+            return None;
+        }
+        let accel = &self.profiles[self.get_number_of_sections() - 1][dof].accel;
+        (accel.duration > 0.0).then_some(accel)
+    }
+
+    pub fn get_profiles(&self) -> &[DataArrayOrVec<Profile, { DOF }>] {
+        &self.profiles[..self.get_number_of_sections()]
     }
 
     pub fn get_duration(&self) -> f64 {
         self.duration
     }
 
-    pub fn get_intermediate_durations(&self) -> &DataArrayOrVec<f64, { DOF }> {
+    /// Get the duration of the trajectory as a duration instead of in [s], rounded to
+    /// nanoseconds
+    pub fn duration(&self) -> Duration {
+        duration_from_secs(self.duration)
+    }
+
+    /// Get the time by which a discrete duration was rounded, see `Settings::duration_rounding`.
+    /// It is positive if the trajectory was stretched to the next multiple of the step, negative
+    /// if it was shortened to the previous multiple, and zero for continuous durations.
+    pub fn get_discretization_remainder(&self) -> f64 {
+        self.discretization_remainder
+    }
+
+    /// Get the duration of the calculation of the trajectory in [µs], which is zero without a clock
+    pub fn get_calculation_duration(&self) -> f64 {
+        self.calculation_duration
+    }
+
+    pub fn get_intermediate_durations(&self) -> &[f64] {
         &self.cumulative_times
     }
 
+    /// Get the window of the moving average that limits the snap, see `InputParameter::max_snap`,
+    /// or zero. The sampled states, polynomials, extrema, path lengths, and times at a velocity or
+    /// acceleration of a snap-limited trajectory are smoothed, while its profiles and times at a
+    /// position refer to the underlying third-order trajectory, which holds the target state
+    /// during the window.
+    pub fn get_snap_window(&self) -> f64 {
+        self.snap_window
+    }
+
     pub fn get_independent_min_durations(&self) -> &DataArrayOrVec<f64, { DOF }> {
         &self.independent_min_durations
     }
 
-    pub fn get_position_extrema(&mut self) -> &DataArrayOrVec<Bound, { DOF }> {
+    /// Get the synchronization that the calculation applied to each DoF. For a trajectory with
+    /// intermediate positions, it is the one of the last section.
+    pub fn get_synchronizations(&self) -> &DataArrayOrVec<DofSynchronization, { DOF }> {
+        &self.synchronizations
+    }
+
+    /// Get the minimum and maximum velocity of each DoF and the times when they are reached
+    pub fn get_velocity_extrema(&self) -> DataArrayOrVec<Bound, { DOF }> {
+        // The velocity is extremal where the acceleration is zero
+        self.get_extrema(PolynomialSegment::velocity, |segment| {
+            let c = &segment.coefficients;
+            roots::solve_cub(0.0, 12.0 * c[4], 6.0 * c[3], 2.0 * c[2])
+        })
+    }
+
+    /// Get the minimum and maximum acceleration of each DoF and the times when they are reached
+    pub fn get_acceleration_extrema(&self) -> DataArrayOrVec<Bound, { DOF }> {
+        // The acceleration is extremal where the jerk is zero, i.e. only within phases of a
+        // snap-limited trajectory
+        self.get_extrema(PolynomialSegment::acceleration, |segment| {
+            let c = &segment.coefficients;
+            (c[4] != 0.0).then(|| -c[3] / (4.0 * c[4]))
+        })
+    }
+
+    /// Extrema of a quantity of each DoF, which is extremal either at the boundaries of a phase or
+    /// at the given times within the phase
+    fn get_extrema<F, G, I>(&self, value_at: F, extremal_times: G) -> DataArrayOrVec<Bound, { DOF }>
+    where
+        F: Fn(&PolynomialSegment, f64) -> f64,
+        G: Fn(&PolynomialSegment) -> I,
+        I: IntoIterator<Item = f64>,
+    {
+        let mut extrema = DataArrayOrVec::new(
+            Some(self.degrees_of_freedom),
+            Bound {
+                min: f64::INFINITY,
+                max: f64::NEG_INFINITY,
+                t_min: 0.0,
+                t_max: 0.0,
+            },
+        );
+        let number_of_sections = self.get_number_of_sections();
         for dof in 0..self.degrees_of_freedom {
-            self.position_extrema[dof] = self.profiles[0][dof].get_position_extrema();
+            // A trajectory without duration stays at the final state of its profiles
+            let rest = (self.duration <= 0.0).then(|| {
+                let p = &self.profiles[number_of_sections - 1][dof];
+                PolynomialSegment::new(0.0, 0.0, p.p[7], p.v[7], p.a[7], 0.0)
+            });
+            let bound = &mut extrema[dof];
+            for segment in self.segments(dof).chain(rest) {
+                let duration = segment.t_end - segment.t_start;
+                let interior = extremal_times(&segment)
+                    .into_iter()
+                    .filter(|&t| 0.0 < t && t < duration);
+                for t in [0.0, duration].into_iter().chain(interior) {
+                    let value = value_at(&segment, t);
+                    if value < bound.min {
+                        bound.min = value;
+                        bound.t_min = segment.t_start + t;
+                    }
+                    if value > bound.max {
+                        bound.max = value;
+                        bound.t_max = segment.t_start + t;
+                    }
+                }
+            }
         }
+        extrema
+    }
 
-        for i in 1..self.profiles.len() {
-            for dof in 0..self.degrees_of_freedom {
-                let section_position_extrema = self.profiles[i][dof].get_position_extrema();
-                if section_position_extrema.max > self.position_extrema[dof].max {
-                    self.position_extrema[dof].max = section_position_extrema.max;
-                    self.position_extrema[dof].t_max = section_position_extrema.t_max;
+    pub fn get_position_extrema(&mut self) -> &DataArrayOrVec<Bound, { DOF }> {
+        // The position is extremal where the velocity changes its sign
+        self.position_extrema = self.get_extrema(PolynomialSegment::position, |segment| {
+            segment.velocity_sign_changes()
+        });
+        &self.position_extrema
+    }
+
+    /// Get the first time when the DoF reaches the given velocity
+    pub fn get_first_time_at_velocity(&self, dof: usize, velocity: f64) -> Option<f64> {
+        self.get_times_at_velocity(dof, velocity).first().copied()
+    }
+
+    /// Get all times when the DoF reaches or crosses the given velocity, in increasing order. If
+    /// the velocity is held, only the time when it is reached is reported.
+    pub fn get_times_at_velocity(&self, dof: usize, velocity: f64) -> Vec<f64> {
+        self.get_times_at_root(dof, |c| {
+            (4.0 * c[4], 3.0 * c[3], 2.0 * c[2], c[1] - velocity)
+        })
+    }
+
+    /// Get the first time when the DoF reaches the given acceleration
+    pub fn get_first_time_at_acceleration(&self, dof: usize, acceleration: f64) -> Option<f64> {
+        self.get_times_at_acceleration(dof, acceleration)
+            .first()
+            .copied()
+    }
+
+    /// Get all times when the DoF reaches or crosses the given acceleration, in increasing order.
+    /// If the acceleration is held, only the time when it is reached is reported.
+    pub fn get_times_at_acceleration(&self, dof: usize, acceleration: f64) -> Vec<f64> {
+        self.get_times_at_root(dof, |c| {
+            (0.0, 12.0 * c[4], 6.0 * c[3], 2.0 * c[2] - acceleration)
+        })
+    }
+
+    /// Get all times when the cubic polynomial `a t^3 + b t^2 + c t + d`, given by the position
+    /// coefficients of each phase, is zero
+    fn get_times_at_root<F>(&self, dof: usize, cubic: F) -> Vec<f64>
+    where
+        F: Fn(&[f64; 5]) -> (f64, f64, f64, f64),
+    {
+        if dof >= self.degrees_of_freedom {
+            return Vec::new();
+        }
+
+        let polynomials = self.to_piecewise_polynomials();
+        let segments = &polynomials[dof];
+        let mut times: Vec<f64> = Vec::new();
+        let mut was_held = false;
+        for (i, segment) in segments.iter().enumerate() {
+            let (a, b, c, d) = cubic(&segment.coefficients);
+            let duration = segment.t_end - segment.t_start;
+            let is_last = i + 1 == segments.len();
+            for t in roots::solve_cub(a, b, c, d) {
+                // Roots at the end of a phase are found again at the start of the next one
+                if t > duration || (t == duration && !is_last) || (t == 0.0 && was_held) {
+                    continue;
                 }
-                if section_position_extrema.min < self.position_extrema[dof].min {
-                    self.position_extrema[dof].min = section_position_extrema.min;
-                    self.position_extrema[dof].t_min = section_position_extrema.t_min;
+
+                let time = segment.t_start + t;
+                if times.last().is_none_or(|&last| time - last > 1e-12) {
+                    times.push(time);
                 }
             }
+            was_held = [a, b, c, d].iter().all(|x| x.abs() < f64::EPSILON);
         }
-
-        &self.position_extrema
+        times
     }
 
     pub fn get_first_time_at_position(&self, dof: usize, position: f64) -> Option<f64> {
@@ -216,10 +1486,13 @@ impl<const DOF: usize> Trajectory<DOF> {
             return None;
         }
 
-        let time; // Or any default value
-        for p in &self.profiles {
-            if let Some((returned_time, _, _)) = p[dof].get_first_state_at_position(position, 0.0) {
-                time = returned_time;
+        for (section, p) in self.get_profiles().iter().enumerate() {
+            let offset = if section > 0 {
+                self.cumulative_times[section - 1]
+            } else {
+                0.0
+            };
+            if let Some((time, _, _)) = p[dof].get_first_state_at_position(position, offset) {
                 return Some(time);
             }
         }
@@ -0,0 +1,207 @@
+//! Export of sampled trajectories to CSV tables and their import.
+use std::io::{self, BufRead, Write};
+
+use crate::trajectory::Trajectory;
+
+/// Table of kinematic states sampled from a trajectory. The states of all DoFs at each time are
+/// stored one after another, as for `Trajectory::sample_into`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SampledTrajectory {
+    pub degrees_of_freedom: usize,
+    pub times: Vec<f64>,
+    pub positions: Vec<f64>,
+    pub velocities: Vec<f64>,
+    pub accelerations: Vec<f64>,
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+impl SampledTrajectory {
+    /// Sample the trajectory every `dt` seconds from zero to its duration. The end of the
+    /// trajectory is always included as the last sample, exactly once even if the duration is a
+    /// multiple of `dt`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dt` is not positive.
+    pub fn from_trajectory<const DOF: usize>(trajectory: &Trajectory<DOF>, dt: f64) -> Self {
+        assert!(dt > 0.0, "sampling time step {} should be positive", dt);
+
+        // Steps closer to the end than the rounding error of `step * dt` are replaced by the end
+        let duration = trajectory.get_duration();
+        let end_tolerance = 1e-9 * dt;
+        let mut times: Vec<f64> = (0..)
+            .map(|step| step as f64 * dt)
+            .take_while(|&time| time < duration - end_tolerance)
+            .collect();
+        times.push(duration);
+
+        let dofs = trajectory.get_profiles()[0].len();
+        let number_of_values = times.len() * dofs;
+        let mut sampled = Self {
+            degrees_of_freedom: dofs,
+            times,
+            positions: vec![0.0; number_of_values],
+            velocities: vec![0.0; number_of_values],
+            accelerations: vec![0.0; number_of_values],
+        };
+        trajectory.sample_into(
+            &sampled.times,
+            &mut sampled.positions,
+            &mut sampled.velocities,
+            &mut sampled.accelerations,
+        );
+        sampled
+    }
+
+    pub fn len(&self) -> usize {
+        self.times.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.times.is_empty()
+    }
+
+    /// Position of all DoFs at the sample with the given index
+    pub fn position(&self, index: usize) -> &[f64] {
+        let dofs = self.degrees_of_freedom;
+        &self.positions[index * dofs..(index + 1) * dofs]
+    }
+
+    /// Velocity of all DoFs at the sample with the given index
+    pub fn velocity(&self, index: usize) -> &[f64] {
+        let dofs = self.degrees_of_freedom;
+        &self.velocities[index * dofs..(index + 1) * dofs]
+    }
+
+    /// Acceleration of all DoFs at the sample with the given index
+    pub fn acceleration(&self, index: usize) -> &[f64] {
+        let dofs = self.degrees_of_freedom;
+        &self.accelerations[index * dofs..(index + 1) * dofs]
+    }
+
+    /// Positions of one DoF at all samples
+    pub fn dof_positions(&self, dof: usize) -> Vec<f64> {
+        self.dof_values(&self.positions, dof)
+    }
+
+    /// Velocities of one DoF at all samples
+    pub fn dof_velocities(&self, dof: usize) -> Vec<f64> {
+        self.dof_values(&self.velocities, dof)
+    }
+
+    /// Accelerations of one DoF at all samples
+    pub fn dof_accelerations(&self, dof: usize) -> Vec<f64> {
+        self.dof_values(&self.accelerations, dof)
+    }
+
+    fn dof_values(&self, values: &[f64], dof: usize) -> Vec<f64> {
+        assert!(
+            dof < self.degrees_of_freedom,
+            "DoF {} of a table with {} DoFs",
+            dof,
+            self.degrees_of_freedom
+        );
+        values
+            .iter()
+            .skip(dof)
+            .step_by(self.degrees_of_freedom)
+            .copied()
+            .collect()
+    }
+
+    /// Write the table with a header line `time,p0,...,v0,...,a0,...` and one line per sample.
+    /// Values are written with the shortest representation that reads back exactly.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let dofs = self.degrees_of_freedom;
+        write!(writer, "time")?;
+        for prefix in ["p", "v", "a"] {
+            for dof in 0..dofs {
+                write!(writer, ",{}{}", prefix, dof)?;
+            }
+        }
+        writeln!(writer)?;
+
+        for (index, time) in self.times.iter().enumerate() {
+            write!(writer, "{}", time)?;
+            for values in [
+                self.position(index),
+                self.velocity(index),
+                self.acceleration(index),
+            ] {
+                for value in values {
+                    write!(writer, ",{}", value)?;
+                }
+            }
+            writeln!(writer)?;
+        }
+        writer.flush()
+    }
+
+    /// Read a table as written by `write_csv`. The number of DoFs is taken from the header.
+    pub fn read_csv<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut lines = reader.lines();
+        let header = lines
+            .next()
+            .ok_or_else(|| invalid_data("missing CSV header".to_string()))??;
+        let number_of_columns = header.split(',').count();
+        if !header.starts_with("time") || (number_of_columns - 1) % 3 != 0 {
+            return Err(invalid_data(format!(
+                "CSV header '{}' should contain the time followed by position, velocity, and acceleration columns",
+                header
+            )));
+        }
+
+        let dofs = (number_of_columns - 1) / 3;
+        let mut sampled = Self {
+            degrees_of_freedom: dofs,
+            ..Default::default()
+        };
+        for (line_number, line) in lines.enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let values = line
+                .split(',')
+                .map(|value| value.trim().parse::<f64>())
+                .collect::<Result<Vec<f64>, _>>()
+                .map_err(|error| invalid_data(format!("line {}: {}", line_number + 2, error)))?;
+            if values.len() != number_of_columns {
+                return Err(invalid_data(format!(
+                    "line {} has {} columns, but the header has {}",
+                    line_number + 2,
+                    values.len(),
+                    number_of_columns
+                )));
+            }
+
+            sampled.times.push(values[0]);
+            sampled.positions.extend_from_slice(&values[1..1 + dofs]);
+            sampled
+                .velocities
+                .extend_from_slice(&values[1 + dofs..1 + 2 * dofs]);
+            sampled
+                .accelerations
+                .extend_from_slice(&values[1 + 2 * dofs..]);
+        }
+        Ok(sampled)
+    }
+}
+
+impl<const DOF: usize> Trajectory<DOF> {
+    /// Sample the kinematic state every `dt` seconds, with the last sample exactly at the
+    /// duration, see `SampledTrajectory::from_trajectory`
+    pub fn resample(&self, dt: f64) -> SampledTrajectory {
+        SampledTrajectory::from_trajectory(self, dt)
+    }
+
+    /// Write the kinematic states sampled every `dt` seconds as a CSV table, see
+    /// `SampledTrajectory::write_csv` for the format.
+    pub fn to_csv<W: Write>(&self, writer: W, dt: f64) -> io::Result<()> {
+        SampledTrajectory::from_trajectory(self, dt).write_csv(writer)
+    }
+}
@@ -0,0 +1,44 @@
+//! Conversion of sampled trajectories and input vectors from and to `ndarray` arrays.
+use ndarray::{Array2, ArrayView1};
+
+use crate::error::RuckigError;
+use crate::trajectory::Trajectory;
+use crate::trajectory_csv::SampledTrajectory;
+use crate::util::DataArrayOrVec;
+
+impl<const DOF: usize> Trajectory<DOF> {
+    /// Sample the trajectory every `dt` seconds from zero to its duration into an array with one
+    /// row per sample. The columns are the time, followed by the positions, velocities, and
+    /// accelerations of all DoFs, as in `SampledTrajectory::write_csv`.
+    pub fn sample_to_array(&self, dt: f64) -> Array2<f64> {
+        let sampled = SampledTrajectory::from_trajectory(self, dt);
+        let dofs = sampled.degrees_of_freedom;
+        Array2::from_shape_fn(
+            (sampled.len(), 1 + 3 * dofs),
+            |(index, column)| match column {
+                0 => sampled.times[index],
+                _ => {
+                    let values = match (column - 1) / dofs {
+                        0 => &sampled.positions,
+                        1 => &sampled.velocities,
+                        _ => &sampled.accelerations,
+                    };
+                    values[index * dofs + (column - 1) % dofs]
+                }
+            },
+        )
+    }
+}
+
+/// Vector with the values of the view, e.g. for `input.current_position = view.try_into()?`. Fails
+/// with `RuckigError::DofMismatch` if the view doesn't have N values for a fixed number of DoFs.
+impl<const N: usize> TryFrom<ArrayView1<'_, f64>> for DataArrayOrVec<f64, N> {
+    type Error = RuckigError;
+
+    fn try_from(view: ArrayView1<'_, f64>) -> Result<Self, Self::Error> {
+        if N > 0 && view.len() != N {
+            return Err(RuckigError::DofMismatch);
+        }
+        Ok(view.iter().copied().collect())
+    }
+}
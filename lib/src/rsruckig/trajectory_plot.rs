@@ -0,0 +1,102 @@
+//! Plots of the kinematic state of a trajectory over time.
+use std::io;
+use std::path::Path;
+
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+use crate::trajectory::{Trajectory, TrajectoryState};
+
+const WIDTH: u32 = 1000;
+const HEIGHT_PER_PLOT: u32 = 300;
+
+fn plot_error<E: std::error::Error + Send + Sync>(error: DrawingAreaErrorKind<E>) -> io::Error {
+    io::Error::other(error.to_string())
+}
+
+impl<const DOF: usize> Trajectory<DOF> {
+    /// Plot the position, velocity, acceleration, and jerk of all DoFs, sampled every `dt`
+    /// seconds, one below the other. The image is written as SVG if the path ends with `.svg`,
+    /// and as PNG otherwise.
+    pub fn plot<P: AsRef<Path>>(&self, path: P, dt: f64) -> io::Result<()> {
+        assert!(dt > 0.0, "sampling time step {} should be positive", dt);
+
+        let path = path.as_ref();
+        let size = (WIDTH, 4 * HEIGHT_PER_PLOT);
+        if path.extension().is_some_and(|extension| extension == "svg") {
+            self.draw(&SVGBackend::new(path, size).into_drawing_area(), dt)
+        } else {
+            self.draw(&BitMapBackend::new(path, size).into_drawing_area(), dt)
+        }
+    }
+
+    fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>, dt: f64) -> io::Result<()>
+    where
+        DB::ErrorType: 'static,
+    {
+        let duration = self.get_duration();
+        let number_of_steps = (duration / dt).ceil() as usize;
+        let states: Vec<_> = (0..=number_of_steps)
+            .map(|step| {
+                let time = (step as f64 * dt).min(duration);
+                (time, self.state_at(time))
+            })
+            .collect();
+
+        let dofs = self.get_profiles()[0].len();
+        root.fill(&WHITE).map_err(plot_error)?;
+        let areas = root.split_evenly((4, 1));
+        let quantities = ["position", "velocity", "acceleration", "jerk"];
+        for (index, (area, quantity)) in areas.iter().zip(quantities).enumerate() {
+            let value = |state: &TrajectoryState<DOF>, dof: usize| match index {
+                0 => state.position[dof],
+                1 => state.velocity[dof],
+                2 => state.acceleration[dof],
+                _ => state.jerk[dof],
+            };
+
+            let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+            for (_, state) in &states {
+                for dof in 0..dofs {
+                    min = min.min(value(state, dof));
+                    max = max.max(value(state, dof));
+                }
+            }
+            let margin = 0.05 * (max - min).max(1e-6);
+
+            let mut chart = ChartBuilder::on(area)
+                .caption(quantity, ("sans-serif", 20))
+                .margin(10)
+                .x_label_area_size(30)
+                .y_label_area_size(60)
+                .build_cartesian_2d(0.0..duration.max(dt), (min - margin)..(max + margin))
+                .map_err(plot_error)?;
+            chart
+                .configure_mesh()
+                .x_desc("time [s]")
+                .draw()
+                .map_err(plot_error)?;
+
+            for dof in 0..dofs {
+                let color = Palette99::pick(dof).to_rgba();
+                chart
+                    .draw_series(LineSeries::new(
+                        states
+                            .iter()
+                            .map(|(time, state)| (*time, value(state, dof))),
+                        &color,
+                    ))
+                    .map_err(plot_error)?
+                    .label(format!("DoF {}", dof))
+                    .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+            }
+            chart
+                .configure_series_labels()
+                .background_style(WHITE.mix(0.8))
+                .border_style(BLACK)
+                .draw()
+                .map_err(plot_error)?;
+        }
+        root.present().map_err(plot_error)
+    }
+}
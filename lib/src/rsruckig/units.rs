@@ -0,0 +1,117 @@
+//! Typed access to the input and output with `uom` quantities. The plain values are in SI units,
+//! i.e. meters for linear DoFs and radians for angular DoFs, so that e.g. limits given in degrees
+//! are converted instead of being mixed up with radians.
+use uom::si::acceleration::meter_per_second_squared;
+use uom::si::angle::radian;
+use uom::si::angular_acceleration::radian_per_second_squared;
+use uom::si::angular_jerk::radian_per_second_cubed;
+use uom::si::angular_velocity::radian_per_second;
+use uom::si::f64::{
+    Acceleration, Angle, AngularAcceleration, AngularJerk, AngularVelocity, Jerk, Length, Velocity,
+};
+use uom::si::jerk::meter_per_second_cubed;
+use uom::si::length::meter;
+use uom::si::velocity::meter_per_second;
+
+use crate::input_parameter::InputParameter;
+use crate::output_parameter::OutputParameter;
+
+impl<const DOF: usize> InputParameter<DOF> {
+    /// Set the current state of a linear DoF
+    pub fn set_linear_state(
+        &mut self,
+        dof: usize,
+        position: Length,
+        velocity: Velocity,
+        acceleration: Acceleration,
+    ) {
+        self.current_position[dof] = position.get::<meter>();
+        self.current_velocity[dof] = velocity.get::<meter_per_second>();
+        self.current_acceleration[dof] = acceleration.get::<meter_per_second_squared>();
+    }
+
+    /// Set the target state of a linear DoF
+    pub fn set_linear_target(
+        &mut self,
+        dof: usize,
+        position: Length,
+        velocity: Velocity,
+        acceleration: Acceleration,
+    ) {
+        self.target_position[dof] = position.get::<meter>();
+        self.target_velocity[dof] = velocity.get::<meter_per_second>();
+        self.target_acceleration[dof] = acceleration.get::<meter_per_second_squared>();
+    }
+
+    /// Set the kinematic limits of a linear DoF
+    pub fn set_linear_limits(
+        &mut self,
+        dof: usize,
+        max_velocity: Velocity,
+        max_acceleration: Acceleration,
+        max_jerk: Jerk,
+    ) {
+        self.max_velocity[dof] = max_velocity.get::<meter_per_second>();
+        self.max_acceleration[dof] = max_acceleration.get::<meter_per_second_squared>();
+        self.max_jerk[dof] = max_jerk.get::<meter_per_second_cubed>();
+    }
+
+    /// Set the current state of an angular DoF
+    pub fn set_angular_state(
+        &mut self,
+        dof: usize,
+        position: Angle,
+        velocity: AngularVelocity,
+        acceleration: AngularAcceleration,
+    ) {
+        self.current_position[dof] = position.get::<radian>();
+        self.current_velocity[dof] = velocity.get::<radian_per_second>();
+        self.current_acceleration[dof] = acceleration.get::<radian_per_second_squared>();
+    }
+
+    /// Set the target state of an angular DoF
+    pub fn set_angular_target(
+        &mut self,
+        dof: usize,
+        position: Angle,
+        velocity: AngularVelocity,
+        acceleration: AngularAcceleration,
+    ) {
+        self.target_position[dof] = position.get::<radian>();
+        self.target_velocity[dof] = velocity.get::<radian_per_second>();
+        self.target_acceleration[dof] = acceleration.get::<radian_per_second_squared>();
+    }
+
+    /// Set the kinematic limits of an angular DoF
+    pub fn set_angular_limits(
+        &mut self,
+        dof: usize,
+        max_velocity: AngularVelocity,
+        max_acceleration: AngularAcceleration,
+        max_jerk: AngularJerk,
+    ) {
+        self.max_velocity[dof] = max_velocity.get::<radian_per_second>();
+        self.max_acceleration[dof] = max_acceleration.get::<radian_per_second_squared>();
+        self.max_jerk[dof] = max_jerk.get::<radian_per_second_cubed>();
+    }
+}
+
+impl<const DOF: usize> OutputParameter<DOF> {
+    /// New position, velocity, and acceleration of a linear DoF
+    pub fn linear_state(&self, dof: usize) -> (Length, Velocity, Acceleration) {
+        (
+            Length::new::<meter>(self.new_position[dof]),
+            Velocity::new::<meter_per_second>(self.new_velocity[dof]),
+            Acceleration::new::<meter_per_second_squared>(self.new_acceleration[dof]),
+        )
+    }
+
+    /// New position, velocity, and acceleration of an angular DoF
+    pub fn angular_state(&self, dof: usize) -> (Angle, AngularVelocity, AngularAcceleration) {
+        (
+            Angle::new::<radian>(self.new_position[dof]),
+            AngularVelocity::new::<radian_per_second>(self.new_velocity[dof]),
+            AngularAcceleration::new::<radian_per_second_squared>(self.new_acceleration[dof]),
+        )
+    }
+}
@@ -1,4 +1,7 @@
 use std::ops::{Deref, DerefMut, Index, IndexMut};
+use std::time::Duration;
+
+use crate::error::RuckigError;
 
 pub fn join<const DOF: usize>(numbers: &[f64], high_precision: bool) -> String {
     if high_precision {
@@ -16,6 +19,37 @@ pub fn join<const DOF: usize>(numbers: &[f64], high_precision: bool) -> String {
     }
 }
 
+/// Neumaier's compensated summation, so that the rounding error of a sum, e.g. of the durations
+/// of many phases or sections, doesn't grow with the number of terms
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompensatedSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl CompensatedSum {
+    pub fn new(value: f64) -> Self {
+        Self {
+            sum: value,
+            compensation: 0.0,
+        }
+    }
+
+    pub fn add(&mut self, value: f64) {
+        let sum = self.sum + value;
+        if self.sum.abs() >= value.abs() {
+            self.compensation += (self.sum - sum) + value;
+        } else {
+            self.compensation += (value - sum) + self.sum;
+        }
+        self.sum = sum;
+    }
+
+    pub fn value(&self) -> f64 {
+        self.sum + self.compensation
+    }
+}
+
 #[inline]
 pub fn integrate(t: f64, p0: f64, v0: f64, a0: f64, j: f64) -> (f64, f64, f64) {
     (
@@ -25,62 +59,261 @@ pub fn integrate(t: f64, p0: f64, v0: f64, a0: f64, j: f64) -> (f64, f64, f64) {
     )
 }
 
-// A utility enum to store either an array or a vector
+/// Integrate a phase with a constant snap `s` for the time `t`, returning the position,
+/// velocity, acceleration, and jerk at its end
+#[inline]
+pub fn integrate_with_snap(
+    t: f64,
+    p0: f64,
+    v0: f64,
+    a0: f64,
+    j0: f64,
+    s: f64,
+) -> (f64, f64, f64, f64) {
+    (
+        p0 + t * (v0 + t * (a0 / 2.0 + t * (j0 / 6.0 + t * s / 24.0))),
+        v0 + t * (a0 + t * (j0 / 2.0 + t * s / 6.0)),
+        a0 + t * (j0 + t * s / 2.0),
+        j0 + t * s,
+    )
+}
+
+/// Convert a time in [s] to a duration rounded to nanoseconds, saturating at zero for negative or
+/// invalid times and at the maximal duration for infinite times
+pub fn duration_from_secs(seconds: f64) -> Duration {
+    if seconds.is_nan() || seconds <= 0.0 {
+        return Duration::ZERO;
+    }
+    Duration::try_from_secs_f64(seconds).unwrap_or(Duration::MAX)
+}
+
+/// Shortest signed distance on a circle with the given period, within `[-period/2, period/2)`
+#[inline]
+pub(crate) fn circular_distance(distance: f64, period: f64) -> f64 {
+    (distance + 0.5 * period).rem_euclid(period) - 0.5 * period
+}
+
+/// Measures the duration of a calculation. There is no clock without the `std` feature and on
+/// `wasm32-unknown-unknown`, where `std::time::Instant` panics, so the measured duration is
+/// always zero there.
+#[derive(Debug)]
+pub(crate) struct Stopwatch {
+    #[cfg(all(
+        feature = "std",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    start: std::time::Instant,
+}
+
+impl Stopwatch {
+    pub(crate) fn start() -> Self {
+        Self {
+            #[cfg(all(
+                feature = "std",
+                not(all(target_arch = "wasm32", target_os = "unknown"))
+            ))]
+            start: std::time::Instant::now(),
+        }
+    }
+
+    /// Elapsed time since the start in microseconds
+    pub(crate) fn elapsed_microseconds(&self) -> f64 {
+        #[cfg(all(
+            feature = "std",
+            not(all(target_arch = "wasm32", target_os = "unknown"))
+        ))]
+        {
+            (self.start.elapsed().as_nanos() as f64) / 1000.0
+        }
+        #[cfg(not(all(
+            feature = "std",
+            not(all(target_arch = "wasm32", target_os = "unknown"))
+        )))]
+        {
+            0.0
+        }
+    }
+}
+
+/// Time budget of an interruptible calculation, which is never exceeded without a clock
 #[derive(Debug)]
-pub enum DataArrayOrVec<T, const N: usize>
+pub(crate) struct Deadline {
+    stopwatch: Stopwatch,
+    budget: f64,
+}
+
+impl Deadline {
+    /// Start the budget in microseconds now
+    pub(crate) fn start(budget: f64) -> Self {
+        Self {
+            stopwatch: Stopwatch::start(),
+            budget,
+        }
+    }
+
+    pub(crate) fn is_exceeded(&self) -> bool {
+        self.stopwatch.elapsed_microseconds() > self.budget
+    }
+}
+
+/// Storage of one value per DoF, either an array for a number of DoFs known at compile time
+/// (N > 0) or a vector for a number of DoFs given at runtime (N = 0).
+///
+/// The storage is selected by the const parameter at compile time. Stable Rust can't map a const
+/// to a type, so both fields exist and the unused one stays empty, i.e. a zero-length array or a
+/// vector without allocation. All accesses branch on `N`, which the compiler resolves, so that
+/// the values of a fixed number of DoFs are indexed like a plain array without checking a variant
+/// at runtime.
+pub struct DataArrayOrVec<T, const N: usize>
 where
     T: std::fmt::Debug,
 {
-    Stack([T; N]),
-    Heap(Vec<T>),
+    array: [T; N],
+    vec: Vec<T>,
+}
+
+impl<T: std::fmt::Debug, const N: usize> DataArrayOrVec<T, N> {
+    /// Store the values of a fixed number of DoFs. For N = 0, this is an empty vector.
+    pub fn from_array(array: [T; N]) -> Self {
+        Self {
+            array,
+            vec: Vec::new(),
+        }
+    }
+
+    /// Store the values of a number of DoFs given at runtime. As the storage follows the type,
+    /// this fails with `DofMismatch` for N > 0 unless the vector has exactly N elements.
+    pub fn from_vec(vec: Vec<T>) -> Result<Self, RuckigError> {
+        if N == 0 {
+            return Ok(Self::from_dynamic_vec(vec));
+        }
+
+        vec.try_into()
+            .map(Self::from_array)
+            .map_err(|_| RuckigError::DofMismatch)
+    }
+
+    // The vector storage of N = 0, which takes any number of elements
+    fn from_dynamic_vec(vec: Vec<T>) -> Self {
+        debug_assert_eq!(N, 0);
+        Self {
+            array: std::array::from_fn(|_| unreachable!()),
+            vec,
+        }
+    }
+
+    #[deprecated(note = "use `DataArrayOrVec::from_array`")]
+    #[allow(non_snake_case)]
+    pub fn Stack(array: [T; N]) -> Self {
+        Self::from_array(array)
+    }
+
+    #[deprecated(note = "use `DataArrayOrVec::from_vec`")]
+    #[allow(non_snake_case)]
+    pub fn Heap(vec: Vec<T>) -> Result<Self, RuckigError> {
+        Self::from_vec(vec)
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        if N > 0 {
+            &self.array
+        } else {
+            &self.vec
+        }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        if N > 0 {
+            &mut self.array
+        } else {
+            &mut self.vec
+        }
+    }
 }
 
 impl<T: Default + Clone + std::fmt::Debug, const N: usize> DataArrayOrVec<T, N> {
     pub fn new(dofs: Option<usize>, initial: T) -> Self {
         let size = dofs.unwrap_or(1);
         if N > 0 {
-            let arr: [T; N] = std::array::from_fn(|_| initial.clone());
-            DataArrayOrVec::Stack(arr)
+            Self::from_array(std::array::from_fn(|_| initial.clone()))
         } else {
-            DataArrayOrVec::Heap(vec![initial; size])
+            Self::from_dynamic_vec(vec![initial; size])
         }
     }
 
     pub fn get(&self, index: usize) -> Option<&T> {
-        match self {
-            DataArrayOrVec::Heap(v) => v.get(index),
-            DataArrayOrVec::Stack(a) => a.get(index),
-        }
+        self.as_slice().get(index)
     }
 
-    pub fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
-        match self {
-            DataArrayOrVec::Heap(v) => Box::new(v.iter()),
-            DataArrayOrVec::Stack(a) => Box::new(a.iter()),
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.as_mut_slice().iter_mut()
+    }
+
+    /// Copy the values, which must be as many as stored. A vector keeps its allocation if it is
+    /// large enough.
+    pub(crate) fn assign(&mut self, values: &[T]) {
+        if N > 0 {
+            self.array.clone_from_slice(values);
+        } else {
+            self.vec.clear();
+            self.vec.extend_from_slice(values);
         }
     }
+}
 
-    pub fn iter_mut(&mut self) -> Box<dyn Iterator<Item = &mut T> + '_> {
-        match self {
-            DataArrayOrVec::Heap(v) => Box::new(v.iter_mut()),
-            DataArrayOrVec::Stack(a) => Box::new(a.iter_mut()),
+// Element-wise helpers, e.g. to convert units or to apply gear ratios to the limits
+impl<const N: usize> DataArrayOrVec<f64, N> {
+    /// Multiply each value by the factor
+    pub fn scale(&mut self, factor: f64) {
+        self.iter_mut().for_each(|x| *x *= factor);
+    }
+
+    /// Add the values of the other vector of the same length
+    pub fn add_assign(&mut self, other: &Self) {
+        assert_eq!(self.len(), other.len(), "vectors of different lengths.");
+        self.iter_mut().zip(other).for_each(|(x, y)| *x += y);
+    }
+
+    /// Clamp each value to the corresponding interval between min and max
+    pub fn clamp_to(&mut self, min: &Self, max: &Self) {
+        assert_eq!(self.len(), min.len(), "vectors of different lengths.");
+        assert_eq!(self.len(), max.len(), "vectors of different lengths.");
+        for ((x, lower), upper) in self.iter_mut().zip(min).zip(max) {
+            *x = x.clamp(*lower, *upper);
         }
     }
+
+    /// Euclidean norm
+    pub fn norm(&self) -> f64 {
+        self.iter().map(|x| x * x).sum::<f64>().sqrt()
+    }
+
+    /// Largest absolute value, or zero for an empty vector
+    pub fn max_abs(&self) -> f64 {
+        self.iter().fold(0.0, |max, x| max.max(x.abs()))
+    }
+}
+
+impl<T: std::fmt::Debug, const N: usize> std::fmt::Debug for DataArrayOrVec<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
 }
 
 impl<T: PartialEq + std::fmt::Debug, const N: usize> PartialEq for DataArrayOrVec<T, N> {
     fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (DataArrayOrVec::Stack(a), DataArrayOrVec::Stack(b)) => a == b,
-            (DataArrayOrVec::Heap(a), DataArrayOrVec::Heap(b)) => a == b,
-            _ => false,
-        }
+        self.as_slice() == other.as_slice()
     }
 }
 
+// Default values for a fixed number of DoFs, and no values for a number given at runtime
 impl<T: Clone + Default + std::fmt::Debug, const N: usize> Default for DataArrayOrVec<T, N> {
     fn default() -> Self {
-        DataArrayOrVec::Heap(Vec::new())
+        Self::from_array(std::array::from_fn(|_| T::default()))
     }
 }
 
@@ -88,10 +321,7 @@ impl<T: Clone + Default + std::fmt::Debug, const N: usize> Index<usize> for Data
     type Output = T;
 
     fn index(&self, index: usize) -> &Self::Output {
-        match self {
-            DataArrayOrVec::Heap(v) => &v[index],
-            DataArrayOrVec::Stack(a) => &a[index],
-        }
+        &self.as_slice()[index]
     }
 }
 
@@ -99,38 +329,306 @@ impl<T: Clone + Default + std::fmt::Debug, const N: usize> IndexMut<usize>
     for DataArrayOrVec<T, N>
 {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        match self {
-            DataArrayOrVec::Heap(v) => &mut v[index],
-            DataArrayOrVec::Stack(a) => &mut a[index],
-        }
+        &mut self.as_mut_slice()[index]
     }
 }
 
 impl<T: Clone + Default + std::fmt::Debug, const N: usize> Clone for DataArrayOrVec<T, N> {
     fn clone(&self) -> Self {
-        match self {
-            DataArrayOrVec::Heap(vec) => DataArrayOrVec::Heap(vec.clone()),
-            DataArrayOrVec::Stack(arr) => DataArrayOrVec::Stack(arr.clone()),
+        Self {
+            array: self.array.clone(),
+            vec: self.vec.clone(),
         }
     }
+
+    // Reuse the existing storage, so that copying between equally sized vectors doesn't allocate
+    fn clone_from(&mut self, source: &Self) {
+        self.array.clone_from(&source.array);
+        self.vec.clone_from(&source.vec);
+    }
 }
 
 impl<T: Clone + Default + std::fmt::Debug, const N: usize> Deref for DataArrayOrVec<T, N> {
     type Target = [T];
 
     fn deref(&self) -> &Self::Target {
-        match self {
-            DataArrayOrVec::Heap(vec) => vec,
-            DataArrayOrVec::Stack(arr) => arr,
-        }
+        self.as_slice()
     }
 }
 
 impl<T: Clone + Default + std::fmt::Debug, const N: usize> DerefMut for DataArrayOrVec<T, N> {
     fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<T: Clone + Default + std::fmt::Debug, const N: usize> AsRef<[T]> for DataArrayOrVec<T, N> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T: Clone + Default + std::fmt::Debug, const N: usize> AsMut<[T]> for DataArrayOrVec<T, N> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<'a, T: Clone + Default + std::fmt::Debug, const N: usize> IntoIterator
+    for &'a DataArrayOrVec<T, N>
+{
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: Clone + Default + std::fmt::Debug, const N: usize> IntoIterator
+    for &'a mut DataArrayOrVec<T, N>
+{
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Owning iterator over the values of a `DataArrayOrVec`
+#[derive(Debug)]
+pub enum IntoIter<T, const N: usize> {
+    Stack(std::array::IntoIter<T, N>),
+    Heap(std::vec::IntoIter<T>),
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        match self {
+            IntoIter::Stack(iter) => iter.next(),
+            IntoIter::Heap(iter) => iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            IntoIter::Stack(iter) => iter.size_hint(),
+            IntoIter::Heap(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    fn next_back(&mut self) -> Option<T> {
         match self {
-            DataArrayOrVec::Heap(vec) => vec,
-            DataArrayOrVec::Stack(arr) => arr,
+            IntoIter::Stack(iter) => iter.next_back(),
+            IntoIter::Heap(iter) => iter.next_back(),
+        }
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> std::iter::FusedIterator for IntoIter<T, N> {}
+
+impl<T: Clone + Default + std::fmt::Debug, const N: usize> IntoIterator for DataArrayOrVec<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        if N > 0 {
+            IntoIter::Stack(self.array.into_iter())
+        } else {
+            IntoIter::Heap(self.vec.into_iter())
+        }
+    }
+}
+
+// As with the deserialization, the storage follows the type. Collecting into the array panics
+// unless the iterator yields exactly N elements.
+impl<T: Clone + Default + std::fmt::Debug, const N: usize> FromIterator<T>
+    for DataArrayOrVec<T, N>
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        if N == 0 {
+            return Self::from_dynamic_vec(iter.into_iter().collect());
+        }
+
+        let mut iter = iter.into_iter();
+        let arr: [T; N] = std::array::from_fn(|i| {
+            iter.next()
+                .unwrap_or_else(|| panic!("expected {} elements, got {}.", N, i))
+        });
+        assert!(iter.next().is_none(), "expected {} elements, got more.", N);
+        Self::from_array(arr)
+    }
+}
+
+impl<T: std::fmt::Debug, const N: usize> TryFrom<Vec<T>> for DataArrayOrVec<T, N> {
+    type Error = RuckigError;
+
+    fn try_from(vec: Vec<T>) -> Result<Self, Self::Error> {
+        Self::from_vec(vec)
+    }
+}
+
+// Serialized as a plain sequence. On deserialization, the storage follows the type: a sequence of
+// exactly N elements for the array, or of any length for the vector (N = 0).
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize + Clone + Default + std::fmt::Debug, const N: usize> serde::Serialize
+    for DataArrayOrVec<T, N>
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de> + Clone + Default + std::fmt::Debug, const N: usize>
+    serde::Deserialize<'de> for DataArrayOrVec<T, N>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if N == 0 {
+            return Ok(DataArrayOrVec::from_dynamic_vec(Vec::<T>::deserialize(deserializer)?));
+        }
+
+        deserializer.deserialize_seq(StackVisitor(std::marker::PhantomData))
+    }
+}
+
+// Fills the array directly from the sequence
+#[cfg(feature = "serde")]
+struct StackVisitor<T, const N: usize>(std::marker::PhantomData<T>);
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de> + Clone + Default + std::fmt::Debug, const N: usize>
+    serde::de::Visitor<'de> for StackVisitor<T, N>
+{
+    type Value = DataArrayOrVec<T, N>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "a sequence of {} elements", N)
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut array: [T; N] = std::array::from_fn(|_| T::default());
+        for (i, value) in array.iter_mut().enumerate() {
+            *value = seq
+                .next_element()?
+                .ok_or_else(|| serde::de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<serde::de::IgnoredAny>()?.is_some() {
+            return Err(serde::de::Error::invalid_length(N + 1, &self));
+        }
+        Ok(DataArrayOrVec::from_array(array))
+    }
+}
+
+/// Serialization of float arrays that keeps their non-finite values, e.g. the infinite default
+/// jerk limit, which formats like JSON can't represent as numbers. They are written as the
+/// strings `"inf"`, `"-inf"` and `"NaN"`, while finite values stay numbers. Used with
+/// `#[serde(with = "...")]` on the fields of type `DataArrayOrVec<f64, N>`, and with the
+/// `option` module on optional ones.
+#[cfg(feature = "serde")]
+pub(crate) mod serde_float {
+    use super::DataArrayOrVec;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Debug, Clone, Copy, Default)]
+    struct Float(f64);
+
+    impl Serialize for Float {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match self.0 {
+                value if value.is_finite() => serializer.serialize_f64(value),
+                value if value.is_nan() => serializer.serialize_str("NaN"),
+                value if value > 0.0 => serializer.serialize_str("inf"),
+                _ => serializer.serialize_str("-inf"),
+            }
+        }
+    }
+
+    struct FloatVisitor;
+
+    impl serde::de::Visitor<'_> for FloatVisitor {
+        type Value = Float;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(formatter, "a number, or one of \"inf\", \"-inf\" and \"NaN\"")
+        }
+
+        fn visit_f64<E: serde::de::Error>(self, value: f64) -> Result<Float, E> {
+            Ok(Float(value))
+        }
+
+        fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Float, E> {
+            Ok(Float(value as f64))
+        }
+
+        fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Float, E> {
+            Ok(Float(value as f64))
+        }
+
+        fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Float, E> {
+            match value {
+                "inf" => Ok(Float(f64::INFINITY)),
+                "-inf" => Ok(Float(f64::NEG_INFINITY)),
+                "NaN" => Ok(Float(f64::NAN)),
+                _ => Err(E::invalid_value(serde::de::Unexpected::Str(value), &self)),
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Float {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_any(FloatVisitor)
+        }
+    }
+
+    pub fn serialize<S: Serializer, const N: usize>(
+        values: &DataArrayOrVec<f64, N>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(values.iter().map(|value| Float(*value)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<DataArrayOrVec<f64, N>, D::Error> {
+        let values = DataArrayOrVec::<Float, N>::deserialize(deserializer)?;
+        Ok(values.iter().map(|value| value.0).collect())
+    }
+
+    pub mod option {
+        use super::{DataArrayOrVec, Float};
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        struct Values<'a, const N: usize>(&'a DataArrayOrVec<f64, N>);
+
+        impl<const N: usize> Serialize for Values<'_, N> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                super::serialize(self.0, serializer)
+            }
+        }
+
+        pub fn serialize<S: Serializer, const N: usize>(
+            values: &Option<DataArrayOrVec<f64, N>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match values {
+                Some(values) => serializer.serialize_some(&Values(values)),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+            deserializer: D,
+        ) -> Result<Option<DataArrayOrVec<f64, N>>, D::Error> {
+            let values = Option::<DataArrayOrVec<Float, N>>::deserialize(deserializer)?;
+            Ok(values.map(|values| values.iter().map(|value| value.0).collect()))
         }
     }
 }
@@ -140,12 +638,12 @@ macro_rules! daov_stack {
     ($($x:expr),+ $(,)?) => {
         {
             let temp: [_; $crate::count_exprs!($($x),*)] = [$($x),*];
-            $crate::util::DataArrayOrVec::Stack(temp)
+            $crate::util::DataArrayOrVec::from_array(temp)
         }
     };
     ($x:expr; $n:expr) => {
         {
-            $crate::util::DataArrayOrVec::Stack([$x; $n])
+            $crate::util::DataArrayOrVec::from_array([$x; $n])
         }
     };
 }
@@ -155,12 +653,14 @@ macro_rules! daov_heap {
     ($($x:expr),+ $(,)?) => {
         {
             let temp: [_; $crate::count_exprs!($($x),*)] = [$($x),*];
-            $crate::util::DataArrayOrVec::Heap(temp.into())
+            ::std::iter::IntoIterator::into_iter(temp).collect::<$crate::util::DataArrayOrVec<_, 0>>()
         }
     };
     ($x:expr; $n:expr) => {
         {
-            $crate::util::DataArrayOrVec::Heap(vec![$x; $n])
+            ::std::iter::repeat($x)
+                .take($n)
+                .collect::<$crate::util::DataArrayOrVec<_, 0>>()
         }
     };
 }
@@ -171,3 +671,13 @@ macro_rules! count_exprs {
     ($x:expr) => (1usize);
     ($x:expr, $($xs:expr),* $(,)?) => (1usize + $crate::count_exprs!($($xs),*));
 }
+
+/// Name the step type and method that calculates the following profiles in the trace of the
+/// `debug-trace` feature
+macro_rules! trace_strategy {
+    ($strategy:expr) => {
+        #[cfg(feature = "debug-trace")]
+        $crate::trace::set_strategy($strategy);
+    };
+}
+pub(crate) use trace_strategy;
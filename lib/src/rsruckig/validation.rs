@@ -0,0 +1,343 @@
+//! Structured report of an input validation.
+use std::fmt;
+
+/// The value of the input that violates a condition
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputQuantity {
+    CurrentPosition,
+    CurrentVelocity,
+    CurrentAcceleration,
+    TargetPosition,
+    TargetVelocity,
+    TargetAcceleration,
+    /// Intermediate position with the given index
+    IntermediatePosition(usize),
+    /// Blend tolerance of the intermediate position with the given index
+    BlendTolerance(usize),
+    MaxPosition,
+    MinPosition,
+    MaxVelocity,
+    MinVelocity,
+    MaxAcceleration,
+    MinAcceleration,
+    MaxJerk,
+    MaxSnap,
+    /// Minimum duration of the trajectory
+    MinimumDuration,
+    /// Maximum duration of the trajectory
+    MaximumDuration,
+    /// Step of a discrete duration
+    DiscretizationStep,
+    /// Period of a circular DoF
+    Period,
+    TargetPositionTolerance,
+    TargetVelocityTolerance,
+    /// Hold time before the motion
+    PreDwell,
+    /// Hold time after the motion
+    PostDwell,
+    /// Measured position that is passed to the input, see
+    /// `OutputParameter::pass_to_input_with_feedback`
+    MeasuredPosition,
+}
+
+impl InputQuantity {
+    fn name(&self) -> String {
+        match self {
+            InputQuantity::CurrentPosition => "current position".to_string(),
+            InputQuantity::CurrentVelocity => "current velocity".to_string(),
+            InputQuantity::CurrentAcceleration => "current acceleration".to_string(),
+            InputQuantity::TargetPosition => "target position".to_string(),
+            InputQuantity::TargetVelocity => "target velocity".to_string(),
+            InputQuantity::TargetAcceleration => "target acceleration".to_string(),
+            InputQuantity::IntermediatePosition(index) => {
+                format!("intermediate position {}", index)
+            }
+            InputQuantity::BlendTolerance(index) => {
+                format!("blend tolerance of intermediate position {}", index)
+            }
+            InputQuantity::MaxPosition => "maximum position limit".to_string(),
+            InputQuantity::MinPosition => "minimum position limit".to_string(),
+            InputQuantity::MaxVelocity => "maximum velocity limit".to_string(),
+            InputQuantity::MinVelocity => "minimum velocity limit".to_string(),
+            InputQuantity::MaxAcceleration => "maximum acceleration limit".to_string(),
+            InputQuantity::MinAcceleration => "minimum acceleration limit".to_string(),
+            InputQuantity::MaxJerk => "maximum jerk limit".to_string(),
+            InputQuantity::MaxSnap => "maximum snap limit".to_string(),
+            InputQuantity::MinimumDuration => "minimum duration".to_string(),
+            InputQuantity::MaximumDuration => "maximum duration".to_string(),
+            InputQuantity::DiscretizationStep => "duration discretization step".to_string(),
+            InputQuantity::Period => "period".to_string(),
+            InputQuantity::TargetPositionTolerance => "target position tolerance".to_string(),
+            InputQuantity::TargetVelocityTolerance => "target velocity tolerance".to_string(),
+            InputQuantity::PreDwell => "pre-dwell duration".to_string(),
+            InputQuantity::PostDwell => "post-dwell duration".to_string(),
+            InputQuantity::MeasuredPosition => "measured position".to_string(),
+        }
+    }
+
+    /// Name of the kinematic order, used for the limits of the quantity
+    fn order_name(&self) -> &'static str {
+        match self {
+            InputQuantity::CurrentPosition
+            | InputQuantity::TargetPosition
+            | InputQuantity::IntermediatePosition(_)
+            | InputQuantity::BlendTolerance(_)
+            | InputQuantity::MaxPosition
+            | InputQuantity::MinPosition
+            | InputQuantity::Period
+            | InputQuantity::TargetPositionTolerance
+            | InputQuantity::MeasuredPosition => "position",
+            InputQuantity::CurrentVelocity
+            | InputQuantity::TargetVelocity
+            | InputQuantity::MaxVelocity
+            | InputQuantity::MinVelocity
+            | InputQuantity::TargetVelocityTolerance => "velocity",
+            InputQuantity::CurrentAcceleration
+            | InputQuantity::TargetAcceleration
+            | InputQuantity::MaxAcceleration
+            | InputQuantity::MinAcceleration => "acceleration",
+            InputQuantity::MaxJerk => "jerk",
+            InputQuantity::MaxSnap => "snap",
+            InputQuantity::MinimumDuration
+            | InputQuantity::MaximumDuration
+            | InputQuantity::DiscretizationStep
+            | InputQuantity::PreDwell
+            | InputQuantity::PostDwell => "duration",
+        }
+    }
+}
+
+/// The condition that a value of the input violates
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ViolationKind {
+    /// The value is NaN
+    NotANumber,
+    /// The value is infinite, although a finite value is expected
+    Infinite,
+    /// A maximum limit is negative
+    Negative,
+    /// The value is zero or negative
+    NotPositive,
+    /// A minimum limit is positive
+    Positive,
+    /// The minimum limit is larger than the maximum limit given by `allowed`
+    MinimumAboveMaximum,
+    /// The value exceeds the maximum limit given by `allowed`
+    AboveMaximum,
+    /// The value undercuts the minimum limit given by `allowed`
+    BelowMinimum,
+    /// The state will inevitably reach the velocity `value` above the maximum velocity limit
+    InevitablyAboveMaximumVelocity,
+    /// The state will inevitably reach the velocity `value` below the minimum velocity limit
+    InevitablyBelowMinimumVelocity,
+    /// The intermediate or measured position has `value` elements instead of `allowed` DoFs
+    WrongLength,
+    /// The control interface of the DoF doesn't support the value, i.e. intermediate positions
+    /// and a snap limit require the position interface, and the acceleration interface has no
+    /// target velocity
+    UnsupportedControlInterface,
+    /// The state of the DoF isn't at rest, which a dwell and a snap limit require
+    NotAtRest,
+    /// The limit selects profiles of an order whose feature isn't enabled, i.e. a finite maximum
+    /// jerk the `third-order` feature, an infinite one the `second-order` feature, and an infinite
+    /// maximum acceleration of the position or velocity interface the `first-order` feature
+    OrderNotCompiled,
+}
+
+/// A single violated condition of the input
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidationViolation {
+    /// The violating DoF, if the condition refers to a single DoF
+    pub dof: Option<usize>,
+    pub quantity: InputQuantity,
+    pub kind: ViolationKind,
+    /// The violating value
+    pub value: f64,
+    /// The limit that the value should keep, if there is one
+    pub allowed: Option<f64>,
+}
+
+impl fmt::Display for ValidationViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = self.quantity.name();
+        let order = self.quantity.order_name();
+        let dof = self.dof.unwrap_or_default();
+        let of_dof = match self.dof {
+            Some(dof) => format!(" of DoF {}", dof),
+            None => String::new(),
+        };
+        let allowed = self.allowed.unwrap_or(f64::NAN);
+        match self.kind {
+            ViolationKind::NotANumber => write!(
+                f,
+                "{} {}{} should be a valid number.",
+                name, self.value, of_dof
+            ),
+            ViolationKind::Infinite => {
+                write!(f, "{} {}{} should be finite.", name, self.value, of_dof)
+            }
+            ViolationKind::Negative => write!(
+                f,
+                "{} {}{} should be larger than or equal to zero.",
+                name, self.value, of_dof
+            ),
+            ViolationKind::NotPositive => write!(
+                f,
+                "{} {}{} should be larger than zero.",
+                name, self.value, of_dof
+            ),
+            ViolationKind::Positive => write!(
+                f,
+                "{} {} of DoF {} should be smaller than or equal to zero.",
+                name, self.value, dof
+            ),
+            ViolationKind::MinimumAboveMaximum => write!(
+                f,
+                "{} {} of DoF {} should be smaller than or equal to its maximum {} limit {}.",
+                name, self.value, dof, order, allowed
+            ),
+            ViolationKind::AboveMaximum => write!(
+                f,
+                "{} {} of DoF {} exceeds its maximum {} limit {}.",
+                name, self.value, dof, order, allowed
+            ),
+            ViolationKind::BelowMinimum => write!(
+                f,
+                "{} {} of DoF {} undercuts its minimum {} limit {}.",
+                name, self.value, dof, order, allowed
+            ),
+            ViolationKind::InevitablyAboveMaximumVelocity => {
+                if self.quantity == InputQuantity::CurrentVelocity {
+                    write!(f, "DoF {} will inevitably reach a velocity {} from the current kinematic state that will exceed its maximum velocity limit {}.", dof, self.value, allowed)
+                } else {
+                    write!(f, "DoF {} will inevitably have reached a velocity {} from the target kinematic state that will exceed its maximum velocity limit {}.", dof, self.value, allowed)
+                }
+            }
+            ViolationKind::InevitablyBelowMinimumVelocity => {
+                if self.quantity == InputQuantity::CurrentVelocity {
+                    write!(f, "DoF {} will inevitably reach a velocity {} from the current kinematic state that will undercut its minimum velocity limit {}.", dof, self.value, allowed)
+                } else {
+                    write!(f, "DoF {} will inevitably have reached a velocity {} from the target kinematic state that will undercut its minimum velocity limit {}.", dof, self.value, allowed)
+                }
+            }
+            ViolationKind::WrongLength => write!(
+                f,
+                "{} has {} values, but there are {} degrees of freedom.",
+                name, self.value, allowed
+            ),
+            ViolationKind::UnsupportedControlInterface
+                if self.quantity == InputQuantity::TargetVelocity =>
+            {
+                write!(
+                    f,
+                    "{} {} of DoF {} should be zero for the acceleration control interface.",
+                    name, self.value, dof
+                )
+            }
+            ViolationKind::UnsupportedControlInterface => {
+                let requirement = if self.quantity == InputQuantity::MaxSnap {
+                    "a snap limit requires"
+                } else {
+                    "intermediate positions require"
+                };
+                write!(
+                    f,
+                    "{} the position control interface for DoF {}.",
+                    requirement, dof
+                )
+            }
+            ViolationKind::NotAtRest => write!(
+                f,
+                "{} {} of DoF {} should be zero for a dwell or a snap limit.",
+                name, self.value, dof
+            ),
+            ViolationKind::OrderNotCompiled => {
+                let feature = match self.quantity {
+                    InputQuantity::MaxJerk if self.value.is_finite() => "third-order",
+                    InputQuantity::MaxJerk => "second-order",
+                    _ => "first-order",
+                };
+                write!(
+                    f,
+                    "{} {} of DoF {} requires the {} feature.",
+                    name, self.value, dof, feature
+                )
+            }
+        }
+    }
+}
+
+/// All violated conditions of an input, in the order in which they are checked
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidationReport {
+    pub violations: Vec<ValidationViolation>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// All violations of a single DoF
+    pub fn violations_of_dof(&self, dof: usize) -> impl Iterator<Item = &ValidationViolation> {
+        self.violations
+            .iter()
+            .filter(move |violation| violation.dof == Some(dof))
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for violation in &self.violations {
+            writeln!(f, "{}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+/// A limit or target state that a calculated trajectory violates
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LimitViolation {
+    /// Time of the sample at which the violation is found
+    pub time: f64,
+    pub dof: usize,
+    /// The violated limit, or the target state that isn't reached at the end of the trajectory
+    pub quantity: InputQuantity,
+    /// The sampled value of the trajectory
+    pub value: f64,
+    /// The value of the limit or target state
+    pub allowed: f64,
+}
+
+impl fmt::Display for LimitViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let order = self.quantity.order_name();
+        match self.quantity {
+            InputQuantity::TargetPosition
+            | InputQuantity::TargetVelocity
+            | InputQuantity::TargetAcceleration => write!(
+                f,
+                "final {} {} of DoF {} misses its target {} {}.",
+                order, self.value, self.dof, order, self.allowed
+            ),
+            InputQuantity::MinPosition
+            | InputQuantity::MinVelocity
+            | InputQuantity::MinAcceleration => write!(
+                f,
+                "{} {} of DoF {} at time {} undercuts its minimum {} limit {}.",
+                order, self.value, self.dof, self.time, order, self.allowed
+            ),
+            _ => write!(
+                f,
+                "{} {} of DoF {} at time {} exceeds its maximum {} limit {}.",
+                order, self.value, self.dof, self.time, order, self.allowed
+            ),
+        }
+    }
+}
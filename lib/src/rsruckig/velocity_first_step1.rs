@@ -0,0 +1,39 @@
+//! Mathematical equations for Step 1 in first-order velocity interface: Extremal profiles
+use crate::block::Block;
+use crate::profile::{ControlSigns, Profile, ReachedLimits};
+use crate::util::trace_strategy;
+
+#[derive(Debug)]
+pub struct VelocityFirstOrderStep1 {
+    _v_max: f64,
+    _v_min: f64,
+}
+
+impl VelocityFirstOrderStep1 {
+    pub fn new(v_max: f64, v_min: f64) -> Self {
+        Self {
+            _v_max: v_max,
+            _v_min: v_min,
+        }
+    }
+    pub fn get_profile(&mut self, input: &Profile, block: &mut Block) -> bool {
+        trace_strategy!("VelocityFirstOrderStep1::get_profile");
+        let p = &mut block.p_min;
+        p.set_boundary_from_profile(input);
+
+        // The target velocity is reached instantly
+        p.t = [0.0; 7];
+
+        if p.check_for_first_order_velocity(
+            ControlSigns::UDDU,
+            ReachedLimits::None,
+            self._v_max,
+            self._v_min,
+        ) {
+            block.t_min =
+                p.t_sum.last().unwrap() + p.pre_dwell + p.brake.duration + p.accel.duration;
+            return true;
+        }
+        false
+    }
+}
@@ -0,0 +1,37 @@
+//! Mathematical equations for Step 2 in first-order velocity interface: Time synchronization
+use crate::profile::{ControlSigns, Profile, ReachedLimits};
+use crate::util::trace_strategy;
+
+#[derive(Debug)]
+pub struct VelocityFirstOrderStep2 {
+    tf: f64,
+    _v_max: f64,
+    _v_min: f64,
+}
+
+impl VelocityFirstOrderStep2 {
+    pub fn new(tf: f64, v_max: f64, v_min: f64) -> Self {
+        Self {
+            tf,
+            _v_max: v_max,
+            _v_min: v_min,
+        }
+    }
+
+    pub fn get_profile(&mut self, profile: &mut Profile) -> bool {
+        trace_strategy!("VelocityFirstOrderStep2::get_profile");
+        // Hold the target velocity for the whole duration
+        profile.t = [0.0, 0.0, 0.0, self.tf, 0.0, 0.0, 0.0];
+
+        if profile.check_for_first_order_velocity(
+            ControlSigns::UDDU,
+            ReachedLimits::None,
+            self._v_max,
+            self._v_min,
+        ) {
+            profile.pf = *profile.p.last().unwrap();
+            return true;
+        }
+        false
+    }
+}
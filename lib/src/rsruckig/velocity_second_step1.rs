@@ -3,6 +3,7 @@
 use crate::{
     block::Block,
     profile::{ControlSigns, Profile, ReachedLimits},
+    util::trace_strategy,
 };
 
 #[derive(Debug)]
@@ -21,6 +22,7 @@ impl VelocitySecondOrderStep1 {
         }
     }
     pub fn get_profile(&mut self, input: &Profile, block: &mut Block) -> bool {
+        trace_strategy!("VelocitySecondOrderStep1::get_profile");
         let p = &mut block.p_min;
         p.set_boundary_from_profile(input);
 
@@ -38,7 +40,8 @@ impl VelocitySecondOrderStep1 {
         p.t[6] = 0.0;
 
         if p.check_for_second_order_velocity(ControlSigns::UDDU, ReachedLimits::Acc0, af) {
-            block.t_min = p.t_sum.last().unwrap() + p.brake.duration + p.accel.duration;
+            block.t_min =
+                p.t_sum.last().unwrap() + p.pre_dwell + p.brake.duration + p.accel.duration;
             return true;
         }
 
@@ -1,6 +1,7 @@
 //! Mathematical equations for Step 2 in second-order velocity interface: Time synchronization
 
 use crate::profile::{ControlSigns, Profile, ReachedLimits};
+use crate::util::trace_strategy;
 
 #[derive(Debug)]
 pub struct VelocitySecondOrderStep2 {
@@ -21,6 +22,7 @@ impl VelocitySecondOrderStep2 {
     }
 
     pub fn get_profile(&mut self, profile: &mut Profile) -> bool {
+        trace_strategy!("VelocitySecondOrderStep2::get_profile");
         let af = self.vd / self.tf;
         profile.t[0] = 0.0;
         profile.t[1] = self.tf;
@@ -3,6 +3,7 @@
 use crate::{
     block::{self, Block, Interval},
     profile::{ControlSigns, Profile, ReachedLimits},
+    util::trace_strategy,
 };
 
 #[derive(Debug)]
@@ -41,6 +42,7 @@ impl VelocityThirdOrderStep1 {
     }
 
     fn time_acc0(&mut self, a_max: f64, a_min: f64, j_max: f64, _: bool) {
+        trace_strategy!("VelocityThirdOrderStep1::time_acc0");
         let profile = &mut self.valid_profiles[self.current_index];
         profile.t[0] = (-self.a0 + a_max) / j_max;
         profile.t[1] = (self.a0 * self.a0 + self.af * self.af) / (2.0 * a_max * j_max)
@@ -59,6 +61,7 @@ impl VelocityThirdOrderStep1 {
     }
 
     fn time_none(&mut self, a_max: f64, a_min: f64, j_max: f64, return_after_found: bool) {
+        trace_strategy!("VelocityThirdOrderStep1::time_none");
         let mut h1 = (self.a0 * self.a0 + self.af * self.af) / 2.0 + j_max * self.vd;
         if h1 >= 0.0 {
             h1 = f64::sqrt(h1);
@@ -119,6 +122,7 @@ impl VelocityThirdOrderStep1 {
         a_min: f64,
         _: f64,
     ) -> bool {
+        trace_strategy!("VelocityThirdOrderStep1::time_all_single_step");
         if f64::abs(self.af - self.a0) > f64::EPSILON {
             return false;
         }
@@ -158,13 +162,17 @@ impl VelocityThirdOrderStep1 {
     }
 
     pub fn get_profile(&mut self, input: &mut Profile, block: &mut block::Block) -> bool {
+        trace_strategy!("VelocityThirdOrderStep1::get_profile");
         // Zero-limits special case
         if self._j_max == 0.0 {
             let p = &mut block.p_min;
             p.set_boundary_from_profile(input);
 
             if self.time_all_single_step(p, self._a_max, self._a_min, self._j_max) {
-                block.t_min = *p.t_sum.last().unwrap_or(&0.0) + p.brake.duration + p.accel.duration;
+                block.t_min = *p.t_sum.last().unwrap_or(&0.0)
+                    + p.pre_dwell
+                    + p.brake.duration
+                    + p.accel.duration;
                 if f64::abs(self.a0) > f64::EPSILON {
                     block.a = Some(Interval::new(block.t_min, f64::INFINITY));
                 }
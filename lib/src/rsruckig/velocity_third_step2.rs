@@ -1,6 +1,7 @@
 //! Mathematical equations for Step 2 in third-order velocity interface: Time synchronization
 
 use crate::profile::{ControlSigns, Profile, ReachedLimits};
+use crate::util::trace_strategy;
 
 pub struct VelocityThirdOrderStep2 {
     a0: f64,
@@ -37,6 +38,7 @@ impl VelocityThirdOrderStep2 {
     }
 
     fn time_acc0(&mut self, profile: &mut Profile, a_max: f64, a_min: f64, j_max: f64) -> bool {
+        trace_strategy!("VelocityThirdOrderStep2::time_acc0");
         // UD Solution 1/2
         {
             let h1 = f64::sqrt(
@@ -117,6 +119,7 @@ impl VelocityThirdOrderStep2 {
     }
 
     fn time_none(&mut self, profile: &mut Profile, a_max: f64, a_min: f64, j_max: f64) -> bool {
+        trace_strategy!("VelocityThirdOrderStep2::time_none");
         if f64::abs(self.a0) < f64::EPSILON
             && f64::abs(self.af) < f64::EPSILON
             && f64::abs(self.vd) < f64::EPSILON
@@ -155,12 +158,14 @@ impl VelocityThirdOrderStep2 {
 
             let jf = self.ad * self.ad / h1;
 
-            if profile.check_for_velocity(
+            if profile.check_for_velocity_with_timing_full(
+                self.tf,
                 ControlSigns::UDDU,
                 ReachedLimits::None,
                 jf,
                 a_max,
                 a_min,
+                j_max,
             ) {
                 profile.pf = *profile.p.last().unwrap();
                 return true;
@@ -176,6 +181,7 @@ impl VelocityThirdOrderStep2 {
     }
 
     pub fn get_profile(&mut self, profile: &mut Profile) -> bool {
+        trace_strategy!("VelocityThirdOrderStep2::get_profile");
         // Test all cases to get ones that match
         // However we should guess which one is correct and try them first...
         if self.vd > 0.0 {
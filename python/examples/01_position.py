@@ -0,0 +1,43 @@
+from copy import copy
+
+from rsruckig import InputParameter, OutputParameter, Result, Ruckig
+
+
+if __name__ == '__main__':
+    # Create instances: the Ruckig OTG as well as input and output parameters
+    otg = Ruckig(3, 0.01)  # DoFs, control cycle
+    inp = InputParameter(3)
+    out = OutputParameter(3)
+
+    # Set input parameters
+    inp.current_position = [0.0, 0.0, 0.5]
+    inp.current_velocity = [0.0, -2.2, -0.5]
+    inp.current_acceleration = [0.0, 2.5, -0.5]
+
+    inp.target_position = [5.0, -2.0, -3.5]
+    inp.target_velocity = [0.0, -0.5, -2.0]
+    inp.target_acceleration = [0.0, 0.0, 0.5]
+
+    inp.max_velocity = [3.0, 1.0, 3.0]
+    inp.max_acceleration = [3.0, 2.0, 1.0]
+    inp.max_jerk = [4.0, 3.0, 2.0]
+
+    print('\t'.join(['t'] + [str(i) for i in range(otg.degrees_of_freedom)]))
+
+    # Generate the trajectory within the control loop
+    first_calculation_duration, first_trajectory, out_list = None, None, []
+    res = Result.Working
+    while res == Result.Working:
+        res = otg.update(inp, out)
+
+        print('\t'.join([f'{out.time:0.3f}'] + [f'{p:0.3f}' for p in out.new_position]))
+        out_list.append(copy(out.new_position))
+
+        out.pass_to_input(inp)
+
+        if not first_trajectory:
+            first_calculation_duration = out.calculation_duration
+            first_trajectory = out.trajectory
+
+    print(f'Calculation duration: {first_calculation_duration:0.1f} [µs]')
+    print(f'Trajectory duration: {first_trajectory.duration:0.4f} [s]')
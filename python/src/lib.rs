@@ -0,0 +1,961 @@
+//! Python bindings for rsruckig, mirroring the API of the upstream `ruckig` Python module.
+//!
+//! The degrees of freedom are always given at runtime, so all types use heap storage.
+
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyValueError};
+use pyo3::prelude::*;
+use rsruckig::error::ThrowErrorHandler;
+use rsruckig::input_parameter::{
+    ControlInterface as RsControlInterface, DurationDiscretization as RsDurationDiscretization,
+    InputParameter as RsInputParameter, Synchronization as RsSynchronization,
+};
+use rsruckig::output_parameter::OutputParameter as RsOutputParameter;
+use rsruckig::result::RuckigResult;
+use rsruckig::ruckig::Ruckig as RsRuckig;
+use rsruckig::trajectory::Trajectory as RsTrajectory;
+use rsruckig::util::DataArrayOrVec;
+
+create_exception!(rsruckig, RuckigError, PyException);
+
+fn to_py_err(error: rsruckig::error::RuckigError) -> PyErr {
+    RuckigError::new_err(error.to_string().trim().to_string())
+}
+
+fn to_data<T: Clone + Default + std::fmt::Debug>(
+    values: Vec<T>,
+    dofs: usize,
+    name: &str,
+) -> PyResult<DataArrayOrVec<T, 0>> {
+    if values.len() != dofs {
+        return Err(PyValueError::new_err(format!(
+            "{} has {} values, but there are {} degrees of freedom.",
+            name,
+            values.len(),
+            dofs
+        )));
+    }
+    Ok(values.into_iter().collect())
+}
+
+fn to_optional_data(
+    values: Option<Vec<f64>>,
+    dofs: usize,
+    name: &str,
+) -> PyResult<Option<DataArrayOrVec<f64, 0>>> {
+    values.map(|values| to_data(values, dofs, name)).transpose()
+}
+
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum ControlInterface {
+    Position,
+    Velocity,
+    Acceleration,
+}
+
+impl From<ControlInterface> for RsControlInterface {
+    fn from(value: ControlInterface) -> Self {
+        match value {
+            ControlInterface::Position => RsControlInterface::Position,
+            ControlInterface::Velocity => RsControlInterface::Velocity,
+            ControlInterface::Acceleration => RsControlInterface::Acceleration,
+        }
+    }
+}
+
+impl From<&RsControlInterface> for ControlInterface {
+    fn from(value: &RsControlInterface) -> Self {
+        match value {
+            RsControlInterface::Position => ControlInterface::Position,
+            RsControlInterface::Velocity => ControlInterface::Velocity,
+            RsControlInterface::Acceleration => ControlInterface::Acceleration,
+        }
+    }
+}
+
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum Synchronization {
+    Time,
+    TimeIfNecessary,
+    Phase,
+    No,
+}
+
+impl From<Synchronization> for RsSynchronization {
+    fn from(value: Synchronization) -> Self {
+        match value {
+            Synchronization::Time => RsSynchronization::Time,
+            Synchronization::TimeIfNecessary => RsSynchronization::TimeIfNecessary,
+            Synchronization::Phase => RsSynchronization::Phase,
+            Synchronization::No => RsSynchronization::None,
+        }
+    }
+}
+
+impl From<&RsSynchronization> for Synchronization {
+    fn from(value: &RsSynchronization) -> Self {
+        match value {
+            RsSynchronization::Time => Synchronization::Time,
+            RsSynchronization::TimeIfNecessary => Synchronization::TimeIfNecessary,
+            RsSynchronization::Phase => Synchronization::Phase,
+            RsSynchronization::None => Synchronization::No,
+        }
+    }
+}
+
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum DurationDiscretization {
+    Continuous,
+    Discrete,
+}
+
+impl From<DurationDiscretization> for RsDurationDiscretization {
+    fn from(value: DurationDiscretization) -> Self {
+        match value {
+            DurationDiscretization::Continuous => RsDurationDiscretization::Continuous,
+            DurationDiscretization::Discrete => RsDurationDiscretization::Discrete,
+        }
+    }
+}
+
+impl From<&RsDurationDiscretization> for DurationDiscretization {
+    fn from(value: &RsDurationDiscretization) -> Self {
+        match value {
+            RsDurationDiscretization::Continuous => DurationDiscretization::Continuous,
+            RsDurationDiscretization::Discrete | RsDurationDiscretization::DiscreteStep(_) => {
+                DurationDiscretization::Discrete
+            }
+        }
+    }
+}
+
+#[pyclass(name = "Result", eq, eq_int)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum PyRuckigResult {
+    Working = 0,
+    Finished = 1,
+    Interrupted = 2,
+    Error = -1,
+    ErrorInvalidInput = -100,
+    ErrorTrajectoryDuration = -101,
+    ErrorPositionalLimits = -102,
+    ErrorZeroLimits = -104,
+    ErrorExecutionTimeCalculation = -110,
+    ErrorSynchronizationCalculation = -111,
+}
+
+impl From<RuckigResult> for PyRuckigResult {
+    fn from(value: RuckigResult) -> Self {
+        match value {
+            RuckigResult::Working => PyRuckigResult::Working,
+            RuckigResult::Finished => PyRuckigResult::Finished,
+            RuckigResult::Interrupted => PyRuckigResult::Interrupted,
+            RuckigResult::Error => PyRuckigResult::Error,
+            RuckigResult::ErrorInvalidInput => PyRuckigResult::ErrorInvalidInput,
+            RuckigResult::ErrorTrajectoryDuration => PyRuckigResult::ErrorTrajectoryDuration,
+            RuckigResult::ErrorPositionalLimits => PyRuckigResult::ErrorPositionalLimits,
+            RuckigResult::ErrorZeroLimits => PyRuckigResult::ErrorZeroLimits,
+            RuckigResult::ErrorExecutionTimeCalculation => {
+                PyRuckigResult::ErrorExecutionTimeCalculation
+            }
+            RuckigResult::ErrorSynchronizationCalculation => {
+                PyRuckigResult::ErrorSynchronizationCalculation
+            }
+        }
+    }
+}
+
+#[pyclass(name = "InputParameter")]
+#[derive(Clone)]
+pub struct PyInputParameter {
+    inner: RsInputParameter<0>,
+}
+
+#[pymethods]
+impl PyInputParameter {
+    #[new]
+    fn new(dofs: usize) -> Self {
+        Self {
+            inner: RsInputParameter::new(Some(dofs)),
+        }
+    }
+
+    #[getter]
+    fn degrees_of_freedom(&self) -> usize {
+        self.inner.degrees_of_freedom
+    }
+
+    #[getter]
+    fn current_position(&self) -> Vec<f64> {
+        self.inner.current_position.to_vec()
+    }
+
+    #[setter]
+    fn set_current_position(&mut self, values: Vec<f64>) -> PyResult<()> {
+        self.inner.current_position =
+            to_data(values, self.inner.degrees_of_freedom, "current_position")?;
+        Ok(())
+    }
+
+    #[getter]
+    fn current_velocity(&self) -> Vec<f64> {
+        self.inner.current_velocity.to_vec()
+    }
+
+    #[setter]
+    fn set_current_velocity(&mut self, values: Vec<f64>) -> PyResult<()> {
+        self.inner.current_velocity =
+            to_data(values, self.inner.degrees_of_freedom, "current_velocity")?;
+        Ok(())
+    }
+
+    #[getter]
+    fn current_acceleration(&self) -> Vec<f64> {
+        self.inner.current_acceleration.to_vec()
+    }
+
+    #[setter]
+    fn set_current_acceleration(&mut self, values: Vec<f64>) -> PyResult<()> {
+        self.inner.current_acceleration =
+            to_data(values, self.inner.degrees_of_freedom, "current_acceleration")?;
+        Ok(())
+    }
+
+    #[getter]
+    fn target_position(&self) -> Vec<f64> {
+        self.inner.target_position.to_vec()
+    }
+
+    #[setter]
+    fn set_target_position(&mut self, values: Vec<f64>) -> PyResult<()> {
+        self.inner.target_position =
+            to_data(values, self.inner.degrees_of_freedom, "target_position")?;
+        Ok(())
+    }
+
+    #[getter]
+    fn target_velocity(&self) -> Vec<f64> {
+        self.inner.target_velocity.to_vec()
+    }
+
+    #[setter]
+    fn set_target_velocity(&mut self, values: Vec<f64>) -> PyResult<()> {
+        self.inner.target_velocity =
+            to_data(values, self.inner.degrees_of_freedom, "target_velocity")?;
+        Ok(())
+    }
+
+    #[getter]
+    fn target_acceleration(&self) -> Vec<f64> {
+        self.inner.target_acceleration.to_vec()
+    }
+
+    #[setter]
+    fn set_target_acceleration(&mut self, values: Vec<f64>) -> PyResult<()> {
+        self.inner.target_acceleration =
+            to_data(values, self.inner.degrees_of_freedom, "target_acceleration")?;
+        Ok(())
+    }
+
+    #[getter]
+    fn max_velocity(&self) -> Vec<f64> {
+        self.inner.max_velocity.to_vec()
+    }
+
+    #[setter]
+    fn set_max_velocity(&mut self, values: Vec<f64>) -> PyResult<()> {
+        self.inner.max_velocity = to_data(values, self.inner.degrees_of_freedom, "max_velocity")?;
+        Ok(())
+    }
+
+    #[getter]
+    fn max_acceleration(&self) -> Vec<f64> {
+        self.inner.max_acceleration.to_vec()
+    }
+
+    #[setter]
+    fn set_max_acceleration(&mut self, values: Vec<f64>) -> PyResult<()> {
+        self.inner.max_acceleration =
+            to_data(values, self.inner.degrees_of_freedom, "max_acceleration")?;
+        Ok(())
+    }
+
+    #[getter]
+    fn max_jerk(&self) -> Vec<f64> {
+        self.inner.max_jerk.to_vec()
+    }
+
+    #[setter]
+    fn set_max_jerk(&mut self, values: Vec<f64>) -> PyResult<()> {
+        self.inner.max_jerk = to_data(values, self.inner.degrees_of_freedom, "max_jerk")?;
+        Ok(())
+    }
+
+    #[getter]
+    fn min_velocity(&self) -> Option<Vec<f64>> {
+        self.inner.min_velocity.as_ref().map(|values| values.to_vec())
+    }
+
+    #[setter]
+    fn set_min_velocity(&mut self, values: Option<Vec<f64>>) -> PyResult<()> {
+        self.inner.min_velocity =
+            to_optional_data(values, self.inner.degrees_of_freedom, "min_velocity")?;
+        Ok(())
+    }
+
+    #[getter]
+    fn min_acceleration(&self) -> Option<Vec<f64>> {
+        self.inner
+            .min_acceleration
+            .as_ref()
+            .map(|values| values.to_vec())
+    }
+
+    #[setter]
+    fn set_min_acceleration(&mut self, values: Option<Vec<f64>>) -> PyResult<()> {
+        self.inner.min_acceleration =
+            to_optional_data(values, self.inner.degrees_of_freedom, "min_acceleration")?;
+        Ok(())
+    }
+
+    #[getter]
+    fn max_snap(&self) -> Option<Vec<f64>> {
+        self.inner.max_snap.as_ref().map(|values| values.to_vec())
+    }
+
+    #[setter]
+    fn set_max_snap(&mut self, values: Option<Vec<f64>>) -> PyResult<()> {
+        self.inner.max_snap = to_optional_data(values, self.inner.degrees_of_freedom, "max_snap")?;
+        Ok(())
+    }
+
+    #[getter]
+    fn max_position(&self) -> Option<Vec<f64>> {
+        self.inner.max_position.as_ref().map(|values| values.to_vec())
+    }
+
+    #[setter]
+    fn set_max_position(&mut self, values: Option<Vec<f64>>) -> PyResult<()> {
+        self.inner.max_position =
+            to_optional_data(values, self.inner.degrees_of_freedom, "max_position")?;
+        Ok(())
+    }
+
+    #[getter]
+    fn min_position(&self) -> Option<Vec<f64>> {
+        self.inner.min_position.as_ref().map(|values| values.to_vec())
+    }
+
+    #[setter]
+    fn set_min_position(&mut self, values: Option<Vec<f64>>) -> PyResult<()> {
+        self.inner.min_position =
+            to_optional_data(values, self.inner.degrees_of_freedom, "min_position")?;
+        Ok(())
+    }
+
+    #[getter]
+    fn enabled(&self) -> Vec<bool> {
+        self.inner.enabled.to_vec()
+    }
+
+    #[setter]
+    fn set_enabled(&mut self, values: Vec<bool>) -> PyResult<()> {
+        self.inner.enabled = to_data(values, self.inner.degrees_of_freedom, "enabled")?;
+        Ok(())
+    }
+
+    #[getter]
+    fn intermediate_positions(&self) -> Vec<Vec<f64>> {
+        self.inner
+            .intermediate_positions
+            .iter()
+            .map(|position| position.to_vec())
+            .collect()
+    }
+
+    #[setter]
+    fn set_intermediate_positions(&mut self, positions: Vec<Vec<f64>>) -> PyResult<()> {
+        self.inner.intermediate_positions = positions
+            .into_iter()
+            .map(|position| {
+                to_data(
+                    position,
+                    self.inner.degrees_of_freedom,
+                    "intermediate_positions",
+                )
+            })
+            .collect::<PyResult<_>>()?;
+        Ok(())
+    }
+
+    #[getter]
+    fn intermediate_blend_tolerances(&self) -> Vec<f64> {
+        self.inner.intermediate_blend_tolerances.iter().copied().collect()
+    }
+
+    #[setter]
+    fn set_intermediate_blend_tolerances(&mut self, tolerances: Vec<f64>) {
+        self.inner.intermediate_blend_tolerances = tolerances.into();
+    }
+
+    #[getter]
+    fn minimum_duration(&self) -> Option<f64> {
+        self.inner.minimum_duration
+    }
+
+    #[setter]
+    fn set_minimum_duration(&mut self, value: Option<f64>) {
+        self.inner.minimum_duration = value;
+    }
+
+    #[getter]
+    fn maximum_duration(&self) -> Option<f64> {
+        self.inner.maximum_duration
+    }
+
+    #[setter]
+    fn set_maximum_duration(&mut self, value: Option<f64>) {
+        self.inner.maximum_duration = value;
+    }
+
+    #[getter]
+    fn pre_dwell(&self) -> Option<f64> {
+        self.inner.pre_dwell
+    }
+
+    #[setter]
+    fn set_pre_dwell(&mut self, value: Option<f64>) {
+        self.inner.pre_dwell = value;
+    }
+
+    #[getter]
+    fn post_dwell(&self) -> Option<f64> {
+        self.inner.post_dwell
+    }
+
+    #[setter]
+    fn set_post_dwell(&mut self, value: Option<f64>) {
+        self.inner.post_dwell = value;
+    }
+
+    #[getter]
+    fn control_interface(&self) -> ControlInterface {
+        (&self.inner.control_interface).into()
+    }
+
+    #[setter]
+    fn set_control_interface(&mut self, value: ControlInterface) {
+        self.inner.control_interface = value.into();
+    }
+
+    #[getter]
+    fn synchronization(&self) -> Synchronization {
+        (&self.inner.synchronization).into()
+    }
+
+    #[setter]
+    fn set_synchronization(&mut self, value: Synchronization) {
+        self.inner.synchronization = value.into();
+    }
+
+    #[getter]
+    fn duration_discretization(&self) -> DurationDiscretization {
+        (&self.inner.duration_discretization).into()
+    }
+
+    #[setter]
+    fn set_duration_discretization(&mut self, value: DurationDiscretization) {
+        self.inner.duration_discretization = value.into();
+    }
+
+    /// Step of a discrete duration independent of the control cycle
+    #[getter]
+    fn duration_discretization_step(&self) -> Option<f64> {
+        match self.inner.duration_discretization {
+            RsDurationDiscretization::DiscreteStep(step) => Some(step),
+            _ => None,
+        }
+    }
+
+    #[setter]
+    fn set_duration_discretization_step(&mut self, value: Option<f64>) {
+        self.inner.duration_discretization = match value {
+            Some(step) => RsDurationDiscretization::DiscreteStep(step),
+            None => RsDurationDiscretization::Continuous,
+        };
+    }
+
+    #[getter]
+    fn per_dof_control_interface(&self) -> Option<Vec<ControlInterface>> {
+        self.inner
+            .per_dof_control_interface
+            .as_ref()
+            .map(|values| values.iter().map(|value| value.into()).collect())
+    }
+
+    #[setter]
+    fn set_per_dof_control_interface(
+        &mut self,
+        values: Option<Vec<ControlInterface>>,
+    ) -> PyResult<()> {
+        self.inner.per_dof_control_interface = values
+            .map(|values| {
+                to_data(
+                    values.into_iter().map(|value| value.into()).collect(),
+                    self.inner.degrees_of_freedom,
+                    "per_dof_control_interface",
+                )
+            })
+            .transpose()?;
+        Ok(())
+    }
+
+    #[getter]
+    fn per_dof_synchronization(&self) -> Option<Vec<Synchronization>> {
+        self.inner
+            .per_dof_synchronization
+            .as_ref()
+            .map(|values| values.iter().map(|value| value.into()).collect())
+    }
+
+    #[setter]
+    fn set_per_dof_synchronization(&mut self, values: Option<Vec<Synchronization>>) -> PyResult<()> {
+        self.inner.per_dof_synchronization = values
+            .map(|values| {
+                to_data(
+                    values.into_iter().map(|value| value.into()).collect(),
+                    self.inner.degrees_of_freedom,
+                    "per_dof_synchronization",
+                )
+            })
+            .transpose()?;
+        Ok(())
+    }
+
+    #[getter]
+    fn per_dof_priority(&self) -> Option<Vec<u32>> {
+        self.inner
+            .per_dof_priority
+            .as_ref()
+            .map(|values| values.to_vec())
+    }
+
+    #[setter]
+    fn set_per_dof_priority(&mut self, values: Option<Vec<u32>>) -> PyResult<()> {
+        self.inner.per_dof_priority = values
+            .map(|values| to_data(values, self.inner.degrees_of_freedom, "per_dof_priority"))
+            .transpose()?;
+        Ok(())
+    }
+
+    #[getter]
+    fn per_dof_group(&self) -> Option<Vec<usize>> {
+        self.inner
+            .per_dof_group
+            .as_ref()
+            .map(|values| values.to_vec())
+    }
+
+    #[setter]
+    fn set_per_dof_group(&mut self, values: Option<Vec<usize>>) -> PyResult<()> {
+        self.inner.per_dof_group = values
+            .map(|values| to_data(values, self.inner.degrees_of_freedom, "per_dof_group"))
+            .transpose()?;
+        Ok(())
+    }
+
+    #[getter]
+    fn per_dof_period(&self) -> Option<Vec<Option<f64>>> {
+        self.inner
+            .per_dof_period
+            .as_ref()
+            .map(|values| values.to_vec())
+    }
+
+    #[setter]
+    fn set_per_dof_period(&mut self, values: Option<Vec<Option<f64>>>) -> PyResult<()> {
+        self.inner.per_dof_period = values
+            .map(|values| to_data(values, self.inner.degrees_of_freedom, "per_dof_period"))
+            .transpose()?;
+        Ok(())
+    }
+
+    #[getter]
+    fn target_position_tolerance(&self) -> Option<Vec<f64>> {
+        self.inner
+            .target_position_tolerance
+            .as_ref()
+            .map(|values| values.to_vec())
+    }
+
+    #[setter]
+    fn set_target_position_tolerance(&mut self, values: Option<Vec<f64>>) -> PyResult<()> {
+        self.inner.target_position_tolerance = to_optional_data(
+            values,
+            self.inner.degrees_of_freedom,
+            "target_position_tolerance",
+        )?;
+        Ok(())
+    }
+
+    #[getter]
+    fn target_velocity_tolerance(&self) -> Option<Vec<f64>> {
+        self.inner
+            .target_velocity_tolerance
+            .as_ref()
+            .map(|values| values.to_vec())
+    }
+
+    #[setter]
+    fn set_target_velocity_tolerance(&mut self, values: Option<Vec<f64>>) -> PyResult<()> {
+        self.inner.target_velocity_tolerance = to_optional_data(
+            values,
+            self.inner.degrees_of_freedom,
+            "target_velocity_tolerance",
+        )?;
+        Ok(())
+    }
+
+    #[pyo3(signature = (check_current_state_within_limits=false, check_target_state_within_limits=true))]
+    fn validate(
+        &self,
+        check_current_state_within_limits: bool,
+        check_target_state_within_limits: bool,
+    ) -> PyResult<bool> {
+        self.inner
+            .validate::<ThrowErrorHandler>(
+                check_current_state_within_limits,
+                check_target_state_within_limits,
+            )
+            .map_err(to_py_err)
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+
+    fn __repr__(&self) -> String {
+        self.inner.to_string()
+    }
+}
+
+/// Position extrema of a single DoF, and the times when they are reached
+#[pyclass(name = "PositionExtrema", get_all)]
+#[derive(Clone)]
+pub struct PyPositionExtrema {
+    min: f64,
+    max: f64,
+    t_min: f64,
+    t_max: f64,
+}
+
+#[pyclass(name = "Trajectory")]
+#[derive(Clone)]
+pub struct PyTrajectory {
+    inner: RsTrajectory<0>,
+}
+
+#[pymethods]
+impl PyTrajectory {
+    #[new]
+    #[pyo3(signature = (dofs, max_number_of_waypoints=0))]
+    fn new(dofs: usize, max_number_of_waypoints: usize) -> Self {
+        Self {
+            inner: RsTrajectory::new_with_waypoints(max_number_of_waypoints, Some(dofs)),
+        }
+    }
+
+    #[getter]
+    fn degrees_of_freedom(&self) -> usize {
+        self.inner.get_independent_min_durations().len()
+    }
+
+    #[getter]
+    fn duration(&self) -> f64 {
+        self.inner.get_duration()
+    }
+
+    #[getter]
+    fn calculation_duration(&self) -> f64 {
+        self.inner.get_calculation_duration()
+    }
+
+    #[getter]
+    fn intermediate_durations(&self) -> Vec<f64> {
+        self.inner.get_intermediate_durations().to_vec()
+    }
+
+    #[getter]
+    fn independent_min_durations(&self) -> Vec<f64> {
+        self.inner.get_independent_min_durations().to_vec()
+    }
+
+    /// Returns the position, velocity and acceleration at the given time, and optionally the
+    /// jerk and the section as well
+    #[pyo3(signature = (time, return_section=false))]
+    fn at_time(&self, py: Python<'_>, time: f64, return_section: bool) -> PyResult<PyObject> {
+        let dofs = self.degrees_of_freedom();
+        let mut new_position = DataArrayOrVec::<f64, 0>::new(Some(dofs), 0.0);
+        let mut new_velocity = DataArrayOrVec::<f64, 0>::new(Some(dofs), 0.0);
+        let mut new_acceleration = DataArrayOrVec::<f64, 0>::new(Some(dofs), 0.0);
+        let mut new_jerk = DataArrayOrVec::<f64, 0>::new(Some(dofs), 0.0);
+        let mut new_section = None;
+        self.inner.at_time(
+            time,
+            &mut Some(&mut new_position),
+            &mut Some(&mut new_velocity),
+            &mut Some(&mut new_acceleration),
+            &mut Some(&mut new_jerk),
+            &mut new_section,
+        );
+
+        if return_section {
+            (
+                new_position.to_vec(),
+                new_velocity.to_vec(),
+                new_acceleration.to_vec(),
+                new_jerk.to_vec(),
+                new_section.unwrap_or(0),
+            )
+                .into_pyobject(py)
+                .map(|tuple| tuple.into_any().unbind())
+        } else {
+            (
+                new_position.to_vec(),
+                new_velocity.to_vec(),
+                new_acceleration.to_vec(),
+            )
+                .into_pyobject(py)
+                .map(|tuple| tuple.into_any().unbind())
+        }
+    }
+
+    fn get_position_extrema(&mut self) -> Vec<PyPositionExtrema> {
+        self.inner
+            .get_position_extrema()
+            .iter()
+            .map(|bound| PyPositionExtrema {
+                min: bound.min,
+                max: bound.max,
+                t_min: bound.t_min,
+                t_max: bound.t_max,
+            })
+            .collect()
+    }
+
+    fn get_first_time_at_position(&self, dof: usize, position: f64) -> Option<f64> {
+        self.inner.get_first_time_at_position(dof, position)
+    }
+}
+
+#[pyclass(name = "OutputParameter")]
+pub struct PyOutputParameter {
+    inner: RsOutputParameter<0>,
+}
+
+#[pymethods]
+impl PyOutputParameter {
+    #[new]
+    #[pyo3(signature = (dofs, max_number_of_waypoints=0))]
+    fn new(dofs: usize, max_number_of_waypoints: usize) -> Self {
+        Self {
+            inner: RsOutputParameter::new_with_waypoints(max_number_of_waypoints, Some(dofs)),
+        }
+    }
+
+    #[getter]
+    fn degrees_of_freedom(&self) -> usize {
+        self.inner.degrees_of_freedom
+    }
+
+    #[getter]
+    fn new_position(&self) -> Vec<f64> {
+        self.inner.new_position.to_vec()
+    }
+
+    #[getter]
+    fn new_velocity(&self) -> Vec<f64> {
+        self.inner.new_velocity.to_vec()
+    }
+
+    #[getter]
+    fn new_acceleration(&self) -> Vec<f64> {
+        self.inner.new_acceleration.to_vec()
+    }
+
+    #[getter]
+    fn new_jerk(&self) -> Vec<f64> {
+        self.inner.new_jerk.to_vec()
+    }
+
+    #[getter]
+    fn time(&self) -> f64 {
+        self.inner.time
+    }
+
+    #[getter]
+    fn new_section(&self) -> usize {
+        self.inner.new_section
+    }
+
+    #[getter]
+    fn did_section_change(&self) -> bool {
+        self.inner.did_section_change
+    }
+
+    #[getter]
+    fn new_calculation(&self) -> bool {
+        self.inner.new_calculation
+    }
+
+    #[getter]
+    fn was_calculation_interrupted(&self) -> bool {
+        self.inner.was_calculation_interrupted
+    }
+
+    #[getter]
+    fn calculation_duration(&self) -> f64 {
+        self.inner.calculation_duration
+    }
+
+    /// A copy of the current trajectory
+    #[getter]
+    fn trajectory(&self) -> PyTrajectory {
+        PyTrajectory {
+            inner: self.inner.trajectory.clone(),
+        }
+    }
+
+    fn pass_to_input(&self, mut input: PyRefMut<'_, PyInputParameter>) {
+        self.inner.pass_to_input(&mut input.inner);
+    }
+
+    fn pass_to_input_with_feedback(
+        &self,
+        mut input: PyRefMut<'_, PyInputParameter>,
+        measured_position: Vec<f64>,
+        alpha: f64,
+    ) -> PyResult<()> {
+        let measured_position = to_data(
+            measured_position,
+            self.inner.degrees_of_freedom,
+            "measured_position",
+        )?;
+        self.inner
+            .pass_to_input_with_feedback(&mut input.inner, &measured_position, alpha)
+            .map_err(to_py_err)
+    }
+
+    fn __repr__(&self) -> String {
+        self.inner.to_string()
+    }
+}
+
+#[pyclass(name = "Ruckig")]
+pub struct PyRuckig {
+    inner: RsRuckig<0, ThrowErrorHandler>,
+}
+
+#[pymethods]
+impl PyRuckig {
+    #[new]
+    #[pyo3(signature = (dofs, delta_time=-1.0, max_number_of_waypoints=0))]
+    fn new(dofs: usize, delta_time: f64, max_number_of_waypoints: usize) -> Self {
+        Self {
+            inner: RsRuckig::new_with_waypoints(max_number_of_waypoints, Some(dofs), delta_time),
+        }
+    }
+
+    #[getter]
+    fn degrees_of_freedom(&self) -> usize {
+        self.inner.degrees_of_freedom
+    }
+
+    #[getter]
+    fn delta_time(&self) -> f64 {
+        self.inner.delta_time
+    }
+
+    #[setter]
+    fn set_delta_time(&mut self, value: f64) {
+        self.inner.delta_time = value;
+    }
+
+    #[getter]
+    fn max_number_of_waypoints(&self) -> usize {
+        self.inner.max_number_of_waypoints
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    #[pyo3(signature = (input, check_current_state_within_limits=false, check_target_state_within_limits=true))]
+    fn validate_input(
+        &self,
+        input: &PyInputParameter,
+        check_current_state_within_limits: bool,
+        check_target_state_within_limits: bool,
+    ) -> PyResult<bool> {
+        self.inner
+            .validate_input(
+                &input.inner,
+                check_current_state_within_limits,
+                check_target_state_within_limits,
+            )
+            .map_err(to_py_err)
+    }
+
+    fn calculate(
+        &mut self,
+        input: &PyInputParameter,
+        mut trajectory: PyRefMut<'_, PyTrajectory>,
+    ) -> PyResult<PyRuckigResult> {
+        self.inner
+            .calculate(&input.inner, &mut trajectory.inner)
+            .map(PyRuckigResult::from)
+            .map_err(to_py_err)
+    }
+
+    fn calculate_with_budget(
+        &mut self,
+        input: &PyInputParameter,
+        mut trajectory: PyRefMut<'_, PyTrajectory>,
+        max_micros: f64,
+    ) -> PyResult<PyRuckigResult> {
+        self.inner
+            .calculate_with_budget(&input.inner, &mut trajectory.inner, max_micros)
+            .map(PyRuckigResult::from)
+            .map_err(to_py_err)
+    }
+
+    fn update(
+        &mut self,
+        input: &PyInputParameter,
+        mut output: PyRefMut<'_, PyOutputParameter>,
+    ) -> PyResult<PyRuckigResult> {
+        self.inner
+            .update(&input.inner, &mut output.inner)
+            .map(PyRuckigResult::from)
+            .map_err(to_py_err)
+    }
+}
+
+#[pymodule]
+#[pyo3(name = "rsruckig")]
+fn rsruckig_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("RuckigError", m.py().get_type::<RuckigError>())?;
+    m.add_class::<ControlInterface>()?;
+    m.add_class::<Synchronization>()?;
+    m.add_class::<DurationDiscretization>()?;
+    m.add_class::<PyRuckigResult>()?;
+    m.add_class::<PyInputParameter>()?;
+    m.add_class::<PyOutputParameter>()?;
+    m.add_class::<PyPositionExtrema>()?;
+    m.add_class::<PyTrajectory>()?;
+    m.add_class::<PyRuckig>()?;
+    Ok(())
+}
@@ -0,0 +1,38 @@
+//! Builds the upstream C++ Ruckig from the checkout in `RUCKIG_CPP_DIR` together with the C
+//! interface in `src/shim.cpp`. The checkout should be at the tag in `package.metadata.ruckig`
+//! of `Cargo.toml`.
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Sources that need the dependencies of the cloud client or of the Python module
+const EXCLUDED_SOURCES: [&str; 2] = ["cloud_client.cpp", "python.cpp"];
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=RUCKIG_CPP_DIR");
+    println!("cargo:rerun-if-changed=src/shim.cpp");
+
+    let ruckig_dir = PathBuf::from(env::var("RUCKIG_CPP_DIR").unwrap_or_else(|_| {
+        panic!("RUCKIG_CPP_DIR should be the absolute path of a checkout of the C++ Ruckig")
+    }));
+    let source_dir = ruckig_dir.join("src").join("ruckig");
+    let mut sources: Vec<PathBuf> = fs::read_dir(&source_dir)
+        .unwrap_or_else(|error| panic!("can't read {}: {}", source_dir.display(), error))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            let name = path.file_name().unwrap().to_string_lossy();
+            name.ends_with(".cpp") && !EXCLUDED_SOURCES.contains(&name.as_ref())
+        })
+        .collect();
+    sources.sort();
+    println!("cargo:rerun-if-changed={}", source_dir.display());
+
+    cc::Build::new()
+        .cpp(true)
+        .std("c++17")
+        .include(ruckig_dir.join("include"))
+        .files(&sources)
+        .file("src/shim.cpp")
+        .warnings(false)
+        .compile("ruckig");
+}
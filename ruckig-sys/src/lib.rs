@@ -0,0 +1,45 @@
+//! Raw bindings to the upstream C++ Ruckig, which is built from the checkout in `RUCKIG_CPP_DIR`.
+//! They only cover what the differential tests of the Rust port need: calculating a trajectory
+//! from a state-to-state input and sampling it.
+use std::os::raw::c_int;
+
+/// Input of `rsruckig_reference_calculate`. All arrays hold `degrees_of_freedom` values.
+#[repr(C)]
+#[derive(Debug)]
+pub struct RuckigReferenceInput {
+    pub degrees_of_freedom: usize,
+    pub delta_time: f64,
+    pub current_position: *const f64,
+    pub current_velocity: *const f64,
+    pub current_acceleration: *const f64,
+    pub target_position: *const f64,
+    pub target_velocity: *const f64,
+    pub target_acceleration: *const f64,
+    pub max_velocity: *const f64,
+    pub max_acceleration: *const f64,
+    pub max_jerk: *const f64,
+    /// Null for the negative maximal velocity
+    pub min_velocity: *const f64,
+    /// Null for the negative maximal acceleration
+    pub min_acceleration: *const f64,
+    /// `ruckig::ControlInterface`: 0 for position, 1 for velocity
+    pub control_interface: c_int,
+    /// `ruckig::Synchronization`: 0 for time, 1 if necessary, 2 for phase, 3 for none
+    pub synchronization: c_int,
+}
+
+extern "C" {
+    /// Calculate the trajectory of the input and sample it at the given fractions of its duration.
+    /// Returns the `ruckig::Result` of the calculation. The duration and the samples are only
+    /// written if it is `Working`, the sample buffers hold `number_of_samples *
+    /// degrees_of_freedom` values.
+    pub fn rsruckig_reference_calculate(
+        input: *const RuckigReferenceInput,
+        number_of_samples: usize,
+        fractions: *const f64,
+        duration: *mut f64,
+        positions: *mut f64,
+        velocities: *mut f64,
+        accelerations: *mut f64,
+    ) -> c_int;
+}
@@ -0,0 +1,77 @@
+// C interface of the upstream C++ Ruckig for the differential tests of the Rust port, see lib.rs
+#include <algorithm>
+#include <cstddef>
+#include <exception>
+#include <vector>
+
+#include <ruckig/ruckig.hpp>
+
+extern "C" {
+
+struct RuckigReferenceInput {
+    size_t degrees_of_freedom;
+    double delta_time;
+    const double* current_position;
+    const double* current_velocity;
+    const double* current_acceleration;
+    const double* target_position;
+    const double* target_velocity;
+    const double* target_acceleration;
+    const double* max_velocity;
+    const double* max_acceleration;
+    const double* max_jerk;
+    const double* min_velocity;
+    const double* min_acceleration;
+    int control_interface;
+    int synchronization;
+};
+
+int rsruckig_reference_calculate(const RuckigReferenceInput* input, size_t number_of_samples, const double* fractions, double* duration, double* positions, double* velocities, double* accelerations) {
+    const size_t dofs = input->degrees_of_freedom;
+    const auto to_vector = [dofs](const double* values) {
+        return std::vector<double>(values, values + dofs);
+    };
+
+    try {
+        ruckig::Ruckig<ruckig::DynamicDOFs> otg {dofs, input->delta_time};
+        ruckig::InputParameter<ruckig::DynamicDOFs> parameter {dofs};
+        parameter.current_position = to_vector(input->current_position);
+        parameter.current_velocity = to_vector(input->current_velocity);
+        parameter.current_acceleration = to_vector(input->current_acceleration);
+        parameter.target_position = to_vector(input->target_position);
+        parameter.target_velocity = to_vector(input->target_velocity);
+        parameter.target_acceleration = to_vector(input->target_acceleration);
+        parameter.max_velocity = to_vector(input->max_velocity);
+        parameter.max_acceleration = to_vector(input->max_acceleration);
+        parameter.max_jerk = to_vector(input->max_jerk);
+        if (input->min_velocity) {
+            parameter.min_velocity = to_vector(input->min_velocity);
+        }
+        if (input->min_acceleration) {
+            parameter.min_acceleration = to_vector(input->min_acceleration);
+        }
+        parameter.control_interface = static_cast<ruckig::ControlInterface>(input->control_interface);
+        parameter.synchronization = static_cast<ruckig::Synchronization>(input->synchronization);
+
+        ruckig::Trajectory<ruckig::DynamicDOFs> trajectory {dofs};
+        const ruckig::Result result = otg.calculate(parameter, trajectory);
+        if (result != ruckig::Result::Working) {
+            return static_cast<int>(result);
+        }
+
+        *duration = trajectory.get_duration();
+        std::vector<double> position(dofs), velocity(dofs), acceleration(dofs);
+        for (size_t i = 0; i < number_of_samples; ++i) {
+            trajectory.at_time(fractions[i] * *duration, position, velocity, acceleration);
+            std::copy(position.begin(), position.end(), positions + i * dofs);
+            std::copy(velocity.begin(), velocity.end(), velocities + i * dofs);
+            std::copy(acceleration.begin(), acceleration.end(), accelerations + i * dofs);
+        }
+        return static_cast<int>(result);
+
+    } catch (const std::exception&) {
+        return static_cast<int>(ruckig::Result::Error);
+    }
+}
+
+}
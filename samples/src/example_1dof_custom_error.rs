@@ -1,5 +1,4 @@
-use log::{error, info};
-use log4rs;
+use log::{error, info, warn};
 use rsruckig::error::RuckigErrorHandler;
 use rsruckig::prelude::*;
 
@@ -10,20 +9,20 @@ use rsruckig::prelude::*;
 pub struct LogErrorHandler;
 
 impl RuckigErrorHandler for LogErrorHandler {
-    fn handle_validation_error(message: &str) -> Result<bool, RuckigError> {
-        error!("{}", message);
+    fn handle_validation_error(error: RuckigError) -> Result<bool, RuckigError> {
+        error!("{}", error);
         Ok(false)
         // Or if you want to throw an error:
-        // Err(RuckigError::new(message.to_string()))
+        // Err(error)
     }
-    fn handle_calculator_error(
-        message: &str,
-        result: RuckigResult,
-    ) -> Result<RuckigResult, RuckigError> {
-        error!("{}: Result: {:?}", message, result);
-        Ok(result)
+    fn handle_calculator_error(error: RuckigError) -> Result<RuckigResult, RuckigError> {
+        error!("{}: Result: {:?}", error, error.result());
+        Ok(error.result())
         // Or if you want to throw an error:
-        // Err(RuckigError::new(format!("{}: {:?}", message, result)))
+        // Err(error)
+    }
+    fn handle_warning(warning: RuckigWarning) {
+        warn!("{}", warning);
     }
 }
 
@@ -2,7 +2,9 @@ use rsruckig::prelude::*;
 
 use float_eq::assert_float_eq;
 use rsruckig::input_parameter::{ControlInterface, DurationDiscretization, Synchronization};
+use rsruckig::roots;
 use rsruckig::trajectory::Trajectory;
+use rsruckig::util::CompensatedSum;
 
 fn almost_equal_vecs(a: &[f64], b: &[f64], epsilon: f64) -> bool {
     if a.len() != b.len() {
@@ -34,11 +36,11 @@ fn test_at_time() {
     // Setup
     let mut otg = Ruckig::<1, ThrowErrorHandler>::new(None, 0.005);
     let mut input = InputParameter::new(None);
-    input.current_position = DataArrayOrVec::Stack([0.0]);
-    input.target_position = DataArrayOrVec::Stack([1.0]);
-    input.max_velocity = DataArrayOrVec::Stack([1.0]);
-    input.max_acceleration = DataArrayOrVec::Stack([1.0]);
-    input.max_jerk = DataArrayOrVec::Stack([1.0]);
+    input.current_position = DataArrayOrVec::from_array([0.0]);
+    input.target_position = DataArrayOrVec::from_array([1.0]);
+    input.max_velocity = DataArrayOrVec::from_array([1.0]);
+    input.max_acceleration = DataArrayOrVec::from_array([1.0]);
+    input.max_jerk = DataArrayOrVec::from_array([1.0]);
 
     let mut traj = Trajectory::new(None);
     let _result = otg.calculate(&input, &mut traj);
@@ -52,9 +54,9 @@ fn test_at_time() {
     assert_eq!(result.unwrap(), RuckigResult::Working);
     assert_float_eq!(traj.get_duration(), 3.1748, abs <= 0.000_1);
 
-    let mut new_position = DataArrayOrVec::Stack([0.0; 1]);
-    let mut new_velocity = DataArrayOrVec::Stack([0.0; 1]);
-    let mut new_acceleration = DataArrayOrVec::Stack([0.0; 1]);
+    let mut new_position = DataArrayOrVec::from_array([0.0; 1]);
+    let mut new_velocity = DataArrayOrVec::from_array([0.0; 1]);
+    let mut new_acceleration = DataArrayOrVec::from_array([0.0; 1]);
     traj.at_time(
         0.0,
         &mut Some(&mut new_position),
@@ -84,17 +86,17 @@ fn test_secondary() {
     let mut input = InputParameter::new(None);
     let mut output = OutputParameter::new(None);
 
-    input.current_position = DataArrayOrVec::Stack([0.0, -2.0, 0.0]);
-    input.current_velocity = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
-    input.current_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.current_position = DataArrayOrVec::from_array([0.0, -2.0, 0.0]);
+    input.current_velocity = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
+    input.current_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
-    input.target_position = DataArrayOrVec::Stack([1.0, -3.0, 2.0]);
-    input.target_velocity = DataArrayOrVec::Stack([0.0, 0.3, 0.0]);
-    input.target_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.target_position = DataArrayOrVec::from_array([1.0, -3.0, 2.0]);
+    input.target_velocity = DataArrayOrVec::from_array([0.0, 0.3, 0.0]);
+    input.target_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
-    input.max_velocity = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_acceleration = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_jerk = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
+    input.max_velocity = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_acceleration = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_jerk = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
 
     let mut traj = Trajectory::new(None);
 
@@ -107,10 +109,10 @@ fn test_secondary() {
     assert_eq!(result.unwrap(), RuckigResult::Working);
     assert_float_eq!(output.trajectory.get_duration(), 4.0, abs <= 0.000_1);
 
-    let mut new_position = DataArrayOrVec::Stack([0.0; 3]);
-    let mut new_velocity = DataArrayOrVec::Stack([0.0; 3]);
-    let mut new_acceleration = DataArrayOrVec::Stack([0.0; 3]);
-    let mut new_jerk = DataArrayOrVec::Stack([0.0; 3]);
+    let mut new_position = DataArrayOrVec::from_array([0.0; 3]);
+    let mut new_velocity = DataArrayOrVec::from_array([0.0; 3]);
+    let mut new_acceleration = DataArrayOrVec::from_array([0.0; 3]);
+    let mut new_jerk = DataArrayOrVec::from_array([0.0; 3]);
 
     output.trajectory.at_time(
         0.0,
@@ -164,7 +166,7 @@ fn test_secondary() {
         &[0.5, -2.6871268303003437, 1.0],
         0.000_1,
     ));
-    assert_eq!(new_jerk, DataArrayOrVec::Stack([0.0, 0.0, -1.0]));
+    assert_eq!(new_jerk, DataArrayOrVec::from_array([0.0, 0.0, -1.0]));
     assert_eq!(new_section, Some(0));
 
     output.trajectory.at_time(
@@ -175,7 +177,7 @@ fn test_secondary() {
         &mut Some(&mut new_jerk),
         &mut new_section,
     );
-    assert_eq!(new_jerk, DataArrayOrVec::Stack([0.0, 0.0, 0.0]));
+    assert_eq!(new_jerk, DataArrayOrVec::from_array([0.0, 0.0, 0.0]));
     assert_eq!(new_section, Some(1));
 
     let independent_min_durations = output.trajectory.get_independent_min_durations();
@@ -235,17 +237,17 @@ fn test_secondary() {
 
     let time = output.trajectory.get_first_time_at_position(6, 0.0);
     assert_eq!(time, None);
-    input.current_position = DataArrayOrVec::Stack([0.0, -2.0, 0.0]);
-    input.current_velocity = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
-    input.current_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.current_position = DataArrayOrVec::from_array([0.0, -2.0, 0.0]);
+    input.current_velocity = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
+    input.current_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
-    input.target_position = DataArrayOrVec::Stack([1.0, -3.0, 2.0]);
-    input.target_velocity = DataArrayOrVec::Stack([2.0, 0.3, 0.0]);
-    input.target_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.target_position = DataArrayOrVec::from_array([1.0, -3.0, 2.0]);
+    input.target_velocity = DataArrayOrVec::from_array([2.0, 0.3, 0.0]);
+    input.target_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
-    input.max_velocity = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_acceleration = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_jerk = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
+    input.max_velocity = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_acceleration = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_jerk = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
 
     let result = otg.update(&input, &mut output);
 
@@ -258,11 +260,19 @@ fn test_secondary() {
                 "Unexpected error message: {}",
                 error_message
             );
+            match e {
+                RuckigError::InvalidInput(violation) => {
+                    assert_eq!(violation.dof, Some(0));
+                    assert_eq!(violation.quantity, InputQuantity::TargetVelocity);
+                    assert_eq!(violation.kind, ViolationKind::AboveMaximum);
+                }
+                _ => panic!("Unexpected error: {:?}", e),
+            }
         }
     }
     assert!(!output.new_calculation);
 
-    input.target_velocity = DataArrayOrVec::Stack([0.2, -0.3, 0.8]);
+    input.target_velocity = DataArrayOrVec::from_array([0.2, -0.3, 0.8]);
     let result = otg.update(&input, &mut output);
     assert_eq!(result.unwrap(), RuckigResult::Working);
     assert!(output.new_calculation);
@@ -272,17 +282,17 @@ fn test_secondary() {
     assert_eq!(result.unwrap(), RuckigResult::Working);
     assert_float_eq!(output.trajectory.get_duration(), 12.0, abs <= 0.000_1);
 
-    input.current_position = DataArrayOrVec::Stack([1300.0, 0.0, 0.02]);
-    input.current_velocity = DataArrayOrVec::Stack([1200.0, 0.0, 0.0]);
-    input.current_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.current_position = DataArrayOrVec::from_array([1300.0, 0.0, 0.02]);
+    input.current_velocity = DataArrayOrVec::from_array([1200.0, 0.0, 0.0]);
+    input.current_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
-    input.target_position = DataArrayOrVec::Stack([1400.0, 0.0, 0.02]);
-    input.target_velocity = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
-    input.target_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.target_position = DataArrayOrVec::from_array([1400.0, 0.0, 0.02]);
+    input.target_velocity = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
+    input.target_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
-    input.max_velocity = DataArrayOrVec::Stack([800.0, 1.0, 1.0]);
-    input.max_acceleration = DataArrayOrVec::Stack([40000.0, 1.0, 1.0]);
-    input.max_jerk = DataArrayOrVec::Stack([200000.0, 1.0, 1.0]);
+    input.max_velocity = DataArrayOrVec::from_array([800.0, 1.0, 1.0]);
+    input.max_acceleration = DataArrayOrVec::from_array([40000.0, 1.0, 1.0]);
+    input.max_jerk = DataArrayOrVec::from_array([200000.0, 1.0, 1.0]);
 
     input.minimum_duration = None;
 
@@ -301,6 +311,183 @@ fn test_secondary() {
     assert_float_eq!(independent_min_durations[2], 0.0, abs <= 0.000_1);
 }
 
+#[test]
+fn test_first_order_velocity() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    input.control_interface = ControlInterface::Velocity;
+    input.current_position = daov_stack![0.0, 1.0];
+    input.current_velocity = daov_stack![0.0, 0.5];
+    input.target_velocity = daov_stack![0.8, -0.3];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![f64::INFINITY, f64::INFINITY];
+
+    // The target velocity is reached instantly
+    let mut traj = Trajectory::new(None);
+    otg.calculate(&input, &mut traj).unwrap();
+    assert_eq!(traj.get_duration(), 0.0);
+    let state = traj.state_at(1.0);
+    assert!(almost_equal_vecs(&state.velocity, &input.target_velocity, 1e-12));
+    assert!(almost_equal_vecs(&state.position, &daov_stack![0.8, 0.7], 1e-12));
+
+    assert_eq!(otg.update(&input, &mut output).unwrap(), RuckigResult::Finished);
+    assert!(almost_equal_vecs(&output.new_velocity, &input.target_velocity, 1e-12));
+    assert!(almost_equal_vecs(&output.new_position, &daov_stack![0.008, 0.997], 1e-12));
+
+    // Synchronized with a third-order DoF, the step is held for the whole duration
+    input.max_acceleration[1] = 1.0;
+    input.max_jerk[1] = 1.0;
+    for synchronization in [Synchronization::Time, Synchronization::Phase] {
+        input.synchronization = synchronization;
+        otg.calculate(&input, &mut traj).unwrap();
+        let duration = traj.get_duration();
+        assert!(duration > 0.0);
+        let state = traj.state_at(duration / 2.0);
+        assert_float_eq!(state.velocity[0], 0.8, abs <= 1e-12);
+        assert_float_eq!(state.position[0], 0.4 * duration, abs <= 1e-12);
+    }
+
+    // The target velocity needs to be within the velocity limits
+    input.target_velocity[0] = 1.2;
+    let violation = input.first_violation(false, true).unwrap();
+    assert_eq!(violation.dof, Some(0));
+    assert_eq!(violation.quantity, InputQuantity::TargetVelocity);
+    assert_eq!(violation.kind, ViolationKind::AboveMaximum);
+}
+
+#[test]
+fn test_mixed_control_interfaces() {
+    let mut otg = Ruckig::<3, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    input.per_dof_control_interface = Some(daov_stack![
+        ControlInterface::Position,
+        ControlInterface::Velocity,
+        ControlInterface::Acceleration,
+    ]);
+    input.current_velocity = daov_stack![0.0, 0.2, 0.4];
+    input.target_position = daov_stack![1.0, 0.0, 0.0];
+    input.target_velocity = daov_stack![0.0, -0.5, 0.0];
+    input.target_acceleration = daov_stack![0.0, 0.0, 0.5];
+    input.max_velocity = daov_stack![1.0, 1.0, 2.5];
+    input.max_acceleration = daov_stack![1.0, 1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0, 1.0];
+
+    // The acceleration is ramped over the synchronized duration
+    let mut traj = Trajectory::new(None);
+    otg.calculate(&input, &mut traj).unwrap();
+    let duration = traj.get_duration();
+    assert_float_eq!(traj.get_independent_min_durations()[2], 0.5, abs <= 1e-12);
+    assert!(duration > 0.5);
+    let state = traj.state_at(duration / 2.0);
+    assert_float_eq!(state.acceleration[2], 0.25, abs <= 1e-12);
+    assert_float_eq!(state.jerk[2], 0.5 / duration, abs <= 1e-12);
+    let end = traj.state_at(duration);
+    assert_float_eq!(end.velocity[2], 0.4 + 0.25 * duration, abs <= 1e-12);
+    assert!(traj.check_limits(&input, 0.001).is_empty());
+
+    let mut output = OutputParameter::new(None);
+    let mut update_input = input.clone();
+    while otg.update(&update_input, &mut output).unwrap() == RuckigResult::Working {
+        output.pass_to_input(&mut update_input);
+    }
+    assert_float_eq!(output.new_acceleration[2], 0.5, abs <= 1e-12);
+    assert_float_eq!(output.new_velocity[1], -0.5, abs <= 1e-12);
+
+    // Phase synchronization falls back to time synchronization
+    input.synchronization = Synchronization::Phase;
+    otg.calculate(&input, &mut traj).unwrap();
+    assert_float_eq!(traj.get_duration(), duration, abs <= 1e-12);
+    assert_eq!(
+        traj.get_synchronizations()[2].synchronization,
+        Synchronization::Time
+    );
+
+    // Without a jerk limit, the target acceleration is reached instantly
+    input.max_jerk[2] = f64::INFINITY;
+    otg.calculate(&input, &mut traj).unwrap();
+    assert_eq!(traj.get_independent_min_durations()[2], 0.0);
+    let state = traj.state_at(0.5);
+    assert_float_eq!(state.acceleration[2], 0.5, abs <= 1e-12);
+    assert_float_eq!(state.position[2], 0.2 + 0.0625, abs <= 1e-12);
+}
+
+#[test]
+fn test_acceleration_interface_velocity_limit() {
+    let mut otg = Ruckig::<1, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    input.control_interface = ControlInterface::Acceleration;
+    input.current_velocity = daov_stack![0.9];
+    input.target_acceleration = daov_stack![1.0];
+    input.max_velocity = daov_stack![1.0];
+    input.max_acceleration = daov_stack![1.0];
+    input.max_jerk = daov_stack![1.0];
+
+    // Ramping up the acceleration would exceed the maximum velocity
+    let mut traj = Trajectory::new(None);
+    assert!(matches!(
+        otg.calculate(&input, &mut traj),
+        Err(RuckigError::Step1Failure { dof: 0, .. })
+    ));
+
+    // Ramping down the acceleration crosses zero within the limits
+    input.current_acceleration = daov_stack![0.5];
+    input.target_acceleration = daov_stack![-1.0];
+    input.current_velocity = daov_stack![0.8];
+    otg.calculate(&input, &mut traj).unwrap();
+    assert_float_eq!(traj.get_duration(), 1.5, abs <= 1e-12);
+    assert_float_eq!(traj.state_at(0.5).velocity[0], 0.925, abs <= 1e-12);
+
+    // The velocity extremum at zero acceleration is limited as well
+    input.current_velocity = daov_stack![0.9];
+    assert!(matches!(
+        otg.calculate(&input, &mut traj),
+        Err(RuckigError::Step1Failure { dof: 0, .. })
+    ));
+
+    // A long synchronized ramp exceeds the maximum velocity
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    input.per_dof_control_interface = Some(daov_stack![
+        ControlInterface::Position,
+        ControlInterface::Acceleration,
+    ]);
+    input.target_position = daov_stack![1.0, 0.0];
+    input.current_velocity = daov_stack![0.0, 0.4];
+    input.target_acceleration = daov_stack![0.0, 0.5];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+    let mut traj = Trajectory::new(None);
+    assert!(matches!(
+        otg.calculate(&input, &mut traj),
+        Err(RuckigError::Step2Failure { dof: 1, .. })
+    ));
+
+    // The acceleration interface has no target velocity
+    input.current_velocity[1] = 0.0;
+    input.target_velocity[1] = 0.5;
+    match otg.calculate(&input, &mut traj) {
+        Err(RuckigError::InvalidInput(violation)) => {
+            assert_eq!(violation.dof, Some(1));
+            assert_eq!(violation.quantity, InputQuantity::TargetVelocity);
+            assert_eq!(violation.kind, ViolationKind::UnsupportedControlInterface);
+        }
+        result => panic!("Unexpected result: {:?}", result),
+    }
+
+    // The current velocity is validated against the limits
+    input.target_velocity[1] = 0.0;
+    input.current_velocity[1] = 1.5;
+    match otg.validate_input(&input, true, false) {
+        Err(RuckigError::InvalidInput(violation)) => {
+            assert_eq!(violation.quantity, InputQuantity::CurrentVelocity);
+            assert_eq!(violation.kind, ViolationKind::AboveMaximum);
+        }
+        result => panic!("Unexpected result: {:?}", result),
+    }
+}
+
 #[test]
 fn test_enabled() {
     // Setup
@@ -308,16 +495,16 @@ fn test_enabled() {
     let mut input = InputParameter::new(None);
     let mut output = OutputParameter::new(None);
 
-    input.enabled = DataArrayOrVec::Stack([true, false, false]);
-    input.current_position = DataArrayOrVec::Stack([0.0, -2.0, 0.0]);
-    input.current_velocity = DataArrayOrVec::Stack([0.0, 0.1, 0.0]);
-    input.current_acceleration = DataArrayOrVec::Stack([0.0, 0.0, -0.2]);
+    input.enabled = DataArrayOrVec::from_array([true, false, false]);
+    input.current_position = DataArrayOrVec::from_array([0.0, -2.0, 0.0]);
+    input.current_velocity = DataArrayOrVec::from_array([0.0, 0.1, 0.0]);
+    input.current_acceleration = DataArrayOrVec::from_array([0.0, 0.0, -0.2]);
 
-    input.target_position = DataArrayOrVec::Stack([1.0, -3.0, 2.0]);
+    input.target_position = DataArrayOrVec::from_array([1.0, -3.0, 2.0]);
 
-    input.max_velocity = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_acceleration = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_jerk = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
+    input.max_velocity = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_acceleration = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_jerk = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
 
     let result = otg.update(&input, &mut output);
 
@@ -328,9 +515,9 @@ fn test_enabled() {
         abs <= 0.000_1
     );
 
-    let mut new_position = DataArrayOrVec::Stack([0.0; 3]);
-    let mut new_velocity = DataArrayOrVec::Stack([0.0; 3]);
-    let mut new_acceleration = DataArrayOrVec::Stack([0.0; 3]);
+    let mut new_position = DataArrayOrVec::from_array([0.0; 3]);
+    let mut new_velocity = DataArrayOrVec::from_array([0.0; 3]);
+    let mut new_acceleration = DataArrayOrVec::from_array([0.0; 3]);
 
     output.trajectory.at_time(
         0.0,
@@ -372,25 +559,25 @@ fn test_enabled() {
     ));
 
     // Make sure that disabled DoFs overwrite prior blocks
-    input.enabled = DataArrayOrVec::Stack([true, true, true]);
-    input.current_position = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
-    input.target_position = DataArrayOrVec::Stack([100.0, -3000.0, 2000.0]);
-    input.target_velocity = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
+    input.enabled = DataArrayOrVec::from_array([true, true, true]);
+    input.current_position = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
+    input.target_position = DataArrayOrVec::from_array([100.0, -3000.0, 2000.0]);
+    input.target_velocity = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
 
     let _result = otg.update(&input, &mut output);
 
-    input.enabled = DataArrayOrVec::Stack([false, false, true]);
-    input.current_position = DataArrayOrVec::Stack([0.0, -2.0, 0.0]);
-    input.current_velocity = DataArrayOrVec::Stack([0.0, 0.2, 0.0]);
-    input.current_acceleration = DataArrayOrVec::Stack([0.0, 0.2, 0.0]);
+    input.enabled = DataArrayOrVec::from_array([false, false, true]);
+    input.current_position = DataArrayOrVec::from_array([0.0, -2.0, 0.0]);
+    input.current_velocity = DataArrayOrVec::from_array([0.0, 0.2, 0.0]);
+    input.current_acceleration = DataArrayOrVec::from_array([0.0, 0.2, 0.0]);
 
-    input.target_position = DataArrayOrVec::Stack([1.0, -3.0, 2.0]);
-    input.target_velocity = DataArrayOrVec::Stack([0.0, 0.0, 0.2]);
-    input.target_acceleration = DataArrayOrVec::Stack([0.0, 0.0, -0.1]);
+    input.target_position = DataArrayOrVec::from_array([1.0, -3.0, 2.0]);
+    input.target_velocity = DataArrayOrVec::from_array([0.0, 0.0, 0.2]);
+    input.target_acceleration = DataArrayOrVec::from_array([0.0, 0.0, -0.1]);
 
-    input.max_velocity = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_acceleration = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_jerk = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
+    input.max_velocity = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_acceleration = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_jerk = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
 
     let result = otg.update(&input, &mut output);
 
@@ -410,17 +597,17 @@ fn test_phase_synchronization() {
     let mut output = OutputParameter::new(None);
     let mut traj = Trajectory::new(None);
 
-    let mut new_position = DataArrayOrVec::Stack([0.0; 3]);
-    let mut new_velocity = DataArrayOrVec::Stack([0.0; 3]);
-    let mut new_acceleration = DataArrayOrVec::Stack([0.0; 3]);
+    let mut new_position = DataArrayOrVec::from_array([0.0; 3]);
+    let mut new_velocity = DataArrayOrVec::from_array([0.0; 3]);
+    let mut new_acceleration = DataArrayOrVec::from_array([0.0; 3]);
 
-    input.current_position = DataArrayOrVec::Stack([0.0, -2.0, 0.0]);
+    input.current_position = DataArrayOrVec::from_array([0.0, -2.0, 0.0]);
 
-    input.target_position = DataArrayOrVec::Stack([1.0, -3.0, 2.0]);
+    input.target_position = DataArrayOrVec::from_array([1.0, -3.0, 2.0]);
 
-    input.max_velocity = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_acceleration = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_jerk = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
+    input.max_velocity = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_acceleration = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_jerk = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
 
     input.synchronization = Synchronization::Phase;
 
@@ -467,13 +654,13 @@ fn test_phase_synchronization() {
         0.000_1,
     ));
 
-    input.current_position = DataArrayOrVec::Stack([0.0, -2.0, 0.0]);
+    input.current_position = DataArrayOrVec::from_array([0.0, -2.0, 0.0]);
 
-    input.target_position = DataArrayOrVec::Stack([10.0, -3.0, 2.0]);
+    input.target_position = DataArrayOrVec::from_array([10.0, -3.0, 2.0]);
 
-    input.max_velocity = DataArrayOrVec::Stack([10.0, 2.0, 1.0]);
-    input.max_acceleration = DataArrayOrVec::Stack([10.0, 2.0, 1.0]);
-    input.max_jerk = DataArrayOrVec::Stack([10.0, 2.0, 1.0]);
+    input.max_velocity = DataArrayOrVec::from_array([10.0, 2.0, 1.0]);
+    input.max_acceleration = DataArrayOrVec::from_array([10.0, 2.0, 1.0]);
+    input.max_jerk = DataArrayOrVec::from_array([10.0, 2.0, 1.0]);
 
     let result = otg.update(&input, &mut output);
 
@@ -505,8 +692,8 @@ fn test_phase_synchronization() {
     ));
 
     // Test equal start and target state
-    input.current_position = DataArrayOrVec::Stack([1.0, -2.0, 3.0]);
-    input.target_position = DataArrayOrVec::Stack([1.0, -2.0, 3.0]);
+    input.current_position = DataArrayOrVec::from_array([1.0, -2.0, 3.0]);
+    input.target_position = DataArrayOrVec::from_array([1.0, -2.0, 3.0]);
 
     let result = otg.update(&input, &mut output);
     output.trajectory.at_time(
@@ -532,17 +719,17 @@ fn test_phase_synchronization() {
         0.000_1,
     ));
 
-    input.current_position = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
-    input.current_velocity = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
-    input.current_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.current_position = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
+    input.current_velocity = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
+    input.current_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
-    input.target_position = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
-    input.target_velocity = DataArrayOrVec::Stack([0.2, 0.3, 0.4]);
-    input.target_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.target_position = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
+    input.target_velocity = DataArrayOrVec::from_array([0.2, 0.3, 0.4]);
+    input.target_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
-    input.max_velocity = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_acceleration = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_jerk = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
+    input.max_velocity = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_acceleration = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_jerk = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
 
     let result = otg.calculate(&input, &mut traj);
     assert_eq!(result.unwrap(), RuckigResult::Working);
@@ -557,13 +744,13 @@ fn test_phase_synchronization() {
         0.000_1,
     ));
 
-    input.current_position = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
-    input.current_velocity = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
-    input.current_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.current_position = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
+    input.current_velocity = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
+    input.current_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
-    input.target_position = DataArrayOrVec::Stack([0.0, 0.0, 0.01]);
-    input.target_velocity = DataArrayOrVec::Stack([0.2, 0.3, 0.4]);
-    input.target_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.target_position = DataArrayOrVec::from_array([0.0, 0.0, 0.01]);
+    input.target_velocity = DataArrayOrVec::from_array([0.2, 0.3, 0.4]);
+    input.target_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
     let result = otg.calculate(&input, &mut traj);
 
@@ -571,13 +758,13 @@ fn test_phase_synchronization() {
     assert_ne!(traj.get_profiles()[0][0].t, traj.get_profiles()[0][1].t);
     assert_ne!(traj.get_profiles()[0][0].t, traj.get_profiles()[0][2].t);
 
-    input.current_position = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
-    input.current_velocity = DataArrayOrVec::Stack([0.4, 0.15, 0.2]);
-    input.current_acceleration = DataArrayOrVec::Stack([0.8, 0.3, 0.4]);
+    input.current_position = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
+    input.current_velocity = DataArrayOrVec::from_array([0.4, 0.15, 0.2]);
+    input.current_acceleration = DataArrayOrVec::from_array([0.8, 0.3, 0.4]);
 
-    input.target_position = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
-    input.target_velocity = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
-    input.target_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.target_position = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
+    input.target_velocity = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
+    input.target_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
     let result = otg.calculate(&input, &mut traj);
 
@@ -593,7 +780,7 @@ fn test_phase_synchronization() {
         0.000_1,
     ));
 
-    input.max_velocity = DataArrayOrVec::Stack([1.0, 0.2, 1.0]);
+    input.max_velocity = DataArrayOrVec::from_array([1.0, 0.2, 1.0]);
 
     let result = otg.calculate(&input, &mut traj);
 
@@ -601,17 +788,17 @@ fn test_phase_synchronization() {
     assert_ne!(traj.get_profiles()[0][0].t, traj.get_profiles()[0][1].t);
     assert_ne!(traj.get_profiles()[0][0].t, traj.get_profiles()[0][2].t);
 
-    input.current_position = DataArrayOrVec::Stack([0.0, 0.02, 1.0]);
-    input.current_velocity = DataArrayOrVec::Stack([-0.2, 0.15, 0.2]);
-    input.current_acceleration = DataArrayOrVec::Stack([-0.4, 0.3, 0.4]);
+    input.current_position = DataArrayOrVec::from_array([0.0, 0.02, 1.0]);
+    input.current_velocity = DataArrayOrVec::from_array([-0.2, 0.15, 0.2]);
+    input.current_acceleration = DataArrayOrVec::from_array([-0.4, 0.3, 0.4]);
 
-    input.target_position = DataArrayOrVec::Stack([0.03, 0.0, 0.0]);
-    input.target_velocity = DataArrayOrVec::Stack([-0.02, 0.015, 0.02]);
-    input.target_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.target_position = DataArrayOrVec::from_array([0.03, 0.0, 0.0]);
+    input.target_velocity = DataArrayOrVec::from_array([-0.02, 0.015, 0.02]);
+    input.target_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
-    input.max_velocity = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_acceleration = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_jerk = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
+    input.max_velocity = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_acceleration = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_jerk = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
     input.control_interface = ControlInterface::Velocity;
 
     let result = otg.calculate(&input, &mut traj);
@@ -628,7 +815,7 @@ fn test_phase_synchronization() {
         0.000_1,
     ));
 
-    input.max_jerk = DataArrayOrVec::Stack([1.0, 0.1, 1.0]);
+    input.max_jerk = DataArrayOrVec::from_array([1.0, 0.1, 1.0]);
 
     let result = otg.calculate(&input, &mut traj);
 
@@ -644,7 +831,7 @@ fn test_phase_synchronization() {
         0.000_1,
     ));
 
-    input.target_acceleration = DataArrayOrVec::Stack([0.01, 0.0, 0.0]);
+    input.target_acceleration = DataArrayOrVec::from_array([0.01, 0.0, 0.0]);
 
     let result = otg.calculate(&input, &mut traj);
 
@@ -652,13 +839,13 @@ fn test_phase_synchronization() {
     assert_ne!(traj.get_profiles()[0][0].t, traj.get_profiles()[0][1].t);
     assert_ne!(traj.get_profiles()[0][0].t, traj.get_profiles()[0][2].t);
 
-    input.current_position = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
-    input.current_velocity = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
-    input.current_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.current_position = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
+    input.current_velocity = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
+    input.current_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
-    input.target_position = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
-    input.target_velocity = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
-    input.target_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.target_position = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
+    input.target_velocity = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
+    input.target_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
     let result = otg.calculate(&input, &mut traj);
 
@@ -673,18 +860,18 @@ fn test_discretion() {
     let mut output = OutputParameter::new(None);
     let mut traj = Trajectory::new(None);
 
-    let mut new_position = DataArrayOrVec::Stack([0.0; 3]);
-    let mut new_velocity = DataArrayOrVec::Stack([0.0; 3]);
-    let mut new_acceleration = DataArrayOrVec::Stack([0.0; 3]);
+    let mut new_position = DataArrayOrVec::from_array([0.0; 3]);
+    let mut new_velocity = DataArrayOrVec::from_array([0.0; 3]);
+    let mut new_acceleration = DataArrayOrVec::from_array([0.0; 3]);
 
-    input.current_position = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.current_position = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
-    input.target_position = DataArrayOrVec::Stack([1.0, -3.0, 2.0]);
-    input.target_velocity = DataArrayOrVec::Stack([0.2, 0.2, 0.2]);
+    input.target_position = DataArrayOrVec::from_array([1.0, -3.0, 2.0]);
+    input.target_velocity = DataArrayOrVec::from_array([0.2, 0.2, 0.2]);
 
-    input.max_velocity = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_acceleration = DataArrayOrVec::Stack([2.0, 2.0, 2.0]);
-    input.max_jerk = DataArrayOrVec::Stack([1.8, 2.4, 2.0]);
+    input.max_velocity = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_acceleration = DataArrayOrVec::from_array([2.0, 2.0, 2.0]);
+    input.max_jerk = DataArrayOrVec::from_array([1.8, 2.4, 2.0]);
 
     input.duration_discretization = DurationDiscretization::Discrete;
 
@@ -705,6 +892,27 @@ fn test_discretion() {
     assert!(almost_equal_vecs(&new_position, &[1.0, -3.0, 2.0], 0.000_1));
 }
 
+#[test]
+fn test_discrete_phase_synchronization() {
+    // Phase synchronization falls back to time synchronization, as the timing of the limiting DoF
+    // doesn't end at the rounded duration. Copying it missed the target state.
+    let mut otg = Ruckig::<1, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.control_interface = ControlInterface::Velocity;
+    input.synchronization = Synchronization::Phase;
+    input.duration_discretization = DurationDiscretization::Discrete;
+    input.target_velocity = DataArrayOrVec::from_array([7e-6]);
+    input.target_acceleration = DataArrayOrVec::from_array([0.07]);
+    input.max_acceleration = DataArrayOrVec::from_array([0.8]);
+    input.max_jerk = DataArrayOrVec::from_array([2600.0]);
+    otg.calculate(&input, &mut traj).unwrap();
+    assert_float_eq!(traj.get_duration(), 0.01, abs <= 1e-12);
+    let state = traj.state_at(0.01);
+    assert_float_eq!(state.velocity[0], 7e-6, abs <= 1e-12);
+    assert_float_eq!(state.acceleration[0], 0.07, abs <= 1e-12);
+}
+
 #[test]
 fn test_per_dof_setting() {
     let mut otg = Ruckig::<3, ThrowErrorHandler>::new(None, 0.005);
@@ -712,25 +920,25 @@ fn test_per_dof_setting() {
     let mut traj = Trajectory::new(None);
 
     // Test case 1
-    input.current_position = DataArrayOrVec::Stack([0.0, -2.0, 0.0]);
-    input.current_velocity = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
-    input.current_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.current_position = DataArrayOrVec::from_array([0.0, -2.0, 0.0]);
+    input.current_velocity = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
+    input.current_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
-    input.target_position = DataArrayOrVec::Stack([1.0, -3.0, 2.0]);
-    input.target_velocity = DataArrayOrVec::Stack([0.0, 0.3, 0.0]);
-    input.target_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.target_position = DataArrayOrVec::from_array([1.0, -3.0, 2.0]);
+    input.target_velocity = DataArrayOrVec::from_array([0.0, 0.3, 0.0]);
+    input.target_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
-    input.max_velocity = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_acceleration = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_jerk = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
+    input.max_velocity = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_acceleration = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_jerk = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
 
     let result = otg.calculate(&input, &mut traj);
     assert_eq!(result.unwrap(), RuckigResult::Working);
     assert_float_eq!(traj.get_duration(), 4.0, abs <= 0.000_1);
 
-    let mut new_position = DataArrayOrVec::Stack([0.0; 3]);
-    let mut new_velocity = DataArrayOrVec::Stack([0.0; 3]);
-    let mut new_acceleration = DataArrayOrVec::Stack([0.0; 3]);
+    let mut new_position = DataArrayOrVec::from_array([0.0; 3]);
+    let mut new_velocity = DataArrayOrVec::from_array([0.0; 3]);
+    let mut new_acceleration = DataArrayOrVec::from_array([0.0; 3]);
 
     traj.at_time(
         2.0,
@@ -767,7 +975,7 @@ fn test_per_dof_setting() {
         0.000_1,
     ));
 
-    input.per_dof_control_interface = Some(DataArrayOrVec::Stack([
+    input.per_dof_control_interface = Some(DataArrayOrVec::from_array([
         ControlInterface::Position,
         ControlInterface::Velocity,
         ControlInterface::Position,
@@ -778,7 +986,7 @@ fn test_per_dof_setting() {
     assert_eq!(result.unwrap(), RuckigResult::Working);
     assert_float_eq!(traj.get_duration(), 4.0, abs <= 0.000_1);
 
-    input.per_dof_synchronization = Some(DataArrayOrVec::Stack([
+    input.per_dof_synchronization = Some(DataArrayOrVec::from_array([
         Synchronization::Time,
         Synchronization::None,
         Synchronization::Time,
@@ -806,7 +1014,7 @@ fn test_per_dof_setting() {
 
     input.control_interface = ControlInterface::Position;
     input.per_dof_control_interface = None;
-    input.per_dof_synchronization = Some(DataArrayOrVec::Stack([
+    input.per_dof_synchronization = Some(DataArrayOrVec::from_array([
         Synchronization::None,
         Synchronization::Time,
         Synchronization::Time,
@@ -867,19 +1075,19 @@ fn test_per_dof_setting() {
 
     assert_float_eq!(new_position[2], input.target_position[2], abs <= 0.000_1);
 
-    input.current_position = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
-    input.current_velocity = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
-    input.current_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.current_position = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
+    input.current_velocity = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
+    input.current_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
-    input.target_position = DataArrayOrVec::Stack([35.0, 35.0, 35.0]);
-    input.target_velocity = DataArrayOrVec::Stack([125.0, 125.0, 100.0]);
-    input.target_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.target_position = DataArrayOrVec::from_array([35.0, 35.0, 35.0]);
+    input.target_velocity = DataArrayOrVec::from_array([125.0, 125.0, 100.0]);
+    input.target_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
-    input.max_velocity = DataArrayOrVec::Stack([125.0, 125.0, 100.0]);
-    input.max_acceleration = DataArrayOrVec::Stack([2000.0, 2000.0, 2000.0]);
-    input.max_jerk = DataArrayOrVec::Stack([20000.0, 20000.0, 20000.0]);
+    input.max_velocity = DataArrayOrVec::from_array([125.0, 125.0, 100.0]);
+    input.max_acceleration = DataArrayOrVec::from_array([2000.0, 2000.0, 2000.0]);
+    input.max_jerk = DataArrayOrVec::from_array([20000.0, 20000.0, 20000.0]);
 
-    input.per_dof_synchronization = Some(DataArrayOrVec::Stack([
+    input.per_dof_synchronization = Some(DataArrayOrVec::from_array([
         Synchronization::Time,
         Synchronization::Time,
         Synchronization::None,
@@ -890,19 +1098,19 @@ fn test_per_dof_setting() {
     assert_eq!(result.unwrap(), RuckigResult::Working);
     assert_float_eq!(traj.get_duration(), 0.4207106781, abs <= 0.000_1);
 
-    input.current_position = DataArrayOrVec::Stack([0.0, -2.0, 0.0]);
-    input.current_velocity = DataArrayOrVec::Stack([0.0, 0.2, 0.0]);
-    input.current_acceleration = DataArrayOrVec::Stack([0.0, 0.2, 0.0]);
+    input.current_position = DataArrayOrVec::from_array([0.0, -2.0, 0.0]);
+    input.current_velocity = DataArrayOrVec::from_array([0.0, 0.2, 0.0]);
+    input.current_acceleration = DataArrayOrVec::from_array([0.0, 0.2, 0.0]);
 
-    input.target_position = DataArrayOrVec::Stack([1.0, -3.0, 2.0]);
-    input.target_velocity = DataArrayOrVec::Stack([0.0, 0.0, 0.2]);
-    input.target_acceleration = DataArrayOrVec::Stack([0.0, 0.0, -0.1]);
+    input.target_position = DataArrayOrVec::from_array([1.0, -3.0, 2.0]);
+    input.target_velocity = DataArrayOrVec::from_array([0.0, 0.0, 0.2]);
+    input.target_acceleration = DataArrayOrVec::from_array([0.0, 0.0, -0.1]);
 
-    input.max_velocity = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_acceleration = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_jerk = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
+    input.max_velocity = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_acceleration = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_jerk = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
 
-    input.per_dof_synchronization = Some(DataArrayOrVec::Stack([
+    input.per_dof_synchronization = Some(DataArrayOrVec::from_array([
         Synchronization::None,
         Synchronization::None,
         Synchronization::Time,
@@ -913,7 +1121,7 @@ fn test_per_dof_setting() {
     assert_eq!(result.unwrap(), RuckigResult::Working);
     assert_float_eq!(traj.get_duration(), 3.7885667284, abs <= 0.000_1);
 
-    input.per_dof_synchronization = Some(DataArrayOrVec::Stack([
+    input.per_dof_synchronization = Some(DataArrayOrVec::from_array([
         Synchronization::None,
         Synchronization::Time,
         Synchronization::None,
@@ -924,32 +1132,32 @@ fn test_per_dof_setting() {
     assert_eq!(result.unwrap(), RuckigResult::Working);
     assert_float_eq!(traj.get_duration(), 3.7885667284, abs <= 0.000_1);
 
-    input.enabled = DataArrayOrVec::Stack([true, false, true]);
+    input.enabled = DataArrayOrVec::from_array([true, false, true]);
 
     let result = otg.calculate(&input, &mut traj);
 
     assert_eq!(result.unwrap(), RuckigResult::Working);
     assert_float_eq!(traj.get_duration(), 3.6578610221, abs <= 0.000_1);
 
-    input.current_position = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
-    input.current_velocity = DataArrayOrVec::Stack([0.2, 0.0, -0.1]);
-    input.current_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.current_position = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
+    input.current_velocity = DataArrayOrVec::from_array([0.2, 0.0, -0.1]);
+    input.current_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
-    input.target_position = DataArrayOrVec::Stack([1.0, -0.2, -0.5]);
-    input.target_velocity = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
-    input.target_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.target_position = DataArrayOrVec::from_array([1.0, -0.2, -0.5]);
+    input.target_velocity = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
+    input.target_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
-    input.max_velocity = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_acceleration = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_jerk = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
+    input.max_velocity = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_acceleration = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_jerk = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
 
-    input.per_dof_synchronization = Some(DataArrayOrVec::Stack([
+    input.per_dof_synchronization = Some(DataArrayOrVec::from_array([
         Synchronization::Phase,
         Synchronization::None,
         Synchronization::Phase,
     ]));
 
-    input.enabled = DataArrayOrVec::Stack([true, true, true]);
+    input.enabled = DataArrayOrVec::from_array([true, true, true]);
 
     let result = otg.calculate(&input, &mut traj);
 
@@ -969,26 +1177,26 @@ fn test_dynamic_dofs() {
     let mut input = InputParameter::new(Some(3));
     let mut output = OutputParameter::new(Some(3));
 
-    input.current_position = DataArrayOrVec::Heap(vec![0.0, -2.0, 0.0]);
-    input.current_velocity = DataArrayOrVec::Heap(vec![0.0, 0.0, 0.0]);
-    input.current_acceleration = DataArrayOrVec::Heap(vec![0.0, 0.0, 0.0]);
+    input.current_position = DataArrayOrVec::from_vec(vec![0.0, -2.0, 0.0]).unwrap();
+    input.current_velocity = DataArrayOrVec::from_vec(vec![0.0, 0.0, 0.0]).unwrap();
+    input.current_acceleration = DataArrayOrVec::from_vec(vec![0.0, 0.0, 0.0]).unwrap();
 
-    input.target_position = DataArrayOrVec::Heap(vec![1.0, -3.0, 2.0]);
-    input.target_velocity = DataArrayOrVec::Heap(vec![0.0, 0.3, 0.0]);
-    input.target_acceleration = DataArrayOrVec::Heap(vec![0.0, 0.0, 0.0]);
+    input.target_position = DataArrayOrVec::from_vec(vec![1.0, -3.0, 2.0]).unwrap();
+    input.target_velocity = DataArrayOrVec::from_vec(vec![0.0, 0.3, 0.0]).unwrap();
+    input.target_acceleration = DataArrayOrVec::from_vec(vec![0.0, 0.0, 0.0]).unwrap();
 
-    input.max_velocity = DataArrayOrVec::Heap(vec![1.0, 1.0, 1.0]);
-    input.max_acceleration = DataArrayOrVec::Heap(vec![1.0, 1.0, 1.0]);
-    input.max_jerk = DataArrayOrVec::Heap(vec![1.0, 1.0, 1.0]);
+    input.max_velocity = DataArrayOrVec::from_vec(vec![1.0, 1.0, 1.0]).unwrap();
+    input.max_acceleration = DataArrayOrVec::from_vec(vec![1.0, 1.0, 1.0]).unwrap();
+    input.max_jerk = DataArrayOrVec::from_vec(vec![1.0, 1.0, 1.0]).unwrap();
 
     let result = otg.update(&input, &mut output);
 
     assert_eq!(result.unwrap(), RuckigResult::Working);
     assert_float_eq!(output.trajectory.get_duration(), 4.0, abs <= 0.000_1);
 
-    let mut new_position = DataArrayOrVec::Heap(vec![0.0; 3]);
-    let mut new_velocity = DataArrayOrVec::Heap(vec![0.0; 3]);
-    let mut new_acceleration = DataArrayOrVec::Heap(vec![0.0; 3]);
+    let mut new_position = DataArrayOrVec::from_vec(vec![0.0; 3]).unwrap();
+    let mut new_velocity = DataArrayOrVec::from_vec(vec![0.0; 3]).unwrap();
+    let mut new_acceleration = DataArrayOrVec::from_vec(vec![0.0; 3]).unwrap();
 
     output.trajectory.at_time(
         0.0,
@@ -1021,34 +1229,34 @@ fn test_zero_limits() {
     let mut input = InputParameter::new(None);
     let mut output = OutputParameter::new(None);
 
-    input.current_position = DataArrayOrVec::Stack([0.0, -2.0, 0.0]);
-    input.current_velocity = DataArrayOrVec::Stack([0.2, 0.0, 0.0]);
-    input.current_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.current_position = DataArrayOrVec::from_array([0.0, -2.0, 0.0]);
+    input.current_velocity = DataArrayOrVec::from_array([0.2, 0.0, 0.0]);
+    input.current_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
-    input.target_position = DataArrayOrVec::Stack([1.0, -3.0, 0.0]);
-    input.target_velocity = DataArrayOrVec::Stack([0.2, 0.0, 0.0]);
-    input.target_acceleration = DataArrayOrVec::Stack([0.0, 0.0, 0.0]);
+    input.target_position = DataArrayOrVec::from_array([1.0, -3.0, 0.0]);
+    input.target_velocity = DataArrayOrVec::from_array([0.2, 0.0, 0.0]);
+    input.target_acceleration = DataArrayOrVec::from_array([0.0, 0.0, 0.0]);
 
-    input.max_velocity = DataArrayOrVec::Stack([1.0, 1.0, 1.0]);
-    input.max_acceleration = DataArrayOrVec::Stack([0.0, 1.0, 0.0]);
-    input.max_jerk = DataArrayOrVec::Stack([0.0, 1.0, 0.0]);
+    input.max_velocity = DataArrayOrVec::from_array([1.0, 1.0, 1.0]);
+    input.max_acceleration = DataArrayOrVec::from_array([0.0, 1.0, 0.0]);
+    input.max_jerk = DataArrayOrVec::from_array([0.0, 1.0, 0.0]);
 
     let result = otg.update(&input, &mut output);
 
     assert_eq!(result.unwrap(), RuckigResult::Working);
     assert_float_eq!(output.trajectory.get_duration(), 5.0, abs <= 0.000_1);
 
-    input.current_position = DataArrayOrVec::Stack([0.0, -2.0, 0.0]);
-    input.current_velocity = DataArrayOrVec::Stack([-0.2, 0.0, 0.0]);
-    input.current_acceleration = DataArrayOrVec::Stack([1.0, 0.0, 0.0]);
+    input.current_position = DataArrayOrVec::from_array([0.0, -2.0, 0.0]);
+    input.current_velocity = DataArrayOrVec::from_array([-0.2, 0.0, 0.0]);
+    input.current_acceleration = DataArrayOrVec::from_array([1.0, 0.0, 0.0]);
 
-    input.target_position = DataArrayOrVec::Stack([0.4, -3.0, 0.0]);
-    input.target_velocity = DataArrayOrVec::Stack([0.8, 0.0, 0.0]);
-    input.target_acceleration = DataArrayOrVec::Stack([1.0, 0.0, 0.0]);
+    input.target_position = DataArrayOrVec::from_array([0.4, -3.0, 0.0]);
+    input.target_velocity = DataArrayOrVec::from_array([0.8, 0.0, 0.0]);
+    input.target_acceleration = DataArrayOrVec::from_array([1.0, 0.0, 0.0]);
 
-    input.max_velocity = DataArrayOrVec::Stack([1.0, 200.0, 0.0]);
-    input.max_acceleration = DataArrayOrVec::Stack([1.0, 200.0, 0.0]);
-    input.max_jerk = DataArrayOrVec::Stack([0.0, 200.0, 0.0]);
+    input.max_velocity = DataArrayOrVec::from_array([1.0, 200.0, 0.0]);
+    input.max_acceleration = DataArrayOrVec::from_array([1.0, 200.0, 0.0]);
+    input.max_jerk = DataArrayOrVec::from_array([0.0, 200.0, 0.0]);
 
     let result = otg.update(&input, &mut output);
 
@@ -1061,13 +1269,17 @@ fn test_zero_limits() {
                 "Unexpected error message: {}",
                 error_message
             );
+            assert!(
+                matches!(e, RuckigError::ZeroLimitsConflict { dof: 0, state } if state.j_max == 0.0)
+            );
+            assert_eq!(e.result(), RuckigResult::ErrorZeroLimits);
         }
     }
 
-    input.target_position = DataArrayOrVec::Stack([0.3, -3.0, 0.0]);
-    input.max_velocity = DataArrayOrVec::Stack([1.0, 2.0, 1.0]);
-    input.max_acceleration = DataArrayOrVec::Stack([1.0, 2.0, 0.0]);
-    input.max_jerk = DataArrayOrVec::Stack([0.0, 2.0, 0.0]);
+    input.target_position = DataArrayOrVec::from_array([0.3, -3.0, 0.0]);
+    input.max_velocity = DataArrayOrVec::from_array([1.0, 2.0, 1.0]);
+    input.max_acceleration = DataArrayOrVec::from_array([1.0, 2.0, 0.0]);
+    input.max_jerk = DataArrayOrVec::from_array([0.0, 2.0, 0.0]);
 
     let result = otg.update(&input, &mut output);
 
@@ -1080,19 +1292,23 @@ fn test_zero_limits() {
                 "Unexpected error message: {}",
                 error_message
             );
+            assert!(matches!(
+                e,
+                RuckigError::ZeroLimitsSynchronizationConflict { dof: 0, .. }
+            ));
         }
     }
 
     input.control_interface = ControlInterface::Velocity;
-    input.current_position = DataArrayOrVec::Stack([0.0, -2.0, 0.0]);
-    input.current_velocity = DataArrayOrVec::Stack([-0.2, 0.0, 0.0]);
-    input.current_acceleration = DataArrayOrVec::Stack([1.0, 0.0, 0.2]);
-    input.target_position = DataArrayOrVec::Stack([0.4, -3.0, 0.0]);
-    input.target_velocity = DataArrayOrVec::Stack([0.9, 0.5, 0.4]);
-    input.target_acceleration = DataArrayOrVec::Stack([1.0, 0.0, 0.2]);
-    input.max_velocity = DataArrayOrVec::Stack([1.0, 2.0, 1.0]);
-    input.max_acceleration = DataArrayOrVec::Stack([1.0, 2.0, 6.0]);
-    input.max_jerk = DataArrayOrVec::Stack([0.0, 2.0, 0.0]);
+    input.current_position = DataArrayOrVec::from_array([0.0, -2.0, 0.0]);
+    input.current_velocity = DataArrayOrVec::from_array([-0.2, 0.0, 0.0]);
+    input.current_acceleration = DataArrayOrVec::from_array([1.0, 0.0, 0.2]);
+    input.target_position = DataArrayOrVec::from_array([0.4, -3.0, 0.0]);
+    input.target_velocity = DataArrayOrVec::from_array([0.9, 0.5, 0.4]);
+    input.target_acceleration = DataArrayOrVec::from_array([1.0, 0.0, 0.2]);
+    input.max_velocity = DataArrayOrVec::from_array([1.0, 2.0, 1.0]);
+    input.max_acceleration = DataArrayOrVec::from_array([1.0, 2.0, 6.0]);
+    input.max_jerk = DataArrayOrVec::from_array([0.0, 2.0, 0.0]);
 
     let result = otg.update(&input, &mut output);
 
@@ -1108,14 +1324,14 @@ fn test_zero_limits() {
         }
     }
 
-    input.max_jerk = DataArrayOrVec::Stack([1.0, 2.0, 0.0]);
+    input.max_jerk = DataArrayOrVec::from_array([1.0, 2.0, 0.0]);
 
     let result = otg.update(&input, &mut output);
 
     assert_eq!(result.unwrap(), RuckigResult::Working);
     assert_float_eq!(output.trajectory.get_duration(), 2.0, abs <= 0.000_1);
 
-    input.max_jerk = DataArrayOrVec::Stack([0.0, 2.0, 20.0]);
+    input.max_jerk = DataArrayOrVec::from_array([0.0, 2.0, 20.0]);
 
     let result = otg.update(&input, &mut output);
 
@@ -1123,6 +1339,24 @@ fn test_zero_limits() {
     assert_float_eq!(output.trajectory.get_duration(), 1.1, abs <= 0.000_1);
 }
 
+#[test]
+fn test_overflowing_durations() {
+    // A subnormal jerk limit overflows the durations, so that no profile is found instead of
+    // panicking on the NaN duration of the profile
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.control_interface = ControlInterface::Velocity;
+    input.target_acceleration = DataArrayOrVec::from_array([1.0980559709463792e-6, 1e-6]);
+    input.max_acceleration = DataArrayOrVec::from_array([32.501960784313724, 1.0]);
+    input.min_acceleration = Some(DataArrayOrVec::from_array([-94.14960173345133, -1.0]));
+    input.max_jerk = DataArrayOrVec::from_array([1.0630782834e-314, 1.0]);
+    assert!(matches!(
+        otg.calculate(&input, &mut traj),
+        Err(RuckigError::Step1Failure { dof: 0, .. })
+    ));
+}
+
 #[test]
 fn test_min_duration() -> Result<(), RuckigError> {
     let mut otg = Ruckig::<1, ThrowErrorHandler>::new(None, 0.01);
@@ -1150,7 +1384,7 @@ fn test_min_duration() -> Result<(), RuckigError> {
     let mut trajectory_min_duration = Trajectory::new(None);
     input.minimum_duration = Some(5.0);
     otg.calculate(&input, &mut trajectory_min_duration)?;
-    let new_duration = trajectory_min_duration.duration.clone();
+    let new_duration = trajectory_min_duration.duration;
     dbg!(duration, new_duration);
     assert!(new_duration > duration);
     assert_float_eq!(new_duration, 5.0, abs <= 0.000_1);
@@ -1158,6 +1392,27 @@ fn test_min_duration() -> Result<(), RuckigError> {
     Ok(())
 }
 
+#[test]
+fn test_velocity_min_duration_jerk_limit() -> Result<(), RuckigError> {
+    // The velocity interface brakes the current acceleration within the jerk limit. Step 2 didn't
+    // check the jerk of its profile without a reached limit.
+    let mut otg = Ruckig::<1, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut trajectory_min_duration = Trajectory::new(None);
+    input.control_interface = ControlInterface::Velocity;
+    input.current_acceleration[0] = -0.003;
+    input.target_velocity[0] = -3e-11;
+    input.max_acceleration[0] = 0.003;
+    input.max_jerk[0] = 0.003;
+    input.minimum_duration = Some(45.0);
+    otg.calculate(&input, &mut trajectory_min_duration)?;
+    assert_float_eq!(trajectory_min_duration.duration, 45.0, abs <= 1e-12);
+    let profile = &trajectory_min_duration.get_profiles()[0][0];
+    assert!(profile.j.iter().all(|j| j.abs() <= 0.003 + 1e-12));
+
+    Ok(())
+}
+
 #[test]
 fn test_matched_signs_phase_sync() {
     let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
@@ -1184,7 +1439,7 @@ fn test_matched_signs_phase_sync() {
         .calculate(&input, &mut trajectory)
         .expect("This trajectory is solvable.");
 
-    let profiles = trajectory.get_profiles().get(0).unwrap();
+    let profiles = trajectory.get_profiles().first().unwrap();
     let dof0_profile = profiles.get(0).unwrap();
     let dof1_profile = profiles.get(1).unwrap();
 
@@ -1217,9 +1472,4117 @@ fn test_mixed_signs_phase_sync() {
         .calculate(&input, &mut trajectory)
         .expect("This trajectory is solvable.");
 
-    let profiles = trajectory.get_profiles().get(0).unwrap();
+    let profiles = trajectory.get_profiles().first().unwrap();
     let dof0_profile = profiles.get(0).unwrap();
     let dof1_profile = profiles.get(1).unwrap();
 
     assert_eq!(dof0_profile.t, dof1_profile.t);
 }
+
+#[test]
+fn test_intermediate_positions() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new_with_waypoints(2, None, 0.01);
+    let mut input = InputParameter::new_with_waypoints(2, None);
+
+    input.current_position = daov_stack![0.0, 0.0];
+    input.target_position = daov_stack![2.0, -1.0];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+
+    input
+        .push_intermediate_position(daov_stack![1.0, 0.5])
+        .unwrap();
+    input
+        .push_intermediate_position(daov_stack![0.5, 1.0])
+        .unwrap();
+    assert!(input
+        .push_intermediate_position(daov_stack![0.0, 0.0])
+        .is_err());
+
+    let mut traj = Trajectory::new_with_waypoints(2, None);
+    let result = otg.calculate(&input, &mut traj);
+    assert_eq!(result.unwrap(), RuckigResult::Working);
+    assert_eq!(traj.get_number_of_sections(), 3);
+    assert_eq!(traj.get_profiles().len(), 3);
+
+    let mut new_position = daov_stack![0.0; 2];
+    let mut new_velocity = daov_stack![0.0; 2];
+    for (section, waypoint) in input.intermediate_positions.iter().enumerate() {
+        traj.at_time(
+            traj.get_intermediate_durations()[section],
+            &mut Some(&mut new_position),
+            &mut Some(&mut new_velocity),
+            &mut None,
+            &mut None,
+            &mut None,
+        );
+        assert!(almost_equal_vecs(&new_position, waypoint, 1e-8));
+        assert!(almost_equal_vecs(&new_velocity, &[0.0, 0.0], 1e-8));
+    }
+
+    traj.at_time(
+        traj.get_duration(),
+        &mut Some(&mut new_position),
+        &mut None,
+        &mut None,
+        &mut None,
+        &mut None,
+    );
+    assert!(almost_equal_vecs(
+        &new_position,
+        &input.target_position,
+        1e-8
+    ));
+    assert_float_eq!(
+        traj.get_intermediate_durations()[2],
+        traj.get_duration(),
+        abs <= 1e-12
+    );
+
+    // Too many waypoints for this instance
+    let mut otg_without_waypoints = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    assert!(otg_without_waypoints.calculate(&input, &mut traj).is_err());
+}
+
+#[test]
+fn test_streaming_intermediate_positions() {
+    let mut otg = Ruckig::<0, ThrowErrorHandler>::new_with_waypoints(3, Some(2), 0.01);
+    let mut input = InputParameter::new_with_waypoints(3, Some(2));
+    let mut output = OutputParameter::new_with_waypoints(3, Some(2));
+
+    input.current_position = daov_heap![0.0, 0.0];
+    input.target_position = daov_heap![1.0, 1.0];
+    input.max_velocity = daov_heap![1.0, 1.0];
+    input.max_acceleration = daov_heap![2.0, 2.0];
+    input.max_jerk = daov_heap![4.0, 4.0];
+    input
+        .push_intermediate_position(daov_heap![0.5, -0.5])
+        .unwrap();
+
+    // A waypoint without a value for each DoF is rejected when it is pushed
+    assert!(matches!(
+        input.push_intermediate_position(daov_heap![0.5]),
+        Err(RuckigError::DofMismatch)
+    ));
+    assert_eq!(input.intermediate_positions.len(), 1);
+
+    let mut reached_waypoints = 0;
+    let mut result = otg.update(&input, &mut output).unwrap();
+    assert_eq!(output.trajectory.get_number_of_sections(), 2);
+    while result == RuckigResult::Working {
+        output.pass_to_input(&mut input);
+
+        if output.did_section_change && !input.intermediate_positions.is_empty() {
+            input.pop_intermediate_position();
+            reached_waypoints += 1;
+            if reached_waypoints == 1 {
+                // Stream in another waypoint while moving
+                input
+                    .push_intermediate_position(daov_heap![1.5, 0.5])
+                    .unwrap();
+            }
+        }
+        result = otg.update(&input, &mut output).unwrap();
+    }
+
+    assert_eq!(result, RuckigResult::Finished);
+    assert_eq!(reached_waypoints, 2);
+    assert!(almost_equal_vecs(
+        &output.new_position,
+        &input.target_position,
+        1e-8
+    ));
+}
+
+#[test]
+fn test_blended_intermediate_positions() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new_with_waypoints(1, None, 0.01);
+    let mut input = InputParameter::new_with_waypoints(1, None);
+    input.synchronization = Synchronization::Phase;
+    input.current_position = daov_stack![0.0, 0.0];
+    input.target_position = daov_stack![1.0, 1.0];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![2.0, 2.0];
+    input.max_jerk = daov_stack![4.0, 4.0];
+    input
+        .push_intermediate_position(daov_stack![1.0, 0.0])
+        .unwrap();
+
+    let mut traj = Trajectory::new_with_waypoints(1, None);
+    otg.calculate(&input, &mut traj).unwrap();
+    let exact_duration = traj.get_duration();
+
+    let tolerance = 0.05;
+    input.intermediate_blend_tolerances = vec![tolerance].into();
+    assert_eq!(otg.calculate(&input, &mut traj).unwrap(), RuckigResult::Working);
+    assert!(traj.get_duration() < exact_duration);
+
+    // The corner is passed with a velocity, and the path stays close to the polyline
+    let mut new_position = daov_stack![0.0; 2];
+    let mut new_velocity = daov_stack![0.0; 2];
+    traj.at_time(
+        traj.get_intermediate_durations()[0],
+        &mut None,
+        &mut Some(&mut new_velocity),
+        &mut None,
+        &mut None,
+        &mut None,
+    );
+    assert!(new_velocity[0] > 0.0 && new_velocity[1] > 0.0);
+
+    // The path cuts the corner, and misses it at most by the tolerance
+    let mut corner_distance = f64::INFINITY;
+    for step in 0..=200 {
+        let time = traj.get_duration() * step as f64 / 200.0;
+        traj.at_time(
+            time,
+            &mut Some(&mut new_position),
+            &mut None,
+            &mut None,
+            &mut None,
+            &mut None,
+        );
+        let deviation = (1.0 - new_position[0]).abs().min(new_position[1].abs());
+        assert!(deviation <= tolerance + 1e-9, "{} at {}", deviation, time);
+        corner_distance = corner_distance.min((1.0 - new_position[0]).hypot(new_position[1]));
+    }
+    assert!(corner_distance > 1e-6, "{}", corner_distance);
+    assert!(corner_distance <= tolerance + 1e-9, "{}", corner_distance);
+
+    // The blend is planned once, and the control cycles only sample it
+    let mut output = OutputParameter::new_with_waypoints(1, None);
+    let mut number_of_calculations = 0;
+    while otg.update(&input, &mut output).unwrap() == RuckigResult::Working {
+        number_of_calculations += output.new_calculation as usize;
+        output.pass_to_input(&mut input);
+    }
+    assert_eq!(number_of_calculations, 1);
+    assert!(almost_equal_vecs(&new_position, &input.target_position, 1e-8));
+
+    input.intermediate_blend_tolerances = vec![f64::NAN].into();
+    let violation = input.first_violation(false, true).unwrap();
+    assert_eq!(violation.quantity, InputQuantity::BlendTolerance(0));
+    assert_eq!(violation.kind, ViolationKind::NotANumber);
+    input.intermediate_blend_tolerances = vec![-0.1].into();
+    let violation = input.first_violation(false, true).unwrap();
+    assert_eq!(violation.kind, ViolationKind::Negative);
+}
+
+#[test]
+fn test_position_limits() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+
+    // DoF 1 starts with a velocity towards its target and has to overshoot
+    input.current_position = daov_stack![0.0, 0.0];
+    input.current_velocity = daov_stack![0.0, 1.0];
+    input.target_position = daov_stack![1.0, 0.2];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+
+    let mut traj = Trajectory::new(None);
+    otg.calculate(&input, &mut traj).unwrap();
+    let overshoot = traj.get_position_extrema()[1].max;
+    assert!(overshoot > 0.2);
+
+    // Limits that enclose the motion don't change it
+    input.min_position = Some(daov_stack![-1.0, -1.0]);
+    input.max_position = Some(daov_stack![1.0, overshoot + 0.1]);
+    let mut limited_traj = Trajectory::new(None);
+    let result = otg.calculate(&input, &mut limited_traj);
+    assert_eq!(result.unwrap(), RuckigResult::Working);
+    assert_float_eq!(
+        limited_traj.get_duration(),
+        traj.get_duration(),
+        abs <= 1e-12
+    );
+
+    // The overshoot can't be avoided within tighter limits
+    input.max_position = Some(daov_stack![1.0, 0.5 * (overshoot + 0.2)]);
+    let result = otg.calculate(&input, &mut limited_traj);
+    match result {
+        Ok(_) => panic!("Expected an error but got a successful result."),
+        Err(e) => assert!(
+            e.to_string().contains("position limits"),
+            "Unexpected error message: {}",
+            e
+        ),
+    }
+
+    let mut ignoring_otg = Ruckig::<2, IgnoreErrorHandler>::new(None, 0.01);
+    let result = ignoring_otg.calculate(&input, &mut limited_traj);
+    assert_eq!(result.unwrap(), RuckigResult::ErrorPositionalLimits);
+
+    // Targets outside of the limits are rejected by the validation
+    input.max_position = Some(daov_stack![0.5, 1.0]);
+    assert!(otg.validate_input(&input, false, true).is_err());
+    assert!(otg.validate_input(&input, false, false).unwrap());
+}
+
+#[test]
+fn test_position_limits_turnaround() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+
+    // DoF 1 moves away from its target, and its time-synchronized profile leaves the limits
+    input.current_velocity = daov_stack![0.0, -0.8];
+    input.target_position = daov_stack![5.0, 0.5];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+
+    let mut traj = Trajectory::new(None);
+    otg.calculate(&input, &mut traj).unwrap();
+    assert!(traj.get_position_extrema()[1].min < -0.68);
+
+    // Turning around with the smallest excursion first stays within the limits
+    input.min_position = Some(daov_stack![-1.0, -0.68]);
+    input.max_position = Some(daov_stack![6.0, 1.0]);
+    let result = otg.calculate(&input, &mut traj);
+    assert_eq!(result.unwrap(), RuckigResult::Working);
+    let extrema = traj.get_position_extrema()[1].clone();
+    assert!(extrema.min >= -0.68 && extrema.max <= 1.0);
+    assert_float_eq!(extrema.min, -0.8 + 1.0 / 6.0 - 0.045, abs <= 1e-9);
+
+    let mut new_position = DataArrayOrVec::from_array([0.0; 2]);
+    let mut new_velocity = DataArrayOrVec::from_array([0.0; 2]);
+    traj.at_time(
+        traj.get_duration(),
+        &mut Some(&mut new_position),
+        &mut Some(&mut new_velocity),
+        &mut None,
+        &mut None,
+        &mut None,
+    );
+    assert!(almost_equal_vecs(&new_position, &[5.0, 0.5], 1e-8));
+    assert!(almost_equal_vecs(&new_velocity, &[0.0, 0.0], 1e-8));
+
+    // Only the slower of the two extremal velocity profiles stays below the maximal position
+    let mut otg = Ruckig::<1, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    input.control_interface = ControlInterface::Velocity;
+    input.current_velocity = daov_stack![-0.4];
+    input.current_acceleration = daov_stack![0.4];
+    input.target_velocity = daov_stack![0.5];
+    input.target_acceleration = daov_stack![0.5];
+    input.max_velocity = daov_stack![1.0];
+    input.max_acceleration = daov_stack![0.5];
+    input.max_jerk = daov_stack![0.2];
+
+    let mut traj = Trajectory::new(None);
+    otg.calculate(&input, &mut traj).unwrap();
+    let t_min = traj.get_duration();
+    assert!(traj.get_position_extrema()[0].max > 0.05);
+
+    input.min_position = Some(daov_stack![-1.0]);
+    input.max_position = Some(daov_stack![0.05]);
+    let result = otg.calculate(&input, &mut traj);
+    assert_eq!(result.unwrap(), RuckigResult::Working);
+    assert!(traj.get_duration() > t_min + 1.0);
+    assert!(traj.get_position_extrema()[0].max <= 0.05);
+}
+
+#[test]
+fn test_tracking() {
+    let mut tracker = Tracker::<1, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    let mut target = TargetState::new(None);
+
+    input.max_velocity = daov_stack![1.0];
+    input.max_acceleration = daov_stack![2.0];
+    input.max_jerk = daov_stack![10.0];
+
+    // Follow a sine wave with a deterministic noise on top
+    let mut max_error_after_settling: f64 = 0.0;
+    for step in 0..1000 {
+        let t = step as f64 * 0.01;
+        let noise = 0.005 * ((step * 7919) % 13) as f64 / 13.0;
+        target.position[0] = 0.5 * (t + 0.5).sin() + noise;
+        target.velocity[0] = 0.5 * (t + 0.5).cos() + 2.0 * noise;
+        target.acceleration[0] = -0.5 * (t + 0.5).sin();
+
+        tracker.update(&target, &input, &mut output).unwrap();
+        assert!(output.new_velocity[0].abs() <= 1.0 + 1e-9);
+        assert!(output.new_acceleration[0].abs() <= 2.0 + 1e-9);
+        assert!(output.new_jerk[0].abs() <= 10.0 + 1e-9);
+
+        if t > 2.0 {
+            max_error_after_settling =
+                max_error_after_settling.max((output.new_position[0] - target.position[0]).abs());
+        }
+        output.pass_to_input(&mut input);
+    }
+    assert!(
+        max_error_after_settling < 0.02,
+        "tracking error {}",
+        max_error_after_settling
+    );
+
+    // A resting target is reached
+    target.position[0] = 0.2;
+    target.velocity[0] = 0.0;
+    target.acceleration[0] = 0.0;
+    let mut result = RuckigResult::Working;
+    for _ in 0..1000 {
+        result = tracker.update(&target, &input, &mut output).unwrap();
+        output.pass_to_input(&mut input);
+        if result == RuckigResult::Finished {
+            break;
+        }
+    }
+    assert_eq!(result, RuckigResult::Finished);
+    assert_float_eq!(output.new_position[0], 0.2, abs <= 1e-6);
+
+    // A target that moves faster than the velocity limit can't be caught up with
+    for step in 0..100 {
+        target.position[0] = 1.0 + 2.0 * step as f64 * 0.01;
+        target.velocity[0] = 2.0;
+        let result = tracker.update(&target, &input, &mut output).unwrap();
+        assert_eq!(result, RuckigResult::Working);
+        assert!(output.new_velocity[0] <= 1.0 + 1e-9);
+        output.pass_to_input(&mut input);
+    }
+    assert_float_eq!(output.new_velocity[0], 1.0, abs <= 1e-9);
+
+    target.position[0] = f64::NAN;
+    assert!(tracker.update(&target, &input, &mut output).is_err());
+}
+
+#[test]
+fn test_tracking_accelerating_target() {
+    let mut tracker = Tracker::<1, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    let mut target = TargetState::new(None);
+
+    input.max_velocity = daov_stack![1.0];
+    input.max_acceleration = daov_stack![2.0];
+    input.max_jerk = daov_stack![10.0];
+
+    // The target accelerates uniformly from an offset start, and reaches the velocity limit
+    let mut max_error_after_settling: f64 = 0.0;
+    for step in 0..500 {
+        let t = step as f64 * 0.01;
+        target.position[0] = 0.3 + 0.1 * t * t;
+        target.velocity[0] = 0.2 * t;
+        target.acceleration[0] = 0.2;
+
+        tracker.update(&target, &input, &mut output).unwrap();
+        assert!(output.new_velocity[0].abs() <= 1.0 + 1e-9);
+        assert!(output.new_acceleration[0].abs() <= 2.0 + 1e-9);
+        assert!(output.new_jerk[0].abs() <= 10.0 + 1e-9);
+
+        // The output catches up with the target and then follows it exactly
+        let t_next = t + 0.01;
+        if (2.0..4.5).contains(&t) {
+            max_error_after_settling = max_error_after_settling
+                .max((output.new_position[0] - (0.3 + 0.1 * t_next * t_next)).abs())
+                .max((output.new_velocity[0] - 0.2 * t_next).abs())
+                .max((output.new_acceleration[0] - 0.2).abs());
+        }
+        output.pass_to_input(&mut input);
+    }
+    assert!(
+        max_error_after_settling < 1e-6,
+        "tracking error {}",
+        max_error_after_settling
+    );
+    assert_float_eq!(output.new_velocity[0], 1.0, abs <= 1e-6);
+}
+
+#[test]
+fn test_tracking_position_limits() {
+    let mut tracker = Tracker::<1, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    let mut target = TargetState::new(None);
+
+    input.max_velocity = daov_stack![1.0];
+    input.max_acceleration = daov_stack![2.0];
+    input.max_jerk = daov_stack![10.0];
+    input.max_position = Some(daov_stack![1.0]);
+
+    // The target accelerates towards the position limit and beyond, which the output doesn't pass
+    for step in 0..500 {
+        let t = step as f64 * 0.01;
+        target.position[0] = 0.3 + 0.1 * t * t;
+        target.velocity[0] = 0.2 * t;
+        target.acceleration[0] = 0.2;
+
+        tracker.update(&target, &input, &mut output).unwrap();
+        assert!(output.new_position[0] <= 1.0 + 1e-9, "position {}", output.new_position[0]);
+        assert!(output.new_velocity[0].abs() <= 1.0 + 1e-9);
+        assert!(output.new_acceleration[0].abs() <= 2.0 + 1e-9);
+        output.pass_to_input(&mut input);
+    }
+    assert_float_eq!(output.new_position[0], 1.0, abs <= 1e-6);
+}
+
+#[test]
+fn test_tracking_replan_tolerance() {
+    let mut tracker = Tracker::<1, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    let mut target = TargetState::new(None);
+
+    input.max_velocity = daov_stack![1.0];
+    input.max_acceleration = daov_stack![2.0];
+    input.max_jerk = daov_stack![10.0];
+
+    // A target moving with a constant velocity and a deterministic noise within the tolerance
+    let follow = |tracker: &mut Tracker<1, ThrowErrorHandler>,
+                  input: &mut InputParameter<1>,
+                  output: &mut OutputParameter<1>,
+                  target: &mut TargetState<1>| {
+        let mut calculations = 0;
+        let mut max_error_after_settling: f64 = 0.0;
+        for step in 0..500 {
+            let t = step as f64 * 0.01;
+            let noise = 0.004 * ((step * 7919) % 13) as f64 / 13.0;
+            target.position[0] = 0.2 + 0.3 * t + noise;
+            target.velocity[0] = 0.3;
+
+            tracker.update(target, input, output).unwrap();
+            assert!(output.new_velocity[0].abs() <= 1.0 + 1e-9);
+            assert!(output.new_acceleration[0].abs() <= 2.0 + 1e-9);
+            assert!(output.new_jerk[0].abs() <= 10.0 + 1e-9);
+            if t > 2.0 {
+                calculations += output.new_calculation as usize;
+                let error = (output.new_position[0] - (0.2 + 0.3 * (t + 0.01))).abs();
+                max_error_after_settling = max_error_after_settling.max(error);
+            }
+            output.pass_to_input(input);
+        }
+        (calculations, max_error_after_settling)
+    };
+
+    let (calculations, _) = follow(&mut tracker, &mut input, &mut output, &mut target);
+    assert_eq!(calculations, 299);
+
+    // The noise doesn't lead to new calculations anymore
+    tracker.calculator.settings.replan_tolerance = 0.005;
+    let (calculations, max_error) = follow(&mut tracker, &mut input, &mut output, &mut target);
+    assert!(calculations <= 1, "{} calculations", calculations);
+    assert!(max_error <= 0.005, "tracking error {}", max_error);
+}
+
+#[test]
+fn test_serde() {
+    let mut otg = Ruckig::<3, ThrowErrorHandler>::new(None, 0.005);
+    let mut input = InputParameter::new(None);
+    input.current_position = daov_stack![0.0, -2.0, 0.0];
+    input.current_velocity = daov_stack![0.0, 0.2, 0.0];
+    input.target_position = daov_stack![1.0, -3.0, 2.0];
+    input.target_velocity = daov_stack![0.0, 0.3, 0.0];
+    input.max_velocity = daov_stack![1.0, 1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0, 1.0];
+    input.min_velocity = Some(daov_stack![-0.5, -0.5, -0.5]);
+    input.synchronization = Synchronization::Phase;
+
+    // A logged input is replayed to the same trajectory
+    let json = serde_json::to_string(&input).unwrap();
+    let replayed: InputParameter<3> = serde_json::from_str(&json).unwrap();
+    assert_eq!(replayed, input);
+
+    let mut traj = Trajectory::new(None);
+    let mut replayed_traj = Trajectory::new(None);
+    otg.calculate(&input, &mut traj).unwrap();
+    otg.calculate(&replayed, &mut replayed_traj).unwrap();
+    assert_eq!(traj.get_duration(), replayed_traj.get_duration());
+
+    let mut output = OutputParameter::new(None);
+    otg.update(&input, &mut output).unwrap();
+    let json = serde_json::to_string(&output).unwrap();
+    let replayed: OutputParameter<3> = serde_json::from_str(&json).unwrap();
+    assert_eq!(replayed.new_position, output.new_position);
+    assert_eq!(
+        replayed.trajectory.get_duration(),
+        output.trajectory.get_duration()
+    );
+
+    // Stack storage requires the exact number of DoFs, heap storage takes any length
+    assert!(serde_json::from_str::<DataArrayOrVec<f64, 3>>("[1.0, 2.0]").is_err());
+    assert!(serde_json::from_str::<DataArrayOrVec<f64, 3>>("[1.0, 2.0, 3.0, 4.0]").is_err());
+    let stack: DataArrayOrVec<f64, 3> = serde_json::from_str("[1.0, 2.0, 3.0]").unwrap();
+    assert_eq!(stack, daov_stack![1.0, 2.0, 3.0]);
+    assert_eq!(serde_json::to_string(&stack).unwrap(), "[1.0,2.0,3.0]");
+    let heap: DataArrayOrVec<f64, 0> = serde_json::from_str("[1.0, 2.0]").unwrap();
+    assert_eq!(heap, daov_heap![1.0, 2.0]);
+}
+
+#[test]
+fn test_serde_non_finite() {
+    // The default jerk limit is infinite, which JSON can't represent as a number
+    let input = InputParameter::<3>::new(None);
+    let json = serde_json::to_string(&input).unwrap();
+    let replayed: InputParameter<3> = serde_json::from_str(&json).unwrap();
+    assert_eq!(replayed, input);
+    assert!(replayed.max_jerk.iter().all(|j| *j == f64::INFINITY));
+
+    let mut input = InputParameter::<0>::new(Some(2));
+    input.target_position = daov_heap![1.0, -1.0];
+    input.max_velocity = daov_heap![1.0, 1.0];
+    input.max_acceleration = daov_heap![1.0, 1.0];
+    input.min_acceleration = Some(daov_heap![f64::NEG_INFINITY, -1.0]);
+    input.max_acceleration[0] = f64::INFINITY;
+    let json = serde_json::to_string(&input).unwrap();
+    let replayed: InputParameter<0> = serde_json::from_str(&json).unwrap();
+    assert_eq!(replayed, input);
+
+    let mut otg = Ruckig::<0, ThrowErrorHandler>::new(Some(2), 0.01);
+    let mut output = OutputParameter::new(Some(2));
+    otg.update(&input, &mut output).unwrap();
+    let json = serde_json::to_string(&output).unwrap();
+    let replayed: OutputParameter<0> = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        replayed.trajectory.get_duration(),
+        output.trajectory.get_duration()
+    );
+}
+
+#[test]
+fn test_validation_report() {
+    let mut input = InputParameter::<3>::new(None);
+    input.current_position = daov_stack![0.0, 0.0, f64::NAN];
+    input.current_velocity = daov_stack![0.0, 2.0, 0.0];
+    input.target_position = daov_stack![1.0, 1.0, 1.0];
+    input.max_velocity = daov_stack![1.0, 1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, -1.0, 1.0];
+
+    let report = input.validation_report(true, true);
+    assert!(!report.is_valid());
+    assert_eq!(report.violations.len(), 3);
+    assert_eq!(report.violations_of_dof(0).count(), 0);
+
+    let violation = &report.violations[0];
+    assert_eq!(violation.dof, Some(1));
+    assert_eq!(violation.quantity, InputQuantity::MaxJerk);
+    assert_eq!(violation.kind, ViolationKind::Negative);
+    assert_eq!(violation.value, -1.0);
+
+    let violation = &report.violations[1];
+    assert_eq!(violation.dof, Some(1));
+    assert_eq!(violation.quantity, InputQuantity::CurrentVelocity);
+    assert_eq!(violation.kind, ViolationKind::AboveMaximum);
+    assert_eq!(violation.value, 2.0);
+    assert_eq!(violation.allowed, Some(1.0));
+
+    let violation = &report.violations[2];
+    assert_eq!(violation.dof, Some(2));
+    assert_eq!(violation.quantity, InputQuantity::CurrentPosition);
+    assert_eq!(violation.kind, ViolationKind::NotANumber);
+
+    // The first violation is the error of the validation
+    match input.validate::<ThrowErrorHandler>(true, true) {
+        Ok(_) => panic!("Expected an error but got a successful result."),
+        Err(e) => assert!(e.to_string().contains(&report.violations[0].to_string())),
+    }
+
+    // Without checking the states, only the invalid limits and numbers remain
+    assert_eq!(input.validation_report(false, false).violations.len(), 2);
+
+    input.current_position[2] = 0.0;
+    input.current_velocity[1] = 0.0;
+    input.max_jerk[1] = 1.0;
+    let report = input.validation_report(true, true);
+    assert!(report.is_valid());
+    assert!(input.validate::<ThrowErrorHandler>(true, true).unwrap());
+}
+
+#[test]
+fn test_non_finite_input() {
+    let mut input = InputParameter::<2>::new(None);
+    input.target_position = daov_stack![1.0, 1.0];
+    input.max_velocity = daov_stack![1.0, f64::INFINITY];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+    input.max_position = Some(daov_stack![f64::INFINITY, f64::INFINITY]);
+    assert!(input.validation_report(true, true).is_valid());
+
+    input.target_velocity[1] = f64::INFINITY;
+    input.current_acceleration[0] = f64::NEG_INFINITY;
+    input.intermediate_positions = vec![daov_stack![0.5, f64::NAN]].into();
+    let report = input.validation_report(false, false);
+    assert_eq!(report.violations.len(), 3);
+
+    let violation = &report.violations[0];
+    assert_eq!(violation.dof, Some(0));
+    assert_eq!(violation.quantity, InputQuantity::CurrentAcceleration);
+    assert_eq!(violation.kind, ViolationKind::Infinite);
+
+    let violation = &report.violations[1];
+    assert_eq!(violation.dof, Some(1));
+    assert_eq!(violation.quantity, InputQuantity::TargetVelocity);
+    assert_eq!(violation.kind, ViolationKind::Infinite);
+    assert_eq!(
+        violation.to_string(),
+        "target velocity inf of DoF 1 should be finite."
+    );
+
+    let violation = &report.violations[2];
+    assert_eq!(violation.dof, Some(1));
+    assert_eq!(violation.quantity, InputQuantity::IntermediatePosition(0));
+    assert_eq!(violation.kind, ViolationKind::NotANumber);
+
+    // The current position is integrated for the velocity interface as well
+    input.target_velocity[1] = 0.0;
+    input.current_acceleration[0] = 0.0;
+    input.intermediate_positions.clear();
+    input.control_interface = ControlInterface::Velocity;
+    input.current_position[0] = f64::NAN;
+    let report = input.validation_report(false, false);
+    assert_eq!(report.violations.len(), 1);
+    assert_eq!(
+        report.violations[0].quantity,
+        InputQuantity::CurrentPosition
+    );
+}
+
+#[test]
+fn test_non_finite_minimum_duration() {
+    // The minimum duration is checked like the other durations, instead of panicking while the
+    // synchronization times are sorted
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.target_position = daov_stack![1.0, -1.0];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+    input.minimum_duration = Some(f64::NAN);
+
+    let report = input.validation_report(false, false);
+    assert_eq!(report.violations.len(), 1);
+    assert_eq!(
+        report.violations[0].quantity,
+        InputQuantity::MinimumDuration
+    );
+    assert_eq!(report.violations[0].kind, ViolationKind::NotANumber);
+    assert!(matches!(
+        otg.calculate(&input, &mut traj),
+        Err(RuckigError::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn test_collect_error_handler() {
+    CollectErrorHandler::clear();
+    CollectErrorHandler::set_capacity(2);
+
+    let mut otg = Ruckig::<1, CollectErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    input.target_position = daov_stack![1.0];
+    input.max_velocity = daov_stack![1.0];
+    input.max_acceleration = daov_stack![1.0];
+    input.max_jerk = daov_stack![1.0];
+
+    // Execution continues after the errors
+    for target_velocity in [2.0, 3.0, 4.0] {
+        input.target_velocity[0] = target_velocity;
+        assert_eq!(
+            otg.update(&input, &mut output).unwrap(),
+            RuckigResult::ErrorInvalidInput
+        );
+    }
+    input.target_velocity[0] = 0.0;
+    assert_eq!(
+        otg.update(&input, &mut output).unwrap(),
+        RuckigResult::Working
+    );
+
+    // Only the latest errors are kept
+    assert_eq!(CollectErrorHandler::error_count(), 3);
+    let errors = CollectErrorHandler::take_errors();
+    assert_eq!(errors.len(), 2);
+    match &errors[0] {
+        RuckigError::InvalidInput(violation) => assert_eq!(violation.value, 3.0),
+        error => panic!("Unexpected error: {:?}", error),
+    }
+    assert!(CollectErrorHandler::errors().is_empty());
+
+    CollectErrorHandler::clear();
+    assert_eq!(CollectErrorHandler::error_count(), 0);
+    CollectErrorHandler::set_capacity(rsruckig::error::COLLECTED_ERRORS_CAPACITY);
+}
+
+#[test]
+fn test_callback_error_handler() {
+    use std::sync::{Arc, Mutex};
+
+    let results = Arc::new(Mutex::new(Vec::new()));
+    let callback_results = results.clone();
+    CallbackErrorHandler::set_callback(move |error| {
+        callback_results.lock().unwrap().push(error.result());
+    });
+
+    let mut otg = Ruckig::<1, CallbackErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    input.target_position = daov_stack![1.0];
+    input.target_velocity = daov_stack![2.0];
+    input.max_velocity = daov_stack![1.0];
+    input.max_acceleration = daov_stack![1.0];
+    input.max_jerk = daov_stack![1.0];
+
+    assert_eq!(
+        otg.update(&input, &mut output).unwrap(),
+        RuckigResult::ErrorInvalidInput
+    );
+    assert_eq!(*results.lock().unwrap(), [RuckigResult::ErrorInvalidInput]);
+
+    CallbackErrorHandler::clear_callback();
+    input.target_velocity[0] = 3.0;
+    assert_eq!(
+        otg.update(&input, &mut output).unwrap(),
+        RuckigResult::ErrorInvalidInput
+    );
+    assert_eq!(results.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn test_error_code_handler() {
+    ErrorCodeHandler::reset();
+    assert_eq!(ErrorCodeHandler::last_error(), None);
+
+    let mut otg = Ruckig::<2, ErrorCodeHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    input.target_position = daov_stack![1.0, 1.0];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, -1.0];
+
+    assert_eq!(
+        otg.update(&input, &mut output).unwrap(),
+        RuckigResult::ErrorInvalidInput
+    );
+    assert_eq!(
+        ErrorCodeHandler::last_error(),
+        Some((RuckigResult::ErrorInvalidInput, Some(1)))
+    );
+    assert_eq!(ErrorCodeHandler::error_count(), 1);
+
+    input.max_jerk[1] = 1.0;
+    assert_eq!(
+        otg.update(&input, &mut output).unwrap(),
+        RuckigResult::Working
+    );
+    assert_eq!(ErrorCodeHandler::error_count(), 1);
+
+    assert_eq!(
+        RuckigResult::try_from(RuckigResult::ErrorZeroLimits as i32),
+        Ok(RuckigResult::ErrorZeroLimits)
+    );
+    assert_eq!(RuckigResult::try_from(-103), Err(-103));
+}
+
+#[test]
+fn test_error_code_handler_storages() {
+    use rsruckig::error::{ErrorCode, ErrorCodeStorage};
+
+    struct ArmErrors;
+    impl ErrorCodeStorage for ArmErrors {
+        fn error_code() -> &'static ErrorCode {
+            static ERROR_CODE: ErrorCode = ErrorCode::new();
+            &ERROR_CODE
+        }
+    }
+    struct GripperErrors;
+    impl ErrorCodeStorage for GripperErrors {
+        fn error_code() -> &'static ErrorCode {
+            static ERROR_CODE: ErrorCode = ErrorCode::new();
+            &ERROR_CODE
+        }
+    }
+
+    let mut arm = Ruckig::<1, ErrorCodeHandler<ArmErrors>>::new(None, 0.01);
+    let mut gripper = Ruckig::<1, ErrorCodeHandler<GripperErrors>>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    input.target_position = daov_stack![1.0];
+    input.max_velocity = daov_stack![1.0];
+    input.max_acceleration = daov_stack![1.0];
+    input.max_jerk = daov_stack![-1.0];
+
+    assert_eq!(
+        arm.update(&input, &mut output).unwrap(),
+        RuckigResult::ErrorInvalidInput
+    );
+    assert_eq!(
+        ErrorCodeHandler::<ArmErrors>::error_code().last_error(),
+        Some((RuckigResult::ErrorInvalidInput, Some(0)))
+    );
+    assert_eq!(ErrorCodeHandler::<GripperErrors>::error_code().last_error(), None);
+
+    input.max_jerk[0] = 1.0;
+    assert_eq!(
+        gripper.update(&input, &mut output).unwrap(),
+        RuckigResult::Working
+    );
+    assert_eq!(ErrorCodeHandler::<GripperErrors>::error_code().error_count(), 0);
+    assert_eq!(ErrorCodeHandler::<ArmErrors>::error_code().error_count(), 1);
+
+    ArmErrors::error_code().reset();
+    assert_eq!(ErrorCodeHandler::<ArmErrors>::error_code().last_error(), None);
+}
+
+#[test]
+fn test_failure_report() {
+    let mut otg = Ruckig::<3, IgnoreErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+
+    input.current_position = daov_stack![0.0, -2.0, 0.0];
+    input.current_velocity = daov_stack![-0.2, 0.0, 0.0];
+    input.current_acceleration = daov_stack![1.0, 0.0, 0.0];
+    input.target_position = daov_stack![0.4, -3.0, 0.0];
+    input.target_velocity = daov_stack![0.8, 0.0, 0.0];
+    input.target_acceleration = daov_stack![1.0, 0.0, 0.0];
+    input.max_velocity = daov_stack![1.0, 200.0, 0.0];
+    input.max_acceleration = daov_stack![1.0, 200.0, 0.0];
+    input.max_jerk = daov_stack![0.0, 200.0, 0.0];
+
+    assert_eq!(
+        otg.update(&input, &mut output).unwrap(),
+        RuckigResult::ErrorZeroLimits
+    );
+    assert_eq!(output.failed_dof, Some(0));
+    assert_eq!(output.failed_stage, Some(CalculationStage::Step1));
+
+    input.current_acceleration = daov_stack![0.0, 0.0, 0.0];
+    input.target_acceleration = daov_stack![0.0, 0.0, 0.0];
+    input.max_jerk = daov_stack![1.0, 1.0, 1.0];
+    input.target_velocity = daov_stack![0.0, 300.0, 0.0];
+    assert_eq!(
+        otg.update(&input, &mut output).unwrap(),
+        RuckigResult::ErrorInvalidInput
+    );
+    assert_eq!(output.failed_dof, Some(1));
+    assert_eq!(output.failed_stage, Some(CalculationStage::Validation));
+
+    input.target_velocity = daov_stack![0.0, 0.0, 0.0];
+    input.max_velocity = daov_stack![1.0, 1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0, 1.0];
+    assert_eq!(
+        otg.update(&input, &mut output).unwrap(),
+        RuckigResult::Working
+    );
+    assert_eq!(output.failed_dof, None);
+    assert_eq!(output.failed_stage, None);
+
+    // Errors that a custom handler turns into results without a stage aren't blamed on the
+    // validation
+    use rsruckig::error::RuckigErrorHandler;
+    struct ContextHandler;
+    impl RuckigErrorHandler for ContextHandler {
+        fn handle_validation_error(error: RuckigError) -> Result<bool, RuckigError> {
+            Err(RuckigError::new(format!("axis group: {}", error)))
+        }
+        fn handle_calculator_error(error: RuckigError) -> Result<RuckigResult, RuckigError> {
+            Err(error)
+        }
+    }
+
+    let mut otg = Ruckig::<3, ContextHandler>::new(None, 0.01);
+    input.target_velocity = daov_stack![0.0, 300.0, 0.0];
+    assert!(otg.update(&input, &mut output).is_err());
+    assert_eq!(output.failed_dof, None);
+    assert_eq!(output.failed_stage, None);
+
+    input.target_velocity = daov_stack![0.0, 0.0, 0.0];
+    input.max_jerk = daov_stack![0.0, 1.0, 1.0];
+    input.current_acceleration = daov_stack![0.5, 0.0, 0.0];
+    assert!(otg.update(&input, &mut output).is_err());
+    assert_eq!(output.failed_dof, Some(0));
+    assert_eq!(output.failed_stage, Some(CalculationStage::Step1));
+}
+
+#[test]
+fn test_warnings() {
+    use rsruckig::error::RuckigErrorHandler;
+    use std::cell::RefCell;
+
+    thread_local! {
+        static WARNINGS: RefCell<Vec<RuckigWarning>> = const { RefCell::new(Vec::new()) };
+    }
+
+    struct WarningHandler;
+
+    impl RuckigErrorHandler for WarningHandler {
+        fn handle_validation_error(error: RuckigError) -> Result<bool, RuckigError> {
+            Err(error)
+        }
+        fn handle_calculator_error(error: RuckigError) -> Result<RuckigResult, RuckigError> {
+            Err(error)
+        }
+        fn handle_warning(warning: RuckigWarning) {
+            WARNINGS.with(|warnings| warnings.borrow_mut().push(warning));
+        }
+    }
+
+    let mut otg = Ruckig::<2, WarningHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.target_position = daov_stack![1.0, 2.0];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+
+    otg.calculate(&input, &mut traj).unwrap();
+    assert!(WARNINGS.with(|warnings| warnings.borrow().is_empty()));
+
+    // The current velocity exceeds its limit
+    input.current_velocity = daov_stack![0.0, 1.5];
+    otg.calculate(&input, &mut traj).unwrap();
+    let warnings = WARNINGS.with(|warnings| warnings.take());
+    assert_eq!(warnings.len(), 1);
+    assert!(matches!(
+        warnings[0],
+        RuckigWarning::BrakeTrajectory { dof: 1, duration } if duration > 0.0
+    ));
+
+    // The input is not collinear
+    input.current_velocity = daov_stack![0.0, 0.0];
+    input.target_position = daov_stack![1.0, 2.0];
+    input.target_velocity = daov_stack![0.5, 0.0];
+    input.synchronization = Synchronization::Phase;
+    otg.calculate(&input, &mut traj).unwrap();
+    let warnings = WARNINGS.with(|warnings| warnings.take());
+    assert_eq!(warnings, [RuckigWarning::PhaseSynchronizationFallback]);
+
+    // A collinear input is phase synchronized
+    input.target_velocity = daov_stack![0.0, 0.0];
+    otg.calculate(&input, &mut traj).unwrap();
+    assert!(WARNINGS.with(|warnings| warnings.borrow().is_empty()));
+
+    input.synchronization = Synchronization::Time;
+    input.target_position = daov_stack![7000.0, 1.0];
+    otg.calculate(&input, &mut traj).unwrap();
+    let warnings = WARNINGS.with(|warnings| warnings.take());
+    assert!(matches!(
+        warnings[..],
+        [RuckigWarning::DurationNearLimit { duration, .. }] if duration > 7000.0
+    ));
+}
+
+#[test]
+fn test_sample_into() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new_with_waypoints(2, None, 0.01);
+    let mut input = InputParameter::new_with_waypoints(2, None);
+    let mut traj = Trajectory::new_with_waypoints(2, None);
+    input.current_velocity = daov_stack![1.5, 0.0];
+    input.target_position = daov_stack![1.0, 1.0];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![2.0, 2.0];
+    input.max_jerk = daov_stack![4.0, 4.0];
+    input.intermediate_positions = vec![daov_stack![0.5, -0.5], daov_stack![1.5, 0.5]].into();
+    otg.calculate(&input, &mut traj).unwrap();
+
+    // Increasing times across all sections and beyond the end, then jumping back
+    let mut times: Vec<f64> = (0..=120)
+        .map(|i| i as f64 * traj.get_duration() / 100.0)
+        .collect();
+    times.extend([0.1, 0.05, 2.0]);
+
+    let mut positions = vec![0.0; 2 * times.len()];
+    let mut velocities = vec![0.0; 2 * times.len()];
+    let mut accelerations = vec![0.0; 2 * times.len()];
+    traj.sample_into(&times, &mut positions, &mut velocities, &mut accelerations);
+
+    let mut new_position = daov_stack![0.0, 0.0];
+    let mut new_velocity = daov_stack![0.0, 0.0];
+    let mut new_acceleration = daov_stack![0.0, 0.0];
+    for (i, &time) in times.iter().enumerate() {
+        traj.at_time(
+            time,
+            &mut Some(&mut new_position),
+            &mut Some(&mut new_velocity),
+            &mut Some(&mut new_acceleration),
+            &mut None,
+            &mut None,
+        );
+        assert_eq!(positions[2 * i..2 * i + 2], *new_position);
+        assert_eq!(velocities[2 * i..2 * i + 2], *new_velocity);
+        assert_eq!(accelerations[2 * i..2 * i + 2], *new_acceleration);
+    }
+}
+
+#[test]
+fn test_state_at() {
+    let mut otg = Ruckig::<0, ThrowErrorHandler>::new_with_waypoints(1, Some(2), 0.01);
+    let mut input = InputParameter::new_with_waypoints(1, Some(2));
+    let mut traj = Trajectory::new_with_waypoints(1, Some(2));
+    input.target_position = daov_heap![1.0, 1.0];
+    input.max_velocity = daov_heap![1.0, 1.0];
+    input.max_acceleration = daov_heap![2.0, 2.0];
+    input.max_jerk = daov_heap![4.0, 4.0];
+    input.intermediate_positions = vec![daov_heap![0.5, -0.5]].into();
+    otg.calculate(&input, &mut traj).unwrap();
+
+    let mut new_position = daov_heap![0.0, 0.0];
+    let mut new_velocity = daov_heap![0.0, 0.0];
+    let mut new_acceleration = daov_heap![0.0, 0.0];
+    let mut new_jerk = daov_heap![0.0, 0.0];
+    for time in [0.0, 0.3, traj.get_intermediate_durations()[0] + 0.1, 10.0] {
+        let mut new_section = None;
+        traj.at_time(
+            time,
+            &mut Some(&mut new_position),
+            &mut Some(&mut new_velocity),
+            &mut Some(&mut new_acceleration),
+            &mut Some(&mut new_jerk),
+            &mut new_section,
+        );
+
+        let state = traj.state_at(time);
+        assert_eq!(state.position, new_position);
+        assert_eq!(state.velocity, new_velocity);
+        assert_eq!(state.acceleration, new_acceleration);
+        assert_eq!(state.jerk, new_jerk);
+        assert_eq!(Some(state.section), new_section);
+    }
+    assert_eq!(traj.state_at(0.3).section, 0);
+    assert_eq!(traj.state_at(10.0).section, 2);
+}
+
+#[test]
+fn test_at_time_many_dofs() {
+    // Per-DoF evaluation of many DoFs should agree across the sampling methods
+    let mut otg = Ruckig::<7, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    for dof in 0..7 {
+        input.current_velocity[dof] = 0.1 * dof as f64;
+        input.target_position[dof] = 1.0 - 0.3 * dof as f64;
+        input.max_velocity[dof] = 1.0;
+        input.max_acceleration[dof] = 1.0 + dof as f64;
+        input.max_jerk[dof] = 2.0;
+    }
+    otg.calculate(&input, &mut traj).unwrap();
+
+    let times = [
+        0.0,
+        0.4,
+        1.3,
+        traj.get_duration(),
+        traj.get_duration() + 0.5,
+    ];
+    let mut positions = vec![0.0; 7 * times.len()];
+    let mut velocities = vec![0.0; 7 * times.len()];
+    let mut accelerations = vec![0.0; 7 * times.len()];
+    traj.sample_into(&times, &mut positions, &mut velocities, &mut accelerations);
+
+    for (i, &time) in times.iter().enumerate() {
+        let state = traj.state_at(time);
+        assert_eq!(positions[7 * i..7 * i + 7], *state.position);
+        assert_eq!(velocities[7 * i..7 * i + 7], *state.velocity);
+        assert_eq!(accelerations[7 * i..7 * i + 7], *state.acceleration);
+    }
+    assert!(almost_equal_vecs(
+        &traj.state_at(traj.get_duration()).position,
+        &input.target_position,
+        1e-8
+    ));
+}
+
+#[test]
+fn test_trajectory_csv() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.target_position = daov_stack![1.0, -0.5];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 2.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+    otg.calculate(&input, &mut traj).unwrap();
+
+    let mut csv = Vec::new();
+    traj.to_csv(&mut csv, 0.1).unwrap();
+    let text = String::from_utf8(csv.clone()).unwrap();
+    assert!(text.starts_with("time,p0,p1,v0,v1,a0,a1\n"));
+
+    let sampled = SampledTrajectory::read_csv(csv.as_slice()).unwrap();
+    assert_eq!(sampled, SampledTrajectory::from_trajectory(&traj, 0.1));
+    assert_eq!(sampled.degrees_of_freedom, 2);
+    assert_eq!(sampled.times[0], 0.0);
+    assert_eq!(*sampled.times.last().unwrap(), traj.get_duration());
+    assert!(almost_equal_vecs(
+        sampled.position(sampled.len() - 1),
+        &input.target_position,
+        1e-8
+    ));
+    assert_eq!(
+        sampled.velocity(3),
+        &*traj.state_at(sampled.times[3]).velocity
+    );
+
+    assert!(SampledTrajectory::read_csv("time,p0,v0\n".as_bytes()).is_err());
+    assert!(SampledTrajectory::read_csv("time,p0,v0,a0\n0.0,1.0,x,0.0\n".as_bytes()).is_err());
+    assert!(SampledTrajectory::read_csv("time,p0,v0,a0\n0.0,1.0\n".as_bytes()).is_err());
+}
+
+#[test]
+fn test_sampled_trajectory_duration_multiple_of_step() {
+    let mut otg = Ruckig::<1, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.target_position = daov_stack![0.2];
+    input.max_velocity = daov_stack![1.0];
+    input.max_acceleration = daov_stack![1.0];
+    input.max_jerk = daov_stack![1.0];
+    input.minimum_duration = Some(2.1);
+    otg.calculate(&input, &mut traj).unwrap();
+    assert_eq!(traj.get_duration(), 2.1);
+
+    // 7 * 0.3 and 3 * 0.7 round differently from 2.1
+    for (dt, number_of_samples) in [(0.3, 8), (0.7, 4), (0.1, 22), (0.25, 10)] {
+        let sampled = SampledTrajectory::from_trajectory(&traj, dt);
+        assert_eq!(sampled.len(), number_of_samples, "dt {}", dt);
+        assert_eq!(*sampled.times.last().unwrap(), 2.1);
+        assert!(sampled.times.windows(2).all(|w| w[1] - w[0] > 1e-6));
+    }
+}
+
+#[test]
+fn test_trajectory_snapshot() {
+    let mut otg = Ruckig::<3, ThrowErrorHandler>::new_with_waypoints(2, None, 0.01);
+    let mut input = InputParameter::new_with_waypoints(2, None);
+    input.current_velocity = daov_stack![0.2, 0.0, -0.1];
+    input.intermediate_positions = vec![daov_stack![0.5, -0.5, 0.2]].into();
+    input.target_position = daov_stack![1.0, 0.5, -0.5];
+    input.max_velocity = daov_stack![1.0, 1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0, 1.0];
+
+    let mut output = OutputParameter::new_with_waypoints(2, None);
+    for _ in 0..50 {
+        otg.update(&input, &mut output).unwrap();
+        output.pass_to_input(&mut input);
+    }
+
+    // A restarted process restores the trajectory, including all sections and sub-profiles
+    let input_json = serde_json::to_string(&input).unwrap();
+    let output_json = serde_json::to_string(&output).unwrap();
+    let restored_input: InputParameter<3> = serde_json::from_str(&input_json).unwrap();
+    let mut restored_output: OutputParameter<3> = serde_json::from_str(&output_json).unwrap();
+    assert_eq!(restored_output.trajectory.get_number_of_sections(), 2);
+    assert_eq!(
+        restored_output.trajectory.get_intermediate_durations(),
+        output.trajectory.get_intermediate_durations()
+    );
+    for time in [0.0, 0.7, 1.5, output.trajectory.get_duration()] {
+        assert_eq!(
+            restored_output.trajectory.state_at(time),
+            output.trajectory.state_at(time)
+        );
+    }
+
+    // ... and continues it without re-planning
+    let mut restored_otg = Ruckig::<3, ThrowErrorHandler>::new_with_waypoints(2, None, 0.01);
+    restored_otg.restore(&restored_input);
+    assert_eq!(
+        restored_otg.update(&restored_input, &mut restored_output),
+        Ok(RuckigResult::Working)
+    );
+    otg.update(&input, &mut output).unwrap();
+    assert!(!restored_output.new_calculation);
+    assert_eq!(restored_output.time, output.time);
+    assert_eq!(restored_output.new_position, output.new_position);
+}
+
+#[test]
+fn test_piecewise_polynomials() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new_with_waypoints(1, None, 0.01);
+    let mut input = InputParameter::new_with_waypoints(1, None);
+    let mut traj = Trajectory::new_with_waypoints(1, None);
+    // The velocity of DoF 1 exceeds its limit, so that it starts with a brake trajectory
+    input.current_velocity = daov_stack![0.0, 1.5];
+    input.intermediate_positions = vec![daov_stack![0.5, 1.0]].into();
+    input.target_position = daov_stack![1.0, 0.2];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 2.0];
+    input.max_jerk = daov_stack![1.0, 2.0];
+    otg.calculate(&input, &mut traj).unwrap();
+    assert!(traj.get_profiles()[0][1].brake.duration > 0.0);
+
+    let polynomials = traj.to_piecewise_polynomials();
+    assert_eq!(polynomials.len(), 2);
+    for (dof, segments) in polynomials.iter().enumerate() {
+        assert_eq!(segments[0].t_start, 0.0);
+        assert!((segments.last().unwrap().t_end - traj.get_duration()).abs() < 1e-12);
+        for pair in segments.windows(2) {
+            assert!((pair[0].t_end - pair[1].t_start).abs() < 1e-12);
+        }
+
+        for segment in segments {
+            for fraction in [0.0, 0.3, 0.9] {
+                let t = fraction * (segment.t_end - segment.t_start);
+                let c = segment.coefficients;
+                let position = c[0] + t * (c[1] + t * (c[2] + t * c[3]));
+                let expected = traj.state_at(segment.t_start + t).position[dof];
+                assert!((position - expected).abs() < 1e-9);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_path_length() {
+    let mut otg = Ruckig::<3, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.current_position = daov_stack![0.0, 1.0, 0.0];
+    input.target_position = daov_stack![3.0, 5.0, 0.0];
+    input.max_velocity = daov_stack![1.0, 1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0, 1.0];
+    input.synchronization = Synchronization::Phase;
+    otg.calculate(&input, &mut traj).unwrap();
+
+    assert!((traj.path_length(0) - 3.0).abs() < 1e-9);
+    assert!((traj.path_length(1) - 4.0).abs() < 1e-9);
+    assert_eq!(traj.path_length(2), 0.0);
+    // Phase synchronization moves along a straight line
+    assert!((traj.euclidean_path_length(&[0, 1]) - 5.0).abs() < 1e-9);
+    assert!((traj.euclidean_path_length(&[0, 1, 2]) - 5.0).abs() < 1e-9);
+    assert!((traj.euclidean_path_length(&[1, 2]) - 4.0).abs() < 1e-9);
+    assert_eq!(traj.euclidean_path_length(&[]), 0.0);
+
+    // Moving away from the target first adds the overshoot twice
+    input.current_velocity = daov_stack![-1.0, 0.0, 0.0];
+    input.synchronization = Synchronization::Time;
+    otg.calculate(&input, &mut traj).unwrap();
+    let minimum = (0..=1000)
+        .map(|i| traj.state_at(0.002 * i as f64).position[0])
+        .fold(f64::INFINITY, f64::min);
+    assert!(minimum < 0.0);
+    assert!((traj.path_length(0) - (3.0 - 2.0 * minimum)).abs() < 1e-6);
+    assert!(traj.euclidean_path_length(&[0, 1]) > traj.path_length(1));
+}
+
+#[test]
+fn test_times_at_velocity_and_acceleration() {
+    let mut otg = Ruckig::<1, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.target_position = daov_stack![4.0];
+    input.max_velocity = daov_stack![1.0];
+    input.max_acceleration = daov_stack![1.0];
+    input.max_jerk = daov_stack![1.0];
+    otg.calculate(&input, &mut traj).unwrap();
+
+    // Accelerating to and decelerating from the maximum velocity
+    let times = traj.get_times_at_velocity(0, 0.5);
+    assert_eq!(times.len(), 2);
+    for &time in &times {
+        assert!((traj.state_at(time).velocity[0] - 0.5).abs() < 1e-9);
+    }
+    assert_eq!(traj.get_first_time_at_velocity(0, 0.5), Some(times[0]));
+
+    // The maximum velocity is held, and reported at its start only
+    let times = traj.get_times_at_velocity(0, 1.0);
+    assert_eq!(times.len(), 1);
+    assert!((times[0] - 2.0).abs() < 1e-9);
+
+    let times = traj.get_times_at_acceleration(0, -0.5);
+    assert_eq!(times.len(), 2);
+    for &time in &times {
+        assert!((traj.state_at(time).acceleration[0] + 0.5).abs() < 1e-9);
+    }
+    assert_eq!(traj.get_times_at_acceleration(0, 0.0)[0], 0.0);
+    assert!((traj.get_first_time_at_acceleration(0, 1.0).unwrap() - 1.0).abs() < 1e-9);
+
+    assert_eq!(traj.get_first_time_at_velocity(0, 2.0), None);
+    assert_eq!(traj.get_first_time_at_acceleration(1, 0.0), None);
+}
+
+#[test]
+fn test_velocity_and_acceleration_extrema() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.target_position = daov_stack![4.0, 0.3];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+    otg.calculate(&input, &mut traj).unwrap();
+
+    let velocity_extrema = traj.get_velocity_extrema();
+    let acceleration_extrema = traj.get_acceleration_extrema();
+    assert!((velocity_extrema[0].max - 1.0).abs() < 1e-9);
+    assert_eq!(velocity_extrema[0].min, 0.0);
+    assert!((acceleration_extrema[0].max - 1.0).abs() < 1e-9);
+    assert!((acceleration_extrema[0].min + 1.0).abs() < 1e-9);
+
+    // Compare against the sampled trajectory, whose extrema lie within the phases
+    let samples: Vec<_> = (0..=2000)
+        .map(|i| traj.state_at(i as f64 * traj.get_duration() / 2000.0))
+        .collect();
+    for (dof, bound) in velocity_extrema.iter().enumerate() {
+        let max = samples
+            .iter()
+            .map(|s| s.velocity[dof])
+            .fold(f64::MIN, f64::max);
+        let min = samples
+            .iter()
+            .map(|s| s.velocity[dof])
+            .fold(f64::MAX, f64::min);
+        assert!(bound.max - max > -1e-12 && bound.max - max < 1e-5);
+        assert!(min - bound.min > -1e-12 && min - bound.min < 1e-5);
+        assert_eq!(traj.state_at(bound.t_max).velocity[dof], bound.max);
+        assert!((traj.state_at(bound.t_min).velocity[dof] - bound.min).abs() < 1e-12);
+    }
+    // The velocity of the short motion peaks within a phase
+    assert!(velocity_extrema[1].max < 1.0);
+    assert!(0.0 < velocity_extrema[1].t_max && velocity_extrema[1].t_max < traj.get_duration());
+    for (dof, bound) in acceleration_extrema.iter().enumerate() {
+        let max = samples
+            .iter()
+            .map(|s| s.acceleration[dof])
+            .fold(f64::MIN, f64::max);
+        let min = samples
+            .iter()
+            .map(|s| s.acceleration[dof])
+            .fold(f64::MAX, f64::min);
+        assert!(bound.max - max > -1e-12 && bound.max - max < 1e-2);
+        assert!(min - bound.min > -1e-12 && min - bound.min < 1e-2);
+    }
+}
+
+#[test]
+fn test_stretch_to_duration() {
+    let mut otg = Ruckig::<3, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.current_position = daov_stack![0.0, -1.0, 0.0];
+    input.current_velocity = daov_stack![0.2, 0.0, -0.3];
+    input.target_position = daov_stack![1.0, 1.0, 0.5];
+    input.target_velocity = daov_stack![0.0, 0.2, 0.0];
+    input.max_velocity = daov_stack![1.0, 1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0, 1.0];
+    otg.calculate(&input, &mut traj).unwrap();
+    let duration = traj.get_duration();
+
+    // Same as calculating with a minimum duration
+    let result = traj.stretch_to_duration::<ThrowErrorHandler>(&input, duration + 1.5);
+    assert_eq!(result, Ok(RuckigResult::Working));
+    assert!((traj.get_duration() - (duration + 1.5)).abs() < 1e-12);
+    let mut expected = Trajectory::new(None);
+    input.minimum_duration = Some(duration + 1.5);
+    otg.calculate(&input, &mut expected).unwrap();
+    for time in [0.0, 1.0, 2.5, duration, traj.get_duration()] {
+        let state = traj.state_at(time);
+        let expected_state = expected.state_at(time);
+        assert!(almost_equal_vecs(
+            &state.position,
+            &expected_state.position,
+            1e-8
+        ));
+        assert!(almost_equal_vecs(
+            &state.velocity,
+            &expected_state.velocity,
+            1e-8
+        ));
+    }
+    let state = traj.state_at(traj.get_duration());
+    assert!(almost_equal_vecs(
+        &state.position,
+        &input.target_position,
+        1e-8
+    ));
+    assert!(almost_equal_vecs(
+        &state.velocity,
+        &input.target_velocity,
+        1e-8
+    ));
+
+    // A shorter duration is not possible
+    let result = traj.stretch_to_duration::<ThrowErrorHandler>(&input, duration);
+    assert!(matches!(result, Err(RuckigError::InvalidDuration { .. })));
+    assert_eq!(
+        traj.stretch_to_duration::<IgnoreErrorHandler>(&input, duration),
+        Ok(RuckigResult::ErrorInvalidInput)
+    );
+    assert!((traj.get_duration() - (duration + 1.5)).abs() < 1e-12);
+}
+
+#[test]
+fn test_reversed() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new_with_waypoints(1, None, 0.01);
+    let mut input = InputParameter::new_with_waypoints(1, None);
+    let mut traj = Trajectory::new_with_waypoints(1, None);
+    input.current_velocity = daov_stack![0.0, 1.5];
+    input.current_acceleration = daov_stack![0.2, 0.0];
+    input.intermediate_positions = vec![daov_stack![0.5, 1.0]].into();
+    input.target_position = daov_stack![1.0, 0.2];
+    input.target_velocity = daov_stack![0.3, 0.0];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 2.0];
+    input.max_jerk = daov_stack![1.0, 2.0];
+    otg.calculate(&input, &mut traj).unwrap();
+
+    let reversed = traj.reversed();
+    let duration = traj.get_duration();
+    assert_eq!(reversed.get_duration(), duration);
+    for i in 0..=100 {
+        let time = duration * i as f64 / 100.0;
+        let state = traj.state_at(duration - time);
+        let reversed_state = reversed.state_at(time);
+        for dof in 0..2 {
+            assert!((reversed_state.position[dof] - state.position[dof]).abs() < 1e-9);
+            assert!((reversed_state.velocity[dof] + state.velocity[dof]).abs() < 1e-9);
+            assert!((reversed_state.acceleration[dof] - state.acceleration[dof]).abs() < 1e-9);
+        }
+    }
+
+    let empty = Trajectory::<2>::new(None).reversed();
+    assert_eq!(empty.get_duration(), 0.0);
+    assert_eq!(empty.state_at(1.0).position, daov_stack![0.0, 0.0]);
+
+    // Reversing twice yields the original motion
+    let twice = reversed.reversed();
+    for time in [0.0, 0.3, 1.7, duration / 2.0, duration] {
+        assert!(almost_equal_vecs(
+            &twice.state_at(time).position,
+            &traj.state_at(time).position,
+            1e-9
+        ));
+    }
+}
+
+#[test]
+fn test_crop() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new_with_waypoints(1, None, 0.01);
+    let mut input = InputParameter::new_with_waypoints(1, None);
+    let mut traj = Trajectory::new_with_waypoints(1, None);
+    input.current_velocity = daov_stack![0.0, 1.5];
+    input.intermediate_positions = vec![daov_stack![0.5, 1.0]].into();
+    input.target_position = daov_stack![1.0, 0.2];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 2.0];
+    input.max_jerk = daov_stack![1.0, 2.0];
+    otg.calculate(&input, &mut traj).unwrap();
+    let duration = traj.get_duration();
+
+    let (t_start, t_end) = (0.35, duration - 0.8);
+    let cropped = traj.crop(t_start, t_end);
+    assert!((cropped.get_duration() - (t_end - t_start)).abs() < 1e-12);
+    for i in 0..=100 {
+        let time = cropped.get_duration() * i as f64 / 100.0;
+        let state = traj.state_at(t_start + time);
+        let cropped_state = cropped.state_at(time);
+        assert!(almost_equal_vecs(
+            &cropped_state.position,
+            &state.position,
+            1e-9
+        ));
+        assert!(almost_equal_vecs(
+            &cropped_state.velocity,
+            &state.velocity,
+            1e-9
+        ));
+        assert!(almost_equal_vecs(
+            &cropped_state.acceleration,
+            &state.acceleration,
+            1e-9
+        ));
+    }
+
+    // The times are clamped to the trajectory
+    let cropped = traj.crop(-1.0, duration + 1.0);
+    assert_eq!(cropped.get_duration(), duration);
+    assert!(almost_equal_vecs(
+        &cropped.state_at(duration).position,
+        &input.target_position,
+        1e-8
+    ));
+    let cropped = traj.crop(1.0, 1.0);
+    assert_eq!(cropped.get_duration(), 0.0);
+    assert_eq!(cropped.state_at(0.0).position, traj.state_at(1.0).position);
+}
+
+#[test]
+fn test_profile_info() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.target_position = daov_stack![4.0, -0.5];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+    otg.calculate(&input, &mut traj).unwrap();
+
+    let info = traj.get_profile_info(0, 0).unwrap();
+    assert_eq!(info.limits, ReachedLimits::Vel);
+    assert_eq!(info.control_signs, ControlSigns::UDDU);
+    assert_eq!(info.direction, Direction::UP);
+    assert_eq!(info.jerk_signs, [1, 0, -1, 0, -1, 0, 1]);
+
+    let info = traj.get_profile_info(0, 1).unwrap();
+    assert_eq!(info.direction, Direction::DOWN);
+    assert_eq!(info, traj.get_profiles()[0][1].info());
+
+    assert_eq!(traj.get_profile_info(1, 0), None);
+    assert_eq!(traj.get_profile_info(0, 2), None);
+}
+
+#[test]
+fn test_profile_name() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.target_position = daov_stack![4.0, -8.0];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 0.5];
+    input.max_jerk = daov_stack![1.0, 1.0];
+    input.synchronization = Synchronization::None;
+    otg.calculate(&input, &mut traj).unwrap();
+
+    let profile = &traj.get_profiles()[0][0];
+    assert_eq!(profile.name(), "UP_VEL_UDDU");
+    assert_eq!(profile.to_string(), profile.name());
+    assert_eq!(
+        traj.get_profile_info(0, 0).unwrap().to_string(),
+        profile.name()
+    );
+    let name = traj.get_profiles()[0][1].name();
+    assert!(name.starts_with("DOWN_") && name.ends_with("_UDDU"));
+    assert_eq!(ReachedLimits::Acc1Vel.to_string(), "ACC1_VEL");
+    assert_eq!(ControlSigns::UDUD.to_string(), "UDUD");
+}
+
+#[test]
+fn test_brake_profile() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    // DoF 0 exceeds its velocity limit and brakes first
+    input.current_velocity = daov_stack![1.5, 0.0];
+    input.target_position = daov_stack![2.0, 1.0];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+    otg.update(&input, &mut output).unwrap();
+
+    let traj = &output.trajectory;
+    assert!(traj.get_brake_profile(1).is_none());
+    assert!(traj.get_accel_profile(0).is_none());
+    assert!(traj.get_brake_profile(2).is_none());
+    let brake = *traj.get_brake_profile(0).unwrap();
+    assert!(brake.duration > 0.0);
+    for time in [0.0, 0.4 * brake.duration, brake.duration] {
+        let (p, v, a) = brake.state_at(time);
+        let state = traj.state_at(time);
+        assert!((p - state.position[0]).abs() < 1e-12);
+        assert!((v - state.velocity[0]).abs() < 1e-12);
+        assert!((a - state.acceleration[0]).abs() < 1e-12);
+    }
+    assert!(brake.state_at(brake.duration).1 <= 1.0 + 1e-12);
+
+    assert!(output.is_braking(0));
+    assert!(!output.is_braking(1));
+    while output.time < brake.duration {
+        output.pass_to_input(&mut input);
+        otg.update(&input, &mut output).unwrap();
+    }
+    assert!(!output.is_braking(0));
+}
+
+#[test]
+fn test_calculate_stop() {
+    let mut otg = Ruckig::<3, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.current_position = daov_stack![0.5, -1.0, 0.0];
+    input.current_velocity = daov_stack![0.8, -0.4, 0.0];
+    input.current_acceleration = daov_stack![0.5, 0.0, 0.0];
+    input.target_position = daov_stack![-3.0, 2.0, 1.0];
+    input.max_velocity = daov_stack![1.0, 1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0, 1.0];
+    input.max_jerk = daov_stack![2.0, 2.0, 2.0];
+    let original = input.clone();
+
+    assert_eq!(
+        otg.calculate_stop(&input, &mut traj),
+        Ok(RuckigResult::Working)
+    );
+    assert_eq!(input, original);
+
+    let end = traj.state_at(traj.get_duration());
+    assert!(almost_equal_vecs(&end.velocity, &[0.0, 0.0, 0.0], 1e-9));
+    assert!(almost_equal_vecs(&end.acceleration, &[0.0, 0.0, 0.0], 1e-9));
+    // DoF 0 continues in its direction of motion until it stands still
+    assert!(end.position[0] > 0.5);
+    assert!(end.position[1] < -1.0);
+    assert_eq!(end.position[2], 0.0);
+
+    // The duration is the time-optimal stop of the slowest DoF
+    input.synchronization = Synchronization::None;
+    otg.calculate_stop(&input, &mut traj).unwrap();
+    let stop_durations = traj.get_independent_min_durations().clone();
+    assert!((traj.get_duration() - stop_durations[0].max(stop_durations[1])).abs() < 1e-12);
+}
+
+#[test]
+fn test_dwell() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.current_position = daov_stack![0.0, 1.0];
+    input.target_position = daov_stack![1.0, -0.5];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+
+    otg.calculate(&input, &mut traj).unwrap();
+    let motion = traj.clone();
+    let motion_duration = motion.get_duration();
+
+    input.pre_dwell = Some(0.4);
+    input.post_dwell = Some(0.5);
+    assert_eq!(otg.calculate(&input, &mut traj), Ok(RuckigResult::Working));
+    assert!((traj.get_duration() - (motion_duration + 0.9)).abs() < 1e-12);
+
+    // The pre-dwell has its own storage, and isn't reported as a brake trajectory
+    for dof in 0..2 {
+        assert_eq!(traj.get_profiles()[0][dof].pre_dwell, 0.4);
+        assert!(traj.get_brake_profile(dof).is_none());
+    }
+
+    // The current state is held first, then the motion follows, and the target state is held last
+    for time in [0.0, 0.2, 0.4] {
+        let state = traj.state_at(time);
+        assert!(almost_equal_vecs(&state.position, &[0.0, 1.0], 1e-12));
+        assert!(almost_equal_vecs(&state.velocity, &[0.0, 0.0], 1e-12));
+    }
+    for time in [0.1, 0.5 * motion_duration, motion_duration - 0.1] {
+        let state = traj.state_at(0.4 + time);
+        let expected = motion.state_at(time);
+        assert!(almost_equal_vecs(&state.position, &expected.position, 1e-9));
+        assert!(almost_equal_vecs(&state.velocity, &expected.velocity, 1e-9));
+    }
+    for time in [0.4 + motion_duration, traj.get_duration() - 0.25] {
+        let state = traj.state_at(time);
+        assert!(almost_equal_vecs(&state.position, &[1.0, -0.5], 1e-9));
+        assert!(almost_equal_vecs(&state.velocity, &[0.0, 0.0], 1e-9));
+    }
+
+    // The update loop keeps working through the dwell times
+    let mut output = OutputParameter::new(None);
+    let mut steps = 0;
+    while otg.update(&input, &mut output).unwrap() == RuckigResult::Working {
+        output.pass_to_input(&mut input);
+        steps += 1;
+    }
+    assert!((steps as f64 * 0.01 - traj.get_duration()).abs() < 0.02);
+    assert!(almost_equal_vecs(&output.new_position, &[1.0, -0.5], 1e-9));
+
+    // With intermediate positions, the pre-dwell is only applied before the first section
+    let mut waypoint_otg = Ruckig::<2, ThrowErrorHandler>::new_with_waypoints(1, None, 0.01);
+    let mut waypoint_input = InputParameter::new_with_waypoints(1, None);
+    waypoint_input.current_position = daov_stack![0.0, 1.0];
+    waypoint_input.target_position = daov_stack![1.0, -0.5];
+    waypoint_input.max_velocity = daov_stack![1.0, 1.0];
+    waypoint_input.max_acceleration = daov_stack![1.0, 1.0];
+    waypoint_input.max_jerk = daov_stack![1.0, 1.0];
+    waypoint_input
+        .push_intermediate_position(daov_stack![0.5, 0.0])
+        .unwrap();
+    waypoint_otg.calculate(&waypoint_input, &mut traj).unwrap();
+    let waypoint_duration = traj.get_duration();
+    waypoint_input.pre_dwell = Some(0.4);
+    waypoint_input.post_dwell = Some(0.5);
+    waypoint_otg.calculate(&waypoint_input, &mut traj).unwrap();
+    assert!((traj.get_duration() - (waypoint_duration + 0.9)).abs() < 1e-12);
+
+    // A dwell requires the state to be at rest
+    input.current_position = daov_stack![0.0, 1.0];
+    input.current_velocity = daov_stack![0.0, 0.2];
+    let violation = input.first_violation(false, true).unwrap();
+    assert_eq!(violation.dof, Some(1));
+    assert_eq!(violation.quantity, InputQuantity::CurrentVelocity);
+    assert_eq!(violation.kind, ViolationKind::NotAtRest);
+    input.pre_dwell = None;
+    assert!(input.first_violation(false, true).is_none());
+
+    input.post_dwell = Some(-1.0);
+    let violation = input.first_violation(false, true).unwrap();
+    assert_eq!(violation.dof, None);
+    assert_eq!(violation.quantity, InputQuantity::PostDwell);
+    assert_eq!(
+        violation.to_string(),
+        "post-dwell duration -1 should be larger than or equal to zero."
+    );
+}
+
+#[test]
+fn test_speed_factor() {
+    let mut otg = Ruckig::<1, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    input.current_position = daov_stack![0.0];
+    input.target_position = daov_stack![10.0];
+    input.max_velocity = daov_stack![1.0];
+    input.max_acceleration = daov_stack![1.0];
+    input.max_jerk = daov_stack![2.0];
+    let start_input = input.clone();
+
+    let mut unscaled_steps = 0;
+    while otg.update(&input, &mut output).unwrap() == RuckigResult::Working {
+        output.pass_to_input(&mut input);
+        unscaled_steps += 1;
+    }
+    assert_eq!(otg.get_speed_factor(), 1.0);
+
+    // Run at half of the speed from the middle of the motion, halt, and continue towards a new
+    // target while still slowed down
+    input = start_input.clone();
+    let mut steps = 0;
+    let mut last_velocity = 0.0;
+    let mut last_acceleration = 0.0;
+    let mut halted_position = None;
+    loop {
+        match steps {
+            100 => otg.set_speed_factor(0.5).unwrap(),
+            250 => otg.set_speed_factor(0.0).unwrap(),
+            850 => {
+                otg.set_speed_factor(0.5).unwrap();
+                input.target_position = daov_stack![12.0];
+            }
+            _ => {}
+        }
+        let result = otg.update(&input, &mut output).unwrap();
+        if steps == 800 {
+            halted_position = Some(output.new_position[0]);
+        }
+        // While halted, the state stays at rest
+        if (800..850).contains(&steps) {
+            assert_eq!(Some(output.new_position[0]), halted_position);
+            assert_eq!(output.new_velocity[0], 0.0);
+        }
+
+        // The scaled motion stays smooth and within the limits
+        assert!(output.new_velocity[0].abs() <= 1.0 + 1e-9);
+        assert!(output.new_acceleration[0].abs() <= 1.0 + 1e-9);
+        assert!(output.new_jerk[0].abs() <= 2.0 + 1e-9, "{}", output.new_jerk[0]);
+        assert!((output.new_velocity[0] - last_velocity).abs() < 0.02);
+        assert!((output.new_acceleration[0] - last_acceleration).abs() < 0.05);
+        last_velocity = output.new_velocity[0];
+        last_acceleration = output.new_acceleration[0];
+
+        output.pass_to_input(&mut input);
+        steps += 1;
+        if result != RuckigResult::Working {
+            break;
+        }
+    }
+    assert!(steps > unscaled_steps + 300);
+    assert!(almost_equal_vecs(&output.new_position, &[12.0], 1e-9));
+    assert_eq!(otg.get_speed_factor(), 0.5);
+    assert!(halted_position.unwrap() > 0.0 && halted_position.unwrap() < 10.0);
+
+    // An invalid speed factor is rejected without changing the speed factor
+    for speed_factor in [-0.5, f64::NAN] {
+        assert!(matches!(
+            otg.set_speed_factor(speed_factor),
+            Err(RuckigError::InvalidSpeedFactor { .. })
+        ));
+    }
+    let mut ignoring_otg = Ruckig::<1, IgnoreErrorHandler>::new(None, 0.01);
+    assert!(ignoring_otg.set_speed_factor(-1.0).is_ok());
+    assert_eq!(ignoring_otg.get_speed_factor(), 1.0);
+    otg.update(&input, &mut output).unwrap();
+    assert_eq!(otg.get_speed_factor(), 0.5);
+}
+
+#[test]
+fn test_pause_and_resume() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    input.current_position = daov_stack![0.0, 0.0];
+    input.target_position = daov_stack![8.0, -4.0];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![2.0, 2.0];
+
+    for _ in 0..150 {
+        otg.update(&input, &mut output).unwrap();
+        output.pass_to_input(&mut input);
+    }
+    let position_before_pause = output.new_position.clone();
+
+    otg.pause();
+    assert!(otg.is_paused());
+    for _ in 0..700 {
+        assert_eq!(otg.update(&input, &mut output), Ok(RuckigResult::Working));
+        output.pass_to_input(&mut input);
+        assert!(output.new_velocity[0].abs() <= 1.0 + 1e-9);
+        assert!(output.new_acceleration[0].abs() <= 1.0 + 1e-9);
+        assert!(output.new_jerk[0].abs() <= 2.0 + 1e-9);
+    }
+
+    // The motion halts along its path in the direction of the target
+    assert_eq!(otg.get_speed_factor(), 0.0);
+    assert!(almost_equal_vecs(&output.new_velocity, &[0.0, 0.0], 1e-12));
+    assert!(almost_equal_vecs(
+        &output.new_acceleration,
+        &[0.0, 0.0],
+        1e-12
+    ));
+    let paused_position = output.new_position.clone();
+    assert!(paused_position[0] > position_before_pause[0] && paused_position[0] < 8.0);
+    assert_eq!(
+        output.trajectory.state_at(output.time).position,
+        paused_position
+    );
+
+    // A speed factor set while paused is applied once the motion resumes
+    otg.set_speed_factor(0.5).unwrap();
+    otg.update(&input, &mut output).unwrap();
+    output.pass_to_input(&mut input);
+    assert_eq!(output.new_position, paused_position);
+
+    otg.resume();
+    assert!(!otg.is_paused());
+    let mut result = RuckigResult::Working;
+    while result == RuckigResult::Working {
+        result = otg.update(&input, &mut output).unwrap();
+        output.pass_to_input(&mut input);
+    }
+    assert_eq!(result, RuckigResult::Finished);
+    assert!(!output.new_calculation);
+    assert_eq!(otg.get_speed_factor(), 0.5);
+    assert!(almost_equal_vecs(&output.new_position, &[8.0, -4.0], 1e-9));
+}
+
+#[test]
+fn test_reset() {
+    let mut otg = Ruckig::<0, ThrowErrorHandler>::new(Some(2), 0.01);
+    let mut input = InputParameter::new(Some(2));
+    let mut output = OutputParameter::new(Some(2));
+    input.current_position = daov_heap![0.0, 0.0];
+    input.target_position = daov_heap![1.0, -1.0];
+    input.max_velocity = daov_heap![1.0, 1.0];
+    input.max_acceleration = daov_heap![1.0, 1.0];
+    input.max_jerk = daov_heap![2.0, 2.0];
+
+    let mut fresh_otg = Ruckig::<0, ThrowErrorHandler>::new(Some(2), 0.01);
+    let mut fresh_output = OutputParameter::new(Some(2));
+    fresh_otg.update(&input, &mut fresh_output).unwrap();
+
+    // Stop an unfinished, slowed down motion in the middle ...
+    otg.set_speed_factor(0.5).unwrap();
+    otg.pause();
+    for _ in 0..100 {
+        otg.update(&input, &mut output).unwrap();
+        output.pass_to_input(&mut input);
+    }
+
+    // ... and start the next motion task with the same instance
+    otg.reset();
+    assert!(!otg.is_paused());
+    assert_eq!(otg.get_speed_factor(), 1.0);
+    input.current_position = daov_heap![0.0, 0.0];
+    input.current_velocity = daov_heap![0.0, 0.0];
+    input.current_acceleration = daov_heap![0.0, 0.0];
+    assert_eq!(otg.update(&input, &mut output), Ok(RuckigResult::Working));
+    assert!(output.new_calculation);
+    assert_eq!(output.time, fresh_output.time);
+    assert_eq!(output.new_position, fresh_output.new_position);
+    assert_eq!(output.new_velocity, fresh_output.new_velocity);
+}
+
+#[test]
+fn test_set_delta_time() {
+    let mut otg = Ruckig::<1, ThrowErrorHandler>::new(None, 0.001);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    input.current_position = daov_stack![0.0];
+    input.target_position = daov_stack![1.0];
+    input.max_velocity = daov_stack![1.0];
+    input.max_acceleration = daov_stack![1.0];
+    input.max_jerk = daov_stack![2.0];
+
+    for _ in 0..100 {
+        otg.update(&input, &mut output).unwrap();
+        output.pass_to_input(&mut input);
+    }
+
+    // A continuous trajectory just continues with the new control cycle
+    otg.set_delta_time(0.004);
+    let time = output.time;
+    otg.update(&input, &mut output).unwrap();
+    output.pass_to_input(&mut input);
+    assert!(!output.new_calculation);
+    assert!((output.time - (time + 0.004)).abs() < 1e-12);
+
+    // A discrete trajectory is re-planned to a multiple of the new control cycle
+    input.duration_discretization = DurationDiscretization::Discrete;
+    otg.update(&input, &mut output).unwrap();
+    output.pass_to_input(&mut input);
+    assert!(output.new_calculation);
+    otg.set_delta_time(0.001);
+    otg.update(&input, &mut output).unwrap();
+    output.pass_to_input(&mut input);
+    assert!(output.new_calculation);
+    let steps = output.trajectory.get_duration() / 0.001;
+    assert!((steps - steps.round()).abs() < 1e-6);
+
+    let mut result = RuckigResult::Working;
+    while result == RuckigResult::Working {
+        result = otg.update(&input, &mut output).unwrap();
+        output.pass_to_input(&mut input);
+    }
+    assert!(almost_equal_vecs(&output.new_position, &[1.0], 1e-9));
+}
+
+#[test]
+fn test_resize_dofs() {
+    let mut otg = Ruckig::<0, ThrowErrorHandler>::new(Some(2), 0.01);
+    let mut input = InputParameter::new(Some(2));
+    let mut output = OutputParameter::new(Some(2));
+    input.target_position = daov_heap![1.0, -1.0];
+    input.max_velocity = daov_heap![1.0, 1.0];
+    input.max_acceleration = daov_heap![1.0, 1.0];
+    input.max_jerk = daov_heap![2.0, 2.0];
+    otg.calculator.settings.duration_rounding = DurationRounding::Nearest;
+    otg.calculator.settings.replan_tolerance = 1e-6;
+    let settings = otg.calculator.settings.clone();
+    otg.update(&input, &mut output).unwrap();
+
+    // Add an axis for the next program, keeping the settings
+    otg.resize_dofs(3);
+    assert_eq!(otg.degrees_of_freedom, 3);
+    assert_eq!(otg.calculator.settings, settings);
+    let mut input = InputParameter::new(Some(3));
+    let mut output = OutputParameter::new(Some(3));
+    input.target_position = daov_heap![1.0, -1.0, 0.5];
+    input.max_velocity = daov_heap![1.0, 1.0, 1.0];
+    input.max_acceleration = daov_heap![1.0, 1.0, 1.0];
+    input.max_jerk = daov_heap![2.0, 2.0, 2.0];
+
+    let mut fresh_otg = Ruckig::<0, ThrowErrorHandler>::with_settings(Some(3), 0.01, settings);
+    let mut fresh_output = OutputParameter::new(Some(3));
+    loop {
+        let result = otg.update(&input, &mut output).unwrap();
+        assert_eq!(fresh_otg.update(&input, &mut fresh_output).unwrap(), result);
+        assert_eq!(output.new_position, fresh_output.new_position);
+        if result != RuckigResult::Working {
+            break;
+        }
+        output.pass_to_input(&mut input);
+    }
+    assert!(almost_equal_vecs(
+        &output.new_position,
+        &[1.0, -1.0, 0.5],
+        1e-9
+    ));
+}
+
+#[test]
+fn test_update_at() {
+    let mut otg = Ruckig::<1, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    input.current_position = daov_stack![0.0];
+    input.target_position = daov_stack![1.0];
+    input.max_velocity = daov_stack![1.0];
+    input.max_acceleration = daov_stack![1.0];
+    input.max_jerk = daov_stack![2.0];
+
+    // The host clock starts at an arbitrary time and wakes up with jitter
+    let start = 1000.0;
+    let mut now = start;
+    for step in 0..150 {
+        now = start + step as f64 * 0.01 + 0.003 * ((step * 7) % 5) as f64 / 5.0;
+        otg.update_at(&input, now, &mut output).unwrap();
+        output.pass_to_input(&mut input);
+    }
+    assert!((output.time - (now - start + 0.01)).abs() < 1e-9);
+    assert_eq!(
+        output.new_position,
+        output.trajectory.state_at(output.time).position
+    );
+
+    // After a reset, the first update advances by the control cycle again
+    otg.reset();
+    input.target_position = daov_stack![0.0];
+    otg.update_at(&input, now + 5.0, &mut output).unwrap();
+    assert!(output.new_calculation);
+    assert_eq!(output.time, 0.01);
+}
+
+#[test]
+fn test_update_with_dt() {
+    let mut otg = Ruckig::<1, ThrowErrorHandler>::new(None, 0.001);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    input.current_position = daov_stack![0.0];
+    input.target_position = daov_stack![1.0];
+    input.max_velocity = daov_stack![1.0];
+    input.max_acceleration = daov_stack![1.0];
+    input.max_jerk = daov_stack![2.0];
+
+    // Occasional double cycles are consumed exactly
+    let mut elapsed = 0.0;
+    let mut step = 0;
+    loop {
+        let dt = if step % 10 == 9 { 0.002 } else { 0.001 };
+        let result = otg.update_with_dt(&input, dt, &mut output).unwrap();
+        elapsed += dt;
+        step += 1;
+        assert!((output.time - elapsed).abs() < 1e-9);
+        if result != RuckigResult::Working {
+            break;
+        }
+        output.pass_to_input(&mut input);
+    }
+    assert!(elapsed > output.trajectory.get_duration());
+    assert!(elapsed - 0.002 <= output.trajectory.get_duration());
+    assert!(almost_equal_vecs(&output.new_position, &[1.0], 1e-9));
+}
+
+#[test]
+fn test_settings() {
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.current_position = daov_stack![0.0, 0.0];
+    input.target_position = daov_stack![100.123, 50.0];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+
+    let mut otg = Ruckig::<2, IgnoreErrorHandler>::new(None, 0.01);
+    assert_eq!(otg.calculate(&input, &mut traj), Ok(RuckigResult::Working));
+    let continuous_duration = traj.get_duration();
+    assert_eq!(otg.calculator.settings, Settings::default());
+
+    // A shorter maximal duration rejects the trajectory, unless the error is disabled
+    let settings = Settings {
+        maximal_duration: 50.0,
+        ..Default::default()
+    };
+    let mut otg = Ruckig::<2, IgnoreErrorHandler>::with_settings(None, 0.01, settings.clone());
+    assert_eq!(
+        otg.calculate(&input, &mut traj),
+        Ok(RuckigResult::ErrorTrajectoryDuration)
+    );
+    otg.calculator.settings.return_error_at_maximal_duration = false;
+    assert_eq!(otg.calculate(&input, &mut traj), Ok(RuckigResult::Working));
+
+    // A tolerance of a full control cycle keeps discrete durations as they are
+    input.duration_discretization = DurationDiscretization::Discrete;
+    otg.calculate(&input, &mut traj).unwrap();
+    assert!(traj.get_duration() > continuous_duration);
+    otg.calculator.settings.discretization_tolerance = 0.01;
+    otg.calculate(&input, &mut traj).unwrap();
+    assert!((traj.get_duration() - continuous_duration).abs() < 1e-12);
+}
+
+#[test]
+fn test_relaxed_mode() {
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.current_position = daov_stack![0.0010071823115640566];
+    input.current_velocity = daov_stack![-0.11061080264365897];
+    input.current_acceleration = daov_stack![-0.2811436633463149];
+    input.target_position = daov_stack![-7.875635216747501e-5];
+    input.max_velocity = daov_stack![0.0018336378938328761];
+    input.max_acceleration = daov_stack![0.0010001593517047541];
+    input.max_jerk = daov_stack![0.0010315123953342083];
+
+    // There is no exact profile for this input due to the numerical precision
+    let mut otg = Ruckig::<1, IgnoreErrorHandler>::new(None, 0.01);
+    assert_eq!(
+        otg.calculate(&input, &mut traj),
+        Ok(RuckigResult::ErrorExecutionTimeCalculation)
+    );
+    assert!(otg.calculator.relaxed_limits.is_empty());
+
+    // The relaxed mode finds a profile that exceeds the limits only slightly
+    let settings = Settings {
+        max_relaxation: 1e-3,
+        ..Default::default()
+    };
+    let mut otg = Ruckig::<1, IgnoreErrorHandler>::with_settings(None, 0.01, settings);
+    assert_eq!(otg.calculate(&input, &mut traj), Ok(RuckigResult::Working));
+    let excess = match otg.calculator.relaxed_limits[..] {
+        [excess] => excess,
+        _ => panic!("expected a single relaxed DoF"),
+    };
+    assert_eq!(excess.dof, 0);
+    assert!(excess.relaxation > 0.0 && excess.relaxation <= 1e-6);
+    for value in [excess.velocity, excess.acceleration, excess.jerk] {
+        assert!((0.0..1e-6).contains(&value));
+    }
+    assert!(excess.boundary < 1e-6);
+
+    let end = traj.state_at(traj.get_duration());
+    assert!((end.position[0] - input.target_position[0]).abs() < 1e-6);
+    assert!(end.velocity[0].abs() < 1e-6);
+
+    // Exact profiles are not relaxed
+    input.current_velocity = daov_stack![0.0];
+    input.current_acceleration = daov_stack![0.0];
+    assert_eq!(otg.calculate(&input, &mut traj), Ok(RuckigResult::Working));
+    assert!(otg.calculator.relaxed_limits.is_empty());
+}
+
+#[test]
+fn test_relaxed_mode_step2() {
+    let mut input = InputParameter::new(None);
+    input.current_position = daov_stack![-1.8319379099947664, 5.459334970202058, 6.93871041018365];
+    input.current_velocity = daov_stack![0.0, 0.0, -0.22888237126144367];
+    input.current_acceleration = daov_stack![-0.09246750056675801, 0.0, 0.0];
+    input.target_position =
+        daov_stack![3.323442915974382, -1.8147754310121949, -2.472281270012791];
+    input.target_velocity = daov_stack![-0.09936433675984702, 0.0, 0.5657544436651166];
+    input.target_acceleration = daov_stack![-0.9558598912820774, 0.0, -0.5590647816897713];
+    input.max_velocity = daov_stack![4.487433952727638, 0.0010822516616480825, 0.9557449331560646];
+    input.max_acceleration =
+        daov_stack![19.689950214713235, 0.08140973498376022, 10.411018828746334];
+    input.max_jerk = daov_stack![82.60686025945736, 0.02429121043679852, 365.0798693054145];
+
+    // The synchronized profile of DoF 2 is only found with relaxed limits
+    let settings = Settings {
+        max_relaxation: 1e-3,
+        ..Default::default()
+    };
+    let mut otg = Ruckig::<3, IgnoreErrorHandler>::with_settings(None, 0.01, settings);
+    let mut output = OutputParameter::new(None);
+    assert_eq!(
+        otg.update(&input, &mut output),
+        Ok(RuckigResult::Working)
+    );
+    let excess = match output.relaxed_limits[..] {
+        [excess] => excess,
+        _ => panic!("expected a single relaxed DoF"),
+    };
+    assert_eq!(excess.dof, 2);
+    assert!(excess.relaxation > 0.0 && excess.relaxation <= 1e-3);
+    for value in [excess.velocity, excess.acceleration, excess.jerk] {
+        assert!((0.0..1e-3).contains(&value));
+    }
+    assert!(excess.boundary < 1e-6);
+    assert_eq!(output.relaxed_limits, otg.calculator.relaxed_limits);
+
+    let duration = output.trajectory.get_duration();
+    let state = output.trajectory.state_at(duration);
+    assert!(almost_equal_vecs(
+        &state.position,
+        &input.target_position,
+        1e-6
+    ));
+
+    // The report belongs to the trajectory, and is replaced by the next calculation
+    output.pass_to_input(&mut input);
+    assert_eq!(
+        otg.update(&input, &mut output),
+        Ok(RuckigResult::Working)
+    );
+    assert_eq!(output.relaxed_limits.len(), 1);
+    input.target_position = daov_stack![0.0, 5.0, 0.0];
+    input.target_velocity = daov_stack![0.0, 0.0, 0.0];
+    input.target_acceleration = daov_stack![0.0, 0.0, 0.0];
+    assert_eq!(
+        otg.update(&input, &mut output),
+        Ok(RuckigResult::Working)
+    );
+    assert!(output.relaxed_limits.is_empty());
+}
+
+#[test]
+fn test_debug_trace() {
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.current_position = daov_stack![0.0010071823115640566];
+    input.current_velocity = daov_stack![-0.11061080264365897];
+    input.current_acceleration = daov_stack![-0.2811436633463149];
+    input.target_position = daov_stack![-7.875635216747501e-5];
+    input.max_velocity = daov_stack![0.0018336378938328761];
+    input.max_acceleration = daov_stack![0.0010001593517047541];
+    input.max_jerk = daov_stack![0.0010315123953342083];
+
+    // All profiles of step 1 are rejected, each for a reason
+    let mut otg = Ruckig::<1, IgnoreErrorHandler>::new(None, 0.01);
+    assert_eq!(
+        otg.calculate(&input, &mut traj),
+        Ok(RuckigResult::ErrorExecutionTimeCalculation)
+    );
+    let trace = &otg.calculator.trace;
+    assert!(!trace.is_empty());
+    for attempt in trace {
+        assert_eq!(attempt.dof, 0);
+        assert_eq!(attempt.stage, CalculationStage::Step1);
+        assert!(attempt.strategy.starts_with("PositionThirdOrderStep1::"));
+        assert!(attempt.failure.is_some(), "{}", attempt);
+    }
+
+    // The trace of a successful calculation replaces the previous one
+    input.current_velocity = daov_stack![0.0];
+    input.current_acceleration = daov_stack![0.0];
+    assert_eq!(otg.calculate(&input, &mut traj), Ok(RuckigResult::Working));
+    let trace = &otg.calculator.trace;
+    assert!(trace.iter().any(|attempt| attempt.failure.is_none()));
+    assert!(trace
+        .iter()
+        .all(|attempt| attempt.stage == CalculationStage::Step1));
+}
+
+#[test]
+fn test_blocks() {
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.current_velocity = daov_stack![0.5];
+    input.current_acceleration = daov_stack![-0.1];
+    input.target_position = daov_stack![0.9];
+    input.target_velocity = daov_stack![0.7];
+    input.max_velocity = daov_stack![1.0];
+    input.max_acceleration = daov_stack![1.0];
+    input.max_jerk = daov_stack![1.0];
+
+    let mut otg = Ruckig::<1, ThrowErrorHandler>::new(None, 0.01);
+    assert_eq!(otg.calculate(&input, &mut traj), Ok(RuckigResult::Working));
+    let block = otg.calculator.get_blocks()[0].clone();
+    assert_float_eq!(block.t_min, traj.get_duration(), abs <= 1e-12);
+    assert!(block.b.is_none());
+    let a = block.a.clone().expect("blocked interval");
+    assert_float_eq!(a.left, 1.8393924707, abs <= 1e-8);
+    assert_float_eq!(a.right, 2.9746873878, abs <= 1e-8);
+    assert_float_eq!(*a.profile.t_sum.last().unwrap(), a.right, abs <= 1e-12);
+
+    let feasible: Vec<_> = block.feasible_durations().collect();
+    assert_eq!(
+        feasible,
+        [(block.t_min, a.left), (a.right, f64::INFINITY)]
+    );
+    for t in [1.0, 2.0, 2.5, 3.0] {
+        let is_feasible = feasible.iter().any(|(left, right)| (*left..=*right).contains(&t));
+        assert_eq!(block.is_blocked(t), !is_feasible, "{}", t);
+    }
+
+    // A minimum duration within the blocked interval is extended to its end
+    input.minimum_duration = Some(2.5);
+    assert_eq!(otg.calculate(&input, &mut traj), Ok(RuckigResult::Working));
+    assert_float_eq!(traj.get_duration(), a.right, abs <= 1e-8);
+}
+
+#[test]
+fn test_check_limits() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.current_position = daov_stack![0.0, -1.0];
+    input.current_velocity = daov_stack![0.2, 0.0];
+    input.target_position = daov_stack![1.0, 1.0];
+    input.target_velocity = daov_stack![0.0, 0.2];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+    otg.calculate(&input, &mut traj).unwrap();
+    assert!(traj.check_limits(&input, 0.001).is_empty());
+
+    // A stricter limit and another target are violated by the calculated trajectory
+    input.max_velocity[1] = 0.5;
+    input.target_position[0] = 1.5;
+    let violations = traj.check_limits(&input, 0.001);
+    assert!(!violations.is_empty());
+    assert!(violations
+        .iter()
+        .filter(|violation| violation.quantity == InputQuantity::MaxVelocity)
+        .all(|violation| violation.dof == 1 && violation.value > 0.5));
+    assert!(violations
+        .iter()
+        .any(|violation| violation.quantity == InputQuantity::MaxVelocity));
+    let missed_target = violations
+        .iter()
+        .find(|violation| violation.quantity == InputQuantity::TargetPosition)
+        .unwrap();
+    assert_eq!(missed_target.dof, 0);
+    assert_float_eq!(missed_target.time, traj.get_duration(), abs <= 1e-12);
+    assert_float_eq!(missed_target.value, 1.0, abs <= 1e-8);
+    assert_eq!(missed_target.allowed, 1.5);
+}
+
+#[test]
+fn test_per_dof_priority() {
+    let mut otg = Ruckig::<3, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.current_position = daov_stack![0.0, 0.0, 0.0];
+    input.target_position = daov_stack![1.0, 4.0, 0.5];
+    input.max_velocity = daov_stack![1.0, 1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0, 1.0];
+    input.per_dof_priority = Some(daov_stack![1, 0, 0]);
+
+    // The prioritized DoF reaches its target before the slower DoFs
+    otg.calculate(&input, &mut traj).unwrap();
+    let t_priority = traj.get_independent_min_durations()[0];
+    assert!(t_priority < traj.get_duration() - 1.0);
+    assert_float_eq!(
+        traj.get_duration(),
+        traj.get_independent_min_durations()[1],
+        abs <= 1e-12
+    );
+    let state = traj.state_at(t_priority);
+    assert_float_eq!(state.position[0], 1.0, abs <= 1e-8);
+    assert_float_eq!(state.velocity[0], 0.0, abs <= 1e-8);
+    let state = traj.state_at(traj.get_duration());
+    assert!(almost_equal_vecs(
+        &state.position,
+        &input.target_position,
+        1e-8
+    ));
+
+    // The other DoFs are stretched to the duration of the slower prioritized DoF
+    input.per_dof_priority = Some(daov_stack![0, 2, 1]);
+    otg.calculate(&input, &mut traj).unwrap();
+    assert_float_eq!(
+        traj.get_duration(),
+        traj.get_independent_min_durations()[1],
+        abs <= 1e-12
+    );
+    let state = traj.state_at(traj.get_duration() - 0.5);
+    assert!(state.velocity[0] > 0.0);
+    assert!(state.velocity[2] > 0.0);
+    assert!(traj.check_limits(&input, 0.01).is_empty());
+
+    // Equal priorities don't change the time synchronization
+    input.per_dof_priority = Some(daov_stack![1, 1, 1]);
+    otg.calculate(&input, &mut traj).unwrap();
+    let state = traj.state_at(traj.get_duration() - 0.5);
+    assert!(state.velocity[0] > 0.0);
+}
+
+#[test]
+fn test_per_dof_group() {
+    let mut otg = Ruckig::<4, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.current_position = daov_stack![0.0, 0.0, 0.0, 0.0];
+    input.target_position = daov_stack![1.0, 2.0, 3.0, 0.2];
+    input.max_velocity = daov_stack![1.0, 1.0, 1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0, 1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0, 1.0, 1.0];
+    input.synchronization = Synchronization::Phase;
+    input.per_dof_group = Some(daov_stack![0, 0, 0, 1]);
+
+    // The arm is phase synchronized, while the gripper finishes on its own
+    otg.calculate(&input, &mut traj).unwrap();
+    let t_arm = traj.get_independent_min_durations()[2];
+    let t_gripper = traj.get_independent_min_durations()[3];
+    assert_float_eq!(traj.get_duration(), t_arm, abs <= 1e-12);
+    for time in [0.5, 1.0, 2.0, 3.0] {
+        let state = traj.state_at(time);
+        assert_float_eq!(state.position[1], 2.0 * state.position[0], abs <= 1e-8);
+        assert_float_eq!(state.position[2], 3.0 * state.position[0], abs <= 1e-8);
+    }
+    let state = traj.state_at(t_gripper);
+    assert_float_eq!(state.position[3], 0.2, abs <= 1e-8);
+    assert!(state.position[0] < 1.0);
+    let state = traj.state_at(traj.get_duration());
+    assert!(almost_equal_vecs(
+        &state.position,
+        &input.target_position,
+        1e-8
+    ));
+
+    // A slower gripper determines the duration, but doesn't stretch the arm
+    input.target_position[3] = 5.0;
+    otg.calculate(&input, &mut traj).unwrap();
+    assert_float_eq!(
+        traj.get_duration(),
+        traj.get_independent_min_durations()[3],
+        abs <= 1e-12
+    );
+    let state = traj.state_at(t_arm);
+    assert!(almost_equal_vecs(
+        &state.position.as_ref()[0..3],
+        &input.target_position.as_ref()[0..3],
+        1e-8
+    ));
+    assert!(traj.check_limits(&input, 0.01).is_empty());
+}
+
+#[test]
+fn test_maximum_duration() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.target_position = daov_stack![1.0, 2.0];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+    otg.calculate(&input, &mut traj).unwrap();
+    let duration = traj.get_duration();
+
+    input.maximum_duration = Some(duration + 0.1);
+    assert_eq!(otg.calculate(&input, &mut traj), Ok(RuckigResult::Working));
+
+    // The post-dwell counts towards the duration
+    input.post_dwell = Some(0.2);
+    assert_eq!(
+        otg.calculate(&input, &mut traj),
+        Err(RuckigError::MaximumDurationExceeded {
+            duration: duration + 0.2,
+            maximum: duration + 0.1,
+        })
+    );
+    assert_eq!(
+        otg.calculator.failed_stage,
+        Some(CalculationStage::Synchronization)
+    );
+
+    input.post_dwell = None;
+    input.maximum_duration = Some(duration - 0.1);
+    let mut otg_ignore = Ruckig::<2, IgnoreErrorHandler>::new(None, 0.01);
+    assert_eq!(
+        otg_ignore.calculate(&input, &mut traj),
+        Ok(RuckigResult::ErrorTrajectoryDuration)
+    );
+
+    // The relaxed mode keeps the trajectory
+    let settings = Settings {
+        max_relaxation: 1e-3,
+        ..Default::default()
+    };
+    let mut otg_relaxed = Ruckig::<2, ThrowErrorHandler>::with_settings(None, 0.01, settings);
+    assert_eq!(
+        otg_relaxed.calculate(&input, &mut traj),
+        Ok(RuckigResult::Working)
+    );
+    assert_float_eq!(traj.get_duration(), duration, abs <= 1e-12);
+
+    input.maximum_duration = Some(f64::NAN);
+    assert!(otg.calculate(&input, &mut traj).is_err());
+}
+
+#[test]
+fn test_discretization_step() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.001);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.current_position = daov_stack![0.0, -0.3];
+    input.current_velocity = daov_stack![0.1, 0.0];
+    input.target_position = daov_stack![1.234, 0.8];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+    input.duration_discretization = DurationDiscretization::DiscreteStep(0.25);
+    otg.calculate(&input, &mut traj).unwrap();
+
+    let steps = traj.get_duration() / 0.25;
+    assert_float_eq!(steps, steps.round(), abs <= 1e-9);
+    assert!(traj.get_duration() >= traj.get_independent_min_durations()[0]);
+    let state = traj.state_at(traj.get_duration());
+    assert!(almost_equal_vecs(
+        &state.position,
+        &input.target_position,
+        1e-8
+    ));
+
+    input.duration_discretization = DurationDiscretization::DiscreteStep(0.0);
+    assert!(otg.calculate(&input, &mut traj).is_err());
+}
+
+#[test]
+fn test_duration_rounding() {
+    let mut input = InputParameter::<2>::new(None);
+    let mut traj = Trajectory::new(None);
+    input.current_position = daov_stack![0.0, -0.3];
+    input.target_position = daov_stack![1.234, 0.8];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    otg.calculate(&input, &mut traj).unwrap();
+    let duration = traj.get_duration();
+    assert_eq!(traj.get_discretization_remainder(), 0.0);
+    let step = 0.25;
+    let remainder = duration % step;
+    input.duration_discretization = DurationDiscretization::DiscreteStep(step);
+
+    otg.calculate(&input, &mut traj).unwrap();
+    assert_float_eq!(
+        traj.get_duration(),
+        duration - remainder + step,
+        abs <= 1e-9
+    );
+    assert_float_eq!(
+        traj.get_discretization_remainder(),
+        step - remainder,
+        abs <= 1e-9
+    );
+
+    // Every mode ends on a multiple of the step. The previous multiple is shorter than the
+    // time-optimal duration, so that rounding down falls back to the next one.
+    for rounding in [
+        DurationRounding::Up,
+        DurationRounding::Down,
+        DurationRounding::Nearest,
+    ] {
+        let settings = Settings {
+            duration_rounding: rounding,
+            ..Default::default()
+        };
+        let mut otg = Ruckig::<2, ThrowErrorHandler>::with_settings(None, 0.01, settings);
+        otg.calculate(&input, &mut traj).unwrap();
+        let steps = traj.get_duration() / step;
+        assert_float_eq!(steps, steps.round(), abs <= 1e-9);
+        assert_float_eq!(
+            traj.get_duration(),
+            duration - remainder + step,
+            abs <= 1e-9
+        );
+        assert_float_eq!(
+            traj.get_duration() - traj.get_discretization_remainder(),
+            duration,
+            abs <= 1e-9
+        );
+        let state = traj.state_at(traj.get_duration());
+        assert!(almost_equal_vecs(
+            &state.position,
+            &input.target_position,
+            1e-8
+        ));
+    }
+}
+
+#[test]
+fn test_circular_dofs() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    input.current_position = daov_stack![350.0, 350.0];
+    input.target_position = daov_stack![10.0, -90.0];
+    input.max_velocity = daov_stack![100.0, 100.0];
+    input.max_acceleration = daov_stack![100.0, 100.0];
+    input.max_jerk = daov_stack![1000.0, 1000.0];
+    input.per_dof_period = Some(daov_stack![Some(360.0), None]);
+
+    // The circular DoF moves forward by 20 degrees, the linear DoF backward by 440 degrees
+    let mut traj = Trajectory::new(None);
+    otg.calculate(&input, &mut traj).unwrap();
+    assert_float_eq!(traj.path_length(0), 20.0, abs <= 1e-8);
+    assert_float_eq!(traj.path_length(1), 440.0, abs <= 1e-8);
+    assert!(traj.check_limits(&input, 0.01).is_empty());
+
+    while otg.update(&input, &mut output).unwrap() == RuckigResult::Working {
+        assert!((0.0..360.0).contains(&output.new_position[0]));
+        output.pass_to_input(&mut input);
+    }
+    assert_float_eq!(output.new_position[0], 10.0, abs <= 1e-8);
+    assert_float_eq!(output.new_position[1], -90.0, abs <= 1e-8);
+
+    input.per_dof_period = Some(daov_stack![Some(0.0), None]);
+    assert!(otg.calculate(&input, &mut traj).is_err());
+}
+
+#[test]
+fn test_target_tolerance() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    input.target_position = daov_stack![1.0, -0.5];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+    input.target_position_tolerance = Some(daov_stack![0.01, 0.01]);
+    input.target_velocity_tolerance = Some(daov_stack![0.05, 0.05]);
+
+    while otg.update(&input, &mut output).unwrap() == RuckigResult::Working {
+        output.pass_to_input(&mut input);
+    }
+
+    // Finished within the window before the end of the trajectory
+    assert!(output.time < output.trajectory.get_duration());
+    for dof in 0..2 {
+        assert!((output.new_position[dof] - input.target_position[dof]).abs() <= 0.01);
+        assert!(output.new_velocity[dof].abs() <= 0.05);
+    }
+
+    input.target_velocity_tolerance = Some(daov_stack![0.05, -0.05]);
+    assert!(otg.update(&input, &mut output).is_err());
+}
+
+#[test]
+fn test_clamp_target_state() {
+    let mut input = InputParameter::<2>::new(None);
+    let mut traj = Trajectory::new(None);
+    input.target_position = daov_stack![1.0, -0.5];
+    input.target_velocity = daov_stack![1.2, 0.0];
+    input.target_acceleration = daov_stack![-0.3, 0.0];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    assert!(otg.calculate(&input, &mut traj).is_err());
+
+    let settings = Settings {
+        clamp_target_state: true,
+        ..Default::default()
+    };
+    let mut otg_clamped = Ruckig::<2, ThrowErrorHandler>::with_settings(None, 0.01, settings);
+    assert_eq!(
+        otg_clamped.calculate(&input, &mut traj),
+        Ok(RuckigResult::Working)
+    );
+
+    // The velocity is clamped to its limit, and the acceleration such that it can be kept
+    let state = traj.state_at(traj.get_duration());
+    assert_float_eq!(state.velocity[0], 1.0, abs <= 1e-8);
+    assert_float_eq!(state.acceleration[0], 0.0, abs <= 1e-8);
+    assert_float_eq!(state.position[1], -0.5, abs <= 1e-8);
+}
+
+#[test]
+fn test_calculate_with_budget() {
+    let mut otg = Ruckig::<3, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.current_position = daov_stack![0.0, -0.3, 0.2];
+    input.target_position = daov_stack![1.0, 0.8, -0.4];
+    input.max_velocity = daov_stack![1.0, 1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0, 1.0];
+
+    assert_eq!(
+        otg.calculate_with_budget(&input, &mut traj, 0.0),
+        Ok(RuckigResult::Interrupted)
+    );
+
+    let mut reference = Trajectory::new(None);
+    otg.calculate(&input, &mut reference).unwrap();
+    assert_eq!(
+        otg.calculate_with_budget(&input, &mut traj, 1e9),
+        Ok(RuckigResult::Working)
+    );
+    assert_eq!(traj.get_duration(), reference.get_duration());
+
+    // The budget applies to a single calculation only
+    assert_eq!(
+        otg.calculate_with_budget(&input, &mut traj, 0.0),
+        Ok(RuckigResult::Interrupted)
+    );
+    assert_eq!(otg.calculate(&input, &mut traj), Ok(RuckigResult::Working));
+    assert_eq!(RuckigResult::try_from(2), Ok(RuckigResult::Interrupted));
+}
+
+#[test]
+fn test_calculation_duration() {
+    let mut otg = Ruckig::<1, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.target_position = daov_stack![1.0];
+    input.max_velocity = daov_stack![1.0];
+    input.max_acceleration = daov_stack![1.0];
+    input.max_jerk = daov_stack![1.0];
+
+    otg.calculate(&input, &mut traj).unwrap();
+    assert!(traj.get_calculation_duration() > 0.0);
+
+    otg.update(&input, &mut output).unwrap();
+    assert!(output.new_calculation);
+    assert!(output.calculation_duration > 0.0);
+
+    // A failed calculation is measured as well
+    output.calculation_duration = 0.0;
+    input.max_jerk = daov_stack![-1.0];
+    assert!(otg.update(&input, &mut output).is_err());
+    assert!(output.calculation_duration > 0.0);
+}
+
+#[test]
+fn test_update_events() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new_with_waypoints(2, None, 0.01);
+    let mut input = InputParameter::new_with_waypoints(2, None);
+    let mut output = OutputParameter::new_with_waypoints(2, None);
+    input.target_position = daov_stack![1.0, 1.0];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![2.0, 2.0];
+    input.max_jerk = daov_stack![4.0, 4.0];
+    input.intermediate_positions = vec![daov_stack![0.5, -0.5], daov_stack![0.8, 0.2]].into();
+
+    let mut events = Vec::new();
+    while otg.update(&input, &mut output).unwrap() == RuckigResult::Working {
+        events.extend_from_slice(&output.events);
+        output.pass_to_input(&mut input);
+    }
+    events.extend_from_slice(&output.events);
+    assert_eq!(
+        events,
+        vec![
+            UpdateEvent::NewCalculation,
+            UpdateEvent::SectionChanged(1),
+            UpdateEvent::SectionChanged(2),
+            UpdateEvent::SectionChanged(3),
+            UpdateEvent::Finished,
+        ]
+    );
+
+    // The end is reported only once
+    output.pass_to_input(&mut input);
+    assert_eq!(otg.update(&input, &mut output), Ok(RuckigResult::Finished));
+    assert!(output.events.is_empty());
+
+    // Several events within a single cycle
+    otg.reset();
+    input.current_position = daov_stack![0.0, 0.0];
+    input.current_velocity = daov_stack![0.0, 0.0];
+    input.current_acceleration = daov_stack![0.0, 0.0];
+    let duration = output.trajectory.get_duration();
+    assert_eq!(
+        otg.update_with_dt(&input, duration + 1.0, &mut output),
+        Ok(RuckigResult::Finished)
+    );
+    assert_eq!(output.events, events);
+}
+
+#[test]
+fn test_pass_to_input_with_feedback() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    input.target_position = daov_stack![1.0, -0.5];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+
+    otg.update(&input, &mut output).unwrap();
+    let measured_position = [output.new_position[0] + 0.1, output.new_position[1]];
+
+    output
+        .pass_to_input_with_feedback(&mut input, &measured_position, 0.0)
+        .unwrap();
+    assert_eq!(input.current_position[0], output.new_position[0]);
+
+    output
+        .pass_to_input_with_feedback(&mut input, &measured_position, 0.25)
+        .unwrap();
+    assert_float_eq!(
+        input.current_position[0],
+        output.new_position[0] + 0.025,
+        abs <= 1e-12
+    );
+    assert_eq!(input.current_position[1], output.new_position[1]);
+    assert_eq!(input.current_velocity[0], output.new_velocity[0]);
+
+    // A measured position without a value for each DoF is rejected, and the input is kept
+    let current_position = input.current_position.clone();
+    match output.pass_to_input_with_feedback(&mut input, &measured_position[..1], 0.25) {
+        Err(RuckigError::InvalidInput(violation)) => {
+            assert_eq!(violation.quantity, InputQuantity::MeasuredPosition);
+            assert_eq!(violation.kind, ViolationKind::WrongLength);
+            assert_eq!(
+                violation.to_string(),
+                "measured position has 1 values, but there are 2 degrees of freedom."
+            );
+        }
+        result => panic!("Unexpected result: {:?}", result),
+    }
+    assert_eq!(input.current_position, current_position);
+}
+
+#[test]
+fn test_previous_trajectory() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    input.target_position = daov_stack![1.0, -0.5];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+
+    otg.update(&input, &mut output).unwrap();
+    assert!(output.previous_trajectory.is_none());
+    output.retain_previous_trajectory();
+    for _ in 0..50 {
+        output.pass_to_input(&mut input);
+        otg.update(&input, &mut output).unwrap();
+    }
+    let duration = output.trajectory.get_duration();
+    let time = output.time;
+
+    // The new trajectory starts where the previous one was left
+    output.pass_to_input(&mut input);
+    input.target_position = daov_stack![-1.0, 0.5];
+    otg.update(&input, &mut output).unwrap();
+    assert!(output.new_calculation);
+    let previous = output.previous_trajectory.as_ref().unwrap();
+    assert_eq!(previous.get_duration(), duration);
+    assert_eq!(output.previous_time, time);
+    let switchover = previous.state_at(output.previous_time);
+    let start = output.trajectory.state_at(0.0);
+    assert!(almost_equal_vecs(&switchover.position, &start.position, 1e-12));
+    assert!(almost_equal_vecs(&switchover.velocity, &start.velocity, 1e-12));
+    assert_ne!(output.trajectory.get_duration(), duration);
+}
+
+#[test]
+fn test_replan_blending() {
+    let mut input = InputParameter::new(None);
+    input.target_position = daov_stack![1.0, -0.5];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+
+    // Jump of the commanded jerk where the target is reversed while accelerating, and the
+    // largest jump within a window afterwards
+    let jerk_jumps = |replan_blending: f64| {
+        let settings = Settings {
+            replan_blending,
+            ..Default::default()
+        };
+        let mut otg = Ruckig::<2, ThrowErrorHandler>::with_settings(None, 0.01, settings);
+        let mut output = OutputParameter::new(None);
+        let mut input = input.clone();
+        let mut jerk = daov_stack![0.0, 0.0];
+        let mut switch_jump: f64 = 0.0;
+        let mut largest_jump: f64 = 0.0;
+        for cycle in 0.. {
+            if cycle == 30 {
+                input.target_position = daov_stack![-1.0, 0.5];
+            }
+            let result = otg.update(&input, &mut output).unwrap();
+            for dof in 0..2 {
+                let jump = (output.new_jerk[dof] - jerk[dof]).abs();
+                assert!(output.new_jerk[dof].abs() <= input.max_jerk[dof] + 1e-12);
+                if cycle == 30 {
+                    switch_jump = switch_jump.max(jump);
+                }
+                if (30..=40).contains(&cycle) {
+                    largest_jump = largest_jump.max(jump);
+                }
+            }
+            if cycle == 30 && replan_blending > 0.0 {
+                // The jerk ramp is approximated by steps when the trajectory is cropped
+                let traj = &output.trajectory;
+                let cropped = traj.crop(0.0, traj.get_duration());
+                for time in [0.003, 0.05, 0.097, 0.5] {
+                    let state = traj.state_at(time);
+                    let cropped_state = cropped.state_at(time);
+                    assert!(almost_equal_vecs(&state.position, &cropped_state.position, 1e-8));
+                    assert!(almost_equal_vecs(&state.velocity, &cropped_state.velocity, 1e-6));
+                    assert!(almost_equal_vecs(
+                        &state.acceleration,
+                        &cropped_state.acceleration,
+                        1e-4
+                    ));
+                }
+            }
+            jerk.clone_from(&output.new_jerk);
+            output.pass_to_input(&mut input);
+            if result == RuckigResult::Finished {
+                break;
+            }
+        }
+        assert!(almost_equal_vecs(
+            &output.new_position,
+            &input.target_position,
+            1e-8
+        ));
+        (switch_jump, largest_jump)
+    };
+
+    let (switch_jump, largest_jump) = jerk_jumps(0.0);
+    assert_float_eq!(switch_jump, 2.0, abs <= 1e-12);
+    assert_float_eq!(largest_jump, 2.0, abs <= 1e-12);
+
+    // The jerk is ramped down from its current value, so it only changes by a cycle of the ramp
+    // at the switch, and jumps by at most the jerk limit into the new trajectory
+    let (switch_jump, largest_jump) = jerk_jumps(0.1);
+    assert!(switch_jump <= 0.1 + 1e-12);
+    assert!(largest_jump <= 1.0 + 1e-12);
+}
+
+#[test]
+fn test_snap_limit() {
+    let mut input = InputParameter::new(None);
+    input.current_position = daov_stack![0.0, 0.2];
+    input.target_position = daov_stack![1.0, -0.5];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 2.0];
+
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut traj = Trajectory::new(None);
+    otg.calculate(&input, &mut traj).unwrap();
+    let duration = traj.get_duration();
+    assert_eq!(traj.get_snap_window(), 0.0);
+
+    // The window is given by the jerk range of the limiting DoF
+    input.max_snap = Some(daov_stack![20.0, 20.0]);
+    otg.calculate(&input, &mut traj).unwrap();
+    let window = traj.get_snap_window();
+    assert_float_eq!(window, 0.2, abs <= 1e-12);
+    assert_float_eq!(traj.get_duration(), duration + window, abs <= 1e-12);
+    assert!(traj.check_limits(&input, 0.001).is_empty());
+
+    let dt = 0.001;
+    let mut previous = traj.state_at(0.0);
+    for step in 1..=(traj.get_duration() / dt).ceil() as usize {
+        let state = traj.state_at(step as f64 * dt);
+        for dof in 0..2 {
+            let snap = (state.jerk[dof] - previous.jerk[dof]) / dt;
+            assert!(snap.abs() <= 20.0 + 1e-6, "snap {} of DoF {}", snap, dof);
+        }
+        previous = state;
+    }
+    let end = traj.state_at(traj.get_duration());
+    assert!(almost_equal_vecs(&end.position, &input.target_position, 1e-12));
+    assert!(almost_equal_vecs(&end.velocity, &daov_stack![0.0, 0.0], 1e-12));
+
+    // The polynomials and extrema describe the smoothed motion
+    let polynomials = traj.to_piecewise_polynomials();
+    let velocity_extrema = traj.get_velocity_extrema();
+    for (dof, segments) in polynomials.iter().enumerate() {
+        assert!((segments.last().unwrap().t_end - traj.get_duration()).abs() < 1e-12);
+        for segment in segments {
+            let t = 0.4 * (segment.t_end - segment.t_start);
+            let state = traj.state_at(segment.t_start + t);
+            assert_float_eq!(segment.position(t), state.position[dof], abs <= 1e-9);
+            assert_float_eq!(segment.jerk(t), state.jerk[dof], abs <= 1e-9);
+        }
+
+        let mut max_velocity: f64 = 0.0;
+        for step in 0..=(traj.get_duration() / dt) as usize {
+            max_velocity = max_velocity.max(traj.state_at(step as f64 * dt).velocity[dof].abs());
+        }
+        let extremum = velocity_extrema[dof].max.max(-velocity_extrema[dof].min);
+        assert!(extremum >= max_velocity - 1e-12 && extremum <= max_velocity + 1e-6);
+    }
+    assert_float_eq!(traj.path_length(0), 1.0, abs <= 1e-9);
+    let position_extrema = traj.get_position_extrema();
+    assert_float_eq!(position_extrema[1].min, -0.5, abs <= 1e-9);
+    assert_float_eq!(position_extrema[1].max, 0.2, abs <= 1e-9);
+
+    // Cropped and reversed trajectories keep the smoothed motion
+    let cropped = traj.crop(0.1, 1.5);
+    assert_float_eq!(cropped.get_duration(), 1.4, abs <= 1e-12);
+    let reversed = traj.reversed();
+    for step in 0..=14 {
+        let t = step as f64 * 0.1;
+        let state = traj.state_at(t + 0.1);
+        let cropped_state = cropped.state_at(t);
+        assert!(almost_equal_vecs(&cropped_state.position, &state.position, 1e-9));
+        assert!(almost_equal_vecs(&cropped_state.acceleration, &state.acceleration, 1e-9));
+        assert!(almost_equal_vecs(&cropped_state.jerk, &state.jerk, 1e-9));
+
+        let state = traj.state_at(traj.get_duration() - t);
+        let reversed_state = reversed.state_at(t);
+        assert!(almost_equal_vecs(&reversed_state.position, &state.position, 1e-9));
+        assert_float_eq!(reversed_state.velocity[1], -state.velocity[1], abs <= 1e-9);
+    }
+    let cropped_extrema = cropped.get_acceleration_extrema();
+    let mut max_acceleration = f64::NEG_INFINITY;
+    for step in 0..=1400 {
+        max_acceleration = max_acceleration.max(cropped.state_at(step as f64 * dt).acceleration[0]);
+    }
+    assert_float_eq!(cropped_extrema[0].max, max_acceleration, abs <= 1e-6);
+    assert!(cropped_extrema[0].max >= max_acceleration - 1e-12);
+
+    // A snap limit requires the target state to be at rest
+    input.current_velocity = daov_stack![0.0, 0.2];
+    assert!(input.first_violation(false, true).is_none());
+    input.target_velocity = daov_stack![0.0, 0.2];
+    let violation = input.first_violation(false, true).unwrap();
+    assert_eq!(violation.dof, Some(1));
+    assert_eq!(violation.quantity, InputQuantity::TargetVelocity);
+    assert_eq!(violation.kind, ViolationKind::NotAtRest);
+}
+
+#[test]
+fn test_snap_limit_replanning() {
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.001);
+    let mut input = InputParameter::new(None);
+    input.current_position = daov_stack![0.0, 0.2];
+    input.target_position = daov_stack![1.0, -0.5];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 2.0];
+    input.max_snap = Some(daov_stack![20.0, 20.0]);
+
+    // The target changes during the motion, and the re-planned trajectory continues from the
+    // current jerk
+    let dt = 0.001;
+    let mut output = OutputParameter::new(None);
+    let mut previous = (input.current_position.clone(), daov_stack![0.0, 0.0]);
+    let mut calculations = 0;
+    for cycle in 0..10000 {
+        if cycle == 700 {
+            input.target_position = daov_stack![-0.5, 0.5];
+        }
+        let (v, a) = (input.current_velocity.clone(), input.current_acceleration.clone());
+        let result = otg.update(&input, &mut output).unwrap();
+        if output.new_calculation {
+            calculations += 1;
+        }
+        for dof in 0..2 {
+            let (p, j) = (previous.0[dof], previous.1[dof]);
+            let position = p + dt * (v[dof] + dt * (a[dof] / 2.0 + dt * j / 6.0));
+            assert_float_eq!(output.new_position[dof], position, abs <= 1e-9);
+            let velocity = v[dof] + dt * (a[dof] + dt * j / 2.0);
+            assert_float_eq!(output.new_velocity[dof], velocity, abs <= 1e-7);
+            assert_float_eq!(output.new_acceleration[dof], a[dof] + dt * j, abs <= 2e-5);
+            let snap = (output.new_jerk[dof] - j) / dt;
+            assert!(snap.abs() <= 20.0 + 1e-6, "snap {} of DoF {}", snap, dof);
+        }
+        assert!(output.new_velocity.iter().all(|v| v.abs() <= 1.0 + 1e-9));
+        assert!(output.new_acceleration.iter().all(|a| a.abs() <= 1.0 + 1e-9));
+        if cycle == 700 {
+            assert!(output.new_calculation);
+            assert!(output.trajectory.get_snap_window() > 0.0);
+        }
+        previous = (output.new_position.clone(), output.new_jerk.clone());
+        output.pass_to_input(&mut input);
+        if result == RuckigResult::Finished {
+            break;
+        }
+    }
+    assert_eq!(calculations, 2);
+    assert!(almost_equal_vecs(&output.new_position, &daov_stack![-0.5, 0.5], 1e-9));
+    assert!(almost_equal_vecs(&output.new_velocity, &daov_stack![0.0, 0.0], 1e-9));
+}
+
+#[test]
+fn test_motion_generator() {
+    let mut generator = MotionGenerator::<2, ThrowErrorHandler>::new(None, 0.01);
+    generator.input.current_position = daov_stack![0.0, 0.2];
+    generator.input.max_velocity = daov_stack![1.0, 1.0];
+    generator.input.max_acceleration = daov_stack![1.0, 1.0];
+    generator.input.max_jerk = daov_stack![1.0, 1.0];
+
+    let mut target = TargetState::new(None);
+    target.position = daov_stack![1.0, -0.5];
+    generator.set_target(&target);
+
+    let mut cycles = 0;
+    while !generator.is_finished() {
+        generator.tick().unwrap();
+        cycles += 1;
+    }
+    let duration = generator.output().trajectory.get_duration();
+    assert_eq!(cycles, (duration / 0.01).ceil() as usize);
+    assert!(almost_equal_vecs(
+        &generator.input.current_position,
+        &target.position,
+        1e-8
+    ));
+
+    // The target is held, and a new target starts a new motion
+    assert_float_eq!(generator.tick().unwrap().new_position[0], 1.0, abs <= 1e-8);
+    assert!(generator.is_finished());
+    target.position = daov_stack![0.5, 0.5];
+    generator.set_target(&target);
+    assert!(generator.tick().unwrap().new_calculation);
+    assert_eq!(generator.result(), RuckigResult::Working);
+}
+
+#[test]
+fn test_calculator() {
+    let mut input = InputParameter::new(None);
+    input.current_position = daov_stack![0.0, 0.2];
+    input.target_position = daov_stack![1.0, -0.5];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut expected = Trajectory::new(None);
+    otg.calculate(&input, &mut expected).unwrap();
+
+    let mut calculator = Calculator::<2, ThrowErrorHandler>::new(None);
+    let mut traj = Trajectory::new(None);
+    assert_eq!(calculator.calculate(&input, &mut traj), Ok(RuckigResult::Working));
+    assert_eq!(traj.get_duration(), expected.get_duration());
+
+    // Without a control cycle, the duration is only discretized by a given step
+    input.duration_discretization = DurationDiscretization::DiscreteStep(0.5);
+    calculator.calculate(&input, &mut traj).unwrap();
+    assert_float_eq!(traj.get_duration() % 0.5, 0.0, abs <= 1e-12);
+    input.duration_discretization = DurationDiscretization::Discrete;
+    assert_eq!(
+        calculator.calculate(&input, &mut traj),
+        Err(RuckigError::InvalidDeltaTime { delta_time: 0.0 })
+    );
+
+    // Waypoints are accepted up to the given number
+    input.duration_discretization = DurationDiscretization::Continuous;
+    input.intermediate_positions = vec![daov_stack![0.5, 0.5]].into();
+    assert!(matches!(
+        calculator.calculate(&input, &mut traj),
+        Err(RuckigError::TooManyWaypoints { count: 1, max: 0 })
+    ));
+    let mut calculator = Calculator::<2, ThrowErrorHandler>::new_with_waypoints(1, None);
+    calculator.calculate(&input, &mut traj).unwrap();
+    assert_eq!(traj.get_intermediate_durations().len(), 2);
+}
+
+#[test]
+fn test_segment_planner() {
+    let mut input = InputParameter::new(None);
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![2.0, 2.0];
+    input.max_jerk = daov_stack![4.0, 4.0];
+
+    let mut via = TargetState::new(None);
+    via.position = daov_stack![1.0, 0.0];
+    via.velocity = daov_stack![0.5, 0.5];
+    let mut target = TargetState::new(None);
+    target.position = daov_stack![1.0, 1.0];
+
+    let mut planner = SegmentPlanner::<2, ThrowErrorHandler>::new(None);
+    let mut traj = Trajectory::new(None);
+    let result = planner.plan(&input, &[via.clone(), target.clone()], &mut traj);
+    assert_eq!(result, Ok(RuckigResult::Working));
+    assert_eq!(traj.get_number_of_sections(), 2);
+
+    // The via state is reached exactly at the end of the first section
+    let t_via = traj.get_intermediate_durations()[0];
+    let state = traj.state_at(t_via);
+    assert!(almost_equal_vecs(&state.position, &via.position, 1e-8));
+    assert!(almost_equal_vecs(&state.velocity, &via.velocity, 1e-8));
+    let state = traj.state_at(traj.get_duration());
+    assert!(almost_equal_vecs(&state.position, &target.position, 1e-8));
+
+    // The sections are joined continuously
+    let before = traj.state_at(t_via - 1e-9);
+    let after = traj.state_at(t_via + 1e-9);
+    assert!(almost_equal_vecs(&before.position, &after.position, 1e-6));
+    assert!(almost_equal_vecs(&before.velocity, &after.velocity, 1e-6));
+
+    // Each segment is planned on its own from the end of the previous one
+    let mut second = input.clone();
+    second.current_position = via.position.clone();
+    second.current_velocity = via.velocity.clone();
+    second.target_position = target.position.clone();
+    let mut calculator = Calculator::<2, ThrowErrorHandler>::new(None);
+    let mut expected = Trajectory::new(None);
+    calculator.calculate(&second, &mut expected).unwrap();
+    assert_float_eq!(
+        traj.get_duration() - t_via,
+        expected.get_duration(),
+        abs <= 1e-8
+    );
+
+    assert!(planner.plan(&input, &[], &mut traj).is_err());
+}
+
+#[test]
+fn test_calculate_batch() {
+    let mut otg = Ruckig::<1, ThrowErrorHandler>::new(None, 0.01);
+    let mut inputs = Vec::new();
+    for target in [1.0, 2.0, f64::NAN, 3.0] {
+        let mut input = InputParameter::new(None);
+        input.target_position = daov_stack![target];
+        input.max_velocity = daov_stack![1.0];
+        input.max_acceleration = daov_stack![1.0];
+        input.max_jerk = daov_stack![1.0];
+        inputs.push(input);
+    }
+    let mut trajectories = vec![Trajectory::new(None); 4];
+    let mut results = vec![Ok(RuckigResult::Finished); 4];
+
+    otg.calculate_batch(&inputs, &mut trajectories, &mut results);
+    assert!(results[2].is_err());
+    for i in [0, 1, 3] {
+        assert_eq!(results[i], Ok(RuckigResult::Working));
+        let mut reference = Trajectory::new(None);
+        otg.calculate(&inputs[i], &mut reference).unwrap();
+        assert_eq!(trajectories[i].get_duration(), reference.get_duration());
+    }
+    assert!(trajectories[0].get_duration() < trajectories[1].get_duration());
+}
+
+#[test]
+fn test_calculate_batch_parallel() {
+    let otg = Ruckig::<0, ThrowErrorHandler>::new(Some(2), 0.01);
+    let mut inputs = Vec::new();
+    for i in 0..256 {
+        let x = i as f64;
+        let mut input = InputParameter::new(Some(2));
+        input.current_position = daov_heap![(0.3 * x).sin(), (0.7 * x).cos()];
+        input.target_position = daov_heap![2.0 * (1.1 * x).cos(), -(0.5 * x).sin()];
+        input.max_velocity = daov_heap![1.0, 1.0];
+        input.max_acceleration = daov_heap![1.0, 1.0];
+        input.max_jerk = daov_heap![1.0, 1.0];
+        inputs.push(input);
+    }
+    let mut trajectories = vec![Trajectory::new(Some(2)); inputs.len()];
+    let mut reference = trajectories.clone();
+    let mut results = vec![Ok(RuckigResult::Finished); inputs.len()];
+    let mut reference_results = results.clone();
+
+    otg.calculate_batch_parallel(&inputs, &mut trajectories, &mut results);
+    let mut sequential = Ruckig::<0, ThrowErrorHandler>::new(Some(2), 0.01);
+    sequential.calculate_batch(&inputs, &mut reference, &mut reference_results);
+    assert_eq!(results, reference_results);
+    for (traj, reference) in trajectories.iter().zip(&reference) {
+        assert_eq!(traj.get_duration(), reference.get_duration());
+    }
+}
+
+#[test]
+fn test_background_calculation() {
+    let mut otg = Ruckig::<1, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    input.target_position = daov_stack![3.0];
+    input.max_velocity = daov_stack![1.0];
+    input.max_acceleration = daov_stack![1.0];
+    input.max_jerk = daov_stack![1.0];
+
+    for _ in 0..50 {
+        otg.update(&input, &mut output).unwrap();
+        output.pass_to_input(&mut input);
+    }
+
+    let mut new_input = input.clone();
+    new_input.target_position = daov_stack![-1.0];
+    let mut calculation = otg.calculate_in_background(&new_input, &output, 0.2);
+    let start_time = calculation.get_start_time();
+    let start_state = output.trajectory.state_at(start_time);
+    while !calculation.is_finished() {
+        std::thread::yield_now();
+    }
+
+    // The current trajectory continues until the start of the new one
+    let switch = loop {
+        if let Some(result) = otg.switch_to_background(&mut calculation, &mut output) {
+            break result;
+        }
+        assert_eq!(otg.update(&input, &mut output), Ok(RuckigResult::Working));
+        output.pass_to_input(&mut input);
+    };
+    assert_eq!(switch, Ok(RuckigResult::Working));
+    assert!(output.time <= 0.0);
+
+    input.target_position = daov_stack![-1.0];
+    let time = output.time;
+    otg.update(&input, &mut output).unwrap();
+    assert!(!output.new_calculation);
+    assert_float_eq!(output.time, time + 0.01, abs <= 1e-12);
+    output.pass_to_input(&mut input);
+    let first_state = output.trajectory.state_at(0.0);
+    assert_float_eq!(
+        first_state.position[0],
+        start_state.position[0],
+        abs <= 1e-12
+    );
+    assert_float_eq!(
+        first_state.velocity[0],
+        start_state.velocity[0],
+        abs <= 1e-12
+    );
+
+    while otg.update(&input, &mut output).unwrap() == RuckigResult::Working {
+        assert!(!output.new_calculation);
+        output.pass_to_input(&mut input);
+    }
+    assert_float_eq!(output.new_position[0], -1.0, abs <= 1e-8);
+
+    // A calculation that finishes after the start of its trajectory is rejected
+    input.target_position = daov_stack![0.5];
+    for _ in 0..10 {
+        otg.update(&input, &mut output).unwrap();
+        output.pass_to_input(&mut input);
+    }
+    let mut calculation = otg.calculate_in_background(&input, &output, 0.0);
+    while !calculation.is_finished() {
+        std::thread::yield_now();
+    }
+    otg.update(&input, &mut output).unwrap();
+    assert!(matches!(
+        otg.switch_to_background(&mut calculation, &mut output),
+        Some(Err(RuckigError::LateBackgroundCalculation { .. }))
+    ));
+}
+
+#[test]
+fn test_replan_tolerance() {
+    let settings = Settings {
+        replan_tolerance: 1e-3,
+        ..Default::default()
+    };
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::with_settings(None, 0.01, settings);
+    let mut input = InputParameter::new(None);
+    let mut output = OutputParameter::new(None);
+    input.target_position = daov_stack![1.0, -0.5];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+
+    otg.update(&input, &mut output).unwrap();
+    assert!(output.new_calculation);
+    output.pass_to_input(&mut input);
+
+    // Noise of the target within the tolerance keeps the trajectory
+    let mut calculations = 0;
+    for cycle in 0..50 {
+        let noise = if cycle % 2 == 0 { 5e-4 } else { -5e-4 };
+        input.target_position = daov_stack![1.0 + noise, -0.5 - noise];
+        otg.update(&input, &mut output).unwrap();
+        output.pass_to_input(&mut input);
+        if output.new_calculation {
+            calculations += 1;
+        }
+    }
+    assert_eq!(calculations, 0);
+
+    input.target_position = daov_stack![1.0, -0.49];
+    otg.update(&input, &mut output).unwrap();
+    assert!(output.new_calculation);
+}
+
+#[test]
+fn test_partial_recalculation() {
+    let mut otg = Ruckig::<3, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    input.current_position = daov_stack![0.0, 0.5, -1.0];
+    input.current_velocity = daov_stack![0.2, 0.0, -0.1];
+    input.target_position = daov_stack![1.0, -0.5, 2.0];
+    input.max_velocity = daov_stack![1.0, 1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0, 1.0];
+
+    let mut trajectory = Trajectory::new(None);
+    otg.calculate(&input, &mut trajectory).unwrap();
+
+    // Only the targets of some DoFs change, so that the blocks of the others are reused
+    for target in [[1.5, -0.5, 2.0], [1.5, -0.5, 4.0], [-3.0, -0.5, 4.0]] {
+        input.target_position = daov_stack![target[0], target[1], target[2]];
+        otg.calculate(&input, &mut trajectory).unwrap();
+
+        let mut reference = Trajectory::new(None);
+        Ruckig::<3, ThrowErrorHandler>::new(None, 0.01)
+            .calculate(&input, &mut reference)
+            .unwrap();
+        assert_eq!(trajectory.get_duration(), reference.get_duration());
+        assert_eq!(
+            trajectory.get_independent_min_durations(),
+            reference.get_independent_min_durations()
+        );
+        for i in 0..=10 {
+            let time = 0.1 * i as f64 * trajectory.get_duration();
+            assert_eq!(
+                trajectory.state_at(time).position,
+                reference.state_at(time).position
+            );
+        }
+    }
+}
+
+#[test]
+fn test_guess_step2_profile() {
+    let settings = Settings {
+        guess_step2_profile: true,
+        ..Default::default()
+    };
+    let mut otg = Ruckig::<3, ThrowErrorHandler>::with_settings(None, 0.01, settings);
+    let mut reference_otg = Ruckig::<3, ThrowErrorHandler>::new(None, 0.01);
+    for i in 0..64 {
+        let x = i as f64;
+        let mut input = InputParameter::new(None);
+        input.current_position = daov_stack![(0.3 * x).sin(), (0.7 * x).cos(), 0.0];
+        input.current_velocity = daov_stack![0.0, 0.2 * (0.9 * x).sin(), 0.0];
+        input.target_position = daov_stack![2.0 * (1.1 * x).cos(), -(0.5 * x).sin(), 0.5];
+        input.target_velocity = daov_stack![0.0, 0.0, 0.3 * (1.3 * x).cos()];
+        input.max_velocity = daov_stack![1.0, 1.0, 1.0];
+        input.max_acceleration = daov_stack![1.0, 1.0, 1.0];
+        input.max_jerk = daov_stack![1.0, 1.0, 1.0];
+
+        let mut trajectory = Trajectory::new(None);
+        let mut reference = Trajectory::new(None);
+        otg.calculate(&input, &mut trajectory).unwrap();
+        reference_otg.calculate(&input, &mut reference).unwrap();
+        assert_float_eq!(
+            trajectory.get_duration(),
+            reference.get_duration(),
+            abs <= 1e-12
+        );
+
+        let state = trajectory.state_at(trajectory.get_duration());
+        assert!(almost_equal_vecs(
+            &state.position,
+            &input.target_position,
+            1e-8
+        ));
+        assert!(almost_equal_vecs(
+            &state.velocity,
+            &input.target_velocity,
+            1e-8
+        ));
+    }
+}
+
+#[test]
+fn test_step2_retries() {
+    let mut input = InputParameter::new(None);
+    input.current_position = daov_stack![-3.301987736751848, -3.5605809447861225, 5.24593325765869];
+    input.current_velocity =
+        daov_stack![-0.34255455221833014, 0.8597163426032728, -0.7817770379023221];
+    input.current_acceleration = daov_stack![0.0, 0.48149696110429874, 0.0];
+    input.target_position = daov_stack![0.7526922116300216, 0.754685737558853, 6.871903050933584];
+    input.target_velocity =
+        daov_stack![-1.5948448285832009, 1.2252290128942516, 2.0201204175607836];
+    input.target_acceleration = daov_stack![0.0, -0.7321505422130645, 0.0];
+    input.max_velocity = daov_stack![3.3624117413154284, 1.397356771608705, 13.514726797084435];
+    input.max_acceleration =
+        daov_stack![0.20597846330426733, 10.92816510639488, 0.0014934370775338372];
+    input.max_jerk = daov_stack![0.917510691211276, 491.1063397916451, 0.10397268047784453];
+    let mut traj = Trajectory::new(None);
+
+    // Without retries, the synchronized profile is missed due to the numerical precision
+    let settings = Settings {
+        step2_retries: 0,
+        ..Default::default()
+    };
+    let mut otg = Ruckig::<3, ThrowErrorHandler>::with_settings(None, 0.01, settings);
+    assert!(matches!(
+        otg.calculate(&input, &mut traj),
+        Err(RuckigError::Step2Failure { .. })
+    ));
+
+    let mut otg = Ruckig::<3, ThrowErrorHandler>::new(None, 0.01);
+    assert_eq!(otg.calculate(&input, &mut traj), Ok(RuckigResult::Working));
+    assert_float_eq!(traj.get_duration(), 2879.3576179492, abs <= 1e-6);
+    assert!(traj.check_limits(&input, 1.0).is_empty());
+    let state = traj.state_at(traj.get_duration());
+    assert!(almost_equal_vecs(
+        &state.position,
+        &input.target_position,
+        1e-8
+    ));
+    assert!(almost_equal_vecs(
+        &state.velocity,
+        &input.target_velocity,
+        1e-8
+    ));
+}
+
+#[test]
+fn test_fallback_tolerances() {
+    let mut input = InputParameter::new(None);
+    input.current_position = daov_stack![-1.8319379099947664, 5.459334970202058, 6.93871041018365];
+    input.current_velocity = daov_stack![0.0, 0.0, -0.22888237126144367];
+    input.current_acceleration = daov_stack![-0.09246750056675801, 0.0, 0.0];
+    input.target_position =
+        daov_stack![3.323442915974382, -1.8147754310121949, -2.472281270012791];
+    input.target_velocity = daov_stack![-0.09936433675984702, 0.0, 0.5657544436651166];
+    input.target_acceleration = daov_stack![-0.9558598912820774, 0.0, -0.5590647816897713];
+    input.max_velocity = daov_stack![4.487433952727638, 0.0010822516616480825, 0.9557449331560646];
+    input.max_acceleration =
+        daov_stack![19.689950214713235, 0.08140973498376022, 10.411018828746334];
+    input.max_jerk = daov_stack![82.60686025945736, 0.02429121043679852, 365.0798693054145];
+    let mut traj = Trajectory::new(None);
+
+    // Not even the retries of step 2 find a profile with the default tolerances
+    let mut otg = Ruckig::<3, ThrowErrorHandler>::new(None, 0.01);
+    assert!(matches!(
+        otg.calculate(&input, &mut traj),
+        Err(RuckigError::Step2Failure { dof: 2, .. })
+    ));
+    assert!(otg.calculator.loosened_tolerances.is_empty());
+
+    let settings = Settings {
+        fallback_tolerances: vec![1e-9, 1e-6],
+        ..Default::default()
+    };
+    let mut otg = Ruckig::<3, ThrowErrorHandler>::with_settings(None, 0.01, settings);
+    assert_eq!(otg.calculate(&input, &mut traj), Ok(RuckigResult::Working));
+    assert_eq!(
+        otg.calculator.loosened_tolerances,
+        [LoosenedTolerance {
+            dof: 2,
+            tolerance: 1e-6
+        }]
+    );
+    let state = traj.state_at(traj.get_duration());
+    assert!(almost_equal_vecs(
+        &state.position,
+        &input.target_position,
+        1e-6
+    ));
+    assert!(almost_equal_vecs(
+        &state.velocity,
+        &input.target_velocity,
+        1e-6
+    ));
+
+    // Profiles found with the default tolerances aren't reported
+    input.target_velocity = daov_stack![0.0, 0.0, 0.0];
+    input.target_acceleration = daov_stack![0.0, 0.0, 0.0];
+    assert_eq!(otg.calculate(&input, &mut traj), Ok(RuckigResult::Working));
+    assert!(otg.calculator.loosened_tolerances.is_empty());
+}
+
+#[test]
+fn test_synchronization_report() {
+    let mut otg = Ruckig::<3, ThrowErrorHandler>::new(None, 0.005);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.current_velocity = daov_stack![0.2, 0.0, -0.1];
+    input.target_position = daov_stack![1.0, -0.2, -0.5];
+    input.max_velocity = daov_stack![1.0, 1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0, 1.0];
+    let report = |traj: &Trajectory<3>| {
+        traj.get_synchronizations()
+            .iter()
+            .map(|s| (s.synchronization.clone(), s.is_limiting))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(otg.calculate(&input, &mut traj), Ok(RuckigResult::Working));
+    assert_eq!(
+        report(&traj),
+        [
+            (Synchronization::Time, true),
+            (Synchronization::Time, false),
+            (Synchronization::Time, false),
+        ]
+    );
+
+    input.per_dof_synchronization = Some(daov_stack![
+        Synchronization::Phase,
+        Synchronization::None,
+        Synchronization::Phase,
+    ]);
+    assert_eq!(otg.calculate(&input, &mut traj), Ok(RuckigResult::Working));
+    assert_eq!(
+        report(&traj),
+        [
+            (Synchronization::Phase, true),
+            (Synchronization::None, false),
+            (Synchronization::Phase, false),
+        ]
+    );
+
+    // Without a target velocity, the DoFs keep their time-optimal profiles
+    input.per_dof_synchronization = None;
+    input.synchronization = Synchronization::TimeIfNecessary;
+    input.enabled = daov_stack![true, false, true];
+    assert_eq!(otg.calculate(&input, &mut traj), Ok(RuckigResult::Working));
+    assert_eq!(
+        report(&traj),
+        [
+            (Synchronization::Time, true),
+            (Synchronization::None, false),
+            (Synchronization::TimeIfNecessary, false),
+        ]
+    );
+}
+
+#[test]
+fn test_compensated_sum() {
+    let mut sum = CompensatedSum::new(1.0);
+    for value in [1e100, 1.0, -1e100] {
+        sum.add(value);
+    }
+    assert_eq!(sum.value(), 2.0);
+
+    let mut sum = CompensatedSum::default();
+    for _ in 0..1_000_000 {
+        sum.add(0.1);
+    }
+    assert_float_eq!(sum.value(), 1e5, ulps <= 1);
+
+    // Long trajectory through many sections still reaches its target state precisely
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new_with_waypoints(100, None, 0.01);
+    let mut input = InputParameter::new(None);
+    for i in 0..99 {
+        let x = i as f64;
+        input
+            .intermediate_positions
+            .push_back(daov_stack![70.3 * x + 0.1 * x.sin(), -33.1 * x]);
+    }
+    input.target_position = daov_stack![6959.823456, -3276.9];
+    input.max_velocity = daov_stack![3.0, 3.0];
+    input.max_acceleration = daov_stack![3.0, 3.0];
+    input.max_jerk = daov_stack![3.0, 3.0];
+
+    let mut trajectory = Trajectory::new_with_waypoints(100, None);
+    otg.calculate(&input, &mut trajectory).unwrap();
+    let state = trajectory.state_at(trajectory.get_duration());
+    assert!(almost_equal_vecs(
+        &state.position,
+        &input.target_position,
+        1e-8
+    ));
+    assert_eq!(
+        trajectory.get_duration(),
+        *trajectory.cumulative_times.last().unwrap()
+    );
+}
+
+#[test]
+fn test_refine_root() {
+    // Quartic with a large spread of its roots, whose small root is ill-conditioned
+    let expected = [1e-4, 2.0, 3.0, 1e4];
+    let [r0, r1, r2, r3] = expected;
+    let coeffs = [
+        1.0,
+        -(r0 + r1 + r2 + r3),
+        r0 * r1 + r0 * r2 + r0 * r3 + r1 * r2 + r1 * r3 + r2 * r3,
+        -(r0 * r1 * r2 + r0 * r1 * r3 + r0 * r2 * r3 + r1 * r2 * r3),
+        r0 * r1 * r2 * r3,
+    ];
+
+    let roots = roots::solve_quart_monic_coeffs(coeffs[1], coeffs[2], coeffs[3], coeffs[4]);
+    assert_eq!(roots.get_data().len(), 4);
+    for &root in roots.get_data() {
+        let refined = roots::refine_root(&coeffs, root);
+        let (residual, _) = roots::poly_eval_compensated(&coeffs, root);
+        let (refined_residual, _) = roots::poly_eval_compensated(&coeffs, refined);
+        assert!(refined_residual.abs() <= residual.abs());
+        assert!(expected
+            .iter()
+            .any(|expected| (refined - expected).abs() <= 1e-12 * expected));
+    }
+}
+
+#[test]
+fn test_roots_api() {
+    // (x - 0.5) (x - 2) (x + 1) = x^3 - 1.5 x^2 - 1.5 x + 1, the negative root is skipped
+    let cubic: Vec<f64> = roots::solve_cub(1.0, -1.5, -1.5, 1.0).into_iter().collect();
+    assert_eq!(cubic.len(), 2);
+    assert_float_eq!(cubic[0], 0.5, abs <= 1e-12);
+    assert_float_eq!(cubic[1], 2.0, abs <= 1e-12);
+
+    // (x - 1) (x - 3) (x^2 + 1) = x^4 - 4 x^3 + 4 x^2 - 4 x + 3
+    let quartic: Vec<f64> = roots::solve_quart_monic_coeffs(-4.0, 4.0, -4.0, 3.0)
+        .into_iter()
+        .collect();
+    assert_eq!(quartic.len(), 2);
+    assert_float_eq!(quartic[0], 1.0, abs <= 1e-12);
+    assert_float_eq!(quartic[1], 3.0, abs <= 1e-12);
+
+    // Quintic x^5 - 2 with its root bracketed by [1, 2]
+    let quintic: roots::ArrayVec<f64, 6> = [1.0, 0.0, 0.0, 0.0, 0.0, -2.0].into_iter().collect();
+    assert_float_eq!(roots::poly_eval(&quintic, 2.0), 30.0, abs <= 1e-12);
+    let derivative = roots::poly_deri(&quintic);
+    assert_eq!(derivative.as_slice(), &[5.0, 0.0, 0.0, 0.0, 0.0]);
+    let root = roots::shrink_interval_default(&quintic, 1.0, 2.0);
+    assert_float_eq!(root, 2.0_f64.powf(0.2), abs <= 1e-12);
+}
+
+#[test]
+fn test_solve_poly() {
+    // (x + 5) (x - 1) (x - 2) (x - 3) (x - 4) = x^5 - 5 x^4 - 15 x^3 + 125 x^2 - 226 x + 120
+    let quintic: roots::ArrayVec<f64, 6> = [1.0, -5.0, -15.0, 125.0, -226.0, 120.0]
+        .into_iter()
+        .collect();
+    let found = roots::solve_poly(&quintic, 0.0, 10.0);
+    assert_eq!(found.len(), 4);
+    for (root, expected) in found.iter().zip([1.0, 2.0, 3.0, 4.0]) {
+        assert_float_eq!(*root, expected, abs <= 1e-10);
+    }
+    assert_eq!(roots::solve_poly(&quintic, -10.0, 10.0).len(), 5);
+    assert_eq!(roots::solve_poly(&quintic, 4.5, 10.0).len(), 0);
+
+    // Leading zeros lower the degree, and a root at the bound is found once
+    let sextic: roots::ArrayVec<f64, 7> =
+        [0.0, 0.0, 0.0, 1.0, 0.0, -1.0, 0.0].into_iter().collect();
+    let found = roots::solve_poly(&sextic, 0.0, 2.0);
+    assert_eq!(found.as_slice(), &[0.0, 1.0]);
+
+    let constant: roots::ArrayVec<f64, 3> = [0.0, 0.0, 1.0].into_iter().collect();
+    assert!(roots::solve_poly(&constant, -1.0, 1.0).is_empty());
+}
+
+#[test]
+fn test_shrink_interval_bounds() {
+    // A root next to the bound stays within the interval, although Newton steps overshoot it by
+    // rounding
+    let quartic: roots::ArrayVec<f64, 5> = [
+        -514542.0697653127,
+        0.0,
+        0.0,
+        -6.161076693014314e-4,
+        1.9762451740072793e-131,
+    ]
+    .into_iter()
+    .collect();
+    let found = roots::solve_poly(&quartic, 0.0, 0.0030518509475997192);
+    assert_eq!(found.len(), 1);
+    assert!((0.0..1e-12).contains(&found[0]));
+}
+
+#[test]
+fn test_data_array_or_vec_slices() {
+    fn sum(values: impl AsRef<[f64]>) -> f64 {
+        values.as_ref().iter().sum()
+    }
+
+    fn scale(mut values: impl AsMut<[f64]>) {
+        values.as_mut().iter_mut().for_each(|x| *x *= 2.0);
+    }
+
+    let mut stack: DataArrayOrVec<f64, 3> = daov_stack![1.0, 2.0, 3.0];
+    let mut heap: DataArrayOrVec<f64, 0> = daov_heap![1.0, 2.0, 3.0];
+    assert_eq!(sum(&stack), 6.0);
+    assert_eq!(sum(&heap), 6.0);
+
+    scale(&mut stack);
+    scale(&mut heap);
+    assert_eq!(&*stack, &[2.0, 4.0, 6.0]);
+    assert_eq!(heap.as_ref(), &[2.0, 4.0, 6.0]);
+    assert_eq!(stack.len(), heap.len());
+    assert_eq!(
+        stack.iter().rev().copied().collect::<Vec<_>>(),
+        vec![6.0, 4.0, 2.0]
+    );
+}
+
+#[test]
+fn test_data_array_or_vec_iterators() {
+    let mut stack: DataArrayOrVec<f64, 3> = daov_stack![1.0, 2.0, 3.0];
+    let mut heap: DataArrayOrVec<f64, 0> = daov_heap![1.0, 2.0, 3.0];
+
+    for (x, y) in (&mut stack).into_iter().zip(&mut heap) {
+        *x += 1.0;
+        *y += 1.0;
+    }
+    assert_eq!((&stack).into_iter().len(), 3);
+    assert_eq!(stack, daov_stack![2.0, 3.0, 4.0]);
+    assert_eq!(heap, daov_heap![2.0, 3.0, 4.0]);
+
+    let iter = stack.clone().into_iter();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.rev().collect::<Vec<_>>(), vec![4.0, 3.0, 2.0]);
+    assert_eq!(heap.clone().into_iter().size_hint(), (3, Some(3)));
+
+    let doubled: DataArrayOrVec<f64, 3> = stack.into_iter().map(|x| 2.0 * x).collect();
+    assert_eq!(doubled, daov_stack![4.0, 6.0, 8.0]);
+    let doubled: DataArrayOrVec<f64, 0> = heap.into_iter().map(|x| 2.0 * x).collect();
+    assert_eq!(doubled, daov_heap![4.0, 6.0, 8.0]);
+
+    let result =
+        std::panic::catch_unwind(|| (0..2).map(f64::from).collect::<DataArrayOrVec<f64, 3>>());
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_data_array_or_vec_from_vec() {
+    let stack = DataArrayOrVec::<f64, 3>::from_vec(vec![1.0, 2.0, 3.0]).unwrap();
+    assert_eq!(stack, daov_stack![1.0, 2.0, 3.0]);
+    assert!(matches!(
+        DataArrayOrVec::<f64, 3>::from_vec(vec![1.0, 2.0]),
+        Err(RuckigError::DofMismatch)
+    ));
+    assert!(matches!(
+        DataArrayOrVec::<f64, 3>::try_from(vec![0.0; 4]),
+        Err(RuckigError::DofMismatch)
+    ));
+
+    let heap: DataArrayOrVec<f64, 0> = vec![1.0, 2.0].try_into().unwrap();
+    assert_eq!(heap, daov_heap![1.0, 2.0]);
+}
+
+#[test]
+fn test_data_array_or_vec_math() {
+    // Convert limits given in degrees on the motor side by a gear ratio
+    let mut max_velocity = daov_stack![90.0, 180.0, 45.0];
+    max_velocity.scale(std::f64::consts::PI / 180.0 / 2.0);
+    assert_float_eq!(max_velocity[1], std::f64::consts::FRAC_PI_2, abs <= 1e-15);
+
+    let mut position: DataArrayOrVec<f64, 0> = daov_heap![1.0, -2.0, 3.0];
+    position.add_assign(&daov_heap![0.5, 0.5, 0.5]);
+    assert_eq!(position, daov_heap![1.5, -1.5, 3.5]);
+
+    position.clamp_to(&daov_heap![-1.0, -1.0, -1.0], &daov_heap![1.0, 1.0, 2.0]);
+    assert_eq!(position, daov_heap![1.0, -1.0, 2.0]);
+
+    let vector: DataArrayOrVec<f64, 2> = daov_stack![3.0, -4.0];
+    assert_eq!(vector.norm(), 5.0);
+    assert_eq!(vector.max_abs(), 4.0);
+    assert_eq!(DataArrayOrVec::<f64, 0>::default().max_abs(), 0.0);
+}
+
+#[test]
+fn test_slice_setters() {
+    let mut input = InputParameter::<0>::new(Some(3));
+    input
+        .set_state(&[0.0, 1.0, 2.0], &[0.1, 0.2, 0.3], &[0.0; 3])
+        .unwrap();
+    input.set_target_position(&[1.0, -1.0, 0.5]).unwrap();
+    input.set_max_velocity(&[1.0; 3]).unwrap();
+    input.set_max_acceleration(&[2.0; 3]).unwrap();
+    input.set_max_jerk(&[3.0; 3]).unwrap();
+    assert_eq!(input.current_position, daov_heap![0.0, 1.0, 2.0]);
+    assert_eq!(input.current_velocity, daov_heap![0.1, 0.2, 0.3]);
+    assert_eq!(input.max_jerk, daov_heap![3.0, 3.0, 3.0]);
+
+    // A mismatched length is rejected and leaves the input unchanged
+    assert_eq!(
+        input.set_target_velocity(&[0.0; 2]),
+        Err(RuckigError::DofMismatch)
+    );
+    assert_eq!(
+        input.set_state(&[5.0; 3], &[0.0; 3], &[0.0; 4]),
+        Err(RuckigError::DofMismatch)
+    );
+    assert_eq!(input.current_position, daov_heap![0.0, 1.0, 2.0]);
+    assert_eq!(input.target_velocity.len(), 3);
+
+    let mut otg = Ruckig::<0, ThrowErrorHandler>::new(Some(3), 0.01);
+    let mut traj = Trajectory::new(Some(3));
+    assert_eq!(otg.calculate(&input, &mut traj), Ok(RuckigResult::Working));
+
+    let mut input = InputParameter::<3>::new(None);
+    input.set_current_position(&[1.0, 2.0, 3.0]).unwrap();
+    assert_eq!(input.current_position, daov_stack![1.0, 2.0, 3.0]);
+    assert!(input.set_max_velocity(&[1.0; 4]).is_err());
+}
+
+#[test]
+fn test_ndarray() {
+    let position = ndarray::array![0.0, 1.0, -1.0];
+    let mut input = InputParameter::<3>::new(None);
+    input.current_position = position.view().try_into().unwrap();
+    input.target_position = ndarray::array![1.0, -1.0, 2.0].view().try_into().unwrap();
+    input.max_velocity = daov_stack![1.0, 1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0, 1.0];
+    assert_eq!(input.current_position, daov_stack![0.0, 1.0, -1.0]);
+
+    let too_short = ndarray::array![1.0, 2.0];
+    assert_eq!(
+        DataArrayOrVec::<f64, 3>::try_from(too_short.view()),
+        Err(RuckigError::DofMismatch)
+    );
+    let heap: DataArrayOrVec<f64, 0> = too_short.view().try_into().unwrap();
+    assert_eq!(heap, daov_heap![1.0, 2.0]);
+
+    let mut otg = Ruckig::<3, ThrowErrorHandler>::new(None, 0.01);
+    let mut traj = Trajectory::new(None);
+    otg.calculate(&input, &mut traj).unwrap();
+
+    let array = traj.sample_to_array(0.01);
+    let sampled = SampledTrajectory::from_trajectory(&traj, 0.01);
+    assert_eq!(array.dim(), (sampled.len(), 10));
+    for (index, row) in array.rows().into_iter().enumerate() {
+        assert_eq!(row[0], sampled.times[index]);
+        assert_eq!(
+            row.slice(ndarray::s![1..4]).to_vec(),
+            sampled.position(index)
+        );
+        assert_eq!(
+            row.slice(ndarray::s![4..7]).to_vec(),
+            sampled.velocity(index)
+        );
+        assert_eq!(
+            row.slice(ndarray::s![7..10]).to_vec(),
+            sampled.acceleration(index)
+        );
+    }
+    assert_eq!(array[[array.nrows() - 1, 0]], traj.get_duration());
+    assert_float_eq!(array[[array.nrows() - 1, 3]], 2.0, abs <= 1e-12);
+}
+
+#[test]
+fn test_units() {
+    use uom::si::acceleration::millimeter_per_second_squared;
+    use uom::si::angle::{degree, radian};
+    use uom::si::angular_acceleration::degree_per_second_squared;
+    use uom::si::angular_jerk::degree_per_second_cubed;
+    use uom::si::angular_velocity::degree_per_second;
+    use uom::si::f64::*;
+    use uom::si::jerk::millimeter_per_second_cubed;
+    use uom::si::length::{meter, millimeter};
+    use uom::si::velocity::millimeter_per_second;
+
+    // A linear rail in millimeters and a rotary axis in degrees
+    let mut input = InputParameter::<2>::new(None);
+    input.set_linear_state(
+        0,
+        Length::new::<millimeter>(100.0),
+        Velocity::new::<millimeter_per_second>(0.0),
+        Acceleration::new::<millimeter_per_second_squared>(0.0),
+    );
+    input.set_linear_target(
+        0,
+        Length::new::<millimeter>(600.0),
+        Velocity::new::<millimeter_per_second>(0.0),
+        Acceleration::new::<millimeter_per_second_squared>(0.0),
+    );
+    input.set_linear_limits(
+        0,
+        Velocity::new::<millimeter_per_second>(500.0),
+        Acceleration::new::<millimeter_per_second_squared>(1000.0),
+        Jerk::new::<millimeter_per_second_cubed>(5000.0),
+    );
+    input.set_angular_state(
+        1,
+        Angle::new::<degree>(0.0),
+        AngularVelocity::new::<degree_per_second>(0.0),
+        AngularAcceleration::new::<degree_per_second_squared>(0.0),
+    );
+    input.set_angular_target(
+        1,
+        Angle::new::<degree>(90.0),
+        AngularVelocity::new::<degree_per_second>(0.0),
+        AngularAcceleration::new::<degree_per_second_squared>(0.0),
+    );
+    input.set_angular_limits(
+        1,
+        AngularVelocity::new::<degree_per_second>(90.0),
+        AngularAcceleration::new::<degree_per_second_squared>(180.0),
+        AngularJerk::new::<degree_per_second_cubed>(720.0),
+    );
+    assert_float_eq!(input.current_position[0], 0.1, abs <= 1e-15);
+    assert_float_eq!(input.target_position[0], 0.6, abs <= 1e-15);
+    assert_float_eq!(
+        input.target_position[1],
+        std::f64::consts::FRAC_PI_2,
+        abs <= 1e-15
+    );
+    assert_float_eq!(
+        input.max_velocity[1],
+        std::f64::consts::FRAC_PI_2,
+        abs <= 1e-15
+    );
+
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut output = OutputParameter::new(None);
+    while otg.update(&input, &mut output).unwrap() == RuckigResult::Working {
+        output.pass_to_input(&mut input);
+    }
+    let (position, velocity, _) = output.linear_state(0);
+    assert_float_eq!(position.get::<meter>(), 0.6, abs <= 1e-12);
+    assert_float_eq!(velocity.value, 0.0, abs <= 1e-12);
+    let (angle, _, _) = output.angular_state(1);
+    assert_float_eq!(angle.get::<degree>(), 90.0, abs <= 1e-10);
+    assert_float_eq!(
+        angle.get::<radian>(),
+        std::f64::consts::FRAC_PI_2,
+        abs <= 1e-12
+    );
+}
+
+#[test]
+fn test_duration_api() {
+    use rsruckig::util::duration_from_secs;
+    use std::time::Duration;
+
+    let mut otg = Ruckig::<1, ThrowErrorHandler>::new_with_cycle(None, Duration::from_millis(4));
+    assert_eq!(otg.delta_time, 0.004);
+    assert_eq!(otg.get_cycle(), Duration::from_millis(4));
+    otg.set_cycle(Duration::from_millis(1));
+    assert_eq!(otg.delta_time, 0.001);
+
+    let mut input = InputParameter::new(None);
+    input.target_position = daov_stack![1.0];
+    input.max_velocity = daov_stack![1.0];
+    input.max_acceleration = daov_stack![1.0];
+    input.max_jerk = daov_stack![1.0];
+
+    let mut traj = Trajectory::new(None);
+    otg.calculate(&input, &mut traj).unwrap();
+    assert_float_eq!(
+        traj.duration().as_secs_f64(),
+        traj.get_duration(),
+        abs <= 1e-9
+    );
+
+    let half = Duration::from_secs_f64(0.5 * traj.get_duration());
+    assert_eq!(
+        traj.state_at_duration(half),
+        traj.state_at(half.as_secs_f64())
+    );
+    let mut position = daov_stack![0.0];
+    traj.at_duration(
+        traj.duration(),
+        &mut Some(&mut position),
+        &mut None,
+        &mut None,
+        &mut None,
+        &mut None,
+    );
+    assert_float_eq!(position[0], 1.0, abs <= 1e-12);
+
+    let mut output = OutputParameter::new(None);
+    otg.update_with_duration(&input, Duration::from_millis(10), &mut output)
+        .unwrap();
+    assert_float_eq!(output.time, 0.01, abs <= 1e-15);
+
+    assert_eq!(duration_from_secs(-1.0), Duration::ZERO);
+    assert_eq!(duration_from_secs(f64::NAN), Duration::ZERO);
+    assert_eq!(duration_from_secs(f64::INFINITY), Duration::MAX);
+}
+
+#[test]
+fn test_plot() {
+    let mut input = InputParameter::new(None);
+    input.current_velocity = daov_stack![0.5, -0.2];
+    input.target_position = daov_stack![1.0, -1.0];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut traj = Trajectory::new(None);
+    otg.calculate(&input, &mut traj).unwrap();
+
+    let directory = std::env::temp_dir().join(format!("rsruckig_plot_{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    let svg = directory.join("trajectory.svg");
+    let png = directory.join("trajectory.png");
+    traj.plot(&svg, 0.01).unwrap();
+    traj.plot(&png, 0.01).unwrap();
+
+    let content = std::fs::read_to_string(&svg).unwrap();
+    assert!(content.starts_with("<svg"));
+    assert!(content.contains("acceleration") && content.contains("DoF 1"));
+    assert!(std::fs::read(&png).unwrap().starts_with(b"\x89PNG"));
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_resample() {
+    let mut otg = Ruckig::<3, ThrowErrorHandler>::new(None, 0.01);
+    let mut input = InputParameter::new(None);
+    let mut traj = Trajectory::new(None);
+    input.target_position = daov_stack![1.0, -0.5, 0.2];
+    input.target_velocity = daov_stack![0.0, 0.1, 0.0];
+    input.max_velocity = daov_stack![1.0, 1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 2.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0, 1.0];
+    otg.calculate(&input, &mut traj).unwrap();
+
+    let sampled = traj.resample(0.03);
+    assert_eq!(sampled, SampledTrajectory::from_trajectory(&traj, 0.03));
+    assert_eq!(sampled.times[1], 0.03);
+    assert_eq!(*sampled.times.last().unwrap(), traj.get_duration());
+    assert!(sampled.times[sampled.len() - 1] - sampled.times[sampled.len() - 2] <= 0.03);
+
+    for dof in 0..3 {
+        let positions = sampled.dof_positions(dof);
+        let velocities = sampled.dof_velocities(dof);
+        let accelerations = sampled.dof_accelerations(dof);
+        assert_eq!(positions.len(), sampled.len());
+        for (index, &time) in sampled.times.iter().enumerate() {
+            let state = traj.state_at(time);
+            assert_eq!(positions[index], state.position[dof]);
+            assert_eq!(velocities[index], state.velocity[dof]);
+            assert_eq!(accelerations[index], state.acceleration[dof]);
+        }
+        assert_float_eq!(
+            *positions.last().unwrap(),
+            input.target_position[dof],
+            abs <= 1e-8
+        );
+    }
+}
@@ -0,0 +1,176 @@
+// Audit of the real-time guarantee: after the construction, `Ruckig::update`, `calculate`,
+// `calculate_batch`, and `Trajectory::at_time` don't allocate. The global allocator counts the
+// allocations of each thread, so this runs as its own binary.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use rsruckig::prelude::*;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+fn check_without_allocations<const DOF: usize>(
+    mut otg: Ruckig<DOF, ThrowErrorHandler>,
+    mut input: InputParameter<DOF>,
+    mut output: OutputParameter<DOF>,
+    target: impl Fn(f64) -> DataArrayOrVec<f64, DOF>,
+) {
+    let dofs = (DOF == 0).then_some(input.degrees_of_freedom);
+    let mut position = DataArrayOrVec::new(dofs, 0.0);
+    let mut velocity = DataArrayOrVec::new(dofs, 0.0);
+    let mut acceleration = DataArrayOrVec::new(dofs, 0.0);
+    let mut jerk = DataArrayOrVec::new(dofs, 0.0);
+    let mut section = None;
+
+    for i in 0..200 {
+        // Change the target regularly so that the calculation is part of the measured cycles
+        if i % 20 == 0 {
+            input.target_position = target(i as f64);
+        }
+
+        let before = allocations();
+        let result = otg.update(&input, &mut output).unwrap();
+        output.trajectory.at_time(
+            0.5 * output.trajectory.get_duration(),
+            &mut Some(&mut position),
+            &mut Some(&mut velocity),
+            &mut Some(&mut acceleration),
+            &mut Some(&mut jerk),
+            &mut section,
+        );
+        assert_eq!(allocations(), before, "cycle {}", i);
+        assert!(matches!(
+            result,
+            RuckigResult::Working | RuckigResult::Finished
+        ));
+        output.pass_to_input(&mut input);
+    }
+}
+
+#[test]
+fn test_stack_dofs_without_allocations() {
+    let mut input = InputParameter::new(None);
+    input.max_velocity = daov_stack![1.0; 8];
+    input.max_acceleration = daov_stack![1.0; 8];
+    input.max_jerk = daov_stack![1.0; 8];
+    check_without_allocations::<8>(
+        Ruckig::new(None, 0.01),
+        input,
+        OutputParameter::new(None),
+        |x| daov_stack![x.sin(), x.cos(), 0.5, -0.5, 0.1 * x, -0.1 * x, 1.0, -1.0],
+    );
+}
+
+#[test]
+fn test_heap_dofs_without_allocations() {
+    let mut input = InputParameter::new(Some(8));
+    input.max_velocity = daov_heap![1.0; 8];
+    input.max_acceleration = daov_heap![1.0; 8];
+    input.max_jerk = daov_heap![1.0; 8];
+    check_without_allocations::<0>(
+        Ruckig::new(Some(8), 0.01),
+        input,
+        OutputParameter::new(Some(8)),
+        |x| daov_heap![x.sin(), x.cos(), 0.5, -0.5, 0.1 * x, -0.1 * x, 1.0, -1.0],
+    );
+}
+
+#[test]
+fn test_previous_trajectory_without_allocations() {
+    let mut input = InputParameter::new(Some(8));
+    input.max_velocity = daov_heap![1.0; 8];
+    input.max_acceleration = daov_heap![1.0; 8];
+    input.max_jerk = daov_heap![1.0; 8];
+    let mut output = OutputParameter::new(Some(8));
+    output.retain_previous_trajectory();
+    check_without_allocations::<0>(Ruckig::new(Some(8), 0.01), input, output, |x| {
+        daov_heap![x.sin(), x.cos(), 0.5, -0.5, 0.1 * x, -0.1 * x, 1.0, -1.0]
+    });
+}
+
+#[test]
+fn test_prepared_and_stop_inputs_without_allocations() {
+    let mut otg = Ruckig::<0, ThrowErrorHandler>::new(Some(3), 0.01);
+    otg.calculator.settings.clamp_target_state = true;
+    let mut input = InputParameter::new(Some(3));
+    input.current_position = daov_heap![350.0, 0.0, 0.0];
+    input.target_position = daov_heap![10.0, 0.5, -0.5];
+    input.target_velocity = daov_heap![0.0, 2.0, 0.0];
+    input.max_velocity = daov_heap![100.0, 1.0, 1.0];
+    input.max_acceleration = daov_heap![100.0, 1.0, 1.0];
+    input.max_jerk = daov_heap![100.0, 1.0, 1.0];
+    input.max_position = Some(daov_heap![400.0, 1.0, 1.0]);
+    input.min_position = Some(daov_heap![-400.0, -1.0, -1.0]);
+    input.per_dof_period = Some(daov_heap![Some(360.0), None, None]);
+    let mut traj = Trajectory::new(Some(3));
+
+    // The first calculations copy the optional fields into the scratch inputs
+    otg.calculate(&input, &mut traj).unwrap();
+    otg.calculate_stop(&input, &mut traj).unwrap();
+
+    for i in 0..50 {
+        input.current_velocity[1] = 0.01 * i as f64;
+        input.target_position[2] = -0.5 + 0.01 * i as f64;
+
+        let before = allocations();
+        assert_eq!(
+            otg.calculate(&input, &mut traj).unwrap(),
+            RuckigResult::Working
+        );
+        assert_eq!(
+            otg.calculate_stop(&input, &mut traj).unwrap(),
+            RuckigResult::Working
+        );
+        assert_eq!(allocations(), before, "cycle {}", i);
+    }
+}
+
+#[test]
+fn test_calculate_batch_without_allocations() {
+    let mut otg = Ruckig::<0, ThrowErrorHandler>::new(Some(2), 0.01);
+    let mut inputs = Vec::new();
+    for i in 0..16 {
+        let x = i as f64;
+        let mut input = InputParameter::new(Some(2));
+        input.target_position = daov_heap![x.sin(), x.cos()];
+        input.max_velocity = daov_heap![1.0, 1.0];
+        input.max_acceleration = daov_heap![1.0, 1.0];
+        input.max_jerk = daov_heap![1.0, 1.0];
+        inputs.push(input);
+    }
+    let mut trajectories = vec![Trajectory::new(Some(2)); inputs.len()];
+    let mut results = vec![Ok(RuckigResult::Finished); inputs.len()];
+
+    let before = allocations();
+    otg.calculate_batch(&inputs, &mut trajectories, &mut results);
+    assert_eq!(allocations(), before);
+    assert!(results
+        .iter()
+        .all(|result| result == &Ok(RuckigResult::Working)));
+}
@@ -0,0 +1,123 @@
+// Regression corpus of numerically difficult inputs in `corpus/hard_inputs.jsonl`, one JSON object
+// per line. A new report is added as a single line with a name, the control cycle, the input, the
+// expected duration and, if it isn't `Working`, the expected result. The current state and the
+// target velocity and acceleration default to zero, the other optional fields to the defaults of
+// `InputParameter`.
+use rsruckig::prelude::*;
+use serde::Deserialize;
+
+/// Largest deviation from the expected duration, as for the known trajectories
+const DURATION_TOLERANCE: f64 = 1e-4;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CorpusEntry {
+    name: String,
+    delta_time: f64,
+    current_position: Vec<f64>,
+    current_velocity: Option<Vec<f64>>,
+    current_acceleration: Option<Vec<f64>>,
+    target_position: Vec<f64>,
+    target_velocity: Option<Vec<f64>>,
+    target_acceleration: Option<Vec<f64>>,
+    max_velocity: Vec<f64>,
+    max_acceleration: Vec<f64>,
+    max_jerk: Vec<f64>,
+    min_velocity: Option<Vec<f64>>,
+    min_acceleration: Option<Vec<f64>>,
+    minimum_duration: Option<f64>,
+    control_interface: Option<ControlInterface>,
+    duration_discretization: Option<DurationDiscretization>,
+    result: Option<RuckigResult>,
+    duration: f64,
+}
+
+impl CorpusEntry {
+    fn input(&self) -> Result<InputParameter<0>, RuckigError> {
+        let dofs = self.current_position.len();
+        let mut input = InputParameter::new(Some(dofs));
+        input.set_current_position(&self.current_position)?;
+        input.set_target_position(&self.target_position)?;
+        input.set_max_velocity(&self.max_velocity)?;
+        input.set_max_acceleration(&self.max_acceleration)?;
+        input.set_max_jerk(&self.max_jerk)?;
+        if let Some(values) = &self.current_velocity {
+            input.set_current_velocity(values)?;
+        }
+        if let Some(values) = &self.current_acceleration {
+            input.set_current_acceleration(values)?;
+        }
+        if let Some(values) = &self.target_velocity {
+            input.set_target_velocity(values)?;
+        }
+        if let Some(values) = &self.target_acceleration {
+            input.set_target_acceleration(values)?;
+        }
+        input.min_velocity = self
+            .min_velocity
+            .clone()
+            .map(DataArrayOrVec::from_vec)
+            .transpose()?;
+        input.min_acceleration = self
+            .min_acceleration
+            .clone()
+            .map(DataArrayOrVec::from_vec)
+            .transpose()?;
+        input.minimum_duration = self.minimum_duration;
+        if let Some(control_interface) = &self.control_interface {
+            input.control_interface = control_interface.clone();
+        }
+        if let Some(duration_discretization) = &self.duration_discretization {
+            input.duration_discretization = duration_discretization.clone();
+        }
+        Ok(input)
+    }
+
+    /// Calculates the trajectory and describes the difference to the expectation, if any
+    fn check(&self) -> Option<String> {
+        let input = match self.input() {
+            Ok(input) => input,
+            Err(error) => return Some(format!("invalid entry: {}", error)),
+        };
+        let mut otg =
+            Ruckig::<0, ThrowErrorHandler>::new(Some(input.degrees_of_freedom), self.delta_time);
+        let mut traj = Trajectory::new(Some(input.degrees_of_freedom));
+        let expected = self.result.unwrap_or(RuckigResult::Working);
+        match otg.calculate(&input, &mut traj) {
+            Ok(result) if result == expected => {}
+            Ok(result) => return Some(format!("result {:?} instead of {:?}", result, expected)),
+            Err(error) => return Some(format!("error {}", error)),
+        }
+        let duration = traj.get_duration();
+        ((duration - self.duration).abs() > DURATION_TOLERANCE)
+            .then(|| format!("duration {} instead of {}", duration, self.duration))
+    }
+}
+
+fn load_corpus(corpus: &str) -> Vec<CorpusEntry> {
+    corpus
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(number, line)| {
+            serde_json::from_str(line)
+                .unwrap_or_else(|error| panic!("corpus line {}: {}", number + 1, error))
+        })
+        .collect()
+}
+
+#[test]
+fn test_hard_inputs_corpus() {
+    let corpus = load_corpus(include_str!("../corpus/hard_inputs.jsonl"));
+    assert!(!corpus.is_empty());
+
+    let failures: Vec<String> = corpus
+        .iter()
+        .filter_map(|entry| {
+            entry
+                .check()
+                .map(|failure| format!("{}: {}", entry.name, failure))
+        })
+        .collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
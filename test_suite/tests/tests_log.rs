@@ -0,0 +1,83 @@
+// Notices of the `log` feature. The logger is global, so this runs as its own binary.
+use std::sync::Mutex;
+
+use rsruckig::prelude::*;
+
+static MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct Logger;
+
+impl log::Log for Logger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        MESSAGES
+            .lock()
+            .unwrap()
+            .push(format!("{} {}", record.level(), record.args()));
+    }
+
+    fn flush(&self) {}
+}
+
+fn take_messages() -> Vec<String> {
+    std::mem::take(&mut *MESSAGES.lock().unwrap())
+}
+
+#[test]
+fn test_log_notices() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Debug);
+
+    let mut otg = Ruckig::<2, ThrowErrorHandler>::new(None, 0.01);
+    let mut traj = Trajectory::new(None);
+
+    let mut input = InputParameter::new(None);
+    input.target_position = daov_stack![1.0, 1.0];
+    input.max_velocity = daov_stack![1.0, 1.0];
+    input.max_acceleration = daov_stack![1.0, 1.0];
+    input.max_jerk = daov_stack![1.0, 1.0];
+    otg.calculate(&input, &mut traj).unwrap();
+    assert!(take_messages().is_empty());
+
+    input.synchronization = Synchronization::Phase;
+    input.target_position = daov_stack![1.0, -0.5];
+    input.current_velocity = daov_stack![0.2, 0.2];
+    otg.calculate(&input, &mut traj).unwrap();
+    assert_eq!(
+        take_messages(),
+        ["WARN phase synchronization is not possible, using time synchronization."]
+    );
+
+    input.synchronization = Synchronization::Time;
+    input.current_velocity = daov_stack![0.0, 2.0];
+    otg.calculate(&input, &mut traj).unwrap();
+    let messages = take_messages();
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].starts_with("WARN DoF 1 violates its limits, a brake pre-trajectory"));
+
+    // The rounding to the control cycle hits a blocked interval, so that the duration is much
+    // longer than the continuous one
+    let mut input = InputParameter::new(None);
+    input.duration_discretization = DurationDiscretization::Discrete;
+    input.current_position = daov_stack![0.2452664622709331, -0.7317112942550654];
+    input.current_velocity = daov_stack![-0.6708963396002399, -0.7571190315893355];
+    input.current_acceleration = daov_stack![-0.6789857730675717, 0.8937279855368017];
+    input.target_position = daov_stack![-0.8039461221779347, -0.583728136420933];
+    input.target_velocity = daov_stack![-0.8979325776897977, 0.8199375510944296];
+    input.target_acceleration = daov_stack![-0.4345818751840635, 0.3187006480155596];
+    input.max_velocity = daov_stack![2.9213529737099364, 2.4221572257525277];
+    input.max_acceleration = daov_stack![2.6352774754158426, 2.1394221621141774];
+    input.max_jerk = daov_stack![1.2281025205735299, 2.9290067169790603];
+    otg.calculate(&input, &mut traj).unwrap();
+    let messages = take_messages();
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].starts_with("DEBUG discrete duration 4.46 s is more than one cycle"));
+
+    input.duration_discretization = DurationDiscretization::Continuous;
+    otg.calculate(&input, &mut traj).unwrap();
+    assert!(traj.get_duration() < 1.3);
+    assert!(take_messages().is_empty());
+}
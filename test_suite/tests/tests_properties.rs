@@ -0,0 +1,137 @@
+// Property-based checks of the documented guarantees for random states and limits over many
+// orders of magnitude. A failing input is shrunk by proptest and printed in full.
+use proptest::prelude::*;
+use rsruckig::prelude::*;
+
+/// Largest deviation of the final state from the target, relative to its magnitude
+const TARGET_TOLERANCE: f64 = 1e-8;
+/// Largest excess of the kinematic limits, relative to their magnitude
+const LIMIT_TOLERANCE: f64 = 1e-12;
+
+#[derive(Debug, Clone)]
+struct DofParameters {
+    current: [f64; 3],
+    target: [f64; 3],
+    limits: [f64; 3],
+}
+
+/// Acceleration of the fraction within the limits, such that the velocity stays within its limits
+/// while the acceleration is braked to zero by the jerk in the given direction
+fn acceleration(fraction: f64, velocity: f64, limits: &[f64; 3], direction: f64) -> f64 {
+    let velocity_margin = limits[0] - direction * fraction.signum() * velocity;
+    fraction * limits[1].min((2.0 * limits[2] * velocity_margin.max(0.0)).sqrt())
+}
+
+/// Limits between 1e-4 and 1e2 times a common scale of the DoF, and states within the limits. Larger
+/// magnitudes are left out as the profiles are checked against absolute precisions, e.g. the
+/// velocity limit up to 1e-12, which the rounding errors of f64 exceed around 1e3.
+fn dof_parameters() -> impl Strategy<Value = DofParameters> {
+    (
+        -3.0..1.0f64,
+        prop::array::uniform3(-1.0..1.0f64),
+        prop::array::uniform3(-1.0..1.0f64),
+        prop::array::uniform3(-1.0..1.0f64),
+    )
+        .prop_map(|(scale, limit_exponents, current, target)| {
+            let scale = 10f64.powf(scale);
+            let limits = limit_exponents.map(|exponent| scale * 10f64.powf(exponent));
+            let current_velocity = limits[0] * current[1];
+            let target_velocity = limits[0] * target[1];
+            DofParameters {
+                current: [
+                    10.0 * scale * current[0],
+                    current_velocity,
+                    acceleration(current[2], current_velocity, &limits, 1.0),
+                ],
+                target: [
+                    10.0 * scale * target[0],
+                    target_velocity,
+                    acceleration(target[2], target_velocity, &limits, -1.0),
+                ],
+                limits,
+            }
+        })
+}
+
+fn input_from(parameters: &[DofParameters]) -> InputParameter<0> {
+    let dofs = parameters.len();
+    let mut input = InputParameter::new(Some(dofs));
+    for (dof, parameters) in parameters.iter().enumerate() {
+        input.current_position[dof] = parameters.current[0];
+        input.current_velocity[dof] = parameters.current[1];
+        input.current_acceleration[dof] = parameters.current[2];
+        input.target_position[dof] = parameters.target[0];
+        input.target_velocity[dof] = parameters.target[1];
+        input.target_acceleration[dof] = parameters.target[2];
+        input.max_velocity[dof] = parameters.limits[0];
+        input.max_acceleration[dof] = parameters.limits[1];
+        input.max_jerk[dof] = parameters.limits[2];
+    }
+    input
+}
+
+fn within(value: f64, limit: f64) -> bool {
+    value.abs() <= limit * (1.0 + LIMIT_TOLERANCE)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2000))]
+
+    #[test]
+    fn test_random_guarantees(parameters in prop::collection::vec(dof_parameters(), 1..=4)) {
+        let input = input_from(&parameters);
+        // States whose velocity would inevitably leave the limits while braking the acceleration
+        prop_assume!(input.validate::<ThrowErrorHandler>(true, true).is_ok());
+
+        let dofs = parameters.len();
+        let mut otg = Ruckig::<0, ThrowErrorHandler>::new(Some(dofs), 0.01);
+        let mut traj = Trajectory::new(Some(dofs));
+        prop_assert_eq!(otg.calculate(&input, &mut traj), Ok(RuckigResult::Working));
+
+        // The final state reaches the target
+        let duration = traj.get_duration();
+        prop_assert!(duration.is_finite() && duration >= 0.0);
+        let state = traj.state_at(duration);
+        for dof in 0..dofs {
+            for (value, target, scale) in [
+                (state.position[dof], input.target_position[dof], input.max_velocity[dof]),
+                (state.velocity[dof], input.target_velocity[dof], input.max_velocity[dof]),
+                (state.acceleration[dof], input.target_acceleration[dof], input.max_acceleration[dof]),
+            ] {
+                prop_assert!(
+                    (value - target).abs() <= TARGET_TOLERANCE * target.abs().max(scale).max(1.0),
+                    "DoF {} reaches {} instead of {}", dof, value, target
+                );
+            }
+        }
+
+        // The limits hold, with the velocity being extremal where the acceleration crosses zero
+        for (dof, profile) in traj.get_profiles()[0].iter().enumerate() {
+            prop_assert!(profile.brake.duration == 0.0);
+            prop_assert!(profile.j.iter().all(|&j| within(j, input.max_jerk[dof])));
+            prop_assert!(profile.a.iter().all(|&a| within(a, input.max_acceleration[dof])));
+            prop_assert!(profile.v.iter().all(|&v| within(v, input.max_velocity[dof])));
+            for phase in 0..7 {
+                let (a0, a1, j) = (profile.a[phase], profile.a[phase + 1], profile.j[phase]);
+                if j != 0.0 && a0 * a1 < 0.0 {
+                    let v = profile.v[phase] - a0 * a0 / (2.0 * j);
+                    prop_assert!(within(v, input.max_velocity[dof]), "velocity {} in phase {}", v, phase);
+                }
+            }
+        }
+
+        // The phases follow each other in time and end at the duration
+        for profile in traj.get_profiles()[0].iter() {
+            prop_assert!(profile.t.iter().all(|&t| t >= 0.0));
+            prop_assert!(profile.t_sum.windows(2).all(|times| times[0] <= times[1]));
+            let end = profile.brake.duration + profile.accel.duration + profile.t_sum[6];
+            prop_assert!((end - duration).abs() <= 1e-12 * duration.max(1.0));
+        }
+        let mut section = 0;
+        for step in 0..=100 {
+            let state = traj.state_at(duration * step as f64 / 100.0);
+            prop_assert!(state.section >= section);
+            section = state.section;
+        }
+    }
+}
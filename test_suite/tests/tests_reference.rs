@@ -0,0 +1,219 @@
+// Differential tests against the upstream C++ Ruckig, which feed identical random inputs to both
+// implementations and compare the results, durations and sampled states. The C++ library is built
+// from a checkout of https://github.com/pantor/ruckig at the tag in ruckig-sys/Cargo.toml by the
+// `cpp-reference` feature:
+//
+//   git clone --depth 1 --branch v0.14.0 https://github.com/pantor/ruckig ../ruckig
+//   RUCKIG_CPP_DIR=$(realpath ../ruckig) cargo test -p rsruckig_test_suite --features cpp-reference \
+//       --test tests_reference
+#![cfg(feature = "cpp-reference")]
+
+use rand_core::SeedableRng;
+use rand_distr::{Distribution, Uniform};
+use rand_pcg::Pcg64;
+use rsruckig::prelude::*;
+use ruckig_sys::{rsruckig_reference_calculate, RuckigReferenceInput};
+use std::ptr;
+
+/// Number of random inputs per configuration
+const NUMBER_OF_INPUTS: usize = 100_000;
+
+/// Number of samples between the start and the end of each trajectory
+const NUMBER_OF_SAMPLES: usize = 16;
+
+/// Largest deviation of the duration and of the sampled states, relative to their magnitude
+const TOLERANCE: f64 = 1e-8;
+
+/// Sample times as fractions of the duration, including the start and the end
+fn sample_fractions() -> Vec<f64> {
+    (0..=NUMBER_OF_SAMPLES)
+        .map(|i| i as f64 / NUMBER_OF_SAMPLES as f64)
+        .collect()
+}
+
+/// Result, duration and states of a trajectory sampled at `sample_fractions` of its duration
+#[derive(Debug)]
+struct Sampled {
+    result: RuckigResult,
+    duration: f64,
+    positions: Vec<f64>,
+    velocities: Vec<f64>,
+    accelerations: Vec<f64>,
+}
+
+impl Sampled {
+    fn new(dofs: usize) -> Self {
+        let number_of_values = dofs * (NUMBER_OF_SAMPLES + 1);
+        Self {
+            result: RuckigResult::Error,
+            duration: 0.0,
+            positions: vec![0.0; number_of_values],
+            velocities: vec![0.0; number_of_values],
+            accelerations: vec![0.0; number_of_values],
+        }
+    }
+}
+
+fn calculate_rust<const DOF: usize>(input: &InputParameter<DOF>, delta_time: f64) -> Sampled {
+    let mut otg = Ruckig::<DOF, IgnoreErrorHandler>::new(None, delta_time);
+    let mut traj = Trajectory::new(None);
+    let mut sampled = Sampled::new(DOF);
+    sampled.result = otg
+        .calculate(input, &mut traj)
+        .unwrap_or(RuckigResult::Error);
+    if sampled.result == RuckigResult::Working {
+        sampled.duration = traj.get_duration();
+        let times: Vec<f64> = sample_fractions()
+            .iter()
+            .map(|fraction| fraction * sampled.duration)
+            .collect();
+        traj.sample_into(
+            &times,
+            &mut sampled.positions,
+            &mut sampled.velocities,
+            &mut sampled.accelerations,
+        );
+    }
+    sampled
+}
+
+fn calculate_reference<const DOF: usize>(input: &InputParameter<DOF>, delta_time: f64) -> Sampled {
+    let optional_pointer = |values: &Option<DataArrayOrVec<f64, DOF>>| {
+        values
+            .as_ref()
+            .map_or(ptr::null(), |values| values.as_ptr())
+    };
+    let reference_input = RuckigReferenceInput {
+        degrees_of_freedom: DOF,
+        delta_time,
+        current_position: input.current_position.as_ptr(),
+        current_velocity: input.current_velocity.as_ptr(),
+        current_acceleration: input.current_acceleration.as_ptr(),
+        target_position: input.target_position.as_ptr(),
+        target_velocity: input.target_velocity.as_ptr(),
+        target_acceleration: input.target_acceleration.as_ptr(),
+        max_velocity: input.max_velocity.as_ptr(),
+        max_acceleration: input.max_acceleration.as_ptr(),
+        max_jerk: input.max_jerk.as_ptr(),
+        min_velocity: optional_pointer(&input.min_velocity),
+        min_acceleration: optional_pointer(&input.min_acceleration),
+        control_interface: match input.control_interface {
+            ControlInterface::Position => 0,
+            ControlInterface::Velocity => 1,
+            ControlInterface::Acceleration => {
+                panic!("the C++ version has no acceleration interface to compare against")
+            }
+        },
+        synchronization: match input.synchronization {
+            Synchronization::Time => 0,
+            Synchronization::TimeIfNecessary => 1,
+            Synchronization::Phase => 2,
+            Synchronization::None => 3,
+        },
+    };
+
+    let fractions = sample_fractions();
+    let mut sampled = Sampled::new(DOF);
+    let result = unsafe {
+        rsruckig_reference_calculate(
+            &reference_input,
+            fractions.len(),
+            fractions.as_ptr(),
+            &mut sampled.duration,
+            sampled.positions.as_mut_ptr(),
+            sampled.velocities.as_mut_ptr(),
+            sampled.accelerations.as_mut_ptr(),
+        )
+    };
+    sampled.result = RuckigResult::try_from(result).unwrap_or(RuckigResult::Error);
+    sampled
+}
+
+fn is_close(a: f64, b: f64) -> bool {
+    (a - b).abs() <= TOLERANCE * a.abs().max(b.abs()).max(1.0)
+}
+
+/// Feed random inputs of the given interface and synchronization to both implementations
+fn check_random_inputs<const DOF: usize>(
+    seed: u64,
+    control_interface: ControlInterface,
+    synchronization: Synchronization,
+    has_min_limits: bool,
+) {
+    let mut rng = Pcg64::seed_from_u64(seed);
+    let state = Uniform::new(-2.0, 2.0);
+    let limit = Uniform::new(0.1, 4.0);
+    let delta_time = 0.005;
+
+    let mut number_of_mismatches = 0;
+    for index in 0..NUMBER_OF_INPUTS {
+        let mut input = InputParameter::<DOF>::new(None);
+        input.control_interface = control_interface.clone();
+        input.synchronization = synchronization.clone();
+        for dof in 0..DOF {
+            input.current_position[dof] = state.sample(&mut rng);
+            input.current_velocity[dof] = state.sample(&mut rng);
+            input.current_acceleration[dof] = state.sample(&mut rng);
+            input.target_position[dof] = state.sample(&mut rng);
+            input.target_velocity[dof] = state.sample(&mut rng);
+            input.target_acceleration[dof] = state.sample(&mut rng);
+            input.max_velocity[dof] = limit.sample(&mut rng) + input.target_velocity[dof].abs();
+            input.max_acceleration[dof] =
+                limit.sample(&mut rng) + input.target_acceleration[dof].abs();
+            input.max_jerk[dof] = limit.sample(&mut rng);
+        }
+        if has_min_limits {
+            let mut min_velocity = DataArrayOrVec::new(None, 0.0);
+            let mut min_acceleration = DataArrayOrVec::new(None, 0.0);
+            for dof in 0..DOF {
+                min_velocity[dof] = -limit.sample(&mut rng) - input.target_velocity[dof].abs();
+                min_acceleration[dof] =
+                    -limit.sample(&mut rng) - input.target_acceleration[dof].abs();
+            }
+            input.min_velocity = Some(min_velocity);
+            input.min_acceleration = Some(min_acceleration);
+        }
+
+        let rust = calculate_rust(&input, delta_time);
+        let reference = calculate_reference(&input, delta_time);
+
+        let is_matching = rust.result == reference.result
+            && is_close(rust.duration, reference.duration)
+            && [
+                (&rust.positions, &reference.positions),
+                (&rust.velocities, &reference.velocities),
+                (&rust.accelerations, &reference.accelerations),
+            ]
+            .iter()
+            .all(|(a, b)| a.iter().zip(b.iter()).all(|(a, b)| is_close(*a, *b)));
+        if !is_matching {
+            number_of_mismatches += 1;
+            eprintln!(
+                "Input {} differs from C++:\n{:?}\nRust: {:?}\nC++: {:?}",
+                index, input, rust, reference
+            );
+        }
+    }
+    assert_eq!(number_of_mismatches, 0, "of {} inputs", NUMBER_OF_INPUTS);
+}
+
+#[test]
+fn test_reference_position_1dof() {
+    check_random_inputs::<1>(1, ControlInterface::Position, Synchronization::Time, false);
+}
+
+#[test]
+fn test_reference_position_3dof() {
+    check_random_inputs::<3>(2, ControlInterface::Position, Synchronization::Time, false);
+    check_random_inputs::<3>(3, ControlInterface::Position, Synchronization::Time, true);
+}
+
+#[test]
+fn test_reference_position_phase_synchronization() {
+    check_random_inputs::<3>(4, ControlInterface::Position, Synchronization::Phase, false);
+}
+
+#[test]
+fn test_reference_velocity_3dof() {
+    check_random_inputs::<3>(5, ControlInterface::Velocity, Synchronization::Time, true);
+}
@@ -0,0 +1,648 @@
+//! JavaScript bindings for rsruckig, e.g. for trajectory previews in the browser.
+//!
+//! Build with `wasm-pack build --target web`. The degrees of freedom are always given at runtime,
+//! so all types use heap storage. Kinematic vectors are passed as `Float64Array`.
+
+use rsruckig::error::{RuckigError, ThrowErrorHandler};
+use rsruckig::input_parameter::{
+    ControlInterface as RsControlInterface, InputParameter as RsInputParameter,
+    Synchronization as RsSynchronization,
+};
+use rsruckig::output_parameter::OutputParameter as RsOutputParameter;
+use rsruckig::result::RuckigResult;
+use rsruckig::ruckig::Ruckig as RsRuckig;
+use rsruckig::trajectory::Trajectory as RsTrajectory;
+use rsruckig::util::DataArrayOrVec;
+use wasm_bindgen::prelude::*;
+
+fn to_js_error(error: RuckigError) -> JsError {
+    JsError::new(error.to_string().trim())
+}
+
+fn to_data(values: Vec<f64>, dofs: usize, name: &str) -> Result<DataArrayOrVec<f64, 0>, JsError> {
+    if values.len() != dofs {
+        return Err(JsError::new(&format!(
+            "{} has {} values, but there are {} degrees of freedom.",
+            name,
+            values.len(),
+            dofs
+        )));
+    }
+    Ok(values.into_iter().collect())
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq)]
+pub enum ControlInterface {
+    Position,
+    Velocity,
+    Acceleration,
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq)]
+pub enum Synchronization {
+    Time,
+    TimeIfNecessary,
+    Phase,
+    None,
+}
+
+#[wasm_bindgen(js_name = Result)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum JsRuckigResult {
+    Working,
+    Finished,
+    Interrupted,
+    Error,
+    ErrorInvalidInput,
+    ErrorTrajectoryDuration,
+    ErrorPositionalLimits,
+    ErrorZeroLimits,
+    ErrorExecutionTimeCalculation,
+    ErrorSynchronizationCalculation,
+}
+
+impl From<RuckigResult> for JsRuckigResult {
+    fn from(value: RuckigResult) -> Self {
+        match value {
+            RuckigResult::Working => JsRuckigResult::Working,
+            RuckigResult::Finished => JsRuckigResult::Finished,
+            RuckigResult::Interrupted => JsRuckigResult::Interrupted,
+            RuckigResult::Error => JsRuckigResult::Error,
+            RuckigResult::ErrorInvalidInput => JsRuckigResult::ErrorInvalidInput,
+            RuckigResult::ErrorTrajectoryDuration => JsRuckigResult::ErrorTrajectoryDuration,
+            RuckigResult::ErrorPositionalLimits => JsRuckigResult::ErrorPositionalLimits,
+            RuckigResult::ErrorZeroLimits => JsRuckigResult::ErrorZeroLimits,
+            RuckigResult::ErrorExecutionTimeCalculation => {
+                JsRuckigResult::ErrorExecutionTimeCalculation
+            }
+            RuckigResult::ErrorSynchronizationCalculation => {
+                JsRuckigResult::ErrorSynchronizationCalculation
+            }
+        }
+    }
+}
+
+#[wasm_bindgen]
+pub struct InputParameter {
+    inner: RsInputParameter<0>,
+}
+
+#[wasm_bindgen]
+impl InputParameter {
+    #[wasm_bindgen(constructor)]
+    pub fn new(dofs: usize) -> InputParameter {
+        InputParameter {
+            inner: RsInputParameter::new(Some(dofs)),
+        }
+    }
+
+    #[wasm_bindgen(getter, js_name = degreesOfFreedom)]
+    pub fn degrees_of_freedom(&self) -> usize {
+        self.inner.degrees_of_freedom
+    }
+
+    #[wasm_bindgen(getter, js_name = currentPosition)]
+    pub fn current_position(&self) -> Vec<f64> {
+        self.inner.current_position.to_vec()
+    }
+
+    #[wasm_bindgen(setter, js_name = currentPosition)]
+    pub fn set_current_position(&mut self, values: Vec<f64>) -> Result<(), JsError> {
+        self.inner.current_position =
+            to_data(values, self.inner.degrees_of_freedom, "currentPosition")?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(getter, js_name = currentVelocity)]
+    pub fn current_velocity(&self) -> Vec<f64> {
+        self.inner.current_velocity.to_vec()
+    }
+
+    #[wasm_bindgen(setter, js_name = currentVelocity)]
+    pub fn set_current_velocity(&mut self, values: Vec<f64>) -> Result<(), JsError> {
+        self.inner.current_velocity =
+            to_data(values, self.inner.degrees_of_freedom, "currentVelocity")?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(getter, js_name = currentAcceleration)]
+    pub fn current_acceleration(&self) -> Vec<f64> {
+        self.inner.current_acceleration.to_vec()
+    }
+
+    #[wasm_bindgen(setter, js_name = currentAcceleration)]
+    pub fn set_current_acceleration(&mut self, values: Vec<f64>) -> Result<(), JsError> {
+        self.inner.current_acceleration =
+            to_data(values, self.inner.degrees_of_freedom, "currentAcceleration")?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(getter, js_name = targetPosition)]
+    pub fn target_position(&self) -> Vec<f64> {
+        self.inner.target_position.to_vec()
+    }
+
+    #[wasm_bindgen(setter, js_name = targetPosition)]
+    pub fn set_target_position(&mut self, values: Vec<f64>) -> Result<(), JsError> {
+        self.inner.target_position =
+            to_data(values, self.inner.degrees_of_freedom, "targetPosition")?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(getter, js_name = targetVelocity)]
+    pub fn target_velocity(&self) -> Vec<f64> {
+        self.inner.target_velocity.to_vec()
+    }
+
+    #[wasm_bindgen(setter, js_name = targetVelocity)]
+    pub fn set_target_velocity(&mut self, values: Vec<f64>) -> Result<(), JsError> {
+        self.inner.target_velocity =
+            to_data(values, self.inner.degrees_of_freedom, "targetVelocity")?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(getter, js_name = targetAcceleration)]
+    pub fn target_acceleration(&self) -> Vec<f64> {
+        self.inner.target_acceleration.to_vec()
+    }
+
+    #[wasm_bindgen(setter, js_name = targetAcceleration)]
+    pub fn set_target_acceleration(&mut self, values: Vec<f64>) -> Result<(), JsError> {
+        self.inner.target_acceleration =
+            to_data(values, self.inner.degrees_of_freedom, "targetAcceleration")?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(getter, js_name = maxVelocity)]
+    pub fn max_velocity(&self) -> Vec<f64> {
+        self.inner.max_velocity.to_vec()
+    }
+
+    #[wasm_bindgen(setter, js_name = maxVelocity)]
+    pub fn set_max_velocity(&mut self, values: Vec<f64>) -> Result<(), JsError> {
+        self.inner.max_velocity = to_data(values, self.inner.degrees_of_freedom, "maxVelocity")?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(getter, js_name = maxAcceleration)]
+    pub fn max_acceleration(&self) -> Vec<f64> {
+        self.inner.max_acceleration.to_vec()
+    }
+
+    #[wasm_bindgen(setter, js_name = maxAcceleration)]
+    pub fn set_max_acceleration(&mut self, values: Vec<f64>) -> Result<(), JsError> {
+        self.inner.max_acceleration =
+            to_data(values, self.inner.degrees_of_freedom, "maxAcceleration")?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(getter, js_name = maxJerk)]
+    pub fn max_jerk(&self) -> Vec<f64> {
+        self.inner.max_jerk.to_vec()
+    }
+
+    #[wasm_bindgen(setter, js_name = maxJerk)]
+    pub fn set_max_jerk(&mut self, values: Vec<f64>) -> Result<(), JsError> {
+        self.inner.max_jerk = to_data(values, self.inner.degrees_of_freedom, "maxJerk")?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(getter, js_name = minVelocity)]
+    pub fn min_velocity(&self) -> Option<Vec<f64>> {
+        self.inner.min_velocity.as_ref().map(|values| values.to_vec())
+    }
+
+    #[wasm_bindgen(setter, js_name = minVelocity)]
+    pub fn set_min_velocity(&mut self, values: Option<Vec<f64>>) -> Result<(), JsError> {
+        self.inner.min_velocity = values
+            .map(|values| to_data(values, self.inner.degrees_of_freedom, "minVelocity"))
+            .transpose()?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(getter, js_name = minAcceleration)]
+    pub fn min_acceleration(&self) -> Option<Vec<f64>> {
+        self.inner
+            .min_acceleration
+            .as_ref()
+            .map(|values| values.to_vec())
+    }
+
+    #[wasm_bindgen(setter, js_name = minAcceleration)]
+    pub fn set_min_acceleration(&mut self, values: Option<Vec<f64>>) -> Result<(), JsError> {
+        self.inner.min_acceleration = values
+            .map(|values| to_data(values, self.inner.degrees_of_freedom, "minAcceleration"))
+            .transpose()?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(getter, js_name = minimumDuration)]
+    pub fn minimum_duration(&self) -> Option<f64> {
+        self.inner.minimum_duration
+    }
+
+    #[wasm_bindgen(setter, js_name = minimumDuration)]
+    pub fn set_minimum_duration(&mut self, value: Option<f64>) {
+        self.inner.minimum_duration = value;
+    }
+
+    #[wasm_bindgen(getter, js_name = maximumDuration)]
+    pub fn maximum_duration(&self) -> Option<f64> {
+        self.inner.maximum_duration
+    }
+
+    #[wasm_bindgen(setter, js_name = maximumDuration)]
+    pub fn set_maximum_duration(&mut self, value: Option<f64>) {
+        self.inner.maximum_duration = value;
+    }
+
+    #[wasm_bindgen(getter, js_name = preDwell)]
+    pub fn pre_dwell(&self) -> Option<f64> {
+        self.inner.pre_dwell
+    }
+
+    #[wasm_bindgen(setter, js_name = preDwell)]
+    pub fn set_pre_dwell(&mut self, value: Option<f64>) {
+        self.inner.pre_dwell = value;
+    }
+
+    #[wasm_bindgen(getter, js_name = postDwell)]
+    pub fn post_dwell(&self) -> Option<f64> {
+        self.inner.post_dwell
+    }
+
+    #[wasm_bindgen(setter, js_name = postDwell)]
+    pub fn set_post_dwell(&mut self, value: Option<f64>) {
+        self.inner.post_dwell = value;
+    }
+
+    #[wasm_bindgen(getter, js_name = controlInterface)]
+    pub fn control_interface(&self) -> ControlInterface {
+        match self.inner.control_interface {
+            RsControlInterface::Position => ControlInterface::Position,
+            RsControlInterface::Velocity => ControlInterface::Velocity,
+            RsControlInterface::Acceleration => ControlInterface::Acceleration,
+        }
+    }
+
+    #[wasm_bindgen(setter, js_name = controlInterface)]
+    pub fn set_control_interface(&mut self, value: ControlInterface) {
+        self.inner.control_interface = match value {
+            ControlInterface::Position => RsControlInterface::Position,
+            ControlInterface::Velocity => RsControlInterface::Velocity,
+            ControlInterface::Acceleration => RsControlInterface::Acceleration,
+        };
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn synchronization(&self) -> Synchronization {
+        match self.inner.synchronization {
+            RsSynchronization::Time => Synchronization::Time,
+            RsSynchronization::TimeIfNecessary => Synchronization::TimeIfNecessary,
+            RsSynchronization::Phase => Synchronization::Phase,
+            RsSynchronization::None => Synchronization::None,
+        }
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_synchronization(&mut self, value: Synchronization) {
+        self.inner.synchronization = match value {
+            Synchronization::Time => RsSynchronization::Time,
+            Synchronization::TimeIfNecessary => RsSynchronization::TimeIfNecessary,
+            Synchronization::Phase => RsSynchronization::Phase,
+            Synchronization::None => RsSynchronization::None,
+        };
+    }
+
+    /// Append an intermediate position, which is reached at rest
+    #[wasm_bindgen(js_name = addIntermediatePosition)]
+    pub fn add_intermediate_position(&mut self, position: Vec<f64>) -> Result<(), JsError> {
+        let position = to_data(
+            position,
+            self.inner.degrees_of_freedom,
+            "intermediatePosition",
+        )?;
+        self.inner.intermediate_positions.push_back(position);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = clearIntermediatePositions)]
+    pub fn clear_intermediate_positions(&mut self) {
+        self.inner.intermediate_positions.clear();
+    }
+
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_js_string(&self) -> String {
+        self.inner.to_string()
+    }
+}
+
+/// Kinematic state of all DoFs at a single point in time
+#[wasm_bindgen]
+pub struct KinematicState {
+    position: Vec<f64>,
+    velocity: Vec<f64>,
+    acceleration: Vec<f64>,
+    jerk: Vec<f64>,
+    section: usize,
+}
+
+#[wasm_bindgen]
+impl KinematicState {
+    #[wasm_bindgen(getter)]
+    pub fn position(&self) -> Vec<f64> {
+        self.position.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn velocity(&self) -> Vec<f64> {
+        self.velocity.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn acceleration(&self) -> Vec<f64> {
+        self.acceleration.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn jerk(&self) -> Vec<f64> {
+        self.jerk.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn section(&self) -> usize {
+        self.section
+    }
+}
+
+#[wasm_bindgen]
+pub struct Trajectory {
+    inner: RsTrajectory<0>,
+}
+
+#[wasm_bindgen]
+impl Trajectory {
+    #[wasm_bindgen(constructor)]
+    pub fn new(dofs: usize, max_number_of_waypoints: Option<usize>) -> Trajectory {
+        Trajectory {
+            inner: RsTrajectory::new_with_waypoints(
+                max_number_of_waypoints.unwrap_or(0),
+                Some(dofs),
+            ),
+        }
+    }
+
+    #[wasm_bindgen(getter, js_name = degreesOfFreedom)]
+    pub fn degrees_of_freedom(&self) -> usize {
+        self.inner.get_independent_min_durations().len()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn duration(&self) -> f64 {
+        self.inner.get_duration()
+    }
+
+    #[wasm_bindgen(getter, js_name = calculationDuration)]
+    pub fn calculation_duration(&self) -> f64 {
+        self.inner.get_calculation_duration()
+    }
+
+    #[wasm_bindgen(getter, js_name = intermediateDurations)]
+    pub fn intermediate_durations(&self) -> Vec<f64> {
+        self.inner.get_intermediate_durations().to_vec()
+    }
+
+    #[wasm_bindgen(getter, js_name = independentMinDurations)]
+    pub fn independent_min_durations(&self) -> Vec<f64> {
+        self.inner.get_independent_min_durations().to_vec()
+    }
+
+    #[wasm_bindgen(js_name = atTime)]
+    pub fn at_time(&self, time: f64) -> KinematicState {
+        let dofs = self.degrees_of_freedom();
+        let mut new_position = DataArrayOrVec::<f64, 0>::new(Some(dofs), 0.0);
+        let mut new_velocity = DataArrayOrVec::<f64, 0>::new(Some(dofs), 0.0);
+        let mut new_acceleration = DataArrayOrVec::<f64, 0>::new(Some(dofs), 0.0);
+        let mut new_jerk = DataArrayOrVec::<f64, 0>::new(Some(dofs), 0.0);
+        let mut new_section = None;
+        self.inner.at_time(
+            time,
+            &mut Some(&mut new_position),
+            &mut Some(&mut new_velocity),
+            &mut Some(&mut new_acceleration),
+            &mut Some(&mut new_jerk),
+            &mut new_section,
+        );
+
+        KinematicState {
+            position: new_position.to_vec(),
+            velocity: new_velocity.to_vec(),
+            acceleration: new_acceleration.to_vec(),
+            jerk: new_jerk.to_vec(),
+            section: new_section.unwrap_or(0),
+        }
+    }
+
+    /// Positions sampled from the start to the end of the trajectory with the given time step,
+    /// flattened with all DoFs of a sample next to each other. Meant for drawing previews.
+    #[wasm_bindgen(js_name = samplePositions)]
+    pub fn sample_positions(&self, time_step: f64) -> Result<Vec<f64>, JsError> {
+        if time_step.is_nan() || time_step <= 0.0 {
+            return Err(JsError::new("time step should be larger than zero."));
+        }
+
+        let dofs = self.degrees_of_freedom();
+        let number_of_samples = (self.inner.get_duration() / time_step).ceil() as usize + 1;
+        let mut positions = Vec::with_capacity(number_of_samples * dofs);
+        let mut new_position = DataArrayOrVec::<f64, 0>::new(Some(dofs), 0.0);
+        for i in 0..number_of_samples {
+            let time = (i as f64 * time_step).min(self.inner.get_duration());
+            self.inner.at_time(
+                time,
+                &mut Some(&mut new_position),
+                &mut None,
+                &mut None,
+                &mut None,
+                &mut None,
+            );
+            positions.extend_from_slice(&new_position);
+        }
+        Ok(positions)
+    }
+
+    #[wasm_bindgen(js_name = getFirstTimeAtPosition)]
+    pub fn get_first_time_at_position(&self, dof: usize, position: f64) -> Option<f64> {
+        self.inner.get_first_time_at_position(dof, position)
+    }
+}
+
+#[wasm_bindgen]
+pub struct OutputParameter {
+    inner: RsOutputParameter<0>,
+}
+
+#[wasm_bindgen]
+impl OutputParameter {
+    #[wasm_bindgen(constructor)]
+    pub fn new(dofs: usize, max_number_of_waypoints: Option<usize>) -> OutputParameter {
+        OutputParameter {
+            inner: RsOutputParameter::new_with_waypoints(
+                max_number_of_waypoints.unwrap_or(0),
+                Some(dofs),
+            ),
+        }
+    }
+
+    #[wasm_bindgen(getter, js_name = newPosition)]
+    pub fn new_position(&self) -> Vec<f64> {
+        self.inner.new_position.to_vec()
+    }
+
+    #[wasm_bindgen(getter, js_name = newVelocity)]
+    pub fn new_velocity(&self) -> Vec<f64> {
+        self.inner.new_velocity.to_vec()
+    }
+
+    #[wasm_bindgen(getter, js_name = newAcceleration)]
+    pub fn new_acceleration(&self) -> Vec<f64> {
+        self.inner.new_acceleration.to_vec()
+    }
+
+    #[wasm_bindgen(getter, js_name = newJerk)]
+    pub fn new_jerk(&self) -> Vec<f64> {
+        self.inner.new_jerk.to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn time(&self) -> f64 {
+        self.inner.time
+    }
+
+    #[wasm_bindgen(getter, js_name = newSection)]
+    pub fn new_section(&self) -> usize {
+        self.inner.new_section
+    }
+
+    #[wasm_bindgen(getter, js_name = didSectionChange)]
+    pub fn did_section_change(&self) -> bool {
+        self.inner.did_section_change
+    }
+
+    #[wasm_bindgen(getter, js_name = newCalculation)]
+    pub fn new_calculation(&self) -> bool {
+        self.inner.new_calculation
+    }
+
+    /// Always zero in the browser, as there is no clock available to WebAssembly
+    #[wasm_bindgen(getter, js_name = calculationDuration)]
+    pub fn calculation_duration(&self) -> f64 {
+        self.inner.calculation_duration
+    }
+
+    /// A copy of the current trajectory
+    #[wasm_bindgen(getter)]
+    pub fn trajectory(&self) -> Trajectory {
+        Trajectory {
+            inner: self.inner.trajectory.clone(),
+        }
+    }
+
+    #[wasm_bindgen(js_name = passToInput)]
+    pub fn pass_to_input(&self, input: &mut InputParameter) {
+        self.inner.pass_to_input(&mut input.inner);
+    }
+
+    #[wasm_bindgen(js_name = passToInputWithFeedback)]
+    pub fn pass_to_input_with_feedback(
+        &self,
+        input: &mut InputParameter,
+        measured_position: Vec<f64>,
+        alpha: f64,
+    ) -> Result<(), JsError> {
+        let measured_position = to_data(
+            measured_position,
+            self.inner.degrees_of_freedom,
+            "measuredPosition",
+        )?;
+        self.inner
+            .pass_to_input_with_feedback(&mut input.inner, &measured_position, alpha)
+            .map_err(to_js_error)
+    }
+}
+
+#[wasm_bindgen]
+pub struct Ruckig {
+    inner: RsRuckig<0, ThrowErrorHandler>,
+}
+
+#[wasm_bindgen]
+impl Ruckig {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        dofs: usize,
+        delta_time: Option<f64>,
+        max_number_of_waypoints: Option<usize>,
+    ) -> Ruckig {
+        Ruckig {
+            inner: RsRuckig::new_with_waypoints(
+                max_number_of_waypoints.unwrap_or(0),
+                Some(dofs),
+                delta_time.unwrap_or(-1.0),
+            ),
+        }
+    }
+
+    #[wasm_bindgen(getter, js_name = degreesOfFreedom)]
+    pub fn degrees_of_freedom(&self) -> usize {
+        self.inner.degrees_of_freedom
+    }
+
+    #[wasm_bindgen(getter, js_name = deltaTime)]
+    pub fn delta_time(&self) -> f64 {
+        self.inner.delta_time
+    }
+
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    #[wasm_bindgen(js_name = validateInput)]
+    pub fn validate_input(
+        &self,
+        input: &InputParameter,
+        check_current_state_within_limits: bool,
+        check_target_state_within_limits: bool,
+    ) -> Result<bool, JsError> {
+        self.inner
+            .validate_input(
+                &input.inner,
+                check_current_state_within_limits,
+                check_target_state_within_limits,
+            )
+            .map_err(to_js_error)
+    }
+
+    pub fn calculate(
+        &mut self,
+        input: &InputParameter,
+        trajectory: &mut Trajectory,
+    ) -> Result<JsRuckigResult, JsError> {
+        self.inner
+            .calculate(&input.inner, &mut trajectory.inner)
+            .map(JsRuckigResult::from)
+            .map_err(to_js_error)
+    }
+
+    pub fn update(
+        &mut self,
+        input: &InputParameter,
+        output: &mut OutputParameter,
+    ) -> Result<JsRuckigResult, JsError> {
+        self.inner
+            .update(&input.inner, &mut output.inner)
+            .map(JsRuckigResult::from)
+            .map_err(to_js_error)
+    }
+}